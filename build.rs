@@ -0,0 +1,13 @@
+use vergen_gitcl::{BuildBuilder, Emitter, GitclBuilder, RustcBuilder};
+
+// Embeds the commit, build time and toolchain into the binary for `--version`.
+fn main() -> anyhow::Result<()> {
+    let build = BuildBuilder::default().build_timestamp(true).build()?;
+    let git = GitclBuilder::default().sha(true).build()?;
+    let rustc = RustcBuilder::default().semver(true).build()?;
+    Emitter::default()
+        .add_instructions(&build)?
+        .add_instructions(&git)?
+        .add_instructions(&rustc)?
+        .emit()
+}
@@ -5,14 +5,55 @@ use pdk::types::{
     CallToolRequest, CallToolResult, Content, ContentType, ListToolsResult, ToolDescription,
 };
 use serde_json::{Value as JsonValue, json};
+use std::time::{SystemTime, UNIX_EPOCH};
 use urlencoding::encode;
 
 const CONTEXT7_API_BASE_URL: &str = "https://context7.com/api"; // Guessed API base URL
+const DEFAULT_CACHE_TTL_SECS: u64 = 3600;
+const MAX_BATCH_LIBRARIES: usize = 5;
+
+fn cache_ttl_secs() -> u64 {
+    config::get("cache_ttl_seconds")
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(DEFAULT_CACHE_TTL_SECS)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Cached entries are stored in the plugin vars as {"value": ..., "expires_at": <epoch secs>}
+fn cache_get(key: &str) -> Option<String> {
+    let entry: String = var::get(key).ok().flatten()?;
+    let entry: JsonValue = serde_json::from_str(&entry).ok()?;
+    if entry.get("expires_at")?.as_u64()? <= now_secs() {
+        return None;
+    }
+    entry.get("value")?.as_str().map(String::from)
+}
+
+fn cache_set(key: &str, value: &str) {
+    let ttl = cache_ttl_secs();
+    if ttl == 0 {
+        return;
+    }
+    let entry = json!({ "value": value, "expires_at": now_secs() + ttl });
+    // Caching is best effort, a failure here only means the next call goes to the API
+    if let Err(e) = var::set(key, entry.to_string()) {
+        warn!("Failed to cache {}: {}", key, e);
+    }
+}
 
 pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
     match input.params.name.as_str() {
         "c7_resolve_library_id" => c7_resolve_library_id(input),
         "c7_get_library_docs" => c7_get_library_docs(input),
+        "c7_get_multiple_library_docs" => c7_get_multiple_library_docs(input),
         _ => Ok(CallToolResult {
             is_error: Some(true),
             content: vec![Content {
@@ -31,6 +72,20 @@ fn c7_resolve_library_id(input: CallToolRequest) -> Result<CallToolResult, Error
     let library_name_val = args.get("library_name").unwrap_or(&JsonValue::Null);
 
     if let JsonValue::String(library_name_as_query) = library_name_val {
+        let cache_key = format!("resolve:{}", library_name_as_query);
+        if let Some(cached) = cache_get(&cache_key) {
+            return Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(cached),
+                    mime_type: Some("text/markdown".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+
         let encoded_query = encode(library_name_as_query);
         let url = format!(
             "{}/v1/search?query={}",
@@ -114,6 +169,7 @@ fn c7_resolve_library_id(input: CallToolRequest) -> Result<CallToolResult, Error
                             let header = "Available Libraries (top matches):\n\nEach result includes information like:\n- Title: Library or package name\n- Context7-compatible library ID: Identifier (format: /org/repo)\n- Description: Short summary\n- Code Snippets: Number of available code examples (if available)\n- GitHub Stars: Popularity indicator (if available)\n\nFor best results, select libraries based on name match, popularity (stars), snippet coverage, and relevance to your use case.\n\n---\n";
                             let final_text =
                                 format!("{}{}", header, results_text_parts.join("\n\n"));
+                            cache_set(&cache_key, &final_text);
 
                             Ok(CallToolResult {
                                 is_error: None,
@@ -241,6 +297,21 @@ fn c7_get_library_docs(input: CallToolRequest) -> Result<CallToolResult, Error>
             CONTEXT7_API_BASE_URL, final_id_for_path_segment, query_params
         );
 
+        // Docs depend on the topic and token budget as well as the library ID, so key on the URL
+        let cache_key = format!("docs:{}", url);
+        if let Some(cached) = cache_get(&cache_key) {
+            return Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(cached),
+                    mime_type: Some("text/markdown".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+
         let mut req = HttpRequest::new(&url).with_method("GET");
         req.headers
             .insert("X-Context7-Source".to_string(), "mcp-server".to_string());
@@ -249,6 +320,7 @@ fn c7_get_library_docs(input: CallToolRequest) -> Result<CallToolResult, Error>
             Ok(res) => {
                 let body_str = String::from_utf8_lossy(&res.body()).to_string();
                 if res.status_code() >= 200 && res.status_code() < 300 {
+                    cache_set(&cache_key, &body_str);
                     // Directly use the body_str as markdown content
                     Ok(CallToolResult {
                         is_error: None,
@@ -306,6 +378,85 @@ fn c7_get_library_docs(input: CallToolRequest) -> Result<CallToolResult, Error>
     }
 }
 
+fn c7_get_multiple_library_docs(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+
+    let library_ids: Vec<&str> = match args.get("library_ids") {
+        Some(JsonValue::Array(ids)) => ids.iter().filter_map(JsonValue::as_str).collect(),
+        _ => Vec::new(),
+    };
+
+    if library_ids.is_empty() {
+        return Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some(
+                    "Missing required parameter: library_ids (or not an array of strings)"
+                        .to_string(),
+                ),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        });
+    }
+
+    if library_ids.len() > MAX_BATCH_LIBRARIES {
+        return Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some(format!(
+                    "Too many library_ids: {} were given but at most {} are allowed per call",
+                    library_ids.len(),
+                    MAX_BATCH_LIBRARIES
+                )),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        });
+    }
+
+    // Extism plugins are single-threaded, so the libraries are fetched one after another
+    let mut results = Vec::new();
+    for library_id in library_ids {
+        let mut doc_args = serde_json::Map::new();
+        doc_args.insert(
+            "context7_compatible_library_id".to_string(),
+            JsonValue::String(library_id.to_string()),
+        );
+        for key in ["topic", "tokens"] {
+            if let Some(value) = args.get(key) {
+                doc_args.insert(key.to_string(), value.clone());
+            }
+        }
+
+        let mut doc_input = input.clone();
+        doc_input.params.arguments = Some(doc_args);
+
+        let result = c7_get_library_docs(doc_input)?;
+        let text = result.content.into_iter().find_map(|c| c.text);
+        if result.is_error == Some(true) {
+            results.push(json!({ "library_id": library_id, "content": null, "error": text }));
+        } else {
+            results.push(json!({ "library_id": library_id, "content": text, "error": null }));
+        }
+    }
+
+    Ok(CallToolResult {
+        is_error: None,
+        content: vec![Content {
+            annotations: None,
+            text: Some(serde_json::to_string(&results)?),
+            mime_type: Some("application/json".to_string()),
+            r#type: ContentType::Text,
+            data: None,
+        }],
+    })
+}
+
 pub(crate) fn describe() -> Result<ListToolsResult, Error> {
     Ok(ListToolsResult {
         tools: vec![
@@ -351,6 +502,35 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                 .unwrap()
                 .clone(),
             },
+            ToolDescription {
+                name: "c7_get_multiple_library_docs".into(),
+                description: "Fetches up-to-date documentation for up to 5 libraries in a single call. Returns a JSON array of {library_id, content, error} objects, one per library; a failure for one library is reported in its error field without failing the others. You must call 'c7_resolve_library_id' first to obtain the exact Context7-compatible library IDs.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "library_ids": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                            },
+                            "maxItems": MAX_BATCH_LIBRARIES,
+                            "description": "Exact Context7-compatible library IDs (e.g., 'mongodb/docs', 'vercel/nextjs') retrieved from 'c7_resolve_library_id'. At most 5.",
+                        },
+                        "topic": {
+                            "type": "string",
+                            "description": "Topic to focus documentation on (e.g., 'hooks', 'routing'). Applies to every library.",
+                        },
+                        "tokens": {
+                            "type": "integer",
+                            "description": "Maximum number of tokens of documentation to retrieve per library (default: 10000).",
+                        },
+                    },
+                    "required": ["library_ids"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
         ],
     })
 }
@@ -9,12 +9,45 @@ use pdk::types::{
 };
 use serde_json::json;
 
+fn fetch_json(url: &str) -> Result<Value, Error> {
+    let mut req = HttpRequest {
+        url: url.to_string(),
+        headers: BTreeMap::new(),
+        method: Some("GET".to_string()),
+    };
+
+    // Add a user agent header to be polite
+    req.headers
+        .insert("User-Agent".to_string(), "crates-io-tool/1.0".to_string());
+
+    let res = http::request::<()>(&req, None)?;
+    if !(200..300).contains(&res.status_code()) {
+        return Err(Error::msg(format!(
+            "Request to {} failed with status {}: {}",
+            url,
+            res.status_code(),
+            String::from_utf8_lossy(&res.body())
+        )));
+    }
+
+    Ok(serde_json::from_slice(&res.body())?)
+}
+
 pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
     match input.params.name.as_str() {
         "crates_io_latest_version" => latest_version(input),
         "crates_io_crate_info" => crate_info(input),
+        "crates_io_dep_tree" => dep_tree(input),
+        "crates_io_reverse_deps" => reverse_deps(input),
+        "crates_io_version_history" => version_history(input),
+        "crates_io_yank_info" => yank_info(input),
+        "crates_io_search" => search(input),
+        "crates_io_feature_flags" => feature_flags(input),
+        "crates_io_owners" => owners(input, "owners", "users", "owners"),
+        "crates_io_teams" => owners(input, "owner_team", "teams", "teams"),
         _ => Ok(CallToolResult {
             is_error: Some(true),
+            structured_content: None,
             content: vec![Content {
                 annotations: None,
                 text: Some(format!("Unknown tool: {}", input.params.name)),
@@ -85,10 +118,12 @@ fn crate_info(input: CallToolRequest) -> Result<CallToolResult, Error> {
                     r#type: ContentType::Text,
                     data: None,
                 }],
+                structured_content: Some(json!({ "crates": results }).as_object().unwrap().clone()),
             })
         } else {
             Ok(CallToolResult {
                 is_error: Some(true),
+                structured_content: None,
                 content: vec![Content {
                     annotations: None,
                     text: Some("Failed to get crate information".into()),
@@ -101,6 +136,7 @@ fn crate_info(input: CallToolRequest) -> Result<CallToolResult, Error> {
     } else {
         Ok(CallToolResult {
             is_error: Some(true),
+            structured_content: None,
             content: vec![Content {
                 annotations: None,
                 text: Some("Please provide crate names".into()),
@@ -155,10 +191,17 @@ fn latest_version(input: CallToolRequest) -> Result<CallToolResult, Error> {
                     r#type: ContentType::Text,
                     data: None,
                 }],
+                structured_content: Some(
+                    results
+                        .into_iter()
+                        .map(|(name, version)| (name, Value::String(version)))
+                        .collect(),
+                ),
             })
         } else {
             Ok(CallToolResult {
                 is_error: Some(true),
+                structured_content: None,
                 content: vec![Content {
                     annotations: None,
                     text: Some("Failed to get latest versions".into()),
@@ -171,6 +214,7 @@ fn latest_version(input: CallToolRequest) -> Result<CallToolResult, Error> {
     } else {
         Ok(CallToolResult {
             is_error: Some(true),
+            structured_content: None,
             content: vec![Content {
                 annotations: None,
                 text: Some("Please provide crate names".into()),
@@ -182,6 +226,694 @@ fn latest_version(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
+// Walks the dependency graph of a crate. Dependencies are resolved to their
+// latest published version, since crates.io only exposes version requirements.
+struct DepTreeBuilder {
+    max_depth: u32,
+    max_nodes: u32,
+    node_count: u32,
+    truncated: bool,
+    latest_versions: BTreeMap<String, String>,
+    dependencies: BTreeMap<String, Vec<Value>>,
+}
+
+impl DepTreeBuilder {
+    fn latest_version(&mut self, crate_name: &str) -> Result<String, Error> {
+        if let Some(version) = self.latest_versions.get(crate_name) {
+            return Ok(version.clone());
+        }
+
+        let json = fetch_json(&format!("https://crates.io/api/v1/crates/{}", crate_name))?;
+        let version = json["crate"]["max_version"]
+            .as_str()
+            .ok_or_else(|| Error::msg(format!("No version found for crate {}", crate_name)))?
+            .to_string();
+        self.latest_versions
+            .insert(crate_name.to_string(), version.clone());
+        Ok(version)
+    }
+
+    fn dependencies(&mut self, crate_name: &str, version: &str) -> Result<Vec<Value>, Error> {
+        let key = format!("{}@{}", crate_name, version);
+        if let Some(deps) = self.dependencies.get(&key) {
+            return Ok(deps.clone());
+        }
+
+        let json = fetch_json(&format!(
+            "https://crates.io/api/v1/crates/{}/{}/dependencies",
+            crate_name, version
+        ))?;
+        let deps = json["dependencies"].as_array().cloned().unwrap_or_default();
+        self.dependencies.insert(key, deps.clone());
+        Ok(deps)
+    }
+
+    fn build_node(
+        &mut self,
+        crate_name: &str,
+        version: &str,
+        kind: &str,
+        depth: u32,
+        path: &mut Vec<String>,
+    ) -> Result<Value, Error> {
+        self.node_count += 1;
+        let mut children = Vec::new();
+
+        if depth < self.max_depth {
+            path.push(crate_name.to_string());
+            for dep in self.dependencies(crate_name, version)? {
+                let dep_kind = dep["kind"].as_str().unwrap_or("normal");
+                // Dev-dependencies of a dependency are never built, so only follow them at the root
+                if depth > 0 && dep_kind == "dev" {
+                    continue;
+                }
+                if self.node_count >= self.max_nodes {
+                    self.truncated = true;
+                    break;
+                }
+
+                let Some(dep_name) = dep["crate_id"].as_str() else {
+                    continue;
+                };
+
+                if path.iter().any(|p| p == dep_name) {
+                    self.node_count += 1;
+                    children.push(json!({
+                        "name": dep_name,
+                        "version": Value::Null,
+                        "kind": dep_kind,
+                        "req": dep["req"],
+                        "cycle": true,
+                        "children": [],
+                    }));
+                    continue;
+                }
+
+                let dep_version = self.latest_version(dep_name)?;
+                let mut child =
+                    self.build_node(dep_name, &dep_version, dep_kind, depth + 1, path)?;
+                child["req"] = dep["req"].clone();
+                child["optional"] = dep["optional"].clone();
+                children.push(child);
+            }
+            path.pop();
+        }
+
+        Ok(json!({
+            "name": crate_name,
+            "version": version,
+            "kind": kind,
+            "children": children,
+        }))
+    }
+}
+
+fn dep_tree(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.unwrap_or_default();
+    if let Some(Value::String(crate_name)) = args.get("crate_name") {
+        let mut builder = DepTreeBuilder {
+            max_depth: args.get("max_depth").and_then(|v| v.as_u64()).unwrap_or(3) as u32,
+            max_nodes: args
+                .get("max_nodes")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(100)
+                .max(1) as u32,
+            node_count: 0,
+            truncated: false,
+            latest_versions: BTreeMap::new(),
+            dependencies: BTreeMap::new(),
+        };
+
+        let result = match args.get("version") {
+            Some(Value::String(version)) => Ok(version.clone()),
+            _ => builder.latest_version(crate_name),
+        }
+        .and_then(|version| builder.build_node(crate_name, &version, "normal", 0, &mut Vec::new()));
+
+        match result {
+            Ok(tree) => {
+                let result = json!({
+                    "tree": tree,
+                    "node_count": builder.node_count,
+                    "truncated": builder.truncated,
+                });
+                Ok(CallToolResult {
+                    is_error: None,
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(serde_json::to_string(&result)?),
+                        mime_type: Some("application/json".to_string()),
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                    structured_content: result.as_object().cloned(),
+                })
+            }
+            Err(e) => Ok(CallToolResult {
+                is_error: Some(true),
+                structured_content: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Failed to build dependency tree: {}", e)),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            }),
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            structured_content: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide a crate name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn reverse_deps(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.unwrap_or_default();
+    if let Some(Value::String(crate_name)) = args.get("crate_name") {
+        let page = args
+            .get("page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1)
+            .max(1);
+        let per_page = args
+            .get("per_page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(20)
+            .clamp(1, 100);
+
+        let json = match fetch_json(&format!(
+            "https://crates.io/api/v1/crates/{}/reverse_dependencies?page={}&per_page={}",
+            crate_name, page, per_page
+        )) {
+            Ok(json) => json,
+            Err(e) => {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    structured_content: None,
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!("Failed to get reverse dependencies: {}", e)),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+        };
+
+        // Each dependency only references the dependent version by id, so index the versions
+        let mut versions = BTreeMap::new();
+        for version in json["versions"].as_array().into_iter().flatten() {
+            if let Some(id) = version["id"].as_i64() {
+                versions.insert(id, version);
+            }
+        }
+
+        let mut dependents = Vec::new();
+        for dep in json["dependencies"].as_array().into_iter().flatten() {
+            let version = dep["version_id"].as_i64().and_then(|id| versions.get(&id));
+            dependents.push(json!({
+                "name": version.and_then(|v| v["crate"].as_str()),
+                "version": version.and_then(|v| v["num"].as_str()),
+                "req": dep["req"].as_str(),
+                "kind": dep["kind"].as_str(),
+                "optional": dep["optional"].as_bool(),
+                "downloads": version.and_then(|v| v["downloads"].as_i64()),
+            }));
+        }
+
+        let result = json!({
+            "total": json["meta"]["total"].as_i64(),
+            "page": page,
+            "per_page": per_page,
+            "dependents": dependents,
+        });
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&result)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+            structured_content: result.as_object().cloned(),
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            structured_content: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide a crate name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn fetch_versions(crate_name: &str) -> Result<Vec<Value>, Error> {
+    let json = fetch_json(&format!(
+        "https://crates.io/api/v1/crates/{}/versions",
+        crate_name
+    ))?;
+    let mut versions = json["versions"].as_array().cloned().unwrap_or_default();
+    // RFC 3339 timestamps sort lexicographically, newest first
+    versions.sort_by(|a, b| {
+        b["created_at"]
+            .as_str()
+            .unwrap_or_default()
+            .cmp(a["created_at"].as_str().unwrap_or_default())
+    });
+    Ok(versions)
+}
+
+fn version_history(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.unwrap_or_default();
+    if let Some(Value::String(crate_name)) = args.get("crate_name") {
+        let yanked_only = args
+            .get("yanked_only")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        match fetch_versions(crate_name) {
+            Ok(versions) => {
+                let history: Vec<Value> = versions
+                    .iter()
+                    .filter(|v| !yanked_only || v["yanked"].as_bool().unwrap_or(false))
+                    .map(|v| {
+                        json!({
+                            "version": v["num"].as_str(),
+                            "yanked": v["yanked"].as_bool(),
+                            "created_at": v["created_at"].as_str(),
+                            "downloads": v["downloads"].as_i64(),
+                            "features": v["features"],
+                        })
+                    })
+                    .collect();
+
+                Ok(CallToolResult {
+                    is_error: None,
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(serde_json::to_string(&history)?),
+                        mime_type: Some("application/json".to_string()),
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                    structured_content: Some(
+                        json!({ "versions": history }).as_object().unwrap().clone(),
+                    ),
+                })
+            }
+            Err(e) => Ok(CallToolResult {
+                is_error: Some(true),
+                structured_content: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Failed to get version history: {}", e)),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            }),
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            structured_content: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide a crate name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn yank_info(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.unwrap_or_default();
+    if let Some(Value::String(crate_name)) = args.get("crate_name") {
+        match fetch_versions(crate_name) {
+            Ok(versions) => {
+                let yanked: Vec<Value> = versions
+                    .iter()
+                    .filter(|v| v["yanked"].as_bool().unwrap_or(false))
+                    .map(|v| {
+                        json!({
+                            "version": v["num"].as_str(),
+                            "created_at": v["created_at"].as_str(),
+                            "yank_message": v["yank_message"].as_str(),
+                        })
+                    })
+                    .collect();
+
+                Ok(CallToolResult {
+                    is_error: None,
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(serde_json::to_string(&yanked)?),
+                        mime_type: Some("application/json".to_string()),
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                    structured_content: Some(
+                        json!({ "versions": yanked }).as_object().unwrap().clone(),
+                    ),
+                })
+            }
+            Err(e) => Ok(CallToolResult {
+                is_error: Some(true),
+                structured_content: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Failed to get yanked versions: {}", e)),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            }),
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            structured_content: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide a crate name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+// Percent-encodes a value for use in a query string
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn search(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.unwrap_or_default();
+    if let Some(Value::String(query)) = args.get("query") {
+        let page = args
+            .get("page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1)
+            .max(1);
+        let per_page = args
+            .get("per_page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(10)
+            .clamp(1, 100);
+        // crates.io calls newly added crates "new"
+        let sort = match args.get("sort").and_then(|v| v.as_str()) {
+            None | Some("relevance") => "relevance",
+            Some("downloads") => "downloads",
+            Some("recent-updates") => "recent-updates",
+            Some("newly-added") => "new",
+            Some(other) => {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    structured_content: None,
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Invalid sort {}, expected one of relevance, downloads, recent-updates, newly-added",
+                            other
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+        };
+
+        let mut url = format!(
+            "https://crates.io/api/v1/crates?q={}&sort={}&page={}&per_page={}",
+            encode_query_value(query),
+            sort,
+            page,
+            per_page
+        );
+        if let Some(category) = args.get("category").and_then(|v| v.as_str()) {
+            url.push_str(&format!("&category={}", encode_query_value(category)));
+        }
+
+        let json = match fetch_json(&url) {
+            Ok(json) => json,
+            Err(e) => {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    structured_content: None,
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!("Failed to search crates: {}", e)),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+        };
+
+        let crates: Vec<Value> = json["crates"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|c| {
+                json!({
+                    "name": c["name"].as_str(),
+                    "description": c["description"].as_str(),
+                    "max_version": c["max_version"].as_str(),
+                    "downloads": c["downloads"].as_i64(),
+                    "repository": c["repository"].as_str(),
+                })
+            })
+            .collect();
+
+        let result = json!({
+            "total": json["meta"]["total"].as_i64(),
+            "page": page,
+            "per_page": per_page,
+            "crates": crates,
+        });
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&result)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+            structured_content: result.as_object().cloned(),
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            structured_content: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide a search query".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn feature_flags(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.unwrap_or_default();
+    if let Some(Value::String(crate_name)) = args.get("crate_name") {
+        let result = match args.get("version") {
+            Some(Value::String(version)) => Ok(version.clone()),
+            _ => fetch_json(&format!("https://crates.io/api/v1/crates/{}", crate_name)).and_then(
+                |json| {
+                    json["crate"]["max_version"]
+                        .as_str()
+                        .map(String::from)
+                        .ok_or_else(|| {
+                            Error::msg(format!("No version found for crate {}", crate_name))
+                        })
+                },
+            ),
+        }
+        .and_then(|version| {
+            let json = fetch_json(&format!(
+                "https://crates.io/api/v1/crates/{}/{}",
+                crate_name, version
+            ))?;
+            Ok(json!({
+                "crate": crate_name,
+                "version": version,
+                "features": json["version"]["features"]
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            }))
+        });
+
+        match result {
+            Ok(result) => Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(serde_json::to_string(&result)?),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+                structured_content: result.as_object().cloned(),
+            }),
+            Err(e) => Ok(CallToolResult {
+                is_error: Some(true),
+                structured_content: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Failed to get feature flags: {}", e)),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            }),
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            structured_content: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide a crate name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+// Lists the owners of a crate from the `endpoint` under /crates/<name>/, which returns
+// them in the `key` array, and returns them in the `field` array.
+fn owners(
+    input: CallToolRequest,
+    endpoint: &str,
+    key: &str,
+    field: &str,
+) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.unwrap_or_default();
+    if let Some(Value::String(crate_name)) = args.get("crate_name") {
+        match fetch_json(&format!(
+            "https://crates.io/api/v1/crates/{}/{}",
+            crate_name, endpoint
+        )) {
+            Ok(json) => {
+                let owners: Vec<Value> = json[key]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|owner| {
+                        json!({
+                            "login": owner["login"].as_str(),
+                            "name": owner["name"].as_str(),
+                            "url": owner["url"].as_str(),
+                            "kind": owner["kind"].as_str(),
+                        })
+                    })
+                    .collect();
+
+                let result = json!({ "crate": crate_name, field: owners });
+                Ok(CallToolResult {
+                    is_error: None,
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(serde_json::to_string(&result)?),
+                        mime_type: Some("application/json".to_string()),
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                    structured_content: result.as_object().cloned(),
+                })
+            }
+            Err(e) => Ok(CallToolResult {
+                is_error: Some(true),
+                structured_content: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Failed to get crate {}: {}", field, e)),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            }),
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            structured_content: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide a crate name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+// The output schema of crates_io_owners and crates_io_teams, which return the owners
+// in the `field` array.
+fn owners_output_schema(field: &str, description: &str) -> serde_json::Map<String, Value> {
+    json!({
+        "type": "object",
+        "properties": {
+            "crate": { "type": "string" },
+            field: {
+                "type": "array",
+                "description": description,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "login": { "type": ["string", "null"] },
+                        "name": { "type": ["string", "null"] },
+                        "url": { "type": ["string", "null"] },
+                        "kind": { "type": ["string", "null"] },
+                    },
+                },
+            },
+        },
+        "required": ["crate", field],
+    })
+    .as_object()
+    .unwrap()
+    .clone()
+}
+
 pub(crate) fn describe() -> Result<ListToolsResult, Error> {
     Ok(ListToolsResult {
         tools: vec![
@@ -201,6 +933,16 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                 .as_object()
                 .unwrap()
                 .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "description": "The latest version of each crate that was found, keyed by crate name",
+                        "additionalProperties": { "type": "string" },
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
             },
             ToolDescription {
                 name: "crates_io_crate_info".into(),
@@ -218,6 +960,375 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                 .as_object()
                 .unwrap()
                 .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            "crates": {
+                                "type": "array",
+                                "description": "Information about each crate that was found",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "name": { "type": "string" },
+                                        "description": { "type": ["string", "null"] },
+                                        "latest_version": { "type": ["string", "null"] },
+                                        "downloads": { "type": ["integer", "null"] },
+                                        "repository": { "type": ["string", "null"] },
+                                        "documentation": { "type": ["string", "null"] },
+                                        "homepage": { "type": ["string", "null"] },
+                                        "keywords": { "type": ["array", "null"], "items": { "type": "string" } },
+                                        "categories": { "type": ["array", "null"], "items": { "type": "string" } },
+                                        "license": { "type": ["string", "null"] },
+                                        "created_at": { "type": ["string", "null"] },
+                                        "updated_at": { "type": ["string", "null"] },
+                                    },
+                                },
+                            },
+                        },
+                        "required": ["crates"],
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+            },
+            ToolDescription {
+                name: "crates_io_dep_tree".into(),
+                description: "Fetches the transitive dependency tree of a crate from crates.io. Each node has a name, version, kind (normal/dev/build), and children. Dependencies are resolved to their latest published version and cycles are marked rather than followed.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "crate_name": {
+                            "type": "string",
+                            "description": "The name of the crate",
+                        },
+                        "version": {
+                            "type": "string",
+                            "description": "The version of the crate. Defaults to the latest version",
+                        },
+                        "max_depth": {
+                            "type": "integer",
+                            "description": "Maximum depth of the tree. Defaults to 3",
+                        },
+                        "max_nodes": {
+                            "type": "integer",
+                            "description": "Maximum number of nodes in the tree. Defaults to 100. The result indicates whether the tree was truncated",
+                        },
+                    },
+                    "required": ["crate_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            "tree": {
+                                "type": "object",
+                                "description": "The root node. Every node has a name, version, kind, and children, which are nodes themselves",
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "version": { "type": ["string", "null"] },
+                                    "kind": { "type": "string" },
+                                    "children": { "type": "array", "items": { "type": "object" } },
+                                },
+                                "required": ["name", "version", "kind", "children"],
+                            },
+                            "node_count": { "type": "integer" },
+                            "truncated": { "type": "boolean" },
+                        },
+                        "required": ["tree", "node_count", "truncated"],
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+            },
+            ToolDescription {
+                name: "crates_io_reverse_deps".into(),
+                description: "Lists the crates that depend on a crate (its reverse dependencies) from crates.io, with their version requirement and download count. Results are paginated".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "crate_name": {
+                            "type": "string",
+                            "description": "The name of the crate",
+                        },
+                        "page": {
+                            "type": "integer",
+                            "description": "The page to fetch, starting at 1. Defaults to 1",
+                        },
+                        "per_page": {
+                            "type": "integer",
+                            "description": "Number of dependents per page (1-100). Defaults to 20",
+                        },
+                    },
+                    "required": ["crate_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            "total": { "type": ["integer", "null"] },
+                            "page": { "type": "integer" },
+                            "per_page": { "type": "integer" },
+                            "dependents": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "name": { "type": ["string", "null"] },
+                                        "version": { "type": ["string", "null"] },
+                                        "req": { "type": ["string", "null"] },
+                                        "kind": { "type": ["string", "null"] },
+                                        "optional": { "type": ["boolean", "null"] },
+                                        "downloads": { "type": ["integer", "null"] },
+                                    },
+                                },
+                            },
+                        },
+                        "required": ["total", "page", "per_page", "dependents"],
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+            },
+            ToolDescription {
+                name: "crates_io_version_history".into(),
+                description: "Fetches the published versions of a crate from crates.io, newest first, with their yanked status, publish date, downloads, and features".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "crate_name": {
+                            "type": "string",
+                            "description": "The name of the crate",
+                        },
+                        "yanked_only": {
+                            "type": "boolean",
+                            "description": "Only return yanked versions. Defaults to false",
+                        },
+                    },
+                    "required": ["crate_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            "versions": {
+                                "type": "array",
+                                "description": "The versions of the crate, newest first",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "version": { "type": ["string", "null"] },
+                                        "yanked": { "type": ["boolean", "null"] },
+                                        "created_at": { "type": ["string", "null"] },
+                                        "downloads": { "type": ["integer", "null"] },
+                                        "features": { "type": ["object", "null"] },
+                                    },
+                                },
+                            },
+                        },
+                        "required": ["versions"],
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+            },
+            ToolDescription {
+                name: "crates_io_yank_info".into(),
+                description: "Fetches the yanked versions of a crate from crates.io, newest first, with the reason they were yanked when the publisher provided one".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "crate_name": {
+                            "type": "string",
+                            "description": "The name of the crate",
+                        },
+                    },
+                    "required": ["crate_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            "versions": {
+                                "type": "array",
+                                "description": "The yanked versions of the crate, newest first",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "version": { "type": ["string", "null"] },
+                                        "created_at": { "type": ["string", "null"] },
+                                        "yank_message": { "type": ["string", "null"] },
+                                    },
+                                },
+                            },
+                        },
+                        "required": ["versions"],
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+            },
+            ToolDescription {
+                name: "crates_io_search".into(),
+                description: "Searches crates.io for crates matching a keyword, to discover crates rather than look up known ones. Returns the name, description, latest version, downloads, and repository of each match".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "The keywords to search for",
+                        },
+                        "category": {
+                            "type": "string",
+                            "description": "Only return crates in this crates.io category slug, e.g. 'web-programming' or 'command-line-utilities'",
+                        },
+                        "sort": {
+                            "type": "string",
+                            "enum": ["relevance", "downloads", "recent-updates", "newly-added"],
+                            "description": "The order of the results. Defaults to relevance",
+                        },
+                        "page": {
+                            "type": "integer",
+                            "description": "The page of results to return, starting at 1. Defaults to 1",
+                        },
+                        "per_page": {
+                            "type": "integer",
+                            "description": "The number of crates per page, up to 100. Defaults to 10",
+                        },
+                    },
+                    "required": ["query"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            "total": { "type": ["integer", "null"] },
+                            "page": { "type": "integer" },
+                            "per_page": { "type": "integer" },
+                            "crates": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "name": { "type": ["string", "null"] },
+                                        "description": { "type": ["string", "null"] },
+                                        "max_version": { "type": ["string", "null"] },
+                                        "downloads": { "type": ["integer", "null"] },
+                                        "repository": { "type": ["string", "null"] },
+                                    },
+                                },
+                            },
+                        },
+                        "required": ["total", "page", "per_page", "crates"],
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+            },
+            ToolDescription {
+                name: "crates_io_feature_flags".into(),
+                description: "Lists the feature flags of a crate version from crates.io, with the features and optional dependencies each one enables. Useful to advise which features to enable in Cargo.toml".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "crate_name": {
+                            "type": "string",
+                            "description": "The name of the crate",
+                        },
+                        "version": {
+                            "type": "string",
+                            "description": "The version of the crate. Defaults to the latest version",
+                        },
+                    },
+                    "required": ["crate_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            "crate": { "type": "string" },
+                            "version": { "type": "string" },
+                            "features": {
+                                "type": "object",
+                                "description": "The features enabled by each feature flag, keyed by feature name. Entries like 'dep:name' enable an optional dependency",
+                                "additionalProperties": {
+                                    "type": "array",
+                                    "items": { "type": "string" },
+                                },
+                            },
+                        },
+                        "required": ["crate", "version", "features"],
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+            },
+            ToolDescription {
+                name: "crates_io_owners".into(),
+                description: "Lists the owners of a crate on crates.io, both users and teams, with their login, name, profile URL, and kind. Useful for supply-chain analysis, e.g. to flag crates owned by a single individual".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "crate_name": {
+                            "type": "string",
+                            "description": "The name of the crate",
+                        },
+                    },
+                    "required": ["crate_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(owners_output_schema(
+                    "owners",
+                    "The owners of the crate. kind is 'user' or 'team'",
+                )),
+            },
+            ToolDescription {
+                name: "crates_io_teams".into(),
+                description: "Lists the teams that own a crate on crates.io, e.g. GitHub teams of an organization, with their login, name, and profile URL".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "crate_name": {
+                            "type": "string",
+                            "description": "The name of the crate",
+                        },
+                    },
+                    "required": ["crate_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+                output_schema: Some(owners_output_schema(
+                    "teams",
+                    "The teams that own the crate. login has the form 'github:org:team'",
+                )),
             },
         ],
     })
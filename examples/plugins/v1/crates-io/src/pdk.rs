@@ -139,6 +139,12 @@ pub mod types {
         #[serde(skip_serializing_if = "Option::is_none")]
         #[serde(default)]
         pub is_error: Option<bool>,
+
+        /// The result of the tool call as a JSON object matching the tool's output schema.
+        #[serde(rename = "structuredContent")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub structured_content: Option<serde_json::Map<String, serde_json::Value>>,
     }
 
     #[derive(
@@ -327,6 +333,12 @@ pub mod types {
         /// The name of the tool. It should match the plugin / binding name.
         #[serde(rename = "name")]
         pub name: String,
+
+        /// The JSON schema describing the structured content of the result, if the tool returns any
+        #[serde(rename = "outputSchema")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub output_schema: Option<serde_json::Map<String, serde_json::Value>>,
     }
 }
 
@@ -134,6 +134,14 @@ fn is_success_status(status_code: u16) -> bool {
     (200..300).contains(&status_code)
 }
 
+/// Helper function to check that a label color is a hex string of the form #RRGGBB
+fn is_valid_hex_color(color: &str) -> bool {
+    match color.strip_prefix('#') {
+        Some(hex) => hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
 fn urlencode_if_needed(input: &str) -> String {
     if input.contains("/") {
         urlencoding::encode(input).to_string()
@@ -149,6 +157,10 @@ pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
         "gl_create_issue" => create_issue(input),
         "gl_get_issue" => get_issue(input),
         "gl_update_issue" => update_issue(input),
+        "gl_close_issue" => set_issue_state(input, "close"),
+        "gl_reopen_issue" => set_issue_state(input, "reopen"),
+        "gl_assign_issue" => assign_issue(input),
+        "gl_unassign_issue" => unassign_issue(input),
         "gl_add_issue_comment" => add_issue_comment(input),
         "gl_list_issues" => gl_list_issues(input),
 
@@ -163,6 +175,12 @@ pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
         "gl_create_merge_request" => create_merge_request(input),
         "gl_update_merge_request" => update_merge_request(input),
         "gl_get_merge_request" => gl_get_merge_request(input),
+        "gl_list_merge_requests" => gl_list_merge_requests(input),
+        "gl_get_mr_changes" => gl_get_mr_changes(input),
+        "gl_merge_mr" => gl_merge_mr(input),
+        "gl_get_protected_branch" => gl_get_protected_branch(input),
+        "gl_protect_branch" => gl_protect_branch(input),
+        "gl_unprotect_branch" => gl_unprotect_branch(input),
 
         // Snippets (GitLab equivalent of Gists)
         "gl_create_snippet" => create_snippet(input),
@@ -170,12 +188,42 @@ pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
         "gl_get_snippet" => get_snippet(input),
         "gl_delete_snippet" => delete_snippet(input),
 
+        // Pipelines
+        "gl_list_pipelines" => gl_list_pipelines(input),
+        "gl_get_pipeline" => gl_get_pipeline(input),
+        "gl_trigger_pipeline" => gl_trigger_pipeline(input),
+        "gl_cancel_pipeline" => gl_cancel_pipeline(input),
+        "gl_list_pipeline_jobs" => gl_list_pipeline_jobs(input),
+        "gl_get_job_log" => gl_get_job_log(input),
+
         // Repository tree
         "gl_get_repo_tree" => gl_get_repo_tree(input),
 
         // Repository members
         "gl_get_repo_members" => gl_get_repo_members(input),
 
+        // Search
+        "gl_search_code" => gl_search_code(input),
+
+        // Labels
+        "gl_list_labels" => gl_list_labels(input),
+        "gl_create_label" => gl_create_label(input),
+        "gl_update_label" => gl_update_label(input),
+        "gl_delete_label" => gl_delete_label(input),
+
+        // Commits
+        "gl_list_commits" => gl_list_commits(input),
+        "gl_get_commit" => gl_get_commit(input),
+        "gl_compare_refs" => gl_compare_refs(input),
+
+        // Tags and releases
+        "gl_create_tag" => gl_create_tag(input),
+        "gl_list_tags" => gl_list_tags(input),
+        "gl_get_tag" => gl_get_tag(input),
+        "gl_delete_tag" => gl_delete_tag(input),
+        "gl_create_release" => gl_create_release(input),
+        "gl_get_release" => gl_get_release(input),
+
         _ => Ok(CallToolResult {
             is_error: Some(true),
             content: vec![Content {
@@ -432,6 +480,273 @@ fn update_issue(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
+/// Closes or reopens an issue, `state_event` being "close" or "reopen"
+fn set_issue_state(input: CallToolRequest, state_event: &str) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(issue_iid))) =
+        (args.get("project_id"), args.get("issue_iid"))
+    {
+        let url = format!(
+            "{}/projects/{}/issues/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            issue_iid
+        );
+        let body = json!({ "state_event": state_event });
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("PUT".to_string()),
+        };
+
+        let res = http::request(&req, Some(&body.to_string()))?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to {} issue: {} - Response: {}",
+                        state_event,
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and issue_iid".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn assign_issue(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (
+        Some(Value::String(project_id)),
+        Some(Value::String(issue_iid)),
+        Some(Value::String(assignee_usernames)),
+    ) = (
+        args.get("project_id"),
+        args.get("issue_iid"),
+        args.get("assignee_usernames"),
+    ) {
+        let usernames: Vec<&str> = assignee_usernames
+            .split(',')
+            .map(|username| username.trim().trim_start_matches('@'))
+            .filter(|username| !username.is_empty())
+            .collect();
+        if usernames.is_empty() {
+            return Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some("Please provide at least one username in assignee_usernames".into()),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+
+        let mut assignee_ids = Vec::new();
+        let mut unknown_usernames = Vec::new();
+        for username in &usernames {
+            match find_user_id(username, &token, &gitlab_url) {
+                Ok(Some(user_id)) => assignee_ids.push(user_id),
+                Ok(None) => unknown_usernames.push(*username),
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(e.to_string()),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+        }
+        // Assign nobody rather than a subset of the requested users
+        if !unknown_usernames.is_empty() {
+            return Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "GitLab users not found: {}",
+                        unknown_usernames.join(", ")
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+
+        set_issue_assignees(project_id, issue_iid, &assignee_ids, &token, &gitlab_url)
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id, issue_iid, and assignee_usernames".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn unassign_issue(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(issue_iid))) =
+        (args.get("project_id"), args.get("issue_iid"))
+    {
+        set_issue_assignees(project_id, issue_iid, &[], &token, &gitlab_url)
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and issue_iid".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+/// Looks up the ID of the user with the given username, `None` if there is no such user
+fn find_user_id(username: &str, token: &str, gitlab_url: &str) -> Result<Option<u64>, Error> {
+    let url = format!(
+        "{}/users?username={}",
+        gitlab_url,
+        urlencoding::encode(username)
+    );
+
+    let mut headers = BTreeMap::new();
+    headers.insert("PRIVATE-TOKEN".to_string(), token.to_string());
+    headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+    let req = HttpRequest {
+        url,
+        headers,
+        method: Some("GET".to_string()),
+    };
+
+    let res = http::request::<()>(&req, None)?;
+
+    if !is_success_status(res.status_code()) {
+        return Err(Error::msg(format!(
+            "Failed to look up user {}: {} - Response: {}",
+            username,
+            res.status_code(),
+            String::from_utf8_lossy(&res.body())
+        )));
+    }
+
+    let users: Vec<Value> = serde_json::from_slice(&res.body())?;
+    Ok(users.first().and_then(|user| user["id"].as_u64()))
+}
+
+/// Replaces the assignees of an issue, unassigning everyone if `assignee_ids` is empty
+fn set_issue_assignees(
+    project_id: &str,
+    issue_iid: &str,
+    assignee_ids: &[u64],
+    token: &str,
+    gitlab_url: &str,
+) -> Result<CallToolResult, Error> {
+    let url = format!(
+        "{}/projects/{}/issues/{}",
+        gitlab_url,
+        urlencode_if_needed(project_id),
+        issue_iid
+    );
+    let body = json!({ "assignee_ids": assignee_ids });
+
+    let mut headers = BTreeMap::new();
+    headers.insert("PRIVATE-TOKEN".to_string(), token.to_string());
+    headers.insert("Content-Type".to_string(), "application/json".to_string());
+    headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+    let req = HttpRequest {
+        url,
+        headers,
+        method: Some("PUT".to_string()),
+    };
+
+    let res = http::request(&req, Some(&body.to_string()))?;
+
+    if is_success_status(res.status_code()) {
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some(format!(
+                    "Failed to update issue assignees: {} - Response: {}",
+                    res.status_code(),
+                    String::from_utf8_lossy(&res.body())
+                )),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
 fn add_issue_comment(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
@@ -1163,40 +1478,43 @@ fn gl_get_merge_request(input: CallToolRequest) -> Result<CallToolResult, Error>
     }
 }
 
-fn create_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
+fn gl_get_mr_changes(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
 
-    if let (Some(Value::String(title)), Some(Value::String(content))) =
-        (args.get("title"), args.get("content"))
+    if let (Some(Value::String(project_id)), Some(Value::String(merge_request_iid))) =
+        (args.get("project_id"), args.get("merge_request_iid"))
     {
-        let url = format!("{}/snippets", gitlab_url);
+        let access_raw_diffs = args
+            .get("access_raw_diffs")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
-        // Get visibility from args or default to "private"
-        let visibility = args
-            .get("visibility")
-            .and_then(|v| v.as_str())
-            .unwrap_or("private");
+        let query_string = if access_raw_diffs {
+            "?access_raw_diffs=true".to_string()
+        } else {
+            "".to_string()
+        };
 
-        let body = json!({
-            "title": title,
-            "file_name": format!("{}.txt", title.to_lowercase().replace(" ", "_")),
-            "content": content,
-            "visibility": visibility
-        });
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}/changes{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            merge_request_iid,
+            query_string
+        );
 
         let mut headers = BTreeMap::new();
         headers.insert("PRIVATE-TOKEN".to_string(), token);
-        headers.insert("Content-Type".to_string(), "application/json".to_string());
         headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
 
         let req = HttpRequest {
             url,
             headers,
-            method: Some("POST".to_string()),
+            method: Some("GET".to_string()),
         };
 
-        let res = http::request(&req, Some(&body.to_string()))?;
+        let res = http::request::<()>(&req, None)?;
 
         if is_success_status(res.status_code()) {
             Ok(CallToolResult {
@@ -1214,7 +1532,11 @@ fn create_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
                 is_error: Some(true),
                 content: vec![Content {
                     annotations: None,
-                    text: Some(format!("Failed to create snippet: {}", res.status_code())),
+                    text: Some(format!(
+                        "Failed to get merge request changes: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
                     mime_type: None,
                     r#type: ContentType::Text,
                     data: None,
@@ -1226,7 +1548,7 @@ fn create_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
             is_error: Some(true),
             content: vec![Content {
                 annotations: None,
-                text: Some("Please provide title and content".into()),
+                text: Some("Please provide project_id and merge_request_iid".into()),
                 mime_type: None,
                 r#type: ContentType::Text,
                 data: None,
@@ -1235,25 +1557,38 @@ fn create_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
-fn update_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
+fn gl_merge_mr(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
 
-    if let (
-        Some(Value::String(snippet_id)),
-        Some(Value::String(title)),
-        Some(Value::String(content)),
-    ) = (
-        args.get("snippet_id"),
-        args.get("title"),
-        args.get("content"),
-    ) {
-        let url = format!("{}/snippets/{}", gitlab_url, snippet_id);
-        let body = json!({
-            "title": title,
-            "file_name": format!("{}.txt", title.to_lowercase().replace(" ", "_")),
-            "content": content,
-        });
+    if let (Some(Value::String(project_id)), Some(Value::String(merge_request_iid))) =
+        (args.get("project_id"), args.get("merge_request_iid"))
+    {
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}/merge",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            merge_request_iid
+        );
+
+        let mut body_map = serde_json::Map::new();
+        if let Some(Value::String(merge_commit_message)) = args.get("merge_commit_message") {
+            body_map.insert(
+                "merge_commit_message".to_string(),
+                json!(merge_commit_message),
+            );
+        }
+        for flag in [
+            "squash",
+            "should_remove_source_branch",
+            "merge_when_pipeline_succeeds",
+        ] {
+            if let Some(Value::Bool(value)) = args.get(flag) {
+                body_map.insert(flag.to_string(), json!(value));
+            }
+        }
+
+        let body = Value::Object(body_map);
 
         let mut headers = BTreeMap::new();
         headers.insert("PRIVATE-TOKEN".to_string(), token);
@@ -1267,70 +1602,37 @@ fn update_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
         };
 
         let res = http::request(&req, Some(&body.to_string()))?;
+        let response_body = String::from_utf8_lossy(&res.body()).to_string();
 
-        if is_success_status(res.status_code()) {
+        // GitLab answers with {"message": "405 Method Not Allowed"} when the merge
+        // request cannot be merged right now (draft, pipeline running, conflicts, ...)
+        let not_mergeable = res.status_code() == 405
+            || serde_json::from_str::<Value>(&response_body)
+                .ok()
+                .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(String::from))
+                .is_some_and(|m| m.starts_with("405"));
+
+        if not_mergeable {
             Ok(CallToolResult {
-                is_error: None,
+                is_error: Some(true),
                 content: vec![Content {
                     annotations: None,
-                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
-                    mime_type: Some("application/json".to_string()),
+                    text: Some(format!(
+                        "Merge request {} is not mergeable right now. It may be a draft, have unresolved discussions, conflicts, or a pipeline that is still running or has failed. Consider setting merge_when_pipeline_succeeds. Response: {}",
+                        merge_request_iid, response_body
+                    )),
+                    mime_type: None,
                     r#type: ContentType::Text,
                     data: None,
                 }],
             })
-        } else {
+        } else if is_success_status(res.status_code()) {
             Ok(CallToolResult {
-                is_error: Some(true),
+                is_error: None,
                 content: vec![Content {
                     annotations: None,
-                    text: Some(format!("Failed to update snippet: {}", res.status_code())),
-                    mime_type: None,
-                    r#type: ContentType::Text,
-                    data: None,
-                }],
-            })
-        }
-    } else {
-        Ok(CallToolResult {
-            is_error: Some(true),
-            content: vec![Content {
-                annotations: None,
-                text: Some("Please provide snippet_id, title, and content".into()),
-                mime_type: None,
-                r#type: ContentType::Text,
-                data: None,
-            }],
-        })
-    }
-}
-
-fn get_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
-    let args = input.params.arguments.clone().unwrap_or_default();
-    let (token, gitlab_url) = get_gitlab_config()?;
-
-    if let Some(Value::String(snippet_id)) = args.get("snippet_id") {
-        let url = format!("{}/snippets/{}", gitlab_url, snippet_id);
-
-        let mut headers = BTreeMap::new();
-        headers.insert("PRIVATE-TOKEN".to_string(), token);
-        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
-
-        let req = HttpRequest {
-            url,
-            headers,
-            method: Some("GET".to_string()),
-        };
-
-        let res = http::request::<()>(&req, None)?;
-
-        if is_success_status(res.status_code()) {
-            Ok(CallToolResult {
-                is_error: None,
-                content: vec![Content {
-                    annotations: None,
-                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
-                    mime_type: Some("application/json".to_string()),
+                    text: Some(response_body),
+                    mime_type: Some("application/json".to_string()),
                     r#type: ContentType::Text,
                     data: None,
                 }],
@@ -1340,7 +1642,11 @@ fn get_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
                 is_error: Some(true),
                 content: vec![Content {
                     annotations: None,
-                    text: Some(format!("Failed to get snippet: {}", res.status_code())),
+                    text: Some(format!(
+                        "Failed to merge merge request: {} - Response: {}",
+                        res.status_code(),
+                        response_body
+                    )),
                     mime_type: None,
                     r#type: ContentType::Text,
                     data: None,
@@ -1352,7 +1658,7 @@ fn get_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
             is_error: Some(true),
             content: vec![Content {
                 annotations: None,
-                text: Some("Please provide snippet_id".into()),
+                text: Some("Please provide project_id and merge_request_iid".into()),
                 mime_type: None,
                 r#type: ContentType::Text,
                 data: None,
@@ -1361,54 +1667,126 @@ fn get_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
-fn delete_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
+fn gl_list_merge_requests(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
 
-    if let Some(Value::String(snippet_id)) = args.get("snippet_id") {
-        let url = format!("{}/snippets/{}", gitlab_url, snippet_id);
+    if let Some(Value::String(project_id)) = args.get("project_id") {
+        let max_merge_requests = args
+            .get("max_merge_requests")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(500) as usize;
 
-        let mut headers = BTreeMap::new();
-        headers.insert("PRIVATE-TOKEN".to_string(), token);
-        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+        let mut all_merge_requests_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+        const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
 
-        let req = HttpRequest {
-            url,
-            headers,
-            method: Some("DELETE".to_string()),
-        };
+        loop {
+            let mut url_params = vec![
+                format!("per_page={}", PER_PAGE_COUNT),
+                format!("page={}", current_page_number),
+            ];
 
-        let res = http::request::<()>(&req, None)?;
+            for filter in [
+                "state",
+                "labels",
+                "author_username",
+                "reviewer_username",
+                "source_branch",
+                "target_branch",
+            ] {
+                if let Some(Value::String(value)) = args.get(filter) {
+                    url_params.push(format!("{}={}", filter, urlencoding::encode(value)));
+                }
+            }
 
-        if is_success_status(res.status_code()) {
-            Ok(CallToolResult {
-                is_error: None,
-                content: vec![Content {
-                    annotations: None,
-                    text: Some("Snippet deleted successfully".into()),
-                    mime_type: None,
-                    r#type: ContentType::Text,
-                    data: None,
-                }],
-            })
-        } else {
-            Ok(CallToolResult {
-                is_error: Some(true),
-                content: vec![Content {
-                    annotations: None,
-                    text: Some(format!("Failed to delete snippet: {}", res.status_code())),
-                    mime_type: None,
-                    r#type: ContentType::Text,
-                    data: None,
-                }],
-            })
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/merge_requests{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to list merge requests page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_merge_requests) => {
+                    let num_fetched = page_merge_requests.len();
+                    all_merge_requests_json.extend(page_merge_requests);
+
+                    if all_merge_requests_json.len() >= max_merge_requests {
+                        all_merge_requests_json.truncate(max_merge_requests);
+                        break; // Reached the requested cap
+                    }
+                    if num_fetched < PER_PAGE_COUNT as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse merge requests data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
         }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_merge_requests_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
     } else {
         Ok(CallToolResult {
             is_error: Some(true),
             content: vec![Content {
                 annotations: None,
-                text: Some("Please provide snippet_id".into()),
+                text: Some("Please provide project_id".into()),
                 mime_type: None,
                 r#type: ContentType::Text,
                 data: None,
@@ -1417,58 +1795,106 @@ fn delete_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
-fn gl_list_branches(input: CallToolRequest) -> Result<CallToolResult, Error> {
+fn create_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
 
-    if let Some(Value::String(project_id)) = args.get("project_id") {
-        let url = format!(
-            "{}/projects/{}/repository/branches",
-            gitlab_url,
-            urlencode_if_needed(project_id)
-        );
-
-        let mut headers = BTreeMap::new();
-        headers.insert("PRIVATE-TOKEN".to_string(), token);
-        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
-
-        let req = HttpRequest {
-            url,
-            headers,
-            method: Some("GET".to_string()),
-        };
-
-        let res = http::request::<()>(&req, None)?;
-
-        if is_success_status(res.status_code()) {
-            Ok(CallToolResult {
-                is_error: None,
-                content: vec![Content {
-                    annotations: None,
-                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
-                    mime_type: Some("application/json".to_string()),
-                    r#type: ContentType::Text,
-                    data: None,
-                }],
-            })
-        } else {
-            Ok(CallToolResult {
+    let title = match args.get("title") {
+        Some(Value::String(title))
+            if args.contains_key("files") || args.contains_key("content") =>
+        {
+            title
+        }
+        _ => {
+            return Ok(CallToolResult {
                 is_error: Some(true),
                 content: vec![Content {
                     annotations: None,
-                    text: Some(format!("Failed to list branches: {}", res.status_code())),
+                    text: Some("Please provide title and either content or files".into()),
                     mime_type: None,
                     r#type: ContentType::Text,
                     data: None,
                 }],
-            })
+            });
+        }
+    };
+
+    let url = format!("{}/snippets", gitlab_url);
+
+    // Get visibility from args or default to "private"
+    let visibility = args
+        .get("visibility")
+        .and_then(|v| v.as_str())
+        .unwrap_or("private");
+
+    let mut body_map = serde_json::Map::new();
+    body_map.insert("title".to_string(), json!(title));
+    body_map.insert("visibility".to_string(), json!(visibility));
+    if let Some(Value::String(description)) = args.get("description") {
+        body_map.insert("description".to_string(), json!(description));
+    }
+    if let Some(files) = args.get("files") {
+        match snippet_files(files, false) {
+            Ok(files) => {
+                body_map.insert("files".to_string(), files);
+            }
+            Err(e) => {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(e),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
         }
+    } else if let Some(Value::String(content)) = args.get("content") {
+        let file_name = match args.get("file_name") {
+            Some(Value::String(file_name)) => file_name.clone(),
+            _ => snippet_file_name(title),
+        };
+        body_map.insert("file_name".to_string(), json!(file_name));
+        body_map.insert("content".to_string(), json!(content));
+    }
+    let body = Value::Object(body_map);
+
+    let mut headers = BTreeMap::new();
+    headers.insert("PRIVATE-TOKEN".to_string(), token);
+    headers.insert("Content-Type".to_string(), "application/json".to_string());
+    headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+    let req = HttpRequest {
+        url,
+        headers,
+        method: Some("POST".to_string()),
+    };
+
+    let res = http::request(&req, Some(&body.to_string()))?;
+
+    if is_success_status(res.status_code()) {
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
     } else {
         Ok(CallToolResult {
             is_error: Some(true),
             content: vec![Content {
                 annotations: None,
-                text: Some("Please provide project_id".into()),
+                text: Some(format!(
+                    "Failed to create snippet: {} - Response: {}",
+                    res.status_code(),
+                    String::from_utf8_lossy(&res.body())
+                )),
                 mime_type: None,
                 r#type: ContentType::Text,
                 data: None,
@@ -1477,32 +1903,180 @@ fn gl_list_branches(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
-fn gl_list_issues(input: CallToolRequest) -> Result<CallToolResult, Error> {
+fn update_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
 
-    if let Some(Value::String(project_id)) = args.get("project_id") {
-        let mut url_params = vec![];
+    let has_changes = ["title", "content", "files", "description", "visibility"]
+        .iter()
+        .any(|key| args.contains_key(*key));
+    let snippet_id = match args.get("snippet_id") {
+        Some(Value::String(snippet_id)) if has_changes => snippet_id,
+        _ => {
+            return Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(
+                        "Please provide snippet_id and at least one of title, content, files, description, or visibility".into(),
+                    ),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+    };
+
+    let url = format!("{}/snippets/{}", gitlab_url, snippet_id);
 
-        if let Some(Value::String(state)) = args.get("state") {
-            url_params.push(format!("state={}", state));
+    let mut body_map = serde_json::Map::new();
+    for field in ["title", "description", "visibility"] {
+        if let Some(Value::String(value)) = args.get(field) {
+            body_map.insert(field.to_string(), json!(value));
         }
-        if let Some(Value::String(labels)) = args.get("labels") {
-            url_params.push(format!("labels={}", urlencoding::encode(labels)));
+    }
+    if let Some(files) = args.get("files") {
+        match snippet_files(files, true) {
+            Ok(files) => {
+                body_map.insert("files".to_string(), files);
+            }
+            Err(e) => {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(e),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+        }
+    } else if let Some(Value::String(content)) = args.get("content") {
+        // The legacy fields only work on single-file snippets
+        let file_name = match (args.get("file_name"), args.get("title")) {
+            (Some(Value::String(file_name)), _) => Some(file_name.clone()),
+            (_, Some(Value::String(title))) => Some(snippet_file_name(title)),
+            _ => None,
+        };
+        if let Some(file_name) = file_name {
+            body_map.insert("file_name".to_string(), json!(file_name));
         }
+        body_map.insert("content".to_string(), json!(content));
+    }
+    let body = Value::Object(body_map);
 
-        let query_string = if url_params.is_empty() {
-            "".to_string()
+    let mut headers = BTreeMap::new();
+    headers.insert("PRIVATE-TOKEN".to_string(), token);
+    headers.insert("Content-Type".to_string(), "application/json".to_string());
+    headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+    let req = HttpRequest {
+        url,
+        headers,
+        method: Some("PUT".to_string()),
+    };
+
+    let res = http::request(&req, Some(&body.to_string()))?;
+
+    if is_success_status(res.status_code()) {
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some(format!(
+                    "Failed to update snippet: {} - Response: {}",
+                    res.status_code(),
+                    String::from_utf8_lossy(&res.body())
+                )),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+/// File name given to the content of a single-file snippet, derived from its title
+fn snippet_file_name(title: &str) -> String {
+    format!("{}.txt", title.to_lowercase().replace(" ", "_"))
+}
+
+/// Checks the `files` argument of the snippet tools and converts it to the `files`
+/// field of the snippets API. Creating a snippet takes a file_path and content per
+/// file, updating one also takes the action to apply to each file.
+fn snippet_files(files: &Value, with_actions: bool) -> Result<Value, String> {
+    let Some(files) = files.as_array().filter(|files| !files.is_empty()) else {
+        return Err("Invalid files: expected a non-empty array of files".into());
+    };
+
+    let mut snippet_files = Vec::with_capacity(files.len());
+    for (i, file) in files.iter().enumerate() {
+        let Some(file_path) = file["file_path"].as_str() else {
+            return Err(format!("Invalid files[{}]: file_path is required", i));
+        };
+        let action = if with_actions {
+            match file["action"].as_str() {
+                Some(action @ ("create" | "update" | "delete" | "move")) => Some(action),
+                _ => {
+                    return Err(format!(
+                        "Invalid files[{}]: action must be one of create, update, delete, or move",
+                        i
+                    ));
+                }
+            }
         } else {
-            format!("?{}", url_params.join("&"))
+            None
         };
 
-        let url = format!(
-            "{}/projects/{}/issues{}",
-            gitlab_url,
-            urlencode_if_needed(project_id),
-            query_string
-        );
+        let mut snippet_file = serde_json::Map::new();
+        snippet_file.insert("file_path".to_string(), json!(file_path));
+        if let Some(action) = action {
+            snippet_file.insert("action".to_string(), json!(action));
+        }
+        match file["content"].as_str() {
+            Some(content) => {
+                snippet_file.insert("content".to_string(), json!(content));
+            }
+            None if matches!(action, None | Some("create") | Some("update")) => {
+                return Err(format!("Invalid files[{}]: content is required", i));
+            }
+            None => {}
+        }
+        if action == Some("move") {
+            let Some(previous_path) = file["previous_path"].as_str() else {
+                return Err(format!(
+                    "Invalid files[{}]: previous_path is required to move a file",
+                    i
+                ));
+            };
+            snippet_file.insert("previous_path".to_string(), json!(previous_path));
+        }
+        snippet_files.push(Value::Object(snippet_file));
+    }
+
+    Ok(Value::Array(snippet_files))
+}
+
+fn get_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(snippet_id)) = args.get("snippet_id") {
+        let url = format!("{}/snippets/{}", gitlab_url, snippet_id);
 
         let mut headers = BTreeMap::new();
         headers.insert("PRIVATE-TOKEN".to_string(), token);
@@ -1532,7 +2106,7 @@ fn gl_list_issues(input: CallToolRequest) -> Result<CallToolResult, Error> {
                 is_error: Some(true),
                 content: vec![Content {
                     annotations: None,
-                    text: Some(format!("Failed to list issues: {}", res.status_code())),
+                    text: Some(format!("Failed to get snippet: {}", res.status_code())),
                     mime_type: None,
                     r#type: ContentType::Text,
                     data: None,
@@ -1544,7 +2118,7 @@ fn gl_list_issues(input: CallToolRequest) -> Result<CallToolResult, Error> {
             is_error: Some(true),
             content: vec![Content {
                 annotations: None,
-                text: Some("Please provide project_id".into()),
+                text: Some("Please provide snippet_id".into()),
                 mime_type: None,
                 r#type: ContentType::Text,
                 data: None,
@@ -1553,137 +2127,107 @@ fn gl_list_issues(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
-fn gl_get_repo_tree(input: CallToolRequest) -> Result<CallToolResult, Error> {
+fn delete_snippet(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
 
-    if let Some(Value::String(project_id_val)) = args.get("project_id") {
-        let project_id = project_id_val.as_str();
-        let requested_path_opt = args.get("path").and_then(|v| v.as_str());
-        let ref_name_opt = args.get("ref").and_then(|v| v.as_str());
-        let recursive_opt = args.get("recursive").and_then(|v| v.as_bool());
-
-        let mut all_entries: Vec<GitLabRepoEntry> = Vec::new();
-        let mut current_page_number: u32 = 1;
-        const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
-        const MAX_PAGES: u32 = 100; // Safety break: 100 pages * 100 items/page = 10,000 items
-
-        loop {
-            if current_page_number > MAX_PAGES {
-                // Log this or return a partial result with a warning if desired
-                // For now, just break and use what we have.
-                // Consider returning an error if this limit is hit.
-                break;
-            }
+    if let Some(Value::String(snippet_id)) = args.get("snippet_id") {
+        let url = format!("{}/snippets/{}", gitlab_url, snippet_id);
 
-            let mut url_params = vec![
-                format!("per_page={}", PER_PAGE_COUNT),
-                format!("page={}", current_page_number),
-            ];
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
 
-            if let Some(path_str) = requested_path_opt {
-                if !path_str.is_empty() {
-                    url_params.push(format!("path={}", urlencoding::encode(path_str)));
-                }
-            }
-            if let Some(ref_name_str) = ref_name_opt {
-                url_params.push(format!("ref={}", urlencoding::encode(ref_name_str)));
-            }
-            if let Some(recursive_bool) = recursive_opt {
-                if recursive_bool {
-                    url_params.push("recursive=true".to_string());
-                }
-            }
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("DELETE".to_string()),
+        };
 
-            let query_string = format!("?{}", url_params.join("&"));
-            let url = format!(
-                "{}/projects/{}/repository/tree{}",
-                gitlab_url,
-                urlencode_if_needed(project_id),
-                query_string
-            );
+        let res = http::request::<()>(&req, None)?;
 
-            let mut headers = BTreeMap::new();
-            headers.insert("PRIVATE-TOKEN".to_string(), token.clone()); // Clone token for loop
-            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some("Snippet deleted successfully".into()),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Failed to delete snippet: {}", res.status_code())),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide snippet_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
 
-            let req = HttpRequest {
-                url: url.clone(),
-                headers,
-                method: Some("GET".to_string()),
-            };
+fn gl_list_branches(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
 
-            let res = http::request::<()>(&req, None)?;
+    if let Some(Value::String(project_id)) = args.get("project_id") {
+        let url = format!(
+            "{}/projects/{}/repository/branches",
+            gitlab_url,
+            urlencode_if_needed(project_id)
+        );
 
-            if !is_success_status(res.status_code()) {
-                return Ok(CallToolResult {
-                    is_error: Some(true),
-                    content: vec![Content {
-                        annotations: None,
-                        text: Some(format!(
-                            "Failed to get repository tree page {} from {}: {} - Response: {}",
-                            current_page_number,
-                            req.url,
-                            res.status_code(),
-                            String::from_utf8_lossy(&res.body())
-                        )),
-                        mime_type: None,
-                        r#type: ContentType::Text,
-                        data: None,
-                    }],
-                });
-            }
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
 
-            match serde_json::from_slice::<Vec<GitLabRepoEntry>>(&res.body()) {
-                Ok(page_entries) => {
-                    let num_fetched = page_entries.len();
-                    all_entries.extend(page_entries);
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
 
-                    if num_fetched < PER_PAGE_COUNT as usize {
-                        break; // Last page fetched
-                    }
-                }
-                Err(e) => {
-                    return Ok(CallToolResult {
-                        is_error: Some(true),
-                        content: vec![Content {
-                            annotations: None,
-                            text: Some(format!(
-                                "Failed to parse repository tree data from GitLab API (page {}): {}",
-                                current_page_number, e
-                            )),
-                            mime_type: None,
-                            r#type: ContentType::Text,
-                            data: None,
-                        }],
-                    });
-                }
-            }
-            current_page_number += 1;
-        }
+        let res = http::request::<()>(&req, None)?;
 
-        // Proceed with building the tree from all_entries
-        match build_and_format_tree_from_entries(all_entries, requested_path_opt, project_id) {
-            Ok(tree_string) => Ok(CallToolResult {
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
                 is_error: None,
                 content: vec![Content {
                     annotations: None,
-                    text: Some(tree_string),
-                    mime_type: Some("text/plain".to_string()),
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
                     r#type: ContentType::Text,
                     data: None,
                 }],
-            }),
-            Err(e_str) => Ok(CallToolResult {
+            })
+        } else {
+            Ok(CallToolResult {
                 is_error: Some(true),
                 content: vec![Content {
                     annotations: None,
-                    text: Some(e_str),
+                    text: Some(format!("Failed to list branches: {}", res.status_code())),
                     mime_type: None,
                     r#type: ContentType::Text,
                     data: None,
                 }],
-            }),
+            })
         }
     } else {
         Ok(CallToolResult {
@@ -1699,50 +2243,303 @@ fn gl_get_repo_tree(input: CallToolRequest) -> Result<CallToolResult, Error> {
     }
 }
 
-fn gl_get_repo_members(input: CallToolRequest) -> Result<CallToolResult, Error> {
+fn gl_get_protected_branch(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.clone().unwrap_or_default();
     let (token, gitlab_url) = get_gitlab_config()?;
 
-    if let Some(Value::String(project_id_val)) = args.get("project_id") {
-        let project_id = project_id_val.as_str();
-        let include_inherited = args
-            .get("include_inherited_members")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-        let query_opt = args.get("query").and_then(|v| v.as_str());
+    if let (Some(Value::String(project_id)), Some(Value::String(branch))) =
+        (args.get("project_id"), args.get("branch"))
+    {
+        let url = format!(
+            "{}/projects/{}/protected_branches/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(branch)
+        );
 
-        let members_path = if include_inherited {
-            "members/all"
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else if res.status_code() == 404 {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Branch {} is not protected, or the project does not exist",
+                        branch
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
         } else {
-            "members"
+            Ok(protected_branch_failure(
+                "get protected branch",
+                res.status_code(),
+                &res.body(),
+            ))
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and branch".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_protect_branch(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(branch))) =
+        (args.get("project_id"), args.get("branch"))
+    {
+        let url = format!(
+            "{}/projects/{}/protected_branches",
+            gitlab_url,
+            urlencode_if_needed(project_id)
+        );
+
+        let mut body_map = serde_json::Map::new();
+        body_map.insert("name".to_string(), json!(branch));
+        for level in ["push_access_level", "merge_access_level"] {
+            if let Some(value) = args.get(level) {
+                match value.as_u64().filter(|v| ACCESS_LEVELS.contains(v)) {
+                    Some(access_level) => {
+                        body_map.insert(level.to_string(), json!(access_level));
+                    }
+                    None => {
+                        return Ok(CallToolResult {
+                            is_error: Some(true),
+                            content: vec![Content {
+                                annotations: None,
+                                text: Some(format!(
+                                    "Invalid {} {}: expected 0 (No access), 30 (Developer), 40 (Maintainer), or 60 (Admin)",
+                                    level, value
+                                )),
+                                mime_type: None,
+                                r#type: ContentType::Text,
+                                data: None,
+                            }],
+                        });
+                    }
+                }
+            }
+        }
+        if let Some(Value::Bool(allow_force_push)) = args.get("allow_force_push") {
+            body_map.insert("allow_force_push".to_string(), json!(allow_force_push));
+        }
+        let body = Value::Object(body_map);
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("POST".to_string()),
         };
 
-        let mut all_members_json: Vec<Value> = Vec::new();
+        let res = http::request(&req, Some(&body.to_string()))?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else if res.status_code() == 409 {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Branch {} is already protected. Unprotect it first to change its access levels.",
+                        branch
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(protected_branch_failure(
+                "protect branch",
+                res.status_code(),
+                &res.body(),
+            ))
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and branch".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_unprotect_branch(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(branch))) =
+        (args.get("project_id"), args.get("branch"))
+    {
+        let url = format!(
+            "{}/projects/{}/protected_branches/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(branch)
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("DELETE".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Branch {} unprotected successfully", branch)),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(protected_branch_failure(
+                "unprotect branch",
+                res.status_code(),
+                &res.body(),
+            ))
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and branch".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+// GitLab access levels accepted for protected branches: No access, Developer,
+// Maintainer and Admin
+const ACCESS_LEVELS: [u64; 4] = [0, 30, 40, 60];
+
+/// Error result of a protected branch request, spelling out the missing permission on 403
+fn protected_branch_failure(action: &str, status_code: u16, body: &[u8]) -> CallToolResult {
+    let text = if status_code == 403 {
+        format!(
+            "Failed to {}: the GitLab token lacks permission. Managing protected branches requires at least the Maintainer role on the project. Response: {}",
+            action,
+            String::from_utf8_lossy(body)
+        )
+    } else {
+        format!(
+            "Failed to {}: {} - Response: {}",
+            action,
+            status_code,
+            String::from_utf8_lossy(body)
+        )
+    };
+
+    CallToolResult {
+        is_error: Some(true),
+        content: vec![Content {
+            annotations: None,
+            text: Some(text),
+            mime_type: None,
+            r#type: ContentType::Text,
+            data: None,
+        }],
+    }
+}
+
+fn gl_list_issues(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(project_id)) = args.get("project_id") {
+        // With an explicit page, return just that page and let the caller paginate
+        if let Some(page) = args.get("page").and_then(|v| v.as_u64()) {
+            return list_issues_page(&args, project_id, page, &token, &gitlab_url);
+        }
+
+        let max_issues = args
+            .get("max_issues")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(500) as usize;
+
+        let mut all_issues_json: Vec<Value> = Vec::new();
         let mut current_page_number: u32 = 1;
         const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
-        const MAX_PAGES: u32 = 100; // Safety break: 100 pages * 100 items/page = 10,000 members
 
         loop {
-            if current_page_number > MAX_PAGES {
-                // Log this or return a partial result with a warning if desired
-                break;
-            }
-
             let mut url_params = vec![
                 format!("per_page={}", PER_PAGE_COUNT),
                 format!("page={}", current_page_number),
             ];
-
-            if let Some(query_str) = query_opt {
-                url_params.push(format!("query={}", urlencoding::encode(query_str)));
-            }
+            url_params.extend(issue_filter_params(&args));
 
             let query_string = format!("?{}", url_params.join("&"));
             let url = format!(
-                "{}/projects/{}/{}{}",
+                "{}/projects/{}/issues{}",
                 gitlab_url,
                 urlencode_if_needed(project_id),
-                members_path,
                 query_string
             );
 
@@ -1764,7 +2561,7 @@ fn gl_get_repo_members(input: CallToolRequest) -> Result<CallToolResult, Error>
                     content: vec![Content {
                         annotations: None,
                         text: Some(format!(
-                            "Failed to get repository members page {} from {}: {} - Response: {}",
+                            "Failed to list issues page {} from {}: {} - Response: {}",
                             current_page_number,
                             req.url,
                             res.status_code(),
@@ -1778,10 +2575,14 @@ fn gl_get_repo_members(input: CallToolRequest) -> Result<CallToolResult, Error>
             }
 
             match serde_json::from_slice::<Vec<Value>>(&res.body()) {
-                Ok(page_members) => {
-                    let num_fetched = page_members.len();
-                    all_members_json.extend(page_members);
+                Ok(page_issues) => {
+                    let num_fetched = page_issues.len();
+                    all_issues_json.extend(page_issues);
 
+                    if all_issues_json.len() >= max_issues {
+                        all_issues_json.truncate(max_issues);
+                        break; // Reached the requested cap
+                    }
                     if num_fetched < PER_PAGE_COUNT as usize {
                         break; // Last page fetched
                     }
@@ -1792,7 +2593,7 @@ fn gl_get_repo_members(input: CallToolRequest) -> Result<CallToolResult, Error>
                         content: vec![Content {
                             annotations: None,
                             text: Some(format!(
-                                "Failed to parse repository members data from GitLab API (page {}): {}",
+                                "Failed to parse issues data from GitLab API (page {}): {}",
                                 current_page_number, e
                             )),
                             mime_type: None,
@@ -1809,7 +2610,7 @@ fn gl_get_repo_members(input: CallToolRequest) -> Result<CallToolResult, Error>
             is_error: None,
             content: vec![Content {
                 annotations: None,
-                text: Some(serde_json::to_string(&all_members_json)?),
+                text: Some(serde_json::to_string(&all_issues_json)?),
                 mime_type: Some("application/json".to_string()),
                 r#type: ContentType::Text,
                 data: None,
@@ -1829,49 +2630,3157 @@ fn gl_get_repo_members(input: CallToolRequest) -> Result<CallToolResult, Error>
     }
 }
 
-pub(crate) fn describe() -> Result<ListToolsResult, Error> {
-    Ok(ListToolsResult {
-        tools: vec![
-            ToolDescription {
-                name: "gl_delete_file".into(),
-                description: "Delete a file in a GitLab project repository. Requires project_id, file_path, branch, and optional commit_message.".into(),
-                input_schema: json!({
-                    "type": "object",
-                    "properties": {
-                        "project_id": {
-                            "type": "string",
-                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
-                        },
-                        "file_path": {
-                            "type": "string",
-                            "description": "The path to the file in the project",
-                        },
-                        "branch": {
-                            "type": "string",
-                            "description": "The name of the branch to delete the file from",
-                        },
-                        "commit_message": {
+/// Query parameters for the `state` and `labels` filters of `gl_list_issues`
+fn issue_filter_params(args: &serde_json::Map<String, Value>) -> Vec<String> {
+    let mut url_params = Vec::new();
+    if let Some(Value::String(state)) = args.get("state") {
+        url_params.push(format!("state={}", state));
+    }
+    if let Some(Value::String(labels)) = args.get("labels") {
+        url_params.push(format!("labels={}", urlencoding::encode(labels)));
+    }
+    url_params
+}
+
+/// Fetches a single page of issues, with `has_more` and `next_page` so the caller
+/// can request the following one.
+fn list_issues_page(
+    args: &serde_json::Map<String, Value>,
+    project_id: &str,
+    page: u64,
+    token: &str,
+    gitlab_url: &str,
+) -> Result<CallToolResult, Error> {
+    let page = page.max(1);
+    let per_page = args
+        .get("per_page")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(20)
+        .clamp(1, 100);
+
+    let mut url_params = vec![format!("per_page={}", per_page), format!("page={}", page)];
+    url_params.extend(issue_filter_params(args));
+    let url = format!(
+        "{}/projects/{}/issues?{}",
+        gitlab_url,
+        urlencode_if_needed(project_id),
+        url_params.join("&")
+    );
+
+    let mut headers = BTreeMap::new();
+    headers.insert("PRIVATE-TOKEN".to_string(), token.to_string());
+    headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+    let req = HttpRequest {
+        url,
+        headers,
+        method: Some("GET".to_string()),
+    };
+
+    let res = http::request::<()>(&req, None)?;
+
+    if !is_success_status(res.status_code()) {
+        return Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some(format!(
+                    "Failed to list issues page {} from {}: {} - Response: {}",
+                    page,
+                    req.url,
+                    res.status_code(),
+                    String::from_utf8_lossy(&res.body())
+                )),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        });
+    }
+
+    let issues = match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+        Ok(issues) => issues,
+        Err(e) => {
+            return Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to parse issues data from GitLab API (page {}): {}",
+                        page, e
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+    };
+
+    // GitLab sends an empty X-Next-Page header on the last page. Fall back to
+    // checking for a full page in case a proxy strips the header.
+    let next_page = match res
+        .headers()
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("x-next-page"))
+    {
+        Some((_, value)) => value.trim().parse::<u64>().ok(),
+        None => (issues.len() as u64 == per_page).then_some(page + 1),
+    };
+
+    let result = json!({
+        "issues": issues,
+        "page": page,
+        "per_page": per_page,
+        "has_more": next_page.is_some(),
+        "next_page": next_page,
+    });
+
+    Ok(CallToolResult {
+        is_error: None,
+        content: vec![Content {
+            annotations: None,
+            text: Some(serde_json::to_string(&result)?),
+            mime_type: Some("application/json".to_string()),
+            r#type: ContentType::Text,
+            data: None,
+        }],
+    })
+}
+
+fn gl_list_pipelines(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(project_id)) = args.get("project_id") {
+        let per_page = args
+            .get("per_page")
+            .and_then(|v| v.as_u64())
+            .map(|v| v.clamp(1, 100) as u32)
+            .unwrap_or(100);
+        let max_pipelines = args
+            .get("max_pipelines")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(500) as usize;
+
+        let mut all_pipelines_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+
+        loop {
+            let mut url_params = vec![
+                format!("per_page={}", per_page),
+                format!("page={}", current_page_number),
+            ];
+
+            for filter in ["status", "ref", "sha", "username"] {
+                if let Some(Value::String(value)) = args.get(filter) {
+                    url_params.push(format!("{}={}", filter, urlencoding::encode(value)));
+                }
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/pipelines{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to list pipelines page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_pipelines) => {
+                    let num_fetched = page_pipelines.len();
+                    all_pipelines_json.extend(page_pipelines);
+
+                    if all_pipelines_json.len() >= max_pipelines {
+                        all_pipelines_json.truncate(max_pipelines);
+                        break; // Reached the requested cap
+                    }
+                    if num_fetched < per_page as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse pipelines data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_pipelines_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_get_pipeline(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(pipeline_id))) =
+        (args.get("project_id"), args.get("pipeline_id"))
+    {
+        let url = format!(
+            "{}/projects/{}/pipelines/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            pipeline_id
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to get pipeline: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and pipeline_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_trigger_pipeline(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(ref_name))) =
+        (args.get("project_id"), args.get("ref"))
+    {
+        let url = format!(
+            "{}/projects/{}/pipeline",
+            gitlab_url,
+            urlencode_if_needed(project_id)
+        );
+
+        let mut body = json!({
+            "ref": ref_name,
+        });
+
+        // Add variables if provided, each one must be a {key, value} object
+        if let Some(variables) = args.get("variables") {
+            let valid = variables.as_array().is_some_and(|vars| {
+                vars.iter().all(|var| {
+                    var.get("key").is_some_and(|k| k.is_string())
+                        && var.get("value").is_some_and(|v| v.is_string())
+                })
+            });
+            if !valid {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(
+                            "variables must be an array of {\"key\": string, \"value\": string} objects"
+                                .into(),
+                        ),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+            body.as_object_mut()
+                .unwrap()
+                .insert("variables".to_string(), variables.clone());
+        }
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("POST".to_string()),
+        };
+
+        let res = http::request(&req, Some(&body.to_string()))?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to trigger pipeline: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and ref".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_cancel_pipeline(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(pipeline_id))) =
+        (args.get("project_id"), args.get("pipeline_id"))
+    {
+        let url = format!(
+            "{}/projects/{}/pipelines/{}/cancel",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            pipeline_id
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("POST".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to cancel pipeline: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and pipeline_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_list_pipeline_jobs(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(pipeline_id))) =
+        (args.get("project_id"), args.get("pipeline_id"))
+    {
+        let scope_opt = args.get("scope").and_then(|v| v.as_str());
+
+        let mut all_jobs_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+        const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
+        const MAX_PAGES: u32 = 100; // Safety break: 100 pages * 100 items/page = 10,000 jobs
+
+        loop {
+            if current_page_number > MAX_PAGES {
+                break;
+            }
+
+            let mut url_params = vec![
+                format!("per_page={}", PER_PAGE_COUNT),
+                format!("page={}", current_page_number),
+            ];
+
+            if let Some(scope_str) = scope_opt {
+                url_params.push(format!("scope={}", urlencoding::encode(scope_str)));
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/pipelines/{}/jobs{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                pipeline_id,
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to list pipeline jobs page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_jobs) => {
+                    let num_fetched = page_jobs.len();
+                    all_jobs_json.extend(page_jobs);
+
+                    if num_fetched < PER_PAGE_COUNT as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse pipeline jobs data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_jobs_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and pipeline_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_get_job_log(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(job_id))) =
+        (args.get("project_id"), args.get("job_id"))
+    {
+        let max_bytes = args
+            .get("max_bytes")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize);
+
+        let url = format!(
+            "{}/projects/{}/jobs/{}/trace",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            job_id
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            let body = res.body();
+            // Keep the end of the log, that's where failures usually are
+            let text = match max_bytes {
+                Some(max_bytes) if body.len() > max_bytes => format!(
+                    "[log truncated: showing the last {} of {} bytes]\n{}",
+                    max_bytes,
+                    body.len(),
+                    String::from_utf8_lossy(&body[body.len() - max_bytes..])
+                ),
+                _ => String::from_utf8_lossy(&body).to_string(),
+            };
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(text),
+                    mime_type: Some("text/plain".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to get job log: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and job_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_get_repo_tree(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(project_id_val)) = args.get("project_id") {
+        let project_id = project_id_val.as_str();
+        let requested_path_opt = args.get("path").and_then(|v| v.as_str());
+        let ref_name_opt = args.get("ref").and_then(|v| v.as_str());
+        let recursive_opt = args.get("recursive").and_then(|v| v.as_bool());
+
+        let mut all_entries: Vec<GitLabRepoEntry> = Vec::new();
+        let mut current_page_number: u32 = 1;
+        const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
+        const MAX_PAGES: u32 = 100; // Safety break: 100 pages * 100 items/page = 10,000 items
+
+        loop {
+            if current_page_number > MAX_PAGES {
+                // Log this or return a partial result with a warning if desired
+                // For now, just break and use what we have.
+                // Consider returning an error if this limit is hit.
+                break;
+            }
+
+            let mut url_params = vec![
+                format!("per_page={}", PER_PAGE_COUNT),
+                format!("page={}", current_page_number),
+            ];
+
+            if let Some(path_str) = requested_path_opt {
+                if !path_str.is_empty() {
+                    url_params.push(format!("path={}", urlencoding::encode(path_str)));
+                }
+            }
+            if let Some(ref_name_str) = ref_name_opt {
+                url_params.push(format!("ref={}", urlencoding::encode(ref_name_str)));
+            }
+            if let Some(recursive_bool) = recursive_opt {
+                if recursive_bool {
+                    url_params.push("recursive=true".to_string());
+                }
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/repository/tree{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone()); // Clone token for loop
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to get repository tree page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<GitLabRepoEntry>>(&res.body()) {
+                Ok(page_entries) => {
+                    let num_fetched = page_entries.len();
+                    all_entries.extend(page_entries);
+
+                    if num_fetched < PER_PAGE_COUNT as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse repository tree data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        // Proceed with building the tree from all_entries
+        match build_and_format_tree_from_entries(all_entries, requested_path_opt, project_id) {
+            Ok(tree_string) => Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(tree_string),
+                    mime_type: Some("text/plain".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            }),
+            Err(e_str) => Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(e_str),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            }),
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_get_repo_members(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(project_id_val)) = args.get("project_id") {
+        let project_id = project_id_val.as_str();
+        let include_inherited = args
+            .get("include_inherited_members")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let query_opt = args.get("query").and_then(|v| v.as_str());
+
+        let members_path = if include_inherited {
+            "members/all"
+        } else {
+            "members"
+        };
+
+        let mut all_members_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+        const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
+        const MAX_PAGES: u32 = 100; // Safety break: 100 pages * 100 items/page = 10,000 members
+
+        loop {
+            if current_page_number > MAX_PAGES {
+                // Log this or return a partial result with a warning if desired
+                break;
+            }
+
+            let mut url_params = vec![
+                format!("per_page={}", PER_PAGE_COUNT),
+                format!("page={}", current_page_number),
+            ];
+
+            if let Some(query_str) = query_opt {
+                url_params.push(format!("query={}", urlencoding::encode(query_str)));
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/{}{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                members_path,
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to get repository members page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_members) => {
+                    let num_fetched = page_members.len();
+                    all_members_json.extend(page_members);
+
+                    if num_fetched < PER_PAGE_COUNT as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse repository members data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_members_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_search_code(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(query))) =
+        (args.get("project_id"), args.get("query"))
+    {
+        let ref_name_opt = args.get("ref").and_then(|v| v.as_str());
+        let max_results = args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(50) as usize;
+
+        let mut all_results_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+        const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
+
+        loop {
+            let mut url_params = vec![
+                "scope=blobs".to_string(),
+                format!("search={}", urlencoding::encode(query)),
+                format!("per_page={}", PER_PAGE_COUNT),
+                format!("page={}", current_page_number),
+            ];
+
+            if let Some(ref_name_str) = ref_name_opt {
+                url_params.push(format!("ref={}", urlencoding::encode(ref_name_str)));
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/search{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                let response_body = String::from_utf8_lossy(&res.body()).to_string();
+                // Without advanced search (Elasticsearch/Zoekt) GitLab rejects the blobs scope
+                let text = if res.status_code() == 400
+                    && response_body.to_lowercase().contains("scope")
+                {
+                    format!(
+                        "Code search is not available for project {}. The GitLab instance or project does not have advanced search enabled. Response: {}",
+                        project_id, response_body
+                    )
+                } else {
+                    format!(
+                        "Failed to search code page {} from {}: {} - Response: {}",
+                        current_page_number,
+                        req.url,
+                        res.status_code(),
+                        response_body
+                    )
+                };
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(text),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_results) => {
+                    let num_fetched = page_results.len();
+                    all_results_json.extend(page_results);
+
+                    if all_results_json.len() >= max_results {
+                        all_results_json.truncate(max_results);
+                        break; // Reached the requested cap
+                    }
+                    if num_fetched < PER_PAGE_COUNT as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse code search data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_results_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and query".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_list_labels(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(project_id)) = args.get("project_id") {
+        let search_opt = args.get("search").and_then(|v| v.as_str());
+
+        let mut all_labels_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+        const PER_PAGE_COUNT: u32 = 100; // GitLab's typical max per_page
+        const MAX_PAGES: u32 = 100; // Safety break: 100 pages * 100 items/page = 10,000 labels
+
+        loop {
+            if current_page_number > MAX_PAGES {
+                break;
+            }
+
+            let mut url_params = vec![
+                "with_counts=true".to_string(),
+                format!("per_page={}", PER_PAGE_COUNT),
+                format!("page={}", current_page_number),
+            ];
+
+            if let Some(search_str) = search_opt {
+                url_params.push(format!("search={}", urlencoding::encode(search_str)));
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/labels{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to list labels page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_labels) => {
+                    let num_fetched = page_labels.len();
+                    all_labels_json.extend(page_labels);
+
+                    if num_fetched < PER_PAGE_COUNT as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse labels data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_labels_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_create_label(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (
+        Some(Value::String(project_id)),
+        Some(Value::String(name)),
+        Some(Value::String(color)),
+    ) = (args.get("project_id"), args.get("name"), args.get("color"))
+    {
+        if !is_valid_hex_color(color) {
+            return Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Invalid color '{}': expected a hex color in the form #RRGGBB",
+                        color
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+
+        let url = format!(
+            "{}/projects/{}/labels",
+            gitlab_url,
+            urlencode_if_needed(project_id)
+        );
+
+        let mut body_map = serde_json::Map::new();
+        body_map.insert("name".to_string(), json!(name));
+        body_map.insert("color".to_string(), json!(color));
+        if let Some(Value::String(description)) = args.get("description") {
+            body_map.insert("description".to_string(), json!(description));
+        }
+        let body = Value::Object(body_map);
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("POST".to_string()),
+        };
+
+        let res = http::request(&req, Some(&body.to_string()))?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to create label: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id, name, and color".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_update_label(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(label_id))) =
+        (args.get("project_id"), args.get("label_id"))
+    {
+        let url = format!(
+            "{}/projects/{}/labels/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(label_id)
+        );
+
+        let mut body_map = serde_json::Map::new();
+        if let Some(Value::String(new_name)) = args.get("new_name") {
+            body_map.insert("new_name".to_string(), json!(new_name));
+        }
+        if let Some(Value::String(color)) = args.get("color") {
+            if !is_valid_hex_color(color) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Invalid color '{}': expected a hex color in the form #RRGGBB",
+                            color
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+            body_map.insert("color".to_string(), json!(color));
+        }
+        if let Some(Value::String(description)) = args.get("description") {
+            body_map.insert("description".to_string(), json!(description));
+        }
+
+        if body_map.is_empty() {
+            return Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some("Please provide at least one field to update (new_name, color, or description)".into()),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+
+        let body = Value::Object(body_map);
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("PUT".to_string()),
+        };
+
+        let res = http::request(&req, Some(&body.to_string()))?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to update label: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and label_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_delete_label(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(label_id))) =
+        (args.get("project_id"), args.get("label_id"))
+    {
+        let url = format!(
+            "{}/projects/{}/labels/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(label_id)
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("DELETE".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some("Label deleted successfully".into()),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to delete label: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and label_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_list_commits(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(project_id)) = args.get("project_id") {
+        let per_page = args
+            .get("per_page")
+            .and_then(|v| v.as_u64())
+            .map(|v| v.clamp(1, 100) as u32)
+            .unwrap_or(100);
+        let max_commits = args
+            .get("max_commits")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(500) as usize;
+
+        let mut all_commits_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+
+        loop {
+            let mut url_params = vec![
+                format!("per_page={}", per_page),
+                format!("page={}", current_page_number),
+            ];
+
+            for filter in ["ref_name", "since", "until", "path"] {
+                if let Some(Value::String(value)) = args.get(filter) {
+                    url_params.push(format!("{}={}", filter, urlencoding::encode(value)));
+                }
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/repository/commits{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to list commits page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_commits) => {
+                    let num_fetched = page_commits.len();
+                    all_commits_json.extend(page_commits);
+
+                    if all_commits_json.len() >= max_commits {
+                        all_commits_json.truncate(max_commits);
+                        break; // Reached the requested cap
+                    }
+                    if num_fetched < per_page as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse commits data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_commits_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_get_commit(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(sha))) =
+        (args.get("project_id"), args.get("sha"))
+    {
+        let url = format!(
+            "{}/projects/{}/repository/commits/{}?stats=true",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(sha)
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to get commit: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and sha".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_compare_refs(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(from)), Some(Value::String(to))) =
+        (args.get("project_id"), args.get("from"), args.get("to"))
+    {
+        let mut url_params = vec![
+            format!("from={}", urlencoding::encode(from)),
+            format!("to={}", urlencoding::encode(to)),
+        ];
+
+        if let Some(Value::Bool(straight)) = args.get("straight") {
+            url_params.push(format!("straight={}", straight));
+        }
+
+        let url = format!(
+            "{}/projects/{}/repository/compare?{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            url_params.join("&")
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to compare refs: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id, from, and to".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_create_tag(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (
+        Some(Value::String(project_id)),
+        Some(Value::String(tag_name)),
+        Some(Value::String(ref_name)),
+    ) = (
+        args.get("project_id"),
+        args.get("tag_name"),
+        args.get("ref"),
+    ) {
+        let url = format!(
+            "{}/projects/{}/repository/tags",
+            gitlab_url,
+            urlencode_if_needed(project_id)
+        );
+
+        let mut body_map = serde_json::Map::new();
+        body_map.insert("tag_name".to_string(), json!(tag_name));
+        body_map.insert("ref".to_string(), json!(ref_name));
+        if let Some(Value::String(message)) = args.get("message") {
+            body_map.insert("message".to_string(), json!(message));
+        }
+        let body = Value::Object(body_map);
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("POST".to_string()),
+        };
+
+        let res = http::request(&req, Some(&body.to_string()))?;
+
+        if !is_success_status(res.status_code()) {
+            return Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to create tag: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        }
+
+        // GitLab dropped release_description from the tags API, so the release is
+        // created separately
+        let Some(Value::String(release_description)) = args.get("release_description") else {
+            return Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            });
+        };
+        let tag: Value = serde_json::from_slice(&res.body())?;
+
+        let release_body = json!({
+            "tag_name": tag_name,
+            "description": release_description,
+        });
+        let release_res = create_release_request(project_id, &release_body, &token, &gitlab_url)?;
+
+        if is_success_status(release_res.status_code()) {
+            let release: Value = serde_json::from_slice(&release_res.body())?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(json!({ "tag": tag, "release": release }).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Tag {} was created, but creating its release failed: {} - Response: {}",
+                        tag_name,
+                        release_res.status_code(),
+                        String::from_utf8_lossy(&release_res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id, tag_name, and ref".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_list_tags(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let Some(Value::String(project_id)) = args.get("project_id") {
+        let per_page = args
+            .get("per_page")
+            .and_then(|v| v.as_u64())
+            .map(|v| v.clamp(1, 100) as u32)
+            .unwrap_or(100);
+        let max_tags = args.get("max_tags").and_then(|v| v.as_u64()).unwrap_or(500) as usize;
+
+        let mut all_tags_json: Vec<Value> = Vec::new();
+        let mut current_page_number: u32 = 1;
+
+        loop {
+            let mut url_params = vec![
+                format!("per_page={}", per_page),
+                format!("page={}", current_page_number),
+            ];
+
+            for filter in ["search", "order_by", "sort"] {
+                if let Some(Value::String(value)) = args.get(filter) {
+                    url_params.push(format!("{}={}", filter, urlencoding::encode(value)));
+                }
+            }
+
+            let query_string = format!("?{}", url_params.join("&"));
+            let url = format!(
+                "{}/projects/{}/repository/tags{}",
+                gitlab_url,
+                urlencode_if_needed(project_id),
+                query_string
+            );
+
+            let mut headers = BTreeMap::new();
+            headers.insert("PRIVATE-TOKEN".to_string(), token.clone());
+            headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+            let req = HttpRequest {
+                url: url.clone(),
+                headers,
+                method: Some("GET".to_string()),
+            };
+
+            let res = http::request::<()>(&req, None)?;
+
+            if !is_success_status(res.status_code()) {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(format!(
+                            "Failed to list tags page {} from {}: {} - Response: {}",
+                            current_page_number,
+                            req.url,
+                            res.status_code(),
+                            String::from_utf8_lossy(&res.body())
+                        )),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+
+            match serde_json::from_slice::<Vec<Value>>(&res.body()) {
+                Ok(page_tags) => {
+                    let num_fetched = page_tags.len();
+                    all_tags_json.extend(page_tags);
+
+                    if all_tags_json.len() >= max_tags {
+                        all_tags_json.truncate(max_tags);
+                        break; // Reached the requested cap
+                    }
+                    if num_fetched < per_page as usize {
+                        break; // Last page fetched
+                    }
+                }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        is_error: Some(true),
+                        content: vec![Content {
+                            annotations: None,
+                            text: Some(format!(
+                                "Failed to parse tags data from GitLab API (page {}): {}",
+                                current_page_number, e
+                            )),
+                            mime_type: None,
+                            r#type: ContentType::Text,
+                            data: None,
+                        }],
+                    });
+                }
+            }
+            current_page_number += 1;
+        }
+
+        Ok(CallToolResult {
+            is_error: None,
+            content: vec![Content {
+                annotations: None,
+                text: Some(serde_json::to_string(&all_tags_json)?),
+                mime_type: Some("application/json".to_string()),
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_get_tag(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(tag_name))) =
+        (args.get("project_id"), args.get("tag_name"))
+    {
+        let url = format!(
+            "{}/projects/{}/repository/tags/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(tag_name)
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to get tag: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and tag_name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_delete_tag(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(tag_name))) =
+        (args.get("project_id"), args.get("tag_name"))
+    {
+        let url = format!(
+            "{}/projects/{}/repository/tags/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(tag_name)
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("DELETE".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!("Tag {} deleted successfully", tag_name)),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to delete tag: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and tag_name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_create_release(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (
+        Some(Value::String(project_id)),
+        Some(Value::String(tag_name)),
+        Some(Value::String(name)),
+        Some(Value::String(description)),
+    ) = (
+        args.get("project_id"),
+        args.get("tag_name"),
+        args.get("name"),
+        args.get("description"),
+    ) {
+        let mut body_map = serde_json::Map::new();
+        body_map.insert("tag_name".to_string(), json!(tag_name));
+        body_map.insert("name".to_string(), json!(name));
+        body_map.insert("description".to_string(), json!(description));
+        if let Some(Value::String(ref_name)) = args.get("ref") {
+            body_map.insert("ref".to_string(), json!(ref_name));
+        }
+        if let Some(assets) = args.get("assets") {
+            let valid_links = assets.as_array().is_some_and(|links| {
+                links
+                    .iter()
+                    .all(|link| link["name"].is_string() && link["url"].is_string())
+            });
+            if !valid_links {
+                return Ok(CallToolResult {
+                    is_error: Some(true),
+                    content: vec![Content {
+                        annotations: None,
+                        text: Some(
+                            "Invalid assets: expected an array of links of the form {\"name\": \"...\", \"url\": \"...\"}".into(),
+                        ),
+                        mime_type: None,
+                        r#type: ContentType::Text,
+                        data: None,
+                    }],
+                });
+            }
+            body_map.insert("assets".to_string(), json!({ "links": assets }));
+        }
+        let body = Value::Object(body_map);
+
+        let res = create_release_request(project_id, &body, &token, &gitlab_url)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to create release: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id, tag_name, name, and description".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn gl_get_release(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let args = input.params.arguments.clone().unwrap_or_default();
+    let (token, gitlab_url) = get_gitlab_config()?;
+
+    if let (Some(Value::String(project_id)), Some(Value::String(tag_name))) =
+        (args.get("project_id"), args.get("tag_name"))
+    {
+        let url = format!(
+            "{}/projects/{}/releases/{}",
+            gitlab_url,
+            urlencode_if_needed(project_id),
+            urlencoding::encode(tag_name)
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("PRIVATE-TOKEN".to_string(), token);
+        headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+        let req = HttpRequest {
+            url,
+            headers,
+            method: Some("GET".to_string()),
+        };
+
+        let res = http::request::<()>(&req, None)?;
+
+        if is_success_status(res.status_code()) {
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(String::from_utf8_lossy(&res.body()).to_string()),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        } else {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(format!(
+                        "Failed to get release: {} - Response: {}",
+                        res.status_code(),
+                        String::from_utf8_lossy(&res.body())
+                    )),
+                    mime_type: None,
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+    } else {
+        Ok(CallToolResult {
+            is_error: Some(true),
+            content: vec![Content {
+                annotations: None,
+                text: Some("Please provide project_id and tag_name".into()),
+                mime_type: None,
+                r#type: ContentType::Text,
+                data: None,
+            }],
+        })
+    }
+}
+
+fn create_release_request(
+    project_id: &str,
+    body: &Value,
+    token: &str,
+    gitlab_url: &str,
+) -> Result<HttpResponse, Error> {
+    let url = format!(
+        "{}/projects/{}/releases",
+        gitlab_url,
+        urlencode_if_needed(project_id)
+    );
+
+    let mut headers = BTreeMap::new();
+    headers.insert("PRIVATE-TOKEN".to_string(), token.to_string());
+    headers.insert("Content-Type".to_string(), "application/json".to_string());
+    headers.insert("User-Agent".to_string(), "hyper-mcp/0.1.0".to_string());
+
+    let req = HttpRequest {
+        url,
+        headers,
+        method: Some("POST".to_string()),
+    };
+
+    http::request(&req, Some(&body.to_string()))
+}
+
+pub(crate) fn describe() -> Result<ListToolsResult, Error> {
+    Ok(ListToolsResult {
+        tools: vec![
+            ToolDescription {
+                name: "gl_delete_file".into(),
+                description: "Delete a file in a GitLab project repository. Requires project_id, file_path, branch, and optional commit_message.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "file_path": {
+                            "type": "string",
+                            "description": "The path to the file in the project",
+                        },
+                        "branch": {
+                            "type": "string",
+                            "description": "The name of the branch to delete the file from",
+                        },
+                        "commit_message": {
+                            "type": "string",
+                            "description": "The commit message. Optional, defaults to 'Delete file via API'",
+                        },
+                        "author_email": {
+                            "type": "string",
+                            "description": "The email of the commit author. Optional.",
+                        },
+                        "author_name": {
+                            "type": "string",
+                            "description": "The name of the commit author. Optional.",
+                        },
+                    },
+                    "required": ["project_id", "file_path", "branch"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_create_issue".into(),
+                description: "Create a new issue in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "The title of the issue",
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "The description of the issue",
+                        },
+                        "labels": {
+                            "type": "string",
+                            "description": "Comma-separated list of labels",
+                        },
+                    },
+                    "required": ["project_id", "title", "description"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_get_issue".into(),
+                description: "Get details of a specific issue".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "issue_iid": {
+                            "type": "string",
+                            "description": "The internal ID of the issue",
+                        },
+                    },
+                    "required": ["project_id", "issue_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_update_issue".into(),
+                description: "Update an existing issue in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "issue_iid": {
+                            "type": "string",
+                            "description": "The internal ID of the issue",
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "The new title of the issue",
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "The new description of the issue",
+                        },
+                        "add_labels": {
+                            "type": "string",
+                            "description": "Comma-separated list of labels to add to the issue",
+                        },
+                        "remove_labels": {
+                            "type": "string",
+                            "description": "Comma-separated list of labels to remove from the issue",
+                        },
+                        "due_date": {
+                            "type": "string",
+                            "description": "The due date of the issue in YYYY-MM-DD format (e.g., 2024-03-11)",
+                        },
+                    },
+                    "required": ["project_id", "issue_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_close_issue".into(),
+                description: "Close an issue in a GitLab project, e.g. to close issue #42".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "issue_iid": {
+                            "type": "string",
+                            "description": "The internal ID of the issue, e.g. '42' for issue #42",
+                        },
+                    },
+                    "required": ["project_id", "issue_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_reopen_issue".into(),
+                description: "Reopen a closed issue in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "issue_iid": {
+                            "type": "string",
+                            "description": "The internal ID of the issue, e.g. '42' for issue #42",
+                        },
+                    },
+                    "required": ["project_id", "issue_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_assign_issue".into(),
+                description: "Assign an issue to one or more users by username, replacing its current assignees. Fails without changing the issue if any username does not exist.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "issue_iid": {
+                            "type": "string",
+                            "description": "The internal ID of the issue",
+                        },
+                        "assignee_usernames": {
+                            "type": "string",
+                            "description": "Comma-separated list of GitLab usernames to assign, e.g. 'alice,bob'",
+                        },
+                    },
+                    "required": ["project_id", "issue_iid", "assignee_usernames"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_unassign_issue".into(),
+                description: "Remove all assignees from an issue".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "issue_iid": {
+                            "type": "string",
+                            "description": "The internal ID of the issue",
+                        },
+                    },
+                    "required": ["project_id", "issue_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_add_issue_comment".into(),
+                description: "Add a comment to an issue in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "issue_iid": {
+                            "type": "string",
+                            "description": "The internal ID of the issue",
+                        },
+                        "comment": {
+                            "type": "string",
+                            "description": "The comment to add to the issue",
+                        },
+                    },
+                    "required": ["project_id", "issue_iid", "comment"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_list_issues".into(),
+                description: "List issues for a project in GitLab. Supports filtering by state and labels. Handles pagination internally, unless a page is given: then only that page is returned as {issues, page, per_page, has_more, next_page}.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "state": {
+                            "type": "string",
+                            "description": "Filter by state: 'opened', 'closed', or 'all'. Defaults to 'opened' if not specified by GitLab.",
+                        },
+                        "labels": {
+                            "type": "string",
+                            "description": "Comma-separated list of label names to filter by.",
+                        },
+                        "max_issues": {
+                            "type": "integer",
+                            "description": "Maximum number of issues to return across all pages. Defaults to 500. Optional.",
+                        },
+                        "page": {
+                            "type": "integer",
+                            "description": "Page to return (starting at 1). When set, only this page is fetched instead of all pages, and max_issues is ignored. Optional.",
+                        },
+                        "per_page": {
+                            "type": "integer",
+                            "description": "Number of issues per page (1-100) when page is set. Defaults to 20. Optional.",
+                        },
+                    },
+                    "required": ["project_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_get_file_contents".into(),
+                description: "Get the contents of a file in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "file_path": {
+                            "type": "string",
+                            "description": "The path to the file in the project",
+                        },
+                        "ref": {
+                            "type": "string",
+                            "description": "The name of the branch, tag or commit (defaults to HEAD)",
+                        },
+                    },
+                    "required": ["project_id", "file_path"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_create_or_update_file".into(),
+                description: "Create or update a file in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "file_path": {
+                            "type": "string",
+                            "description": "The path to the file in the project",
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "The content to write to the file",
+                        },
+                        "branch": {
+                            "type": "string",
+                            "description": "The name of the branch to create or update the file in",
+                        },
+                        "author_email": {
+                            "type": "string",
+                            "description": "The email of the commit author",
+                        },
+                        "author_name": {
+                            "type": "string",
+                            "description": "The name of the commit author",
+                        },
+                        "commit_message": {
+                            "type": "string",
+                            "description": "The commit message. Defaults to 'Update file via API' if not specified.",
+                        },
+                    },
+                    "required": ["project_id", "file_path", "content", "branch"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_create_branch".into(),
+                description: "Create a new branch in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "branch_name": {
+                            "type": "string",
+                            "description": "The name of the new branch",
+                        },
+                        "ref": {
+                            "type": "string",
+                            "description": "The branch name or commit SHA to create the new branch from",
+                        },
+                    },
+                    "required": ["project_id", "branch_name", "ref"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_create_merge_request".into(),
+                description: "Create a new merge request in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "source_branch": {
+                            "type": "string",
+                            "description": "The name of the source branch",
+                        },
+                        "target_branch": {
+                            "type": "string",
+                            "description": "The name of the target branch",
+                        },
+                    },
+                    "required": ["project_id", "source_branch", "target_branch"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_update_merge_request".into(),
+                description: "Update an existing merge request in a GitLab project.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "merge_request_iid": {
+                            "type": "string",
+                            "description": "The internal ID (IID) of the merge request to update",
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "The new title for the merge request.",
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "The new description for the merge request.",
+                        },
+                        // Consider adding other common updatable fields like:
+                        // "target_branch": { "type": "string", "description": "The target branch" },
+                        // "state_event": { "type": "string", "description": "Event to change MR state (e.g., 'close', 'reopen')" }
+                    },
+                    "required": ["project_id", "merge_request_iid", "title", "description"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_get_merge_request".into(),
+                description: "Get details of a specific merge request in a GitLab project.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "merge_request_iid": {
+                            "type": "string",
+                            "description": "The internal ID (IID) of the merge request",
+                        },
+                    },
+                    "required": ["project_id", "merge_request_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_list_merge_requests".into(),
+                description: "List merge requests for a project in GitLab. Supports filtering by state, labels, author, reviewer, and branches. Handles pagination internally.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "state": {
+                            "type": "string",
+                            "description": "Filter by state: 'opened', 'closed', 'merged', or 'all'. Optional.",
+                        },
+                        "labels": {
+                            "type": "string",
+                            "description": "Comma-separated list of label names to filter by. Optional.",
+                        },
+                        "author_username": {
+                            "type": "string",
+                            "description": "Return merge requests created by the given username. Optional.",
+                        },
+                        "reviewer_username": {
+                            "type": "string",
+                            "description": "Return merge requests with the given username as a reviewer. Optional.",
+                        },
+                        "source_branch": {
+                            "type": "string",
+                            "description": "Return merge requests with the given source branch. Optional.",
+                        },
+                        "target_branch": {
+                            "type": "string",
+                            "description": "Return merge requests with the given target branch. Optional.",
+                        },
+                        "max_merge_requests": {
+                            "type": "integer",
+                            "description": "Maximum number of merge requests to return across all pages. Defaults to 500. Optional.",
+                        },
+                    },
+                    "required": ["project_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_get_mr_changes".into(),
+                description: "Get the changes (diffs) of a specific merge request in a GitLab project. Returns the raw GitLab response including a `changes` array with per-file diffs.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "merge_request_iid": {
+                            "type": "string",
+                            "description": "The internal ID (IID) of the merge request",
+                        },
+                        "access_raw_diffs": {
+                            "type": "boolean",
+                            "description": "Retrieve the diffs via Gitaly in unidiff format, bypassing the database size limits. Defaults to false. Optional.",
+                        },
+                    },
+                    "required": ["project_id", "merge_request_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_merge_mr".into(),
+                description: "Merge a merge request in a GitLab project. Fails with a descriptive error if the merge request is not currently mergeable.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "merge_request_iid": {
+                            "type": "string",
+                            "description": "The internal ID (IID) of the merge request to merge",
+                        },
+                        "merge_commit_message": {
+                            "type": "string",
+                            "description": "Custom merge commit message. Optional.",
+                        },
+                        "squash": {
+                            "type": "boolean",
+                            "description": "Squash the commits into a single commit on merge. Optional.",
+                        },
+                        "should_remove_source_branch": {
+                            "type": "boolean",
+                            "description": "Remove the source branch after merging. Optional.",
+                        },
+                        "merge_when_pipeline_succeeds": {
+                            "type": "boolean",
+                            "description": "Merge automatically once the pipeline succeeds instead of immediately. Optional.",
+                        },
+                    },
+                    "required": ["project_id", "merge_request_iid"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_get_protected_branch".into(),
+                description: "Get the protection settings of a branch, including the access levels allowed to push and merge. Access levels: 0 = No access, 30 = Developer, 40 = Maintainer, 60 = Admin.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "branch": {
+                            "type": "string",
+                            "description": "The name of the branch, e.g. 'main'",
+                        },
+                    },
+                    "required": ["project_id", "branch"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_protect_branch".into(),
+                description: "Protect a branch, restricting who can push to and merge into it. Requires the Maintainer role. Fails if the branch is already protected.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "branch": {
+                            "type": "string",
+                            "description": "The name of the branch, e.g. 'main'",
+                        },
+                        "push_access_level": {
+                            "type": "integer",
+                            "enum": [0, 30, 40, 60],
+                            "description": "Access level allowed to push: 0 = No access, 30 = Developer, 40 = Maintainer, 60 = Admin. Defaults to 40. Optional.",
+                        },
+                        "merge_access_level": {
+                            "type": "integer",
+                            "enum": [0, 30, 40, 60],
+                            "description": "Access level allowed to merge: 0 = No access, 30 = Developer, 40 = Maintainer, 60 = Admin. Defaults to 40. Optional.",
+                        },
+                        "allow_force_push": {
+                            "type": "boolean",
+                            "description": "Allow users who can push to force push. Defaults to false. Optional.",
+                        },
+                    },
+                    "required": ["project_id", "branch"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_unprotect_branch".into(),
+                description: "Remove the protection of a branch. Requires the Maintainer role.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "branch": {
+                            "type": "string",
+                            "description": "The name of the branch, e.g. 'main'",
+                        },
+                    },
+                    "required": ["project_id", "branch"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_create_snippet".into(),
+                description: "Create a new snippet, either from a single content or from several files".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "title": {
+                            "type": "string",
+                            "description": "The title of the snippet",
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "The content of a single-file snippet. Ignored if files is set.",
+                        },
+                        "file_name": {
+                            "type": "string",
+                            "description": "The file name of a single-file snippet. Defaults to the title in snake case with a .txt extension. Optional.",
+                        },
+                        "files": {
+                            "type": "array",
+                            "description": "The files of a multi-file snippet. Use instead of content.",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "file_path": {
+                                        "type": "string",
+                                        "description": "The path of the file",
+                                    },
+                                    "content": {
+                                        "type": "string",
+                                        "description": "The content of the file",
+                                    },
+                                },
+                                "required": ["file_path", "content"],
+                            },
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "The description of the snippet. Optional.",
+                        },
+                        "visibility": {
+                            "type": "string",
+                            "description": "The visibility level of the snippet (private, internal, or public). Defaults to private if not specified.",
+                        },
+                    },
+                    "required": ["title"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_update_snippet".into(),
+                description: "Update an existing snippet. Multi-file snippets are updated through files, with an action per file.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "snippet_id": {
+                            "type": "string",
+                            "description": "The ID of the snippet",
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "The new title of the snippet. Optional.",
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "The new content of a single-file snippet. Ignored if files is set. Optional.",
+                        },
+                        "file_name": {
+                            "type": "string",
+                            "description": "The new file name of a single-file snippet. Defaults to the title in snake case with a .txt extension when a title is given. Optional.",
+                        },
+                        "files": {
+                            "type": "array",
+                            "description": "Changes to the files of the snippet. Optional.",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "action": {
+                                        "type": "string",
+                                        "enum": ["create", "update", "delete", "move"],
+                                        "description": "The action to apply to the file",
+                                    },
+                                    "file_path": {
+                                        "type": "string",
+                                        "description": "The path of the file",
+                                    },
+                                    "previous_path": {
+                                        "type": "string",
+                                        "description": "The previous path of the file. Required by the move action.",
+                                    },
+                                    "content": {
+                                        "type": "string",
+                                        "description": "The content of the file. Required by the create and update actions, optional for move.",
+                                    },
+                                },
+                                "required": ["action", "file_path"],
+                            },
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "The new description of the snippet. Optional.",
+                        },
+                        "visibility": {
+                            "type": "string",
+                            "description": "The new visibility level of the snippet (private, internal, or public). Optional.",
+                        },
+                    },
+                    "required": ["snippet_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_get_snippet".into(),
+                description: "Get details of a specific snippet".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "snippet_id": {
+                            "type": "string",
+                            "description": "The ID of the snippet",
+                        },
+                    },
+                    "required": ["snippet_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_delete_snippet".into(),
+                description: "Delete a snippet".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "snippet_id": {
+                            "type": "string",
+                            "description": "The ID of the snippet",
+                        },
+                    },
+                    "required": ["snippet_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_list_branches".into(),
+                description: "List all branches in a GitLab project".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                    },
+                    "required": ["project_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_list_pipelines".into(),
+                description: "List CI/CD pipelines for a project in GitLab. Supports filtering by status, ref, sha, and username. Handles pagination internally.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "status": {
+                            "type": "string",
+                            "description": "Filter by pipeline status, e.g. 'running', 'pending', 'success', 'failed', 'canceled', 'skipped', 'created', 'manual'. Optional.",
+                        },
+                        "ref": {
                             "type": "string",
-                            "description": "The commit message. Optional, defaults to 'Delete file via API'",
+                            "description": "Filter by the branch or tag the pipeline ran for. Optional.",
                         },
-                        "author_email": {
+                        "sha": {
                             "type": "string",
-                            "description": "The email of the commit author. Optional.",
+                            "description": "Filter by the commit SHA the pipeline ran for. Optional.",
                         },
-                        "author_name": {
+                        "username": {
                             "type": "string",
-                            "description": "The name of the commit author. Optional.",
+                            "description": "Filter by the username of the user who triggered the pipeline. Optional.",
+                        },
+                        "per_page": {
+                            "type": "integer",
+                            "description": "Number of pipelines fetched per API page (1-100). Defaults to 100. Optional.",
+                        },
+                        "max_pipelines": {
+                            "type": "integer",
+                            "description": "Maximum number of pipelines to return across all pages. Defaults to 500. Optional.",
                         },
                     },
-                    "required": ["project_id", "file_path", "branch"],
+                    "required": ["project_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_create_issue".into(),
-                description: "Create a new issue in a GitLab project".into(),
+                name: "gl_get_pipeline".into(),
+                description: "Get details of a specific CI/CD pipeline in a GitLab project.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -1879,28 +5788,53 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "title": {
+                        "pipeline_id": {
                             "type": "string",
-                            "description": "The title of the issue",
+                            "description": "The ID of the pipeline",
                         },
-                        "description": {
+                    },
+                    "required": ["project_id", "pipeline_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_trigger_pipeline".into(),
+                description: "Create (trigger) a new CI/CD pipeline for a ref in a GitLab project.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
                             "type": "string",
-                            "description": "The description of the issue",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "labels": {
+                        "ref": {
                             "type": "string",
-                            "description": "Comma-separated list of labels",
+                            "description": "The branch or tag to run the pipeline for",
+                        },
+                        "variables": {
+                            "type": "array",
+                            "description": "Variables available in the pipeline. Optional.",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "key": { "type": "string" },
+                                    "value": { "type": "string" },
+                                },
+                                "required": ["key", "value"],
+                            },
                         },
                     },
-                    "required": ["project_id", "title", "description"],
+                    "required": ["project_id", "ref"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_get_issue".into(),
-                description: "Get details of a specific issue".into(),
+                name: "gl_cancel_pipeline".into(),
+                description: "Cancel the running jobs of a CI/CD pipeline in a GitLab project.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -1908,20 +5842,20 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "issue_iid": {
+                        "pipeline_id": {
                             "type": "string",
-                            "description": "The internal ID of the issue",
+                            "description": "The ID of the pipeline",
                         },
                     },
-                    "required": ["project_id", "issue_iid"],
+                    "required": ["project_id", "pipeline_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_update_issue".into(),
-                description: "Update an existing issue in a GitLab project".into(),
+                name: "gl_list_pipeline_jobs".into(),
+                description: "List the jobs of a CI/CD pipeline in a GitLab project, including their status, stage, and runner. Handles pagination internally.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -1929,40 +5863,49 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "issue_iid": {
-                            "type": "string",
-                            "description": "The internal ID of the issue",
-                        },
-                        "title": {
+                        "pipeline_id": {
                             "type": "string",
-                            "description": "The new title of the issue",
+                            "description": "The ID of the pipeline",
                         },
-                        "description": {
+                        "scope": {
                             "type": "string",
-                            "description": "The new description of the issue",
+                            "description": "Filter by job status, e.g. 'failed', 'success', 'running', 'pending', 'canceled', 'skipped', 'manual'. Optional.",
                         },
-                        "add_labels": {
+                    },
+                    "required": ["project_id", "pipeline_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_get_job_log".into(),
+                description: "Get the log (trace) of a CI/CD job in a GitLab project as plain text.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
                             "type": "string",
-                            "description": "Comma-separated list of labels to add to the issue",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "remove_labels": {
+                        "job_id": {
                             "type": "string",
-                            "description": "Comma-separated list of labels to remove from the issue",
+                            "description": "The ID of the job",
                         },
-                        "due_date": {
-                            "type": "string",
-                            "description": "The due date of the issue in YYYY-MM-DD format (e.g., 2024-03-11)",
+                        "max_bytes": {
+                            "type": "integer",
+                            "description": "Maximum number of bytes to return. Longer logs are truncated from the start, keeping the most recent output. Optional.",
                         },
                     },
-                    "required": ["project_id", "issue_iid"],
+                    "required": ["project_id", "job_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_add_issue_comment".into(),
-                description: "Add a comment to an issue in a GitLab project".into(),
+                name: "gl_get_repo_tree".into(),
+                description: "Get the list of files and directories in a project repository. Handles pagination internally.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -1970,24 +5913,28 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "issue_iid": {
+                        "path": {
                             "type": "string",
-                            "description": "The internal ID of the issue",
+                            "description": "The path inside the repository. Used to get content of subdirectories. Optional.",
                         },
-                        "comment": {
+                        "ref": {
                             "type": "string",
-                            "description": "The comment to add to the issue",
+                            "description": "The name of a repository branch or tag or if not given the default branch. Optional.",
+                        },
+                        "recursive": {
+                            "type": "boolean",
+                            "description": "Boolean value used to get a recursive tree. If you want a complete tree, set this to true. Default is false. Optional.",
                         },
                     },
-                    "required": ["project_id", "issue_iid", "comment"],
+                    "required": ["project_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_list_issues".into(),
-                description: "List issues for a project in GitLab. Supports filtering by state and labels.".into(),
+                name: "gl_get_repo_members".into(),
+                description: "Get a list of members for a GitLab project. Supports fetching direct or inherited members and filtering by query. Handles pagination internally.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -1995,13 +5942,13 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "state": {
-                            "type": "string",
-                            "description": "Filter by state: 'opened', 'closed', or 'all'. Defaults to 'opened' if not specified by GitLab.",
+                        "include_inherited_members": {
+                            "type": "boolean",
+                            "description": "Set to true to include inherited members (e.g., from groups). Defaults to false (direct members only). Optional.",
                         },
-                        "labels": {
+                        "query": {
                             "type": "string",
-                            "description": "Comma-separated list of label names to filter by.",
+                            "description": "Filter by username, name, or public email. Optional.",
                         },
                     },
                     "required": ["project_id"],
@@ -2011,8 +5958,8 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                 .clone(),
             },
             ToolDescription {
-                name: "gl_get_file_contents".into(),
-                description: "Get the contents of a file in a GitLab project".into(),
+                name: "gl_search_code".into(),
+                description: "Search for code in a GitLab project repository. Returns matching files with filename, ref, startline, and a snippet of the matching content. Requires advanced search to be enabled on the GitLab instance.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2020,24 +5967,28 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "file_path": {
+                        "query": {
                             "type": "string",
-                            "description": "The path to the file in the project",
+                            "description": "The search query",
                         },
                         "ref": {
                             "type": "string",
-                            "description": "The name of the branch, tag or commit (defaults to HEAD)",
+                            "description": "The name of a repository branch or tag to search on. Defaults to the default branch. Optional.",
+                        },
+                        "max_results": {
+                            "type": "integer",
+                            "description": "Maximum number of results to return across all pages. Defaults to 50. Optional.",
                         },
                     },
-                    "required": ["project_id", "file_path"],
+                    "required": ["project_id", "query"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_create_or_update_file".into(),
-                description: "Create or update a file in a GitLab project".into(),
+                name: "gl_list_labels".into(),
+                description: "List the labels of a GitLab project, including their color, description, and open issue and merge request counts. Handles pagination internally.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2045,40 +5996,49 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "file_path": {
-                            "type": "string",
-                            "description": "The path to the file in the project",
-                        },
-                        "content": {
+                        "search": {
                             "type": "string",
-                            "description": "The content to write to the file",
+                            "description": "Only return labels whose name contains this keyword. Optional.",
                         },
-                        "branch": {
+                    },
+                    "required": ["project_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "gl_create_label".into(),
+                description: "Create a new label in a GitLab project.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
                             "type": "string",
-                            "description": "The name of the branch to create or update the file in",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "author_email": {
+                        "name": {
                             "type": "string",
-                            "description": "The email of the commit author",
+                            "description": "The name of the label",
                         },
-                        "author_name": {
+                        "color": {
                             "type": "string",
-                            "description": "The name of the commit author",
+                            "description": "The color of the label as a hex string in the form #RRGGBB (e.g. '#FF0000')",
                         },
-                        "commit_message": {
+                        "description": {
                             "type": "string",
-                            "description": "The commit message. Defaults to 'Update file via API' if not specified.",
+                            "description": "The description of the label. Optional.",
                         },
                     },
-                    "required": ["project_id", "file_path", "content", "branch"],
+                    "required": ["project_id", "name", "color"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_create_branch".into(),
-                description: "Create a new branch in a GitLab project".into(),
+                name: "gl_update_label".into(),
+                description: "Update an existing label in a GitLab project. At least one of new_name, color, or description must be provided.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2086,24 +6046,32 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "branch_name": {
+                        "label_id": {
                             "type": "string",
-                            "description": "The name of the new branch",
+                            "description": "The ID or name of the label",
                         },
-                        "ref": {
+                        "new_name": {
                             "type": "string",
-                            "description": "The branch name or commit SHA to create the new branch from",
+                            "description": "The new name of the label. Optional.",
+                        },
+                        "color": {
+                            "type": "string",
+                            "description": "The new color of the label as a hex string in the form #RRGGBB (e.g. '#FF0000'). Optional.",
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "The new description of the label. Optional.",
                         },
                     },
-                    "required": ["project_id", "branch_name", "ref"],
+                    "required": ["project_id", "label_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_create_merge_request".into(),
-                description: "Create a new merge request in a GitLab project".into(),
+                name: "gl_delete_label".into(),
+                description: "Delete a label from a GitLab project.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2111,24 +6079,20 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "source_branch": {
-                            "type": "string",
-                            "description": "The name of the source branch",
-                        },
-                        "target_branch": {
+                        "label_id": {
                             "type": "string",
-                            "description": "The name of the target branch",
+                            "description": "The ID or name of the label",
                         },
                     },
-                    "required": ["project_id", "source_branch", "target_branch"],
+                    "required": ["project_id", "label_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_update_merge_request".into(),
-                description: "Update an existing merge request in a GitLab project.".into(),
+                name: "gl_list_commits".into(),
+                description: "List the commits of a GitLab project repository, newest first. Supports filtering by ref, date range, and file path. Handles pagination internally.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2136,31 +6100,40 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "merge_request_iid": {
+                        "ref_name": {
                             "type": "string",
-                            "description": "The internal ID (IID) of the merge request to update",
+                            "description": "The name of a repository branch, tag, or revision range. Defaults to the default branch. Optional.",
                         },
-                        "title": {
+                        "since": {
                             "type": "string",
-                            "description": "The new title for the merge request.",
+                            "description": "Only commits after or on this date are returned, in ISO 8601 format (e.g. '2024-01-01T00:00:00Z'). Optional.",
                         },
-                        "description": {
+                        "until": {
                             "type": "string",
-                            "description": "The new description for the merge request.",
+                            "description": "Only commits before or on this date are returned, in ISO 8601 format (e.g. '2024-12-31T23:59:59Z'). Optional.",
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Only return commits that touch this file path. Optional.",
+                        },
+                        "per_page": {
+                            "type": "integer",
+                            "description": "Number of commits fetched per API page (1-100). Defaults to 100. Optional.",
+                        },
+                        "max_commits": {
+                            "type": "integer",
+                            "description": "Maximum number of commits to return across all pages. Defaults to 500. Optional.",
                         },
-                        // Consider adding other common updatable fields like:
-                        // "target_branch": { "type": "string", "description": "The target branch" },
-                        // "state_event": { "type": "string", "description": "Event to change MR state (e.g., 'close', 'reopen')" }
                     },
-                    "required": ["project_id", "merge_request_iid", "title", "description"],
+                    "required": ["project_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_get_merge_request".into(),
-                description: "Get details of a specific merge request in a GitLab project.".into(),
+                name: "gl_get_commit".into(),
+                description: "Get the full metadata of a specific commit in a GitLab project, including author, committer, message, parents, and line stats.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2168,104 +6141,142 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "merge_request_iid": {
+                        "sha": {
                             "type": "string",
-                            "description": "The internal ID (IID) of the merge request",
+                            "description": "The commit hash or the name of a branch or tag",
                         },
                     },
-                    "required": ["project_id", "merge_request_iid"],
+                    "required": ["project_id", "sha"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_create_snippet".into(),
-                description: "Create a new snippet".into(),
+                name: "gl_compare_refs".into(),
+                description: "Compare two refs (branches, tags, or commits) of a GitLab project. Returns the commits and per-file diffs between them.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
-                        "title": {
+                        "project_id": {
                             "type": "string",
-                            "description": "The title of the snippet",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "content": {
+                        "from": {
                             "type": "string",
-                            "description": "The content of the snippet",
+                            "description": "The commit SHA or branch/tag name to compare from",
                         },
-                        "visibility": {
+                        "to": {
                             "type": "string",
-                            "description": "The visibility level of the snippet (private, internal, or public). Defaults to private if not specified.",
+                            "description": "The commit SHA or branch/tag name to compare to",
+                        },
+                        "straight": {
+                            "type": "boolean",
+                            "description": "Compare 'from' and 'to' directly instead of from their merge base. Defaults to false. Optional.",
                         },
                     },
-                    "required": ["title", "content"],
+                    "required": ["project_id", "from", "to"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_update_snippet".into(),
-                description: "Update an existing snippet".into(),
+                name: "gl_create_tag".into(),
+                description: "Create a tag in a GitLab project. Pass message to create an annotated tag, and release_description to also create a release for the tag.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
-                        "snippet_id": {
+                        "project_id": {
                             "type": "string",
-                            "description": "The ID of the snippet",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "title": {
+                        "tag_name": {
                             "type": "string",
-                            "description": "The new title of the snippet",
+                            "description": "The name of the tag, e.g. 'v1.2.0'",
                         },
-                        "content": {
+                        "ref": {
+                            "type": "string",
+                            "description": "The commit SHA, branch, or tag to create the tag from",
+                        },
+                        "message": {
+                            "type": "string",
+                            "description": "Message of the tag. Creates an annotated tag instead of a lightweight one. Optional.",
+                        },
+                        "release_description": {
                             "type": "string",
-                            "description": "The new content of the snippet",
+                            "description": "Markdown description of a release to create for the tag. Optional.",
                         },
                     },
-                    "required": ["snippet_id", "title", "content"],
+                    "required": ["project_id", "tag_name", "ref"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_get_snippet".into(),
-                description: "Get details of a specific snippet".into(),
+                name: "gl_list_tags".into(),
+                description: "List the tags of a GitLab project. Handles pagination internally, capped by max_tags.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
-                        "snippet_id": {
+                        "project_id": {
                             "type": "string",
-                            "description": "The ID of the snippet",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "search": {
+                            "type": "string",
+                            "description": "Only return tags whose name matches. Use ^term to match the start and term$ to match the end. Optional.",
+                        },
+                        "order_by": {
+                            "type": "string",
+                            "enum": ["name", "updated", "version"],
+                            "description": "Order tags by name, updated date, or version. Defaults to 'updated'. Optional.",
+                        },
+                        "sort": {
+                            "type": "string",
+                            "enum": ["asc", "desc"],
+                            "description": "Sort order. Defaults to 'desc'. Optional.",
+                        },
+                        "per_page": {
+                            "type": "integer",
+                            "description": "Number of tags fetched per request, between 1 and 100. Defaults to 100. Optional.",
+                        },
+                        "max_tags": {
+                            "type": "integer",
+                            "description": "Maximum number of tags to return. Defaults to 500. Optional.",
                         },
                     },
-                    "required": ["snippet_id"],
+                    "required": ["project_id"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_delete_snippet".into(),
-                description: "Delete a snippet".into(),
+                name: "gl_get_tag".into(),
+                description: "Get a tag of a GitLab project, including the commit it points to and its release".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
-                        "snippet_id": {
+                        "project_id": {
                             "type": "string",
-                            "description": "The ID of the snippet",
+                            "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
+                        },
+                        "tag_name": {
+                            "type": "string",
+                            "description": "The name of the tag, e.g. 'v1.2.0'",
                         },
                     },
-                    "required": ["snippet_id"],
+                    "required": ["project_id", "tag_name"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_list_branches".into(),
-                description: "List all branches in a GitLab project".into(),
+                name: "gl_delete_tag".into(),
+                description: "Delete a tag from a GitLab project".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2273,16 +6284,20 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
+                        "tag_name": {
+                            "type": "string",
+                            "description": "The name of the tag, e.g. 'v1.2.0'",
+                        },
                     },
-                    "required": ["project_id"],
+                    "required": ["project_id", "tag_name"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_get_repo_tree".into(),
-                description: "Get the list of files and directories in a project repository. Handles pagination internally.".into(),
+                name: "gl_create_release".into(),
+                description: "Create a release for a tag in a GitLab project, optionally with asset links".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2290,28 +6305,55 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "path": {
+                        "tag_name": {
                             "type": "string",
-                            "description": "The path inside the repository. Used to get content of subdirectories. Optional.",
+                            "description": "The tag to create the release for, e.g. 'v1.2.0'. Created from ref if it does not exist.",
+                        },
+                        "name": {
+                            "type": "string",
+                            "description": "The name of the release",
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "Markdown description of the release",
                         },
                         "ref": {
                             "type": "string",
-                            "description": "The name of a repository branch or tag or if not given the default branch. Optional.",
+                            "description": "The commit SHA or branch to create the tag from if tag_name does not exist yet. Optional.",
                         },
-                        "recursive": {
-                            "type": "boolean",
-                            "description": "Boolean value used to get a recursive tree. If you want a complete tree, set this to true. Default is false. Optional.",
+                        "assets": {
+                            "type": "array",
+                            "description": "Links to attach to the release as assets. Optional.",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "name": {
+                                        "type": "string",
+                                        "description": "The name of the link",
+                                    },
+                                    "url": {
+                                        "type": "string",
+                                        "description": "The URL of the link",
+                                    },
+                                    "link_type": {
+                                        "type": "string",
+                                        "enum": ["other", "runbook", "image", "package"],
+                                        "description": "The type of the link. Defaults to 'other'. Optional.",
+                                    },
+                                },
+                                "required": ["name", "url"],
+                            },
                         },
                     },
-                    "required": ["project_id"],
+                    "required": ["project_id", "tag_name", "name", "description"],
                 })
                 .as_object()
                 .unwrap()
                 .clone(),
             },
             ToolDescription {
-                name: "gl_get_repo_members".into(),
-                description: "Get a list of members for a GitLab project. Supports fetching direct or inherited members and filtering by query. Handles pagination internally.".into(),
+                name: "gl_get_release".into(),
+                description: "Get the release of a tag in a GitLab project".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -2319,16 +6361,12 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                             "type": "string",
                             "description": "The project identifier - can be a numeric project ID (e.g. '123') or a URL-encoded path (e.g. 'group%2Fproject')",
                         },
-                        "include_inherited_members": {
-                            "type": "boolean",
-                            "description": "Set to true to include inherited members (e.g., from groups). Defaults to false (direct members only). Optional.",
-                        },
-                        "query": {
+                        "tag_name": {
                             "type": "string",
-                            "description": "Filter by username, name, or public email. Optional.",
+                            "description": "The name of the tag, e.g. 'v1.2.0'",
                         },
                     },
-                    "required": ["project_id"],
+                    "required": ["project_id", "tag_name"],
                 })
                 .as_object()
                 .unwrap()
@@ -4,7 +4,7 @@ mod pdk;
 use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
 use base64::Engine;
 use extism_pdk::*;
-use image::Rgba;
+use image::{Rgba, RgbaImage};
 use imageproc::drawing::draw_text_mut;
 use pdk::types::{
     CallToolRequest, CallToolResult, Content, ContentType, ListToolsResult, ToolDescription,
@@ -57,6 +57,18 @@ struct TextConfig {
     align: String,
     start: f32,
     stop: f32,
+    #[serde(default = "default_outline_width")]
+    outline_width: f32,
+    #[serde(default = "default_shadow_offset")]
+    shadow_offset: (f32, f32),
+}
+
+fn default_outline_width() -> f32 {
+    2.0
+}
+
+fn default_shadow_offset() -> (f32, f32) {
+    (3.0, 3.0)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -103,80 +115,82 @@ fn list_templates(_input: CallToolRequest) -> Result<CallToolResult, Error> {
 
 fn get_template(input: CallToolRequest) -> Result<CallToolResult, Error> {
     let args = input.params.arguments.unwrap_or_default();
-    let template_id = args
-        .get("template_id")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| Error::msg("template_id is required"))?;
-
-    let templates: Vec<MemeTemplate> = serde_json::from_str(embedded::TEMPLATES_JSON)?;
-
-    let template = templates
-        .iter()
-        .find(|t| t.id == template_id)
-        .ok_or_else(|| Error::msg("Template not found"))?;
-
-    Ok(CallToolResult {
-        is_error: None,
-        content: vec![Content {
-            annotations: None,
-            text: Some(serde_json::to_string_pretty(&template)?),
-            mime_type: Some("application/json".to_string()),
-            r#type: ContentType::Text,
-            data: None,
-        }],
-    })
-}
-
-fn generate_meme(input: CallToolRequest) -> Result<CallToolResult, Error> {
-    let args = input.params.arguments.unwrap_or_default();
-
-    let template_id = args
-        .get("template_id")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| Error::msg("template_id is required"))?;
-
-    let texts = args
-        .get("texts")
+    let styles = args
+        .get("styles")
         .and_then(|v| v.as_array())
-        .ok_or_else(|| Error::msg("texts array is required"))?;
-
-    // Load template configuration
-    let config = TemplateConfig::load(template_id)?;
+        .cloned()
+        .unwrap_or_default();
+
+    let custom_image_url = args.get("custom_image_url").and_then(|v| v.as_str());
+
+    let (mut image, text_configs, texts) = match custom_image_url {
+        Some(image_url) => {
+            let text_positions = args
+                .get("text_positions")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| {
+                    Error::msg("text_positions array is required when custom_image_url is set")
+                })?;
+            let (text_configs, texts) = parse_text_positions(text_positions)?;
+            (fetch_image(image_url)?, text_configs, texts)
+        }
+        None => {
+            let template_id = args
+                .get("template_id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| Error::msg("template_id is required"))?;
+
+            let texts = args
+                .get("texts")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| Error::msg("texts array is required"))?;
+
+            // Load template configuration
+            let config = TemplateConfig::load(template_id)?;
+
+            // Get the default image from embedded resources
+            let image_name = if embedded::get_template_image(template_id, "default.jpg").is_some() {
+                "default.jpg"
+            } else if embedded::get_template_image(template_id, "default.png").is_some() {
+                "default.png"
+            } else {
+                return Err(Error::msg(format!(
+                    "No default template image found for {}",
+                    template_id
+                )));
+            };
+
+            let image_data =
+                embedded::get_template_image(template_id, image_name).ok_or_else(|| {
+                    Error::msg(format!(
+                        "Template image {} {} not found",
+                        template_id, image_name
+                    ))
+                })?;
+
+            let texts = texts
+                .iter()
+                .take(config.text.len())
+                .map(|v| {
+                    v.as_str()
+                        .map(String::from)
+                        .ok_or_else(|| Error::msg("Invalid text entry"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
 
-    // Get the default image from embedded resources
-    let image_name = if embedded::get_template_image(template_id, "default.jpg").is_some() {
-        "default.jpg"
-    } else if embedded::get_template_image(template_id, "default.png").is_some() {
-        "default.png"
-    } else {
-        return Err(Error::msg(format!(
-            "No default template image found for {}",
-            template_id
-        )));
+            (
+                image::load_from_memory(image_data)?.to_rgba8(),
+                config.text,
+                texts,
+            )
+        }
     };
-
-    let image_data = embedded::get_template_image(template_id, image_name).ok_or_else(|| {
-        Error::msg(format!(
-            "Template image {} {} not found",
-            template_id, image_name
-        ))
-    })?;
-
-    let mut image = image::load_from_memory(image_data)?.to_rgba8();
     let (image_width, image_height) = image.dimensions();
 
     let font = FontArc::try_from_slice(embedded::FONT_DATA)?;
 
     // Draw each text configuration
-    for (i, text_config) in config.text.iter().enumerate() {
-        if i >= texts.len() {
-            break;
-        }
-
-        let text = texts[i]
-            .as_str()
-            .ok_or_else(|| Error::msg("Invalid text entry"))?;
-
+    for (i, (text_config, text)) in text_configs.iter().zip(texts.iter()).enumerate() {
         let text = if text_config.style == "upper" {
             text.to_uppercase()
         } else {
@@ -197,30 +211,45 @@ fn generate_meme(input: CallToolRequest) -> Result<CallToolResult, Error> {
             _ => image_width as f32 - (2.0 * padding),
         };
 
-        // Calculate appropriate scale that prevents overflow
-        let scale = calculate_max_scale(&font, &text, available_width, desired_height);
-
-        // Calculate text width for positioning using the adjusted scale
-        let text_width = calculate_text_width(&font, &text, scale);
-
-        // Calculate x position based on anchor and alignment, now with padding
-        let x = match text_config.align.as_str() {
-            "center" => ((image_width as f32 - text_width) / 2.0
-                + (image_width as f32 * text_config.anchor_x))
-                .max(padding) as i32,
-            "left" => ((image_width as f32 * text_config.anchor_x) + padding) as i32,
-            "right" => ((image_width as f32 * text_config.anchor_x) - text_width - padding)
-                .max(padding) as i32,
-            _ => ((image_width as f32 - text_width) / 2.0).max(padding) as i32,
-        };
-
-        // Calculate y position based on anchor
-        let y = (image_height as f32 * text_config.anchor_y) as i32;
+        // Break the text into lines that fit, stacked using the scale as the line height
+        let scale = PxScale::from(desired_height);
+        let lines = wrap_text(&font, &text, available_width, scale);
 
         // Convert color string to RGBA
         let color = color_to_rgba(&text_config.color);
 
-        draw_text_mut(&mut image, color, x, y, scale, &font, &text);
+        let style = styles.get(i).and_then(|v| v.as_str()).unwrap_or("none");
+
+        for (line_index, line) in lines.iter().enumerate() {
+            // Calculate line width for positioning
+            let text_width = calculate_text_width(&font, line, scale);
+
+            // Calculate x position based on anchor and alignment, now with padding
+            let x = match text_config.align.as_str() {
+                "center" => ((image_width as f32 - text_width) / 2.0
+                    + (image_width as f32 * text_config.anchor_x))
+                    .max(padding) as i32,
+                "left" => ((image_width as f32 * text_config.anchor_x) + padding) as i32,
+                "right" => ((image_width as f32 * text_config.anchor_x) - text_width - padding)
+                    .max(padding) as i32,
+                _ => ((image_width as f32 - text_width) / 2.0).max(padding) as i32,
+            };
+
+            // Calculate y position based on anchor and the line's position in the block
+            let y =
+                (image_height as f32 * text_config.anchor_y + line_index as f32 * scale.y) as i32;
+
+            draw_styled_text(
+                &mut image,
+                style,
+                text_config,
+                color,
+                (x, y),
+                scale,
+                &font,
+                line,
+            );
+        }
     }
 
     // Convert image to bytes
@@ -240,6 +269,141 @@ fn generate_meme(input: CallToolRequest) -> Result<CallToolResult, Error> {
     })
 }
 
+#[allow(clippy::too_many_arguments)]
+fn draw_styled_text(
+    image: &mut RgbaImage,
+    style: &str,
+    text_config: &TextConfig,
+    color: Rgba<u8>,
+    (x, y): (i32, i32),
+    scale: PxScale,
+    font: &FontArc,
+    text: &str,
+) {
+    match style {
+        "outline" => {
+            // Stamp the text in black around the target position to form the outline
+            let width = text_config.outline_width.round() as i32;
+            for dx in [-width, 0, width] {
+                for dy in [-width, 0, width] {
+                    if dx != 0 || dy != 0 {
+                        draw_text_mut(
+                            image,
+                            Rgba([0, 0, 0, 255]),
+                            x + dx,
+                            y + dy,
+                            scale,
+                            font,
+                            text,
+                        );
+                    }
+                }
+            }
+        }
+        "shadow" => {
+            // Draw onto a transparent layer first so the shadow is alpha-blended onto the image
+            let (width, height) = image.dimensions();
+            let mut layer = RgbaImage::new(width, height);
+            let (offset_x, offset_y) = text_config.shadow_offset;
+            draw_text_mut(
+                &mut layer,
+                Rgba([0, 0, 0, 255]),
+                x + offset_x.round() as i32,
+                y + offset_y.round() as i32,
+                scale,
+                font,
+                text,
+            );
+            for pixel in layer.pixels_mut() {
+                pixel[3] /= 2;
+            }
+            image::imageops::overlay(image, &layer, 0, 0);
+        }
+        _ => {}
+    }
+
+    draw_text_mut(image, color, x, y, scale, font, text);
+}
+
+fn fetch_image(image_url: &str) -> Result<RgbaImage, Error> {
+    // The host only lets this through if the image host is in runtime_config.allowed_hosts
+    let req = HttpRequest {
+        url: image_url.to_string(),
+        headers: Default::default(),
+        method: Some("GET".to_string()),
+    };
+    let res = http::request::<()>(&req, None)?;
+    if !(200..300).contains(&res.status_code()) {
+        return Err(Error::msg(format!(
+            "Failed to fetch image from {}: {}",
+            image_url,
+            res.status_code()
+        )));
+    }
+
+    Ok(image::load_from_memory(&res.body())?.to_rgba8())
+}
+
+// Turns the text_positions argument into text configs, since custom images have no template config
+fn parse_text_positions(
+    text_positions: &[serde_json::Value],
+) -> Result<(Vec<TextConfig>, Vec<String>), Error> {
+    let mut text_configs = Vec::new();
+    let mut texts = Vec::new();
+
+    for position in text_positions {
+        let text = position
+            .get("text")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::msg("Each text position requires a text"))?;
+        let x_pct = position
+            .get("x_pct")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(50.0) as f32;
+        let y_pct = position
+            .get("y_pct")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(5.0) as f32;
+        let scale_pct = position
+            .get("scale_pct")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(10.0) as f32;
+        let align = position
+            .get("align")
+            .and_then(|v| v.as_str())
+            .unwrap_or("center");
+
+        // Centered text is offset from the middle of the image, so shift the anchor accordingly
+        let anchor_x = match align {
+            "left" | "right" => x_pct / 100.0,
+            _ => x_pct / 100.0 - 0.5,
+        };
+
+        text_configs.push(TextConfig {
+            style: "default".to_string(),
+            color: position
+                .get("color")
+                .and_then(|v| v.as_str())
+                .unwrap_or("white")
+                .to_string(),
+            font: String::new(),
+            anchor_x,
+            anchor_y: y_pct / 100.0,
+            angle: 0.0,
+            scale_x: 1.0,
+            scale_y: scale_pct / 100.0,
+            align: align.to_string(),
+            start: 0.0,
+            stop: 1.0,
+            outline_width: default_outline_width(),
+            shadow_offset: default_shadow_offset(),
+        });
+        texts.push(text.to_string());
+    }
+
+    Ok((text_configs, texts))
+}
+
 fn calculate_text_width(font: &FontArc, text: &str, scale: PxScale) -> f32 {
     let scaled_font = font.as_scaled(scale);
     let mut width = 0.0;
@@ -268,22 +432,45 @@ fn color_to_rgba(color: &str) -> Rgba<u8> {
     }
 }
 
-fn calculate_max_scale(
-    font: &FontArc,
-    text: &str,
-    target_width: f32,
-    desired_height: f32,
-) -> PxScale {
-    let initial_scale = PxScale::from(desired_height);
-    let initial_width = calculate_text_width(font, text, initial_scale);
-
-    if initial_width <= target_width {
-        return initial_scale;
+/// Greedily breaks text into lines no wider than `available_width`. Words that are
+/// wider than a whole line on their own are broken between characters.
+fn wrap_text(font: &FontArc, text: &str, available_width: f32, scale: PxScale) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if calculate_text_width(font, &candidate, scale) <= available_width {
+            current = candidate;
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+
+        // The word starts a new line; force breaks if it still doesn't fit
+        for c in word.chars() {
+            current.push(c);
+            if current.chars().count() > 1
+                && calculate_text_width(font, &current, scale) > available_width
+            {
+                current.pop();
+                lines.push(std::mem::take(&mut current));
+                current.push(c);
+            }
+        }
     }
 
-    // Scale down proportionally if text is too wide
-    let scale_factor = target_width / initial_width;
-    PxScale::from(desired_height * scale_factor)
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
 }
 
 impl TemplateConfig {
@@ -337,17 +524,45 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                     "properties": {
                         "template_id": {
                             "type": "string",
-                            "description": "The ID of the template to use",
+                            "description": "The ID of the template to use. Required unless custom_image_url is set",
                         },
                         "texts": {
                             "type": "array",
                             "items": {
                                 "type": "string"
                             },
-                            "description": "Array of text strings to place on the meme",
+                            "description": "Array of text strings to place on the meme. Required unless custom_image_url is set",
+                        },
+                        "custom_image_url": {
+                            "type": "string",
+                            "description": "URL of an image to use instead of a template. The image host must be in the plugin's allowed_hosts",
+                        },
+                        "text_positions": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "text": { "type": "string" },
+                                    "x_pct": { "type": "number", "description": "Horizontal anchor as a percentage of the image width. Defaults to 50" },
+                                    "y_pct": { "type": "number", "description": "Top of the text as a percentage of the image height. Defaults to 5" },
+                                    "scale_pct": { "type": "number", "description": "Text height as a percentage of the image height. Defaults to 10" },
+                                    "color": { "type": "string", "description": "Text color. Defaults to white" },
+                                    "align": { "type": "string", "enum": ["left", "center", "right"], "description": "Text alignment around x_pct. Defaults to center" }
+                                },
+                                "required": ["text"]
+                            },
+                            "description": "Texts and where to draw them on the custom image. Required when custom_image_url is set",
+                        },
+                        "styles": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": ["none", "outline", "shadow"]
+                            },
+                            "description": "Optional rendering style for each text, in the same order as texts. 'outline' draws a black outline around the text and 'shadow' draws a semi-transparent drop shadow. Defaults to 'none'",
                         }
                     },
-                    "required": ["template_id", "texts"]
+                    "required": []
                 })
                 .as_object()
                 .unwrap()
@@ -4,12 +4,23 @@ use extism_pdk::*;
 use pdk::types::{
     CallToolRequest, CallToolResult, Content, ContentType, ListToolsResult, ToolDescription,
 };
-use rusqlite::Connection;
+use rusqlite::{Connection, params_from_iter, types::Value as SqlValue};
 use serde_json::json;
-use std::sync::Once;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, Once};
 
 static DB_INIT: Once = Once::new();
 
+// Connections with an open transaction, keyed by transaction id. The plugin instance
+// persists between calls, so these outlive the call that began the transaction.
+static TRANSACTIONS: LazyLock<Mutex<HashMap<String, Connection>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Connections with attached databases, keyed by connection id, so read queries can join
+// across the attached databases on later calls.
+static CONNECTIONS: LazyLock<Mutex<HashMap<String, Connection>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 fn init_db(db_path: &str) -> Result<(), Error> {
     let _conn = Connection::open_with_flags(
         db_path,
@@ -24,22 +35,103 @@ fn get_db_path() -> Result<String, Error> {
         .ok_or_else(|| Error::msg("db_path configuration is required but not set"))
 }
 
-fn execute_read_query(query: &str, db_path: &str) -> Result<String, Error> {
-    let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+fn allow_raw_sql() -> Result<bool, Error> {
+    Ok(config::get("allow_raw_sql")?
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(true))
+}
+
+fn raw_sql_disabled(tool_name: &str) -> CallToolResult {
+    CallToolResult {
+        is_error: Some(true),
+        content: vec![Content {
+            annotations: None,
+            text: Some(format!(
+                "{} does not accept raw SQL because allow_raw_sql is false. Use sqlite_read_query_params or sqlite_write_query_params with ? placeholders instead",
+                tool_name
+            )),
+            mime_type: None,
+            r#type: ContentType::Text,
+            data: None,
+        }],
+    }
+}
+
+fn json_to_sql_value(value: &serde_json::Value) -> Result<SqlValue, Error> {
+    match value {
+        serde_json::Value::Null => Ok(SqlValue::Null),
+        serde_json::Value::Bool(b) => Ok(SqlValue::Integer(*b as i64)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(SqlValue::Integer(i)),
+            None => n
+                .as_f64()
+                .map(SqlValue::Real)
+                .ok_or_else(|| Error::msg(format!("unsupported numeric parameter: {}", n))),
+        },
+        serde_json::Value::String(s) => Ok(SqlValue::Text(s.clone())),
+        _ => Err(Error::msg(format!(
+            "unsupported parameter {}: only strings, numbers, booleans, and nulls can be bound",
+            value
+        ))),
+    }
+}
+
+fn get_query_params(
+    args: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<SqlValue>, Error> {
+    match args.get("params") {
+        Some(serde_json::Value::Array(values)) => values.iter().map(json_to_sql_value).collect(),
+        None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+        _ => Err(Error::msg("params parameter must be an array")),
+    }
+}
+
+fn value_ref_to_json(value: rusqlite::types::ValueRef) -> serde_json::Value {
+    match value {
+        rusqlite::types::ValueRef::Null => serde_json::Value::Null,
+        rusqlite::types::ValueRef::Integer(i) => json!(i),
+        rusqlite::types::ValueRef::Real(f) => json!(f),
+        rusqlite::types::ValueRef::Text(s) => json!(s),
+        rusqlite::types::ValueRef::Blob(b) => json!(b),
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn execute_read_query(
+    query: &str,
+    params: &[SqlValue],
+    connection_id: Option<&str>,
+    db_path: &str,
+) -> Result<String, Error> {
+    match connection_id {
+        Some(connection_id) => {
+            let connections = CONNECTIONS
+                .lock()
+                .map_err(|_| Error::msg("connection state is poisoned"))?;
+            let conn = connections.get(connection_id).ok_or_else(|| {
+                Error::msg(format!("no open connection with id {}", connection_id))
+            })?;
+            read_rows(conn, query, params)
+        }
+        None => {
+            let conn =
+                Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+            read_rows(&conn, query, params)
+        }
+    }
+}
+
+fn read_rows(conn: &Connection, query: &str, params: &[SqlValue]) -> Result<String, Error> {
     let mut stmt = conn.prepare(query)?;
     let column_names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
 
-    let rows = stmt.query_map([], |row| {
+    let rows = stmt.query_map(params_from_iter(params), |row| {
         let mut map = serde_json::Map::new();
         for (i, col_name) in column_names.iter().enumerate() {
-            let value = match row.get_ref(i)? {
-                rusqlite::types::ValueRef::Null => serde_json::Value::Null,
-                rusqlite::types::ValueRef::Integer(i) => json!(i),
-                rusqlite::types::ValueRef::Real(f) => json!(f),
-                rusqlite::types::ValueRef::Text(s) => json!(s),
-                rusqlite::types::ValueRef::Blob(b) => json!(b),
-            };
-            map.insert(col_name.clone(), value);
+            map.insert(col_name.clone(), value_ref_to_json(row.get_ref(i)?));
         }
         Ok(map)
     })?;
@@ -49,12 +141,189 @@ fn execute_read_query(query: &str, db_path: &str) -> Result<String, Error> {
     Ok(serde_json::to_string(&results)?)
 }
 
-fn execute_write_query(query: &str, db_path: &str) -> Result<String, Error> {
+fn export_query(
+    table_name: Option<&str>,
+    query: Option<&str>,
+    limit: u32,
+) -> Result<String, Error> {
+    match (query, table_name) {
+        (Some(query), _) => Ok(format!(
+            "SELECT * FROM ({}) LIMIT {}",
+            query.trim().trim_end_matches(';'),
+            limit
+        )),
+        (None, Some(table_name)) => Ok(format!(
+            "SELECT * FROM {} LIMIT {}",
+            quote_identifier(table_name),
+            limit
+        )),
+        (None, None) => Err(Error::msg("table_name or query parameter is required")),
+    }
+}
+
+fn fetch_rows(
+    query: &str,
+    db_path: &str,
+) -> Result<(Vec<String>, Vec<Vec<serde_json::Value>>), Error> {
     let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
-    let affected = conn.execute(query, [])?;
+    let mut stmt = conn.prepare(query)?;
+    let column_names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+
+    let rows = stmt.query_map([], |row| {
+        (0..column_names.len())
+            .map(|i| Ok(value_ref_to_json(row.get_ref(i)?)))
+            .collect::<Result<Vec<_>, rusqlite::Error>>()
+    })?;
+
+    let rows = rows.collect::<Result<Vec<_>, _>>()?;
+    Ok((column_names, rows))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn export_csv(query: &str, db_path: &str) -> Result<String, Error> {
+    let (column_names, rows) = fetch_rows(query, db_path)?;
+
+    let mut csv = String::new();
+    let header: Vec<String> = column_names.iter().map(|c| csv_field(c)).collect();
+    csv.push_str(&header.join(","));
+    csv.push_str("\r\n");
+
+    for row in rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|value| match value {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(s) => csv_field(s),
+                other => csv_field(&other.to_string()),
+            })
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+
+    Ok(csv)
+}
+
+fn export_json(query: &str, db_path: &str) -> Result<String, Error> {
+    let (column_names, rows) = fetch_rows(query, db_path)?;
+
+    let results: Vec<serde_json::Map<String, serde_json::Value>> = rows
+        .into_iter()
+        .map(|row| column_names.iter().cloned().zip(row).collect())
+        .collect();
+    Ok(serde_json::to_string_pretty(&results)?)
+}
+
+fn execute_write_query(
+    query: &str,
+    params: &[SqlValue],
+    transaction_id: Option<&str>,
+    db_path: &str,
+) -> Result<String, Error> {
+    let affected = match transaction_id {
+        Some(transaction_id) => {
+            let transactions = TRANSACTIONS
+                .lock()
+                .map_err(|_| Error::msg("transaction state is poisoned"))?;
+            let conn = transactions.get(transaction_id).ok_or_else(|| {
+                Error::msg(format!("no open transaction with id {}", transaction_id))
+            })?;
+            conn.execute(query, params_from_iter(params))?
+        }
+        None => {
+            let conn =
+                Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+            conn.execute(query, params_from_iter(params))?
+        }
+    };
     Ok(json!({ "rows_affected": affected }).to_string())
 }
 
+fn begin_transaction(transaction_id: &str, db_path: &str) -> Result<String, Error> {
+    let mut transactions = TRANSACTIONS
+        .lock()
+        .map_err(|_| Error::msg("transaction state is poisoned"))?;
+    if transactions.contains_key(transaction_id) {
+        return Err(Error::msg(format!(
+            "a transaction with id {} is already open",
+            transaction_id
+        )));
+    }
+
+    let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+    conn.execute_batch("BEGIN")?;
+    transactions.insert(transaction_id.to_string(), conn);
+    Ok(json!({ "status": "success", "transaction_id": transaction_id }).to_string())
+}
+
+fn end_transaction(transaction_id: &str, statement: &str) -> Result<String, Error> {
+    let conn = TRANSACTIONS
+        .lock()
+        .map_err(|_| Error::msg("transaction state is poisoned"))?
+        .remove(transaction_id)
+        .ok_or_else(|| Error::msg(format!("no open transaction with id {}", transaction_id)))?;
+    // Dropping the connection on failure rolls back whatever is still pending
+    conn.execute_batch(statement)?;
+    Ok(json!({ "status": "success", "transaction_id": transaction_id }).to_string())
+}
+
+fn attach_database(
+    path: &str,
+    alias: &str,
+    connection_id: &str,
+    db_path: &str,
+) -> Result<String, Error> {
+    // The path is bound as a parameter, so only the alias needs quoting
+    let attach = format!("ATTACH DATABASE ? AS {}", quote_identifier(alias));
+    let mut connections = CONNECTIONS
+        .lock()
+        .map_err(|_| Error::msg("connection state is poisoned"))?;
+    match connections.get(connection_id) {
+        Some(conn) => {
+            conn.execute(&attach, [path])?;
+        }
+        None => {
+            let conn =
+                Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+            conn.execute(&attach, [path])?;
+            connections.insert(connection_id.to_string(), conn);
+        }
+    }
+    Ok(json!({ "status": "success", "connection_id": connection_id, "alias": alias }).to_string())
+}
+
+fn detach_database(connection_id: &str, alias: &str) -> Result<String, Error> {
+    let mut connections = CONNECTIONS
+        .lock()
+        .map_err(|_| Error::msg("connection state is poisoned"))?;
+    let conn = connections
+        .get(connection_id)
+        .ok_or_else(|| Error::msg(format!("no open connection with id {}", connection_id)))?;
+    conn.execute(&format!("DETACH DATABASE {}", quote_identifier(alias)), [])?;
+
+    // The connection is closed once the last attached database is detached
+    let attached: i64 = conn.query_row(
+        "SELECT count(*) FROM pragma_database_list WHERE name NOT IN ('main', 'temp')",
+        [],
+        |row| row.get(0),
+    )?;
+    let closed = attached == 0;
+    if closed {
+        connections.remove(connection_id);
+    }
+    Ok(
+        json!({ "status": "success", "connection_id": connection_id, "closed": closed })
+            .to_string(),
+    )
+}
+
 fn create_table(query: &str, db_path: &str) -> Result<String, Error> {
     let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
     conn.execute(query, [])?;
@@ -72,36 +341,570 @@ fn describe_table(table_name: &str, db_path: &str) -> Result<String, Error> {
     let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
     let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table_name))?;
 
-    let columns = stmt.query_map([], |row| {
-        Ok(json!({
-            "cid": row.get::<_, i64>(0)?,
-            "name": row.get::<_, String>(1)?,
-            "type": row.get::<_, String>(2)?,
-            "notnull": row.get::<_, bool>(3)?,
-            "dflt_value": row.get::<_, Option<String>>(4)?,
-            "pk": row.get::<_, bool>(5)?
-        }))
-    })?;
+    let columns = stmt.query_map([], |row| {
+        Ok(json!({
+            "cid": row.get::<_, i64>(0)?,
+            "name": row.get::<_, String>(1)?,
+            "type": row.get::<_, String>(2)?,
+            "notnull": row.get::<_, bool>(3)?,
+            "dflt_value": row.get::<_, Option<String>>(4)?,
+            "pk": row.get::<_, bool>(5)?
+        }))
+    })?;
+
+    let schema: Vec<serde_json::Value> = columns.filter_map(Result::ok).collect();
+    Ok(json!({ "schema": schema }).to_string())
+}
+
+fn get_schema_ddl(table_name: Option<&str>, db_path: &str) -> Result<String, Error> {
+    let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+    // Tables come first so the statements can be replayed in order. Automatic indexes
+    // have no SQL, and sqlite_ tables are internal
+    let mut stmt = conn.prepare(
+        "SELECT sql FROM sqlite_master \
+         WHERE type IN ('table', 'index', 'trigger', 'view') AND sql IS NOT NULL \
+         AND name NOT LIKE 'sqlite_%' AND (?1 IS NULL OR tbl_name = ?1) \
+         ORDER BY CASE type WHEN 'table' THEN 0 WHEN 'view' THEN 1 WHEN 'index' THEN 2 ELSE 3 END, name",
+    )?;
+    let statements: Vec<String> = stmt
+        .query_map([table_name], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(statements
+        .iter()
+        .map(|sql| format!("{};\n", sql))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn ensure_fts5(conn: &Connection) -> Result<(), Error> {
+    let mut stmt = conn.prepare("PRAGMA compile_options")?;
+    let options: Vec<String> = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    if options.iter().any(|o| o == "ENABLE_FTS5") {
+        Ok(())
+    } else {
+        Err(Error::msg("FTS5 is not available in this SQLite build"))
+    }
+}
+
+fn create_fts_index(table_name: &str, db_path: &str) -> Result<String, Error> {
+    let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+    ensure_fts5(&conn)?;
+
+    // Columns with TEXT affinity, following SQLite's type affinity rules
+    let mut stmt = conn.prepare(&format!(
+        "PRAGMA table_info({})",
+        quote_identifier(table_name)
+    ))?;
+    let columns: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(1)?, row.get(2)?)))?
+        .collect::<Result<_, _>>()?;
+    let text_columns: Vec<String> = columns
+        .into_iter()
+        .filter(|(_, col_type)| {
+            let col_type = col_type.to_uppercase();
+            !col_type.contains("INT")
+                && (col_type.contains("CHAR")
+                    || col_type.contains("CLOB")
+                    || col_type.contains("TEXT"))
+        })
+        .map(|(name, _)| name)
+        .collect();
+
+    if text_columns.is_empty() {
+        return Err(Error::msg(format!(
+            "table {} has no text columns to index",
+            table_name
+        )));
+    }
+
+    let fts_table = format!("{}_fts", table_name);
+    let column_list: Vec<String> = text_columns.iter().map(|c| quote_identifier(c)).collect();
+    // External content table: the index reads column values from the source table
+    conn.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE {fts} USING fts5({columns}, content={content}, content_rowid='rowid');
+         INSERT INTO {fts}({fts}) VALUES('rebuild');",
+        fts = quote_identifier(&fts_table),
+        columns = column_list.join(", "),
+        content = quote_identifier(table_name),
+    ))?;
+
+    Ok(json!({ "status": "success", "fts_table": fts_table, "columns": text_columns }).to_string())
+}
+
+fn fts_query(table_name: &str, search: &str, limit: u32, db_path: &str) -> Result<String, Error> {
+    {
+        let conn =
+            Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+        ensure_fts5(&conn)?;
+    }
+
+    let fts_table = quote_identifier(&format!("{}_fts", table_name));
+    execute_read_query(
+        &format!(
+            "SELECT rowid, *, snippet({fts}, -1, '[', ']', '...', 16) AS snippet, rank \
+             FROM {fts} WHERE {fts} MATCH ? ORDER BY rank LIMIT ?",
+            fts = fts_table
+        ),
+        &[
+            SqlValue::Text(search.to_string()),
+            SqlValue::Integer(limit as i64),
+        ],
+        None,
+        db_path,
+    )
+}
+
+fn parse_csv(data: &str) -> Result<Vec<Vec<String>>, Error> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => in_quotes = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') | (false, '\r') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(Error::msg("CSV data has an unterminated quoted field"));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    // Blank lines carry no row
+    records.retain(|r| !(r.len() == 1 && r[0].is_empty()));
+    Ok(records)
+}
+
+// Empty fields are imported as NULL, and numbers as INTEGER or REAL so they keep their
+// type in columns created without one
+fn csv_value(field: &str) -> SqlValue {
+    if field.is_empty() {
+        SqlValue::Null
+    } else if let Ok(i) = field.parse::<i64>() {
+        SqlValue::Integer(i)
+    } else if let Some(f) = field.parse::<f64>().ok().filter(|f| f.is_finite()) {
+        SqlValue::Real(f)
+    } else {
+        SqlValue::Text(field.to_string())
+    }
+}
+
+fn csv_buffer_var(table_name: &str) -> String {
+    format!("csv_import:{}", table_name)
+}
+
+// Appends a chunk of a CSV import to the buffer of `table_name`, kept in an Extism var
+// until the final chunk arrives
+fn buffer_csv_chunk(table_name: &str, csv_data: &str) -> Result<String, Error> {
+    let key = csv_buffer_var(table_name);
+    let mut buffer: String = var::get(&key)?.unwrap_or_default();
+    buffer.push_str(csv_data);
+    var::set(&key, buffer.as_str())?;
+    Ok(json!({ "status": "buffered", "buffered_bytes": buffer.len() }).to_string())
+}
+
+fn import_csv(
+    table_name: &str,
+    csv_data: &str,
+    create_table: bool,
+    db_path: &str,
+) -> Result<(String, bool), Error> {
+    let key = csv_buffer_var(table_name);
+    let csv_data = match var::get::<String>(&key)? {
+        Some(buffer) => {
+            var::remove(&key)?;
+            buffer + csv_data
+        }
+        None => csv_data.to_string(),
+    };
+
+    let mut records = parse_csv(&csv_data)?.into_iter();
+    let headers = records
+        .next()
+        .ok_or_else(|| Error::msg("CSV data must start with a header row"))?;
+    if headers.iter().any(|h| h.is_empty()) {
+        return Err(Error::msg("CSV header has an empty column name"));
+    }
+
+    let mut conn =
+        Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+    let tx = conn.transaction()?;
+    let table = quote_identifier(table_name);
+    let columns: Vec<String> = headers.iter().map(|h| quote_identifier(h)).collect();
+
+    if create_table {
+        tx.execute_batch(&format!(
+            "DROP TABLE IF EXISTS {table}; CREATE TABLE {table} ({});",
+            columns.join(", ")
+        ))?;
+    } else {
+        let table_columns: Vec<String> = tx
+            .prepare(&format!("PRAGMA table_info({})", table))?
+            .query_map([], |row| row.get(1))?
+            .collect::<Result<_, _>>()?;
+        if table_columns.is_empty() {
+            return Err(Error::msg(format!(
+                "table {} does not exist. Set create_table to true to create it",
+                table_name
+            )));
+        }
+        let unknown: Vec<&str> = headers
+            .iter()
+            .filter(|h| !table_columns.contains(h))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(Error::msg(format!(
+                "CSV columns {} are not in table {}, which has columns {}",
+                unknown.join(", "),
+                table_name,
+                table_columns.join(", ")
+            )));
+        }
+    }
+
+    let mut rows_inserted = 0;
+    let mut errors = Vec::new();
+    {
+        let mut stmt = tx.prepare(&format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            table,
+            columns.join(", "),
+            vec!["?"; columns.len()].join(", ")
+        ))?;
+        // The header is row 1
+        for (i, record) in records.enumerate() {
+            if record.len() != headers.len() {
+                errors.push(format!(
+                    "row {}: expected {} fields, found {}",
+                    i + 2,
+                    headers.len(),
+                    record.len()
+                ));
+                continue;
+            }
+            match stmt.execute(params_from_iter(record.iter().map(|f| csv_value(f)))) {
+                Ok(_) => rows_inserted += 1,
+                Err(e) => errors.push(format!("row {}: {}", i + 2, e)),
+            }
+        }
+    }
+
+    // Either every row is imported or none is
+    if errors.is_empty() {
+        tx.commit()?;
+    } else {
+        rows_inserted = 0;
+    }
+    let failed = !errors.is_empty();
+    Ok((
+        json!({ "rows_inserted": rows_inserted, "errors": errors }).to_string(),
+        failed,
+    ))
+}
+
+pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
+    let db_path = get_db_path()?;
+    DB_INIT.call_once(|| {
+        init_db(&db_path).expect("Failed to initialize database");
+    });
+
+    match input.params.name.as_str() {
+        "sqlite_read_query" => {
+            if !allow_raw_sql()? {
+                return Ok(raw_sql_disabled("sqlite_read_query"));
+            }
+
+            let args = input.params.arguments.unwrap_or_default();
+            let query = match args.get("query") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("query parameter is required")),
+            };
+
+            let connection_id = args.get("connection_id").and_then(|v| v.as_str());
+
+            let result = execute_read_query(query, &[], connection_id, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_write_query" => {
+            if !allow_raw_sql()? {
+                return Ok(raw_sql_disabled("sqlite_write_query"));
+            }
+
+            let args = input.params.arguments.unwrap_or_default();
+            let query = match args.get("query") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("query parameter is required")),
+            };
+
+            let transaction_id = args.get("transaction_id").and_then(|v| v.as_str());
+
+            let result = execute_write_query(query, &[], transaction_id, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_read_query_params" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let query = match args.get("query") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("query parameter is required")),
+            };
+            let params = get_query_params(&args)?;
+            let connection_id = args.get("connection_id").and_then(|v| v.as_str());
+
+            let result = execute_read_query(query, &params, connection_id, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_write_query_params" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let query = match args.get("query") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("query parameter is required")),
+            };
+            let params = get_query_params(&args)?;
+            let transaction_id = args.get("transaction_id").and_then(|v| v.as_str());
+
+            let result = execute_write_query(query, &params, transaction_id, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_create_table" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let query = match args.get("query") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("query parameter is required")),
+            };
+
+            let result = create_table(query, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_list_tables" => {
+            let result = list_tables(&db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_describe_table" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let table_name = match args.get("table_name") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("table_name parameter is required")),
+            };
+
+            let result = describe_table(table_name, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_get_schema_ddl" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let table_name = args.get("table_name").and_then(|v| v.as_str());
+
+            let result = get_schema_ddl(table_name, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/sql".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_begin_transaction" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let transaction_id = match args.get("transaction_id") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("transaction_id parameter is required")),
+            };
+
+            let result = begin_transaction(transaction_id, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_commit_transaction" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let transaction_id = match args.get("transaction_id") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("transaction_id parameter is required")),
+            };
+
+            let result = end_transaction(transaction_id, "COMMIT")?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_rollback_transaction" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let transaction_id = match args.get("transaction_id") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("transaction_id parameter is required")),
+            };
+
+            let result = end_transaction(transaction_id, "ROLLBACK")?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_export_csv" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let table_name = args.get("table_name").and_then(|v| v.as_str());
+            let query = args.get("query").and_then(|v| v.as_str());
+            let limit = args
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|v| v.min(u32::MAX as u64) as u32)
+                .unwrap_or(10000);
+
+            if query.is_some() && !allow_raw_sql()? {
+                return Ok(raw_sql_disabled("sqlite_export_csv"));
+            }
 
-    let schema: Vec<serde_json::Value> = columns.filter_map(Result::ok).collect();
-    Ok(json!({ "schema": schema }).to_string())
-}
+            let result = export_csv(&export_query(table_name, query, limit)?, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("text/csv".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_export_json" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let table_name = args.get("table_name").and_then(|v| v.as_str());
+            let query = args.get("query").and_then(|v| v.as_str());
+            let limit = args
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|v| v.min(u32::MAX as u64) as u32)
+                .unwrap_or(10000);
 
-pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
-    let db_path = get_db_path()?;
-    DB_INIT.call_once(|| {
-        init_db(&db_path).expect("Failed to initialize database");
-    });
+            if query.is_some() && !allow_raw_sql()? {
+                return Ok(raw_sql_disabled("sqlite_export_json"));
+            }
 
-    match input.params.name.as_str() {
-        "sqlite_read_query" => {
+            let result = export_json(&export_query(table_name, query, limit)?, &db_path)?;
+            Ok(CallToolResult {
+                is_error: None,
+                content: vec![Content {
+                    annotations: None,
+                    text: Some(result),
+                    mime_type: Some("application/json".to_string()),
+                    r#type: ContentType::Text,
+                    data: None,
+                }],
+            })
+        }
+        "sqlite_create_fts_index" => {
             let args = input.params.arguments.unwrap_or_default();
-            let query = match args.get("query") {
+            let table_name = match args.get("table_name") {
                 Some(v) if v.is_string() => v.as_str().unwrap(),
-                _ => return Err(Error::msg("query parameter is required")),
+                _ => return Err(Error::msg("table_name parameter is required")),
             };
 
-            let result = execute_read_query(query, &db_path)?;
+            let result = create_fts_index(table_name, &db_path)?;
             Ok(CallToolResult {
                 is_error: None,
                 content: vec![Content {
@@ -113,14 +916,23 @@ pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
                 }],
             })
         }
-        "sqlite_write_query" => {
+        "sqlite_fts_query" => {
             let args = input.params.arguments.unwrap_or_default();
-            let query = match args.get("query") {
+            let table_name = match args.get("table_name") {
                 Some(v) if v.is_string() => v.as_str().unwrap(),
-                _ => return Err(Error::msg("query parameter is required")),
+                _ => return Err(Error::msg("table_name parameter is required")),
+            };
+            let search = match args.get("search") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("search parameter is required")),
             };
+            let limit = args
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|v| v.min(u32::MAX as u64) as u32)
+                .unwrap_or(20);
 
-            let result = execute_write_query(query, &db_path)?;
+            let result = fts_query(table_name, search, limit, &db_path)?;
             Ok(CallToolResult {
                 is_error: None,
                 content: vec![Content {
@@ -132,14 +944,22 @@ pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
                 }],
             })
         }
-        "sqlite_create_table" => {
+        "sqlite_attach_database" => {
             let args = input.params.arguments.unwrap_or_default();
-            let query = match args.get("query") {
+            let path = match args.get("path") {
                 Some(v) if v.is_string() => v.as_str().unwrap(),
-                _ => return Err(Error::msg("query parameter is required")),
+                _ => return Err(Error::msg("path parameter is required")),
             };
+            let alias = match args.get("alias") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("alias parameter is required")),
+            };
+            let connection_id = args
+                .get("connection_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or(alias);
 
-            let result = create_table(query, &db_path)?;
+            let result = attach_database(path, alias, connection_id, &db_path)?;
             Ok(CallToolResult {
                 is_error: None,
                 content: vec![Content {
@@ -151,8 +971,18 @@ pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
                 }],
             })
         }
-        "sqlite_list_tables" => {
-            let result = list_tables(&db_path)?;
+        "sqlite_detach_database" => {
+            let args = input.params.arguments.unwrap_or_default();
+            let connection_id = match args.get("connection_id") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("connection_id parameter is required")),
+            };
+            let alias = match args.get("alias") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("alias parameter is required")),
+            };
+
+            let result = detach_database(connection_id, alias)?;
             Ok(CallToolResult {
                 is_error: None,
                 content: vec![Content {
@@ -164,16 +994,29 @@ pub(crate) fn call(input: CallToolRequest) -> Result<CallToolResult, Error> {
                 }],
             })
         }
-        "sqlite_describe_table" => {
+        "sqlite_import_csv" => {
             let args = input.params.arguments.unwrap_or_default();
             let table_name = match args.get("table_name") {
                 Some(v) if v.is_string() => v.as_str().unwrap(),
                 _ => return Err(Error::msg("table_name parameter is required")),
             };
+            let csv_data = match args.get("csv_data") {
+                Some(v) if v.is_string() => v.as_str().unwrap(),
+                _ => return Err(Error::msg("csv_data parameter is required")),
+            };
+            let create_table = args
+                .get("create_table")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let more = args.get("more").and_then(|v| v.as_bool()).unwrap_or(false);
 
-            let result = describe_table(table_name, &db_path)?;
+            let (result, failed) = if more {
+                (buffer_csv_chunk(table_name, csv_data)?, false)
+            } else {
+                import_csv(table_name, csv_data, create_table, &db_path)?
+            };
             Ok(CallToolResult {
-                is_error: None,
+                is_error: failed.then_some(true),
                 content: vec![Content {
                     annotations: None,
                     text: Some(result),
@@ -208,6 +1051,10 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                         "query": {
                             "type": "string",
                             "description": "SELECT SQL query to execute",
+                        },
+                        "connection_id": {
+                            "type": "string",
+                            "description": "Id of a connection returned by sqlite_attach_database, to query its attached databases. Optional",
                         }
                     },
                     "required": ["query"],
@@ -226,6 +1073,66 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                         "query": {
                             "type": "string",
                             "description": "SQL query to execute",
+                        },
+                        "transaction_id": {
+                            "type": "string",
+                            "description": "Id of an open transaction to execute the query in. Optional",
+                        }
+                    },
+                    "required": ["query"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_read_query_params".into(),
+                description: "Execute a SELECT query on the SQLite database with ? placeholders bound to the given parameters".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "SELECT SQL query to execute, using ? placeholders for values",
+                        },
+                        "params": {
+                            "type": "array",
+                            "items": {
+                                "type": ["string", "number", "boolean", "null"],
+                            },
+                            "description": "Values bound to the ? placeholders, in order",
+                        },
+                        "connection_id": {
+                            "type": "string",
+                            "description": "Id of a connection returned by sqlite_attach_database, to query its attached databases. Optional",
+                        }
+                    },
+                    "required": ["query"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_write_query_params".into(),
+                description: "Execute an INSERT, UPDATE, or DELETE query on the SQLite database with ? placeholders bound to the given parameters".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "SQL query to execute, using ? placeholders for values",
+                        },
+                        "params": {
+                            "type": "array",
+                            "items": {
+                                "type": ["string", "number", "boolean", "null"],
+                            },
+                            "description": "Values bound to the ? placeholders, in order",
+                        },
+                        "transaction_id": {
+                            "type": "string",
+                            "description": "Id of an open transaction to execute the query in. Optional",
                         }
                     },
                     "required": ["query"],
@@ -280,6 +1187,241 @@ pub(crate) fn describe() -> Result<ListToolsResult, Error> {
                 .unwrap()
                 .clone(),
             },
+            ToolDescription {
+                name: "sqlite_get_schema_ddl".into(),
+                description: "Get the CREATE statements of the tables, views, indexes, and triggers in the SQLite database, e.g. to write a migration script".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "table_name": {
+                            "type": "string",
+                            "description": "Only return the statements of this table and its indexes and triggers. Optional",
+                        }
+                    },
+                    "required": [],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_begin_transaction".into(),
+                description: "Begin a transaction on the SQLite database. Pass the transaction_id to sqlite_write_query to execute queries in it".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "transaction_id": {
+                            "type": "string",
+                            "description": "Id identifying the transaction",
+                        }
+                    },
+                    "required": ["transaction_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_commit_transaction".into(),
+                description: "Commit an open transaction".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "transaction_id": {
+                            "type": "string",
+                            "description": "Id of the transaction",
+                        }
+                    },
+                    "required": ["transaction_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_rollback_transaction".into(),
+                description: "Roll back an open transaction".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "transaction_id": {
+                            "type": "string",
+                            "description": "Id of the transaction",
+                        }
+                    },
+                    "required": ["transaction_id"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_export_csv".into(),
+                description: "Export the rows of a table or query as CSV with a header row".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "table_name": {
+                            "type": "string",
+                            "description": "Name of the table to export",
+                        },
+                        "query": {
+                            "type": "string",
+                            "description": "SELECT SQL query to export instead of the whole table. Optional",
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of rows to export. Defaults to 10000",
+                        }
+                    },
+                    "required": ["table_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_export_json".into(),
+                description: "Export the rows of a table or query as pretty-printed JSON".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "table_name": {
+                            "type": "string",
+                            "description": "Name of the table to export",
+                        },
+                        "query": {
+                            "type": "string",
+                            "description": "SELECT SQL query to export instead of the whole table. Optional",
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of rows to export. Defaults to 10000",
+                        }
+                    },
+                    "required": ["table_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_create_fts_index".into(),
+                description: "Create an FTS5 full-text search index named <table_name>_fts over the text columns of a table".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "table_name": {
+                            "type": "string",
+                            "description": "Name of the table to index",
+                        }
+                    },
+                    "required": ["table_name"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_fts_query".into(),
+                description: "Search the FTS5 index of a table created by sqlite_create_fts_index. Returns matching rows ranked by relevance, each with a snippet highlighting the match in [brackets]".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "table_name": {
+                            "type": "string",
+                            "description": "Name of the indexed table",
+                        },
+                        "search": {
+                            "type": "string",
+                            "description": "FTS5 search expression, e.g. 'sqlite AND search' or 'data*'",
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of results. Defaults to 20",
+                        }
+                    },
+                    "required": ["table_name", "search"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_attach_database".into(),
+                description: "Attach another SQLite database file under an alias, on a connection kept open for sqlite_read_query and sqlite_read_query_params. Tables of the attached database are queried as <alias>.<table>, e.g. to join across databases".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Path to the database file to attach",
+                        },
+                        "alias": {
+                            "type": "string",
+                            "description": "Schema name of the attached database in queries",
+                        },
+                        "connection_id": {
+                            "type": "string",
+                            "description": "Id of the connection to attach to. A new connection is opened if there is none with this id. Defaults to the alias",
+                        }
+                    },
+                    "required": ["path", "alias"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_detach_database".into(),
+                description: "Detach a database attached by sqlite_attach_database. The connection is closed once its last database is detached".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "connection_id": {
+                            "type": "string",
+                            "description": "Id of the connection",
+                        },
+                        "alias": {
+                            "type": "string",
+                            "description": "Alias of the database to detach",
+                        }
+                    },
+                    "required": ["connection_id", "alias"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
+            ToolDescription {
+                name: "sqlite_import_csv".into(),
+                description: "Import CSV data with a header row into a table in a single transaction. Either every row is inserted or none is. Returns the number of rows inserted and the errors of the rows that failed".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "table_name": {
+                            "type": "string",
+                            "description": "Name of the table to import into",
+                        },
+                        "csv_data": {
+                            "type": "string",
+                            "description": "CSV content, starting with a header row of column names",
+                        },
+                        "create_table": {
+                            "type": "boolean",
+                            "description": "Drop the table and create it again with the columns of the header row. Otherwise the rows are appended to the existing table, whose columns must include every header column. Defaults to false",
+                        },
+                        "more": {
+                            "type": "boolean",
+                            "description": "Set to true to buffer csv_data as one chunk of a large CSV. The import runs on the next call for the same table without more, on the chunks concatenated in order. Defaults to false",
+                        }
+                    },
+                    "required": ["table_name", "csv_data"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            },
         ],
     })
 }
@@ -0,0 +1,665 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const DEFAULT_DOCKER_HOST: &str = "http://localhost:2375";
+
+const DEFAULT_LOG_TAIL: i64 = 100;
+
+const DEFAULT_STOP_TIMEOUT_SECS: i64 = 10;
+
+// Returns the base URL of the Docker Engine API from `DOCKER_HOST`. Plugins can only
+// make HTTP requests, so a daemon listening on a Unix socket has to be exposed over
+// TCP first, e.g. with `socat TCP-LISTEN:2375,fork UNIX-CONNECT:/var/run/docker.sock`.
+fn docker_url(docker_host: Option<&str>) -> Result<String> {
+    let docker_host = docker_host.unwrap_or(DEFAULT_DOCKER_HOST);
+    let url = if let Some(address) = docker_host.strip_prefix("tcp://") {
+        format!("http://{address}")
+    } else if docker_host.starts_with("unix://") || docker_host.starts_with('/') {
+        return Err(anyhow!(
+            "DOCKER_HOST {docker_host} is a Unix socket, which plugins cannot connect to. Expose the daemon over TCP and set DOCKER_HOST to its http:// URL"
+        ));
+    } else {
+        docker_host.to_string()
+    };
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+fn docker_request(method: &str, path: &str, body: Option<&Value>) -> Result<Vec<u8>> {
+    let base_url = docker_url(config::get("DOCKER_HOST")?.as_deref())?;
+    let req = HttpRequest::new(format!("{base_url}{path}"))
+        .with_method(method)
+        .with_header("Content-Type", "application/json")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+
+    let res = request(&req, body.map(serde_json::to_vec).transpose()?)?;
+    let res_body = res.body();
+    // 304 is returned when starting a started or stopping a stopped container
+    if !(200..300).contains(&res.status_code()) && res.status_code() != 304 {
+        // Errors look like `{"message": "No such container: ..."}`
+        let message = serde_json::from_slice::<Value>(&res_body)
+            .ok()
+            .and_then(|error| error["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| String::from_utf8_lossy(&res_body).to_string());
+        return Err(anyhow!(
+            "Docker API returned {}: {}",
+            res.status_code(),
+            message
+        ));
+    }
+    Ok(res_body)
+}
+
+fn docker_json(method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+    Ok(serde_json::from_slice(&docker_request(
+        method, path, body,
+    )?)?)
+}
+
+fn encode_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{key}={}", urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// Splits the output of a container without a TTY into stdout and stderr. Docker
+// multiplexes both streams into frames with an 8-byte header: the stream type (1 for
+// stdout, 2 for stderr), three zero bytes and the big-endian length of the payload.
+// Output that is not multiplexed, e.g. of a container with a TTY, is returned as stdout.
+fn demultiplex(output: &[u8]) -> (String, String) {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut rest = output;
+    while !rest.is_empty() {
+        let is_frame = rest.len() >= 8 && matches!(rest[0], 0..=2) && rest[1..4] == [0, 0, 0];
+        if !is_frame {
+            return (String::from_utf8_lossy(output).to_string(), String::new());
+        }
+        let len = u32::from_be_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize;
+        let payload = &rest[8..(8 + len).min(rest.len())];
+        match rest[0] {
+            2 => stderr.extend_from_slice(payload),
+            _ => stdout.extend_from_slice(payload),
+        }
+        rest = &rest[8 + payload.len()..];
+    }
+    (
+        String::from_utf8_lossy(&stdout).to_string(),
+        String::from_utf8_lossy(&stderr).to_string(),
+    )
+}
+
+// Returns the error of an image pull, if any. The pull progress is a stream of JSON
+// objects, and a failed pull still returns `200 OK` with an `error` in the stream.
+fn pull_error(progress: &[u8]) -> Option<String> {
+    serde_json::Deserializer::from_slice(progress)
+        .into_iter::<Value>()
+        .filter_map(Result::ok)
+        .find_map(|event| event["error"].as_str().map(str::to_string))
+}
+
+fn get_container_id(args: &Map<String, Value>, name: &str) -> Result<String> {
+    Ok(urlencoding::encode(get_required_string_arg(args, name)?).to_string())
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    text_result(serde_json::to_string(value)?)
+}
+
+fn text_result(text: String) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "docker_container_logs" => container_logs(&args),
+        "docker_exec" => exec(&args),
+        "docker_inspect_container" => inspect_container(&args),
+        "docker_list_containers" => list_containers(&args),
+        "docker_list_images" => list_images(&args),
+        "docker_pull_image" => pull_image(&args),
+        "docker_remove_container" => remove_container(&args),
+        "docker_start_container" => start_container(&args),
+        "docker_stop_container" => stop_container(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn container_logs(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "id");
+    let id = get_container_id(args, "id")?;
+    let tail = get_int_arg(args, "tail").unwrap_or(DEFAULT_LOG_TAIL);
+    let query = encode_query(&[
+        ("stdout", "true".to_string()),
+        ("stderr", "true".to_string()),
+        ("tail", tail.to_string()),
+    ]);
+
+    let output = docker_request("GET", &format!("/containers/{id}/logs?{query}"), None)?;
+    let (stdout, stderr) = demultiplex(&output);
+    json_result(&json!({"stdout": stdout, "stderr": stderr}))
+}
+
+fn exec(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "container_id", "command");
+    let id = get_container_id(args, "container_id")?;
+    let command: Vec<&str> = get_array_arg(args, "command")
+        .map(|command| command.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    if command.is_empty() {
+        return Ok(error_result(
+            "Error: 'command' must be a non-empty array of strings".to_string(),
+        ));
+    }
+
+    let exec = docker_json(
+        "POST",
+        &format!("/containers/{id}/exec"),
+        Some(&json!({"AttachStdout": true, "AttachStderr": true, "Cmd": command})),
+    )?;
+    let exec_id = exec["Id"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Docker API returned no exec ID"))?;
+    let output = docker_request(
+        "POST",
+        &format!("/exec/{exec_id}/start"),
+        Some(&json!({"Detach": false, "Tty": false})),
+    )?;
+    let (stdout, stderr) = demultiplex(&output);
+    let inspect = docker_json("GET", &format!("/exec/{exec_id}/json"), None)?;
+
+    json_result(&json!({
+        "exit_code": inspect["ExitCode"],
+        "stdout": stdout,
+        "stderr": stderr,
+    }))
+}
+
+fn inspect_container(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "id");
+    let id = get_container_id(args, "id")?;
+    json_result(&docker_json(
+        "GET",
+        &format!("/containers/{id}/json"),
+        None,
+    )?)
+}
+
+fn list_containers(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let mut filters = Map::new();
+    if let Some(label) = get_string_arg(args, "label") {
+        filters.insert("label".to_string(), json!([label]));
+    }
+    if let Some(name) = get_string_arg(args, "name") {
+        filters.insert("name".to_string(), json!([name]));
+    }
+    let mut query = vec![(
+        "all",
+        get_bool_arg(args, "all").unwrap_or(false).to_string(),
+    )];
+    if !filters.is_empty() {
+        query.push(("filters", Value::Object(filters).to_string()));
+    }
+
+    let containers = docker_json(
+        "GET",
+        &format!("/containers/json?{}", encode_query(&query)),
+        None,
+    )?;
+    json_result(&containers)
+}
+
+fn list_images(_args: &Map<String, Value>) -> Result<CallToolResult> {
+    json_result(&docker_json("GET", "/images/json", None)?)
+}
+
+fn pull_image(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "image");
+    let image = get_required_string_arg(args, "image")?;
+    // Pull `latest` rather than every tag when no tag or digest is given
+    let has_tag = image.contains('@')
+        || image
+            .rsplit_once(':')
+            .is_some_and(|(_, tag)| !tag.contains('/'));
+    let query = if has_tag {
+        encode_query(&[("fromImage", image.to_string())])
+    } else {
+        encode_query(&[
+            ("fromImage", image.to_string()),
+            ("tag", "latest".to_string()),
+        ])
+    };
+
+    let progress = docker_request("POST", &format!("/images/create?{query}"), None)?;
+    if let Some(error) = pull_error(&progress) {
+        return Ok(error_result(format!(
+            "Error: failed to pull {image}: {error}"
+        )));
+    }
+    text_result(format!("Pulled {image}"))
+}
+
+fn remove_container(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "id");
+    let id = get_container_id(args, "id")?;
+    let force = get_bool_arg(args, "force").unwrap_or(false);
+    docker_request(
+        "DELETE",
+        &format!(
+            "/containers/{id}?{}",
+            encode_query(&[("force", force.to_string())])
+        ),
+        None,
+    )?;
+    text_result(format!("Removed container {}", urlencoding::decode(&id)?))
+}
+
+fn start_container(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "id");
+    let id = get_container_id(args, "id")?;
+    docker_request("POST", &format!("/containers/{id}/start"), None)?;
+    text_result(format!("Started container {}", urlencoding::decode(&id)?))
+}
+
+fn stop_container(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "id");
+    let id = get_container_id(args, "id")?;
+    let timeout = get_int_arg(args, "timeout_secs").unwrap_or(DEFAULT_STOP_TIMEOUT_SECS);
+    docker_request("POST", &format!("/containers/{id}/stop?t={timeout}"), None)?;
+    text_result(format!("Stopped container {}", urlencoding::decode(&id)?))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations(read_only: bool, destructive: bool, idempotent: bool) -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(destructive),
+        idempotent_hint: Some(idempotent),
+        open_world_hint: Some(false),
+        read_only_hint: Some(read_only),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn container_id_schema() -> Value {
+    json!({
+        "id": {
+            "type": "string",
+            "description": "The ID or name of the container.",
+        },
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(true, false, true),
+                description: Some(
+                    "Returns the most recent stdout and stderr output of a container.".to_string(),
+                ),
+                input_schema: tool_schema(
+                    json!({
+                        "id": {
+                            "type": "string",
+                            "description": "The ID or name of the container.",
+                        },
+                        "tail": {
+                            "type": "integer",
+                            "description": format!("Number of lines to return from the end of the logs. Defaults to {DEFAULT_LOG_TAIL}."),
+                        },
+                    }),
+                    &["id"],
+                ),
+                name: "docker_container_logs".to_string(),
+                title: Some("Container Logs".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, true, false),
+                description: Some(
+                    "Runs a command in a running container and returns its exit code and output."
+                        .to_string(),
+                ),
+                input_schema: tool_schema(
+                    json!({
+                        "container_id": {
+                            "type": "string",
+                            "description": "The ID or name of the container.",
+                        },
+                        "command": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "The command and its arguments, e.g. [\"ls\", \"-la\", \"/app\"].",
+                        },
+                    }),
+                    &["container_id", "command"],
+                ),
+                name: "docker_exec".to_string(),
+                title: Some("Exec in Container".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, false, true),
+                description: Some("Returns the low-level details of a container.".to_string()),
+                input_schema: tool_schema(container_id_schema(), &["id"]),
+                name: "docker_inspect_container".to_string(),
+                title: Some("Inspect Container".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, false, true),
+                description: Some(
+                    "Lists containers, optionally filtered by label or name.".to_string(),
+                ),
+                input_schema: tool_schema(
+                    json!({
+                        "all": {
+                            "type": "boolean",
+                            "description": "Also list stopped containers. Defaults to false.",
+                        },
+                        "label": {
+                            "type": "string",
+                            "description": "Only list containers with this label, e.g. 'app' or 'app=web'.",
+                        },
+                        "name": {
+                            "type": "string",
+                            "description": "Only list containers whose name contains this text.",
+                        },
+                    }),
+                    &[],
+                ),
+                name: "docker_list_containers".to_string(),
+                title: Some("List Containers".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, false, true),
+                description: Some("Lists the images on the Docker host.".to_string()),
+                input_schema: tool_schema(json!({}), &[]),
+                name: "docker_list_images".to_string(),
+                title: Some("List Images".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, false, true),
+                description: Some("Pulls an image from a registry.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "image": {
+                            "type": "string",
+                            "description": "The image to pull, e.g. 'nginx:1.27'. Pulls the 'latest' tag if none is given.",
+                        },
+                    }),
+                    &["image"],
+                ),
+                name: "docker_pull_image".to_string(),
+                title: Some("Pull Image".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, true, true),
+                description: Some("Removes a container.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "id": {
+                            "type": "string",
+                            "description": "The ID or name of the container.",
+                        },
+                        "force": {
+                            "type": "boolean",
+                            "description": "Kill and remove the container if it is running. Defaults to false.",
+                        },
+                    }),
+                    &["id"],
+                ),
+                name: "docker_remove_container".to_string(),
+                title: Some("Remove Container".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, false, true),
+                description: Some("Starts a stopped container.".to_string()),
+                input_schema: tool_schema(container_id_schema(), &["id"]),
+                name: "docker_start_container".to_string(),
+                title: Some("Start Container".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, true, true),
+                description: Some(
+                    "Stops a running container, killing it if it does not stop within the timeout."
+                        .to_string(),
+                ),
+                input_schema: tool_schema(
+                    json!({
+                        "id": {
+                            "type": "string",
+                            "description": "The ID or name of the container.",
+                        },
+                        "timeout_secs": {
+                            "type": "integer",
+                            "description": format!("Seconds to wait before killing the container. Defaults to {DEFAULT_STOP_TIMEOUT_SECS}."),
+                        },
+                    }),
+                    &["id"],
+                ),
+                name: "docker_stop_container".to_string(),
+                title: Some("Stop Container".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn frame(stream: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![stream, 0, 0, 0];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    #[test]
+    fn test_docker_url() {
+        assert_eq!(docker_url(None).unwrap(), "http://localhost:2375");
+        assert_eq!(
+            docker_url(Some("tcp://10.0.0.5:2375")).unwrap(),
+            "http://10.0.0.5:2375"
+        );
+        assert_eq!(
+            docker_url(Some("https://docker.internal/")).unwrap(),
+            "https://docker.internal"
+        );
+        assert!(docker_url(Some("unix:///var/run/docker.sock")).is_err());
+        assert!(docker_url(Some("/var/run/docker.sock")).is_err());
+    }
+
+    #[test]
+    fn test_demultiplex() {
+        let output = [
+            frame(1, b"hello\n"),
+            frame(2, b"warning\n"),
+            frame(1, b"world\n"),
+        ]
+        .concat();
+        assert_eq!(
+            demultiplex(&output),
+            ("hello\nworld\n".to_string(), "warning\n".to_string())
+        );
+
+        // Output of a container with a TTY is not multiplexed
+        assert_eq!(
+            demultiplex(b"plain output\n"),
+            ("plain output\n".to_string(), String::new())
+        );
+        assert_eq!(demultiplex(b""), (String::new(), String::new()));
+    }
+
+    #[test]
+    fn test_pull_error() {
+        assert_eq!(
+            pull_error(
+                br#"{"status":"Pulling from library/nginx","id":"1.27"}
+{"status":"Status: Downloaded newer image for nginx:1.27"}"#
+            ),
+            None
+        );
+        assert_eq!(
+            pull_error(
+                br#"{"status":"Pulling from library/nope"}
+{"errorDetail":{"message":"manifest unknown"},"error":"manifest unknown"}"#
+            ),
+            Some("manifest unknown".to_string())
+        );
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("docker_stop_container", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("docker_exec", json!({"container_id": "web", "command": []})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "docker_container_logs",
+                "docker_exec",
+                "docker_inspect_container",
+                "docker_list_containers",
+                "docker_list_images",
+                "docker_pull_image",
+                "docker_remove_container",
+                "docker_start_container",
+                "docker_stop_container",
+            ]
+        );
+    }
+}
@@ -0,0 +1,472 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use base64::{
+    alphabet,
+    engine::{
+        general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+        DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig,
+    },
+    Engine as _,
+};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+// URL-safe base64 is often written without padding, so it is optional when decoding.
+const URL_SAFE_LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// Returns the base64 of `input` without line breaks and PEM armor lines like
+// `-----BEGIN CERTIFICATE-----`, so certificates and keys can be decoded as they are.
+fn base64_body(input: &str) -> String {
+    input
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("-----"))
+        .flat_map(|line| line.chars().filter(|c| !c.is_whitespace()))
+        .collect()
+}
+
+// Decodes standard base64, which must be padded, or URL-safe base64 when `url_safe` is
+// set or the input uses its `-` and `_` characters.
+fn parse_base64(input: &str, url_safe: bool) -> Result<Vec<u8>> {
+    let body = base64_body(input);
+    let decoded = if url_safe || body.contains(['-', '_']) {
+        URL_SAFE_LENIENT.decode(&body)
+    } else {
+        STANDARD.decode(&body)
+    };
+    decoded.map_err(|e| anyhow!("invalid base64: {e}"))
+}
+
+fn parse_hex(input: &str) -> Result<Vec<u8>> {
+    let digits: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    let digits = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+        .unwrap_or(&digits);
+    hex::decode(digits).map_err(|e| anyhow!("invalid hex: {e}"))
+}
+
+fn parse_base58(input: &str) -> Result<Vec<u8>> {
+    bs58::decode(input.trim())
+        .into_vec()
+        .map_err(|e| anyhow!("invalid base58: {e}"))
+}
+
+fn text_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    }
+}
+
+// Returns decoded bytes as text, or when they aren't UTF-8, like a DER certificate,
+// as a base64 binary block with a note saying so.
+fn decoded_result(bytes: Vec<u8>) -> CallToolResult {
+    match String::from_utf8(bytes) {
+        Ok(text) => text_result(text),
+        Err(e) => {
+            let bytes = e.into_bytes();
+            CallToolResult {
+                content: vec![
+                    ContentBlock::Text(TextContent {
+                        text: format!(
+                            "The decoded {} bytes are not UTF-8 text, so they are returned as binary data.",
+                            bytes.len()
+                        ),
+
+                        ..Default::default()
+                    }),
+                    ContentBlock::Image(ImageContent {
+                        data: STANDARD.encode(&bytes),
+                        mime_type: "application/octet-stream".to_string(),
+
+                        ..Default::default()
+                    }),
+                ],
+
+                ..Default::default()
+            }
+        }
+    }
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "decode_base58" => decode_base58(&args),
+        "decode_base64" => decode_base64(&args),
+        "decode_hex" => decode_hex(&args),
+        "encode_base58" => encode_base58(&args),
+        "encode_base64" => encode_base64(&args),
+        "encode_hex" => encode_hex(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn decode_base58(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input");
+    let input = get_required_string_arg(args, "input")?;
+    Ok(decoded_result(parse_base58(input)?))
+}
+
+fn decode_base64(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input");
+    let input = get_required_string_arg(args, "input")?;
+    let url_safe = get_bool_arg(args, "url_safe").unwrap_or(false);
+    Ok(decoded_result(parse_base64(input, url_safe)?))
+}
+
+fn decode_hex(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input");
+    let input = get_required_string_arg(args, "input")?;
+    Ok(decoded_result(parse_hex(input)?))
+}
+
+fn encode_base58(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input");
+    let input = get_required_string_arg(args, "input")?;
+    Ok(text_result(bs58::encode(input).into_string()))
+}
+
+fn encode_base64(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input");
+    let input = get_required_string_arg(args, "input")?;
+    let encoded = if get_bool_arg(args, "url_safe").unwrap_or(false) {
+        URL_SAFE_NO_PAD.encode(input)
+    } else {
+        STANDARD.encode(input)
+    };
+    Ok(text_result(encoded))
+}
+
+fn encode_hex(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input");
+    let input = get_required_string_arg(args, "input")?;
+    Ok(text_result(hex::encode(input)))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn input_property(description: &str) -> Value {
+    json!({
+        "type": "string",
+        "description": description,
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Decodes base58, as used by Bitcoin addresses and IPFS hashes. Bytes that are not UTF-8 text are returned as binary data.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "input": input_property("The base58 to decode."),
+                    }),
+                    &["input"],
+                ),
+                name: "decode_base58".to_string(),
+                title: Some("Decode Base58".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Decodes base64, including line-wrapped PEM like certificates and keys. Bytes that are not UTF-8 text are returned as binary data.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "input": input_property("The base64 to decode. Standard base64 must be padded with '='."),
+                        "url_safe": {
+                            "type": "boolean",
+                            "description": "Whether the input uses the URL-safe alphabet, where padding is optional. Detected from '-' and '_' in the input otherwise.",
+                        },
+                    }),
+                    &["input"],
+                ),
+                name: "decode_base64".to_string(),
+                title: Some("Decode Base64".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Decodes hex, ignoring whitespace and a 0x prefix. Bytes that are not UTF-8 text are returned as binary data.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "input": input_property("The hex to decode."),
+                    }),
+                    &["input"],
+                ),
+                name: "decode_hex".to_string(),
+                title: Some("Decode Hex".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Encodes the UTF-8 bytes of a string as base58.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "input": input_property("The string to encode."),
+                    }),
+                    &["input"],
+                ),
+                name: "encode_base58".to_string(),
+                title: Some("Encode Base58".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Encodes the UTF-8 bytes of a string as base64.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "input": input_property("The string to encode."),
+                        "url_safe": {
+                            "type": "boolean",
+                            "description": "Whether to use the URL-safe alphabet without padding. Defaults to false.",
+                        },
+                    }),
+                    &["input"],
+                ),
+                name: "encode_base64".to_string(),
+                title: Some("Encode Base64".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Encodes the UTF-8 bytes of a string as lowercase hex.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "input": input_property("The string to encode."),
+                    }),
+                    &["input"],
+                ),
+                name: "encode_hex".to_string(),
+                title: Some("Encode Hex".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn result_text(result: &CallToolResult) -> &str {
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        &content.text
+    }
+
+    #[test]
+    fn test_encode() {
+        let result = call("encode_base64", json!({"input": "<<???>>"})).unwrap();
+        assert_eq!(result_text(&result), "PDw/Pz8+Pg==");
+
+        let result = call(
+            "encode_base64",
+            json!({"input": "<<???>>", "url_safe": true}),
+        )
+        .unwrap();
+        assert_eq!(result_text(&result), "PDw_Pz8-Pg");
+
+        let result = call("encode_hex", json!({"input": "hi ✓"})).unwrap();
+        assert_eq!(result_text(&result), "686920e29c93");
+
+        let result = call("encode_base58", json!({"input": "hello world"})).unwrap();
+        assert_eq!(result_text(&result), "StV1DL6CwTryKyV");
+    }
+
+    #[test]
+    fn test_parse_base64() {
+        assert_eq!(
+            parse_base64("aMOpbGxvPz4=", false).unwrap(),
+            "héllo?>".as_bytes()
+        );
+        assert_eq!(parse_base64("PDw_Pz8-Pg", false).unwrap(), b"<<???>>");
+        assert_eq!(
+            parse_base64("c3ViamVjdHM_X2Q=", true).unwrap(),
+            b"subjects?_d"
+        );
+        assert!(parse_base64("aMOpbGxvPz4", false).is_err());
+        assert!(parse_base64("aMOp*GxvPz4=", false).is_err());
+
+        let pem = "-----BEGIN CERTIFICATE-----\nMIL/\n-----END CERTIFICATE-----\n";
+        assert_eq!(parse_base64(pem, false).unwrap(), [0x30, 0x82, 0xff]);
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("0x6869 20e2\n9c93").unwrap(), "hi ✓".as_bytes());
+        assert_eq!(parse_hex("ABCD").unwrap(), [0xab, 0xcd]);
+        assert!(parse_hex("abc").is_err());
+        assert!(parse_hex("zz").is_err());
+    }
+
+    #[test]
+    fn test_parse_base58() {
+        assert_eq!(parse_base58(" StV1DL6CwTryKyV\n").unwrap(), b"hello world");
+        // 0, O, I and l are not in the base58 alphabet
+        assert!(parse_base58("StV1DL6CwTryKy0").is_err());
+    }
+
+    #[test]
+    fn test_decoded_result() {
+        let result = call("decode_base64", json!({"input": "aMOpbGxvPz4="})).unwrap();
+        assert_eq!(result.content.len(), 1);
+        assert_eq!(result_text(&result), "héllo?>");
+
+        let result = call("decode_base64", json!({"input": "MIL/"})).unwrap();
+        assert_eq!(result.is_error, None);
+        assert_eq!(result.content.len(), 2);
+        let ContentBlock::Image(content) = &result.content[1] else {
+            panic!("expected binary content");
+        };
+        assert_eq!(content.data, "MIL/");
+        assert_eq!(content.mime_type, "application/octet-stream");
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("decode_hex", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("decode_base64", json!({"input": "a"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "decode_base58",
+                "decode_base64",
+                "decode_hex",
+                "encode_base58",
+                "encode_base64",
+                "encode_hex"
+            ]
+        );
+    }
+}
@@ -0,0 +1,551 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use extism_pdk::{http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const FRANKFURTER_API_URL: &str = "https://api.frankfurter.app";
+
+// Returns the currency code in upper case if it looks like an ISO 4217 code, so that
+// typos fail here rather than as a less helpful API error.
+fn currency_code(code: &str) -> Result<String> {
+    let code = code.trim();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(anyhow!(
+            "'{code}' is not a 3-letter ISO 4217 currency code like USD or EUR"
+        ));
+    }
+    Ok(code.to_ascii_uppercase())
+}
+
+fn get_currency_arg(args: &Map<String, Value>, name: &str) -> Result<String> {
+    currency_code(get_required_string_arg(args, name)?)
+}
+
+// Returns the codes of the `targets` array, or `None` for all currencies if it is
+// absent or empty.
+fn get_targets_arg(args: &Map<String, Value>) -> Result<Option<Vec<String>>> {
+    let Some(targets) = get_array_arg(args, "targets") else {
+        return Ok(None);
+    };
+    let codes = targets
+        .iter()
+        .map(|target| {
+            target
+                .as_str()
+                .ok_or_else(|| anyhow!("'targets' must be an array of currency codes"))
+                .and_then(currency_code)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((!codes.is_empty()).then_some(codes))
+}
+
+fn get_date_arg(args: &Map<String, Value>, name: &str) -> Result<NaiveDate> {
+    let date = get_required_string_arg(args, name)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| anyhow!("'{name}' must be a date like 2024-01-31, got '{date}'"))
+}
+
+// Rounds to 4 decimal places, or to 4 significant digits for values below 0.01 so
+// that rates of weak currencies against strong ones, like IDR to USD, don't round to 0.
+fn round_amount(value: f64) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    let decimals = if value.abs() < 0.01 {
+        3 - value.abs().log10().floor() as i32
+    } else {
+        4
+    };
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+// Rounds every rate of a `rates` object, which maps currency codes to rates.
+fn round_rates(rates: &Value) -> Value {
+    match rates {
+        Value::Object(rates) => Value::Object(
+            rates
+                .iter()
+                .map(|(code, rate)| {
+                    let rate = match rate {
+                        Value::Object(_) => round_rates(rate),
+                        _ => rate
+                            .as_f64()
+                            .map(|rate| json!(round_amount(rate)))
+                            .unwrap_or_else(|| rate.clone()),
+                    };
+                    (code.clone(), rate)
+                })
+                .collect(),
+        ),
+        _ => rates.clone(),
+    }
+}
+
+fn frankfurter_get(path: &str, base: &str, targets: Option<&[String]>) -> Result<Value> {
+    let mut url = format!("{FRANKFURTER_API_URL}{path}?from={base}");
+    if let Some(targets) = targets {
+        url.push_str(&format!("&to={}", targets.join(",")));
+    }
+
+    let req = HttpRequest::new(url)
+        .with_method("GET")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    let res = request::<()>(&req, None)?;
+    let body = res.body();
+    if !(200..300).contains(&res.status_code()) {
+        // Errors look like `{"message": "not found"}`
+        let message = serde_json::from_slice::<Value>(&body)
+            .ok()
+            .and_then(|error| error["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| String::from_utf8_lossy(&body).to_string());
+        return Err(anyhow!(
+            "Frankfurter returned {}: {}",
+            res.status_code(),
+            message
+        ));
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn rates_result(rates: &Value) -> Result<CallToolResult> {
+    json_result(&json!({
+        "base": rates["base"],
+        "date": rates["date"],
+        "rates": round_rates(&rates["rates"]),
+    }))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "forex_convert" => forex_convert(&args),
+        "forex_historical" => forex_historical(&args),
+        "forex_latest" => forex_latest(&args),
+        "forex_time_series" => forex_time_series(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn forex_convert(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "amount", "from", "to");
+    let Some(amount) = args["amount"].as_f64() else {
+        return Ok(error_result("Error: 'amount' must be a number".to_string()));
+    };
+    let from = get_currency_arg(args, "from")?;
+    let to = get_currency_arg(args, "to")?;
+    if from == to {
+        return json_result(&json!({
+            "amount": amount,
+            "from": from,
+            "to": to,
+            "rate": 1.0,
+            "converted_amount": round_amount(amount),
+        }));
+    }
+
+    let rates = frankfurter_get("/latest", &from, Some(std::slice::from_ref(&to)))?;
+    let rate = rates["rates"][&to]
+        .as_f64()
+        .ok_or_else(|| anyhow!("Frankfurter returned no rate for {from} to {to}"))?;
+    json_result(&json!({
+        "amount": amount,
+        "from": from,
+        "to": to,
+        "date": rates["date"],
+        "rate": round_amount(rate),
+        "converted_amount": round_amount(amount * rate),
+    }))
+}
+
+fn forex_historical(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "base", "date");
+    let base = get_currency_arg(args, "base")?;
+    let targets = get_targets_arg(args)?;
+    let date = get_date_arg(args, "date")?;
+
+    let rates = frankfurter_get(&format!("/{date}"), &base, targets.as_deref())?;
+    rates_result(&rates)
+}
+
+fn forex_latest(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "base");
+    let base = get_currency_arg(args, "base")?;
+    let targets = get_targets_arg(args)?;
+
+    let rates = frankfurter_get("/latest", &base, targets.as_deref())?;
+    rates_result(&rates)
+}
+
+fn forex_time_series(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "base", "target", "start_date", "end_date");
+    let base = get_currency_arg(args, "base")?;
+    let target = get_currency_arg(args, "target")?;
+    let start_date = get_date_arg(args, "start_date")?;
+    let end_date = get_date_arg(args, "end_date")?;
+    if start_date > end_date {
+        return Ok(error_result(
+            "Error: 'start_date' must not be after 'end_date'".to_string(),
+        ));
+    }
+
+    let rates = frankfurter_get(
+        &format!("/{start_date}..{end_date}"),
+        &base,
+        Some(std::slice::from_ref(&target)),
+    )?;
+    json_result(&json!({
+        "base": rates["base"],
+        "start_date": rates["start_date"],
+        "end_date": rates["end_date"],
+        "rates": round_rates(&rates["rates"]),
+    }))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn currency_property(description: &str) -> Value {
+    json!({
+        "type": "string",
+        "pattern": "^[A-Za-z]{3}$",
+        "description": description,
+    })
+}
+
+fn targets_property() -> Value {
+    json!({
+        "type": "array",
+        "items": {"type": "string", "pattern": "^[A-Za-z]{3}$"},
+        "description": "ISO 4217 codes of the currencies to return rates for, e.g. ['USD', 'GBP']. Defaults to all available currencies.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Converts an amount from one currency to another at the latest exchange rate.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "amount": {
+                            "type": "number",
+                            "description": "The amount to convert.",
+                        },
+                        "from": currency_property("ISO 4217 code of the currency to convert from, e.g. USD."),
+                        "to": currency_property("ISO 4217 code of the currency to convert to, e.g. EUR."),
+                    }),
+                    &["amount", "from", "to"],
+                ),
+                name: "forex_convert".to_string(),
+                title: Some("Convert Currency".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the exchange rates of a base currency on a past date. Rates are published on working days, so weekends and holidays return the previous working day's rates.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "base": currency_property("ISO 4217 code of the base currency, e.g. USD."),
+                        "targets": targets_property(),
+                        "date": {
+                            "type": "string",
+                            "description": "The date, like 2024-01-31. Rates are available from 1999-01-04.",
+                        },
+                    }),
+                    &["base", "date"],
+                ),
+                name: "forex_historical".to_string(),
+                title: Some("Historical Exchange Rates".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the latest exchange rates of a base currency, published by the European Central Bank on working days.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "base": currency_property("ISO 4217 code of the base currency, e.g. USD."),
+                        "targets": targets_property(),
+                    }),
+                    &["base"],
+                ),
+                name: "forex_latest".to_string(),
+                title: Some("Latest Exchange Rates".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the daily exchange rates of a currency pair between two dates.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "base": currency_property("ISO 4217 code of the base currency, e.g. USD."),
+                        "target": currency_property("ISO 4217 code of the currency to return rates for, e.g. EUR."),
+                        "start_date": {
+                            "type": "string",
+                            "description": "The first date, like 2024-01-01.",
+                        },
+                        "end_date": {
+                            "type": "string",
+                            "description": "The last date, like 2024-01-31.",
+                        },
+                    }),
+                    &["base", "target", "start_date", "end_date"],
+                ),
+                name: "forex_time_series".to_string(),
+                title: Some("Exchange Rate Time Series".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_currency_code() {
+        assert_eq!(currency_code("usd").unwrap(), "USD");
+        assert_eq!(currency_code(" EUR ").unwrap(), "EUR");
+        assert!(currency_code("US").is_err());
+        assert!(currency_code("EURO").is_err());
+        assert!(currency_code("U$D").is_err());
+        assert!(currency_code("").is_err());
+    }
+
+    #[test]
+    fn test_get_targets_arg() {
+        let args = json!({"targets": ["gbp", "JPY"]});
+        assert_eq!(
+            get_targets_arg(args.as_object().unwrap()).unwrap(),
+            Some(vec!["GBP".to_string(), "JPY".to_string()])
+        );
+
+        let args = json!({"targets": []});
+        assert_eq!(get_targets_arg(args.as_object().unwrap()).unwrap(), None);
+        assert_eq!(get_targets_arg(&Map::new()).unwrap(), None);
+
+        let args = json!({"targets": ["GBP", 1]});
+        assert!(get_targets_arg(args.as_object().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_round_amount() {
+        assert_eq!(round_amount(1.234567), 1.2346);
+        assert_eq!(round_amount(157.123449), 157.1234);
+        assert_eq!(round_amount(0.0000634521), 0.00006345);
+        assert_eq!(round_amount(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_round_rates() {
+        let rates = json!({
+            "2024-01-02": {"EUR": 0.913459},
+            "2024-01-03": {"EUR": 0.915751},
+        });
+        assert_eq!(
+            round_rates(&rates),
+            json!({
+                "2024-01-02": {"EUR": 0.9135},
+                "2024-01-03": {"EUR": 0.9158},
+            })
+        );
+        assert_eq!(
+            round_rates(&json!({"GBP": 0.78654, "JPY": 141.8})),
+            json!({"GBP": 0.7865, "JPY": 141.8})
+        );
+    }
+
+    #[test]
+    fn test_forex_convert_same_currency() {
+        let result = call(
+            "forex_convert",
+            json!({"amount": 12.5, "from": "usd", "to": "USD"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, None);
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        let conversion: Value = serde_json::from_str(&content.text).unwrap();
+        assert_eq!(conversion["rate"], 1.0);
+        assert_eq!(conversion["converted_amount"], 12.5);
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call(
+            "forex_convert",
+            json!({"amount": 10, "from": "DOLLAR", "to": "EUR"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "forex_convert",
+            json!({"amount": "10", "from": "USD", "to": "EUR"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("forex_latest", json!({"base": "USD", "targets": ["EU"]})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "forex_historical",
+            json!({"base": "USD", "date": "31/01/2024"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "forex_time_series",
+            json!({
+                "base": "USD",
+                "target": "EUR",
+                "start_date": "2024-02-01",
+                "end_date": "2024-01-01",
+            }),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "forex_convert",
+                "forex_historical",
+                "forex_latest",
+                "forex_time_series"
+            ]
+        );
+    }
+}
@@ -0,0 +1,474 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+use std::net::IpAddr;
+
+// The free ip-api.com endpoints are only available over plain HTTP
+const IP_API_URL: &str = "http://ip-api.com";
+
+const FIELDS: &str =
+    "status,message,query,country,countryCode,regionName,city,lat,lon,isp,org,timezone";
+
+const MAX_BULK_ADDRESSES: usize = 100;
+
+fn is_public(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation())
+        }
+        IpAddr::V6(ip) => {
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local fc00::/7 and link-local fe80::/10 addresses
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+// Parses an IPv4 or IPv6 address, rejecting private and reserved addresses that have
+// no location.
+fn parse_ip(address: &str) -> Result<IpAddr> {
+    let address = address.trim();
+    // Allow IPv6 addresses in URL form, like `[2001:db8::1]`
+    let unbracketed = address
+        .strip_prefix('[')
+        .and_then(|address| address.strip_suffix(']'))
+        .unwrap_or(address);
+    let ip: IpAddr = unbracketed
+        .parse()
+        .map_err(|_| anyhow!("'{address}' is not a valid IPv4 or IPv6 address"))?;
+    if let IpAddr::V6(v6) = ip {
+        // Look up IPv4-mapped addresses like `::ffff:8.8.8.8` as IPv4
+        if let Some(v4) = v6.to_ipv4_mapped() {
+            return parse_ip(&v4.to_string());
+        }
+    }
+    if !is_public(&ip) {
+        return Err(anyhow!("'{address}' is a private or reserved address"));
+    }
+    Ok(ip)
+}
+
+fn ip_version(ip: &IpAddr) -> u8 {
+    match ip {
+        IpAddr::V4(_) => 4,
+        IpAddr::V6(_) => 6,
+    }
+}
+
+fn ip_api_request(req: HttpRequest, body: Option<Value>) -> Result<Value> {
+    let req = req.with_header("User-Agent", "hyper-mcp/0.1.0");
+    let res = request(
+        &req,
+        body.map(|body| serde_json::to_vec(&body)).transpose()?,
+    )?;
+    let body = res.body();
+    if res.status_code() == 429 {
+        return Err(anyhow!(
+            "ip-api.com rate limit reached, which is 45 lookups or 15 bulk lookups per minute"
+        ));
+    }
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(
+            "ip-api.com returned {}: {}",
+            res.status_code(),
+            String::from_utf8_lossy(&body)
+        ));
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+// Converts an ip-api.com response, where failures look like
+// `{"status": "fail", "message": "reserved range", "query": "10.0.0.1"}`.
+fn location(response: &Value) -> Value {
+    if response["status"] != "success" {
+        return json!({
+            "ip": response["query"],
+            "error": response["message"].as_str().unwrap_or("lookup failed"),
+        });
+    }
+    let ip_version = response["query"]
+        .as_str()
+        .and_then(|ip| ip.parse::<IpAddr>().ok())
+        .map(|ip| ip_version(&ip));
+    json!({
+        "ip": response["query"],
+        "ip_version": ip_version,
+        "country": response["country"],
+        "country_code": response["countryCode"],
+        "region": response["regionName"],
+        "city": response["city"],
+        "lat": response["lat"],
+        "lon": response["lon"],
+        "isp": response["isp"],
+        "org": response["org"],
+        "timezone": response["timezone"],
+    })
+}
+
+fn lookup(ip: Option<&IpAddr>) -> Result<Value> {
+    let ip = ip.map(IpAddr::to_string).unwrap_or_default();
+    let req =
+        HttpRequest::new(format!("{IP_API_URL}/json/{ip}?fields={FIELDS}")).with_method("GET");
+    Ok(location(&ip_api_request(req, None)?))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "geoip_bulk" => geoip_bulk(&args),
+        "geoip_lookup" => geoip_lookup(&args),
+        "geoip_my_ip" => geoip_my_ip(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn geoip_bulk(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "ip_addresses");
+    let Some(addresses) = get_array_arg(args, "ip_addresses") else {
+        return Ok(error_result(
+            "Error: 'ip_addresses' must be an array of IP addresses".to_string(),
+        ));
+    };
+    if addresses.is_empty() || addresses.len() > MAX_BULK_ADDRESSES {
+        return Ok(error_result(format!(
+            "Error: 'ip_addresses' must contain between 1 and {MAX_BULK_ADDRESSES} addresses"
+        )));
+    }
+
+    // Invalid and private addresses get an error in place, and aren't sent
+    let parsed: Vec<Result<IpAddr>> = addresses
+        .iter()
+        .map(|address| {
+            address
+                .as_str()
+                .ok_or_else(|| anyhow!("'ip_addresses' must only contain strings"))
+                .and_then(parse_ip)
+        })
+        .collect();
+    let queries: Vec<String> = parsed
+        .iter()
+        .filter_map(|ip| ip.as_ref().ok().map(IpAddr::to_string))
+        .collect();
+
+    let responses = if queries.is_empty() {
+        Vec::new()
+    } else {
+        let req = HttpRequest::new(format!("{IP_API_URL}/batch?fields={FIELDS}"))
+            .with_method("POST")
+            .with_header("Content-Type", "application/json");
+        match ip_api_request(req, Some(json!(queries)))? {
+            Value::Array(responses) => responses,
+            _ => return Err(anyhow!("ip-api.com returned an unexpected batch response")),
+        }
+    };
+    let mut locations = responses.into_iter();
+
+    let results: Vec<Value> = addresses
+        .iter()
+        .zip(parsed)
+        .map(|(address, ip)| match ip {
+            Ok(_) => locations
+                .next()
+                .map(|response| location(&response))
+                .unwrap_or_else(|| json!({"ip": address, "error": "no result returned"})),
+            Err(e) => json!({"ip": address, "error": e.to_string()}),
+        })
+        .collect();
+
+    json_result(&json!(results))
+}
+
+fn geoip_lookup(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "ip_address");
+    let ip = parse_ip(get_required_string_arg(args, "ip_address")?)?;
+    json_result(&lookup(Some(&ip))?)
+}
+
+fn geoip_my_ip(_args: &Map<String, Value>) -> Result<CallToolResult> {
+    json_result(&lookup(None)?)
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some(format!("Returns the locations of up to {MAX_BULK_ADDRESSES} IPv4 or IPv6 addresses in one request, in the order given. Addresses that can't be located have an error instead.")),
+                input_schema: tool_schema(
+                    json!({
+                        "ip_addresses": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "maxItems": MAX_BULK_ADDRESSES,
+                            "description": "The IPv4 or IPv6 addresses to locate.",
+                        },
+                    }),
+                    &["ip_addresses"],
+                ),
+                name: "geoip_bulk".to_string(),
+                title: Some("Bulk IP Geolocation".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the country, region, city, coordinates, ISP, organization and timezone of an IPv4 or IPv6 address.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "ip_address": {
+                            "type": "string",
+                            "description": "The IPv4 or IPv6 address, like 8.8.8.8 or 2001:4860:4860::8888.",
+                        },
+                    }),
+                    &["ip_address"],
+                ),
+                name: "geoip_lookup".to_string(),
+                title: Some("IP Geolocation".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the external IP address of the server running the plugin, with its location.".to_string()),
+                input_schema: tool_schema(json!({}), &[]),
+                name: "geoip_my_ip".to_string(),
+                title: Some("My IP".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_parse_ip() {
+        assert_eq!(parse_ip("8.8.8.8").unwrap().to_string(), "8.8.8.8");
+        assert_eq!(
+            parse_ip(" 2001:4860:4860:0:0:0:0:8888 ")
+                .unwrap()
+                .to_string(),
+            "2001:4860:4860::8888"
+        );
+        assert_eq!(
+            parse_ip("[2606:4700:4700::1111]").unwrap().to_string(),
+            "2606:4700:4700::1111"
+        );
+        assert_eq!(parse_ip("::ffff:1.1.1.1").unwrap().to_string(), "1.1.1.1");
+
+        assert!(parse_ip("example.com").is_err());
+        assert!(parse_ip("256.1.1.1").is_err());
+        assert!(parse_ip("10.0.0.1").is_err());
+        assert!(parse_ip("127.0.0.1").is_err());
+        assert!(parse_ip("::1").is_err());
+        assert!(parse_ip("fd12:3456::1").is_err());
+        assert!(parse_ip("fe80::1").is_err());
+    }
+
+    #[test]
+    fn test_location() {
+        let response = json!({
+            "status": "success",
+            "query": "2001:4860:4860::8888",
+            "country": "United States",
+            "countryCode": "US",
+            "regionName": "California",
+            "city": "Mountain View",
+            "lat": 37.4056,
+            "lon": -122.0775,
+            "isp": "Google LLC",
+            "org": "Google Public DNS",
+            "timezone": "America/Los_Angeles",
+        });
+        assert_eq!(
+            location(&response),
+            json!({
+                "ip": "2001:4860:4860::8888",
+                "ip_version": 6,
+                "country": "United States",
+                "country_code": "US",
+                "region": "California",
+                "city": "Mountain View",
+                "lat": 37.4056,
+                "lon": -122.0775,
+                "isp": "Google LLC",
+                "org": "Google Public DNS",
+                "timezone": "America/Los_Angeles",
+            })
+        );
+
+        let response = json!({"status": "fail", "message": "invalid query", "query": "1.2.3"});
+        assert_eq!(
+            location(&response),
+            json!({"ip": "1.2.3", "error": "invalid query"})
+        );
+    }
+
+    #[test]
+    fn test_geoip_bulk_without_valid_addresses() {
+        let result = call(
+            "geoip_bulk",
+            json!({"ip_addresses": ["192.168.1.1", "not-an-ip", 42]}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, None);
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        let results: Vec<Value> = serde_json::from_str(&content.text).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|result| result["error"].is_string()));
+        assert_eq!(results[2]["ip"], 42);
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("geoip_lookup", json!({"ip_address": "localhost"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("geoip_bulk", json!({"ip_addresses": "8.8.8.8"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("geoip_bulk", json!({"ip_addresses": vec!["8.8.8.8"; 101]})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["geoip_bulk", "geoip_lookup", "geoip_my_ip"]);
+    }
+}
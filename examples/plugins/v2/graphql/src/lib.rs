@@ -0,0 +1,520 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+// The types and fields of a schema, without the directives and deprecation details of
+// the full introspection query that GraphQL tools use.
+const INTROSPECTION_QUERY: &str = r#"
+query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        args { name type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name type { ...TypeRef } }
+      interfaces { name }
+      enumValues(includeDeprecated: true) { name }
+      possibleTypes { name }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } }
+}
+"#;
+
+const BUILTIN_SCALARS: [&str; 5] = ["Boolean", "Float", "ID", "Int", "String"];
+
+// Returns the endpoint from the `url` argument, or `GRAPHQL_URL` when it isn't given.
+fn get_url(args: &Map<String, Value>) -> Result<String> {
+    match get_string_arg(args, "url") {
+        Some(url) => Ok(url.to_string()),
+        None => config::get("GRAPHQL_URL")?
+            .ok_or_else(|| anyhow!("'url' is required when no GRAPHQL_URL configuration is set")),
+    }
+}
+
+// Returns the `Authorization` header for `GRAPHQL_TOKEN`. Tokens without a scheme are
+// sent as bearer tokens, while ones like `token ghp_...` are sent as they are.
+fn authorization(token: &str) -> String {
+    let token = token.trim();
+    if token.contains(' ') {
+        token.to_string()
+    } else {
+        format!("Bearer {token}")
+    }
+}
+
+fn graphql_post(url: &str, body: &Value) -> Result<Value> {
+    let mut req = HttpRequest::new(url)
+        .with_method("POST")
+        .with_header("Accept", "application/json")
+        .with_header("Content-Type", "application/json")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    if let Some(token) = config::get("GRAPHQL_TOKEN")? {
+        req = req.with_header("Authorization", authorization(&token));
+    }
+
+    let res = request(&req, Some(serde_json::to_vec(body)?))?;
+    parse_response(res.status_code(), &res.body())
+}
+
+// Parses the response of a GraphQL request. Servers may report `errors` with a 200 or
+// a 4xx status, so only failed responses without them are treated as errors here.
+fn parse_response(status: u16, body: &[u8]) -> Result<Value> {
+    let response: Value = serde_json::from_slice(body).map_err(|_| {
+        anyhow!(
+            "the server returned {status}: {}",
+            String::from_utf8_lossy(body)
+        )
+    })?;
+    if !(200..300).contains(&status) && !has_errors(&response) {
+        return Err(anyhow!("the server returned {status}: {response}"));
+    }
+    Ok(response)
+}
+
+fn has_errors(response: &Value) -> bool {
+    matches!(response.get("errors"), Some(Value::Array(errors)) if !errors.is_empty())
+}
+
+// Returns the whole response of a request that failed, as it may have partial `data`
+// along with the `errors`.
+fn errors_result(response: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        is_error: Some(true),
+
+        ..json_result(response)?
+    })
+}
+
+// Formats a type reference like `[String!]!`.
+fn type_ref(value: &Value) -> String {
+    match value["kind"].as_str() {
+        Some("NON_NULL") => format!("{}!", type_ref(&value["ofType"])),
+        Some("LIST") => format!("[{}]", type_ref(&value["ofType"])),
+        _ => value["name"].as_str().unwrap_or("?").to_string(),
+    }
+}
+
+// Formats a field like `user(id: ID!): User`.
+fn field_signature(field: &Value) -> String {
+    let args: Vec<String> = field["args"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|arg| {
+            format!(
+                "{}: {}",
+                arg["name"].as_str().unwrap_or("?"),
+                type_ref(&arg["type"])
+            )
+        })
+        .collect();
+    let name = field["name"].as_str().unwrap_or("?");
+    if args.is_empty() {
+        format!("{name}: {}", type_ref(&field["type"]))
+    } else {
+        format!("{name}({}): {}", args.join(", "), type_ref(&field["type"]))
+    }
+}
+
+fn names(values: &Value) -> Option<Vec<&str>> {
+    values.as_array().map(|values| {
+        values
+            .iter()
+            .filter_map(|value| value["name"].as_str())
+            .collect()
+    })
+}
+
+// Summarizes an introspection result as a list of types, leaving out the built-in
+// scalars and the `__` types of introspection itself.
+fn schema_summary(schema: &Value) -> Value {
+    let types: Vec<Value> = schema["types"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|t| {
+            let name = t["name"].as_str().unwrap_or_default();
+            !name.starts_with("__") && !BUILTIN_SCALARS.contains(&name)
+        })
+        .map(|t| {
+            let mut summary = json!({
+                "kind": t["kind"],
+                "name": t["name"],
+            });
+            if let Some(description) = t["description"].as_str().filter(|d| !d.is_empty()) {
+                summary["description"] = json!(description);
+            }
+            if let Some(fields) = t["fields"].as_array() {
+                summary["fields"] = json!(fields.iter().map(field_signature).collect::<Vec<_>>());
+            }
+            if let Some(fields) = t["inputFields"].as_array() {
+                summary["input_fields"] =
+                    json!(fields.iter().map(field_signature).collect::<Vec<_>>());
+            }
+            for (key, name) in [
+                ("interfaces", "interfaces"),
+                ("enumValues", "enum_values"),
+                ("possibleTypes", "possible_types"),
+            ] {
+                if let Some(values) = names(&t[key]).filter(|values| !values.is_empty()) {
+                    summary[name] = json!(values);
+                }
+            }
+            summary
+        })
+        .collect();
+
+    json!({
+        "query_type": schema["queryType"]["name"],
+        "mutation_type": schema["mutationType"]["name"],
+        "subscription_type": schema["subscriptionType"]["name"],
+        "types": types,
+    })
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "graphql_introspect" => graphql_introspect(&args),
+        "graphql_query" => graphql_query(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn graphql_introspect(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let url = get_url(args)?;
+
+    let response = graphql_post(&url, &json!({ "query": INTROSPECTION_QUERY }))?;
+    if has_errors(&response) {
+        return errors_result(&response);
+    }
+    json_result(&schema_summary(&response["data"]["__schema"]))
+}
+
+fn graphql_query(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "query");
+    let query = get_required_string_arg(args, "query")?;
+    let url = get_url(args)?;
+
+    let mut body = json!({ "query": query });
+    match args.get("variables") {
+        Some(Value::Object(variables)) => body["variables"] = Value::Object(variables.clone()),
+        Some(Value::Null) | None => {}
+        Some(_) => return Err(anyhow!("'variables' must be an object")),
+    }
+    if let Some(operation_name) = get_string_arg(args, "operation_name") {
+        body["operationName"] = json!(operation_name);
+    }
+
+    let response = graphql_post(&url, &body)?;
+    if has_errors(&response) {
+        return errors_result(&response);
+    }
+    json_result(&response["data"])
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn url_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "The GraphQL endpoint, like https://api.example.com/graphql. Defaults to the GRAPHQL_URL configuration.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Lists the types of a GraphQL API with their fields, like 'user(id: ID!): User', using introspection.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "url": url_property(),
+                    }),
+                    &[],
+                ),
+                name: "graphql_introspect".to_string(),
+                title: Some("Introspect GraphQL Schema".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    destructive_hint: Some(true),
+                    idempotent_hint: Some(false),
+                    read_only_hint: Some(false),
+
+                    ..annotations().unwrap_or_default()
+                }),
+                description: Some("Runs a GraphQL query or mutation and returns its data. Responses with errors are returned whole, as they may have partial data.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "operation_name": {
+                            "type": "string",
+                            "description": "The operation to run when the query document has several.",
+                        },
+                        "query": {
+                            "type": "string",
+                            "description": "The GraphQL query or mutation.",
+                        },
+                        "url": url_property(),
+                        "variables": {
+                            "type": "object",
+                            "description": "The values of the variables of the query.",
+                        },
+                    }),
+                    &["query"],
+                ),
+                name: "graphql_query".to_string(),
+                title: Some("GraphQL Query".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_authorization() {
+        assert_eq!(authorization("abc123"), "Bearer abc123");
+        assert_eq!(authorization(" token ghp_abc\n"), "token ghp_abc");
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response =
+            parse_response(200, br#"{"data": {"viewer": {"login": "octocat"}}}"#).unwrap();
+        assert!(!has_errors(&response));
+        assert_eq!(response["data"]["viewer"]["login"], "octocat");
+
+        // Errors may come with a 4xx status and partial data
+        let body = br#"{"data": {"viewer": null}, "errors": [{"message": "Bad credentials"}]}"#;
+        let response = parse_response(401, body).unwrap();
+        assert!(has_errors(&response));
+        let result = errors_result(&response).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        assert!(!has_errors(&json!({"data": {}, "errors": []})));
+        assert!(parse_response(502, b"Bad Gateway").is_err());
+        assert!(parse_response(500, br#"{"message": "oops"}"#).is_err());
+    }
+
+    #[test]
+    fn test_schema_summary() {
+        let string = json!({"kind": "SCALAR", "name": "String", "ofType": null});
+        let non_null =
+            |of_type: Value| json!({"kind": "NON_NULL", "name": null, "ofType": of_type});
+        let schema = json!({
+            "queryType": {"name": "Query"},
+            "mutationType": null,
+            "subscriptionType": null,
+            "types": [
+                {
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "description": "",
+                    "fields": [
+                        {
+                            "name": "user",
+                            "args": [{"name": "id", "type": non_null(json!({"kind": "SCALAR", "name": "ID"}))}],
+                            "type": {"kind": "OBJECT", "name": "User"},
+                        },
+                        {
+                            "name": "tags",
+                            "args": [],
+                            "type": non_null(json!({"kind": "LIST", "name": null, "ofType": non_null(string.clone())})),
+                        },
+                    ],
+                    "inputFields": null,
+                    "interfaces": [],
+                    "enumValues": null,
+                    "possibleTypes": null,
+                },
+                {
+                    "kind": "ENUM",
+                    "name": "Role",
+                    "description": "A user's role",
+                    "fields": null,
+                    "inputFields": null,
+                    "interfaces": null,
+                    "enumValues": [{"name": "ADMIN"}, {"name": "MEMBER"}],
+                    "possibleTypes": null,
+                },
+                string,
+                {"kind": "OBJECT", "name": "__Type", "fields": []},
+            ],
+        });
+        assert_eq!(
+            schema_summary(&schema),
+            json!({
+                "query_type": "Query",
+                "mutation_type": null,
+                "subscription_type": null,
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": ["user(id: ID!): User", "tags: [String!]!"],
+                    },
+                    {
+                        "kind": "ENUM",
+                        "name": "Role",
+                        "description": "A user's role",
+                        "enum_values": ["ADMIN", "MEMBER"],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("graphql_query", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["graphql_introspect", "graphql_query"]);
+    }
+}
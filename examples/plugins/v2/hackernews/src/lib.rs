@@ -0,0 +1,653 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use extism_pdk::{http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const HACKER_NEWS_API_URL: &str = "https://hacker-news.firebaseio.com/v0";
+
+const ALGOLIA_API_URL: &str = "https://hn.algolia.com/api/v1";
+
+const DEFAULT_LIMIT: i64 = 10;
+
+const MAX_LIMIT: i64 = 100;
+
+const DEFAULT_MAX_DEPTH: i64 = 3;
+
+const MAX_DEPTH: i64 = 10;
+
+const DEFAULT_MAX_COMMENTS: i64 = 100;
+
+const MAX_COMMENTS: i64 = 500;
+
+const DATE_RANGES: [&str; 5] = ["all", "past_day", "past_week", "past_month", "past_year"];
+
+fn get_bounded_int_arg(
+    args: &Map<String, Value>,
+    name: &str,
+    default: i64,
+    max: i64,
+) -> Result<i64> {
+    let value = get_int_arg(args, name).unwrap_or(default);
+    if !(1..=max).contains(&value) {
+        return Err(anyhow!("'{name}' must be between 1 and {max}"));
+    }
+    Ok(value)
+}
+
+fn http_get(url: String) -> Result<Value> {
+    let req = HttpRequest::new(url)
+        .with_method("GET")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    let res = request::<()>(&req, None)?;
+    let body = res.body();
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(
+            "Hacker News returned {}: {}",
+            res.status_code(),
+            String::from_utf8_lossy(&body)
+        ));
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+// Returns an item, or `None` if it doesn't exist, for which the API returns `null`.
+fn get_item(id: i64) -> Result<Option<Value>> {
+    let item = http_get(format!("{HACKER_NEWS_API_URL}/item/{id}.json"))?;
+    Ok((!item.is_null()).then_some(item))
+}
+
+fn format_time(time: &Value) -> Value {
+    time.as_i64()
+        .and_then(|time| DateTime::from_timestamp(time, 0))
+        .map(|time| json!(time.to_rfc3339()))
+        .unwrap_or(Value::Null)
+}
+
+fn story_summary(item: &Value) -> Value {
+    json!({
+        "id": item["id"],
+        "title": item["title"],
+        "url": item["url"],
+        "score": item["score"],
+        "by": item["by"],
+        "descendants": item["descendants"],
+        "time": format_time(&item["time"]),
+    })
+}
+
+// Fetches the stories of a list like `topstories` one by one, as plugins can't make
+// concurrent requests, skipping those that were deleted.
+fn stories(list: &str, limit: usize) -> Result<Vec<Value>> {
+    let ids = http_get(format!("{HACKER_NEWS_API_URL}/{list}.json"))?;
+    let mut stories = Vec::new();
+    for id in ids
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_i64)
+    {
+        if stories.len() == limit {
+            break;
+        }
+        if let Some(item) = get_item(id)? {
+            if !is_removed(&item) {
+                stories.push(story_summary(&item));
+            }
+        }
+    }
+    Ok(stories)
+}
+
+fn is_removed(item: &Value) -> bool {
+    item["deleted"].as_bool().unwrap_or(false) || item["dead"].as_bool().unwrap_or(false)
+}
+
+fn comment(item: &Value) -> Map<String, Value> {
+    let mut comment = Map::new();
+    comment.insert("id".to_string(), item["id"].clone());
+    comment.insert("by".to_string(), item["by"].clone());
+    comment.insert("time".to_string(), format_time(&item["time"]));
+    comment.insert("text".to_string(), item["text"].clone());
+    comment
+}
+
+// Builds the tree of the comments `ids` and their replies, down to `max_depth` levels.
+// At most `budget` comments are fetched, depth-first, so that large threads don't make
+// hundreds of requests; `reply_count` tells how many replies a comment has in total.
+fn comment_tree(
+    ids: &Value,
+    depth: i64,
+    max_depth: i64,
+    budget: &mut usize,
+    fetch: &mut impl FnMut(i64) -> Result<Option<Value>>,
+) -> Result<Vec<Value>> {
+    let mut comments = Vec::new();
+    for id in ids
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_i64)
+    {
+        if *budget == 0 {
+            break;
+        }
+        let Some(item) = fetch(id)? else {
+            continue;
+        };
+        *budget -= 1;
+        if is_removed(&item) {
+            continue;
+        }
+
+        let mut comment = comment(&item);
+        let reply_count = item["kids"].as_array().map_or(0, Vec::len);
+        comment.insert("reply_count".to_string(), json!(reply_count));
+        if depth < max_depth && reply_count > 0 && *budget > 0 {
+            let replies = comment_tree(&item["kids"], depth + 1, max_depth, budget, fetch)?;
+            comment.insert("replies".to_string(), json!(replies));
+        }
+        comments.push(Value::Object(comment));
+    }
+    Ok(comments)
+}
+
+// Returns the Algolia `numericFilters` for a date range, relative to `now`.
+fn date_filter(date_range: &str, now: i64) -> Result<Option<String>> {
+    let days = match date_range {
+        "all" => return Ok(None),
+        "past_day" => 1,
+        "past_week" => 7,
+        "past_month" => 30,
+        "past_year" => 365,
+        _ => {
+            return Err(anyhow!(
+                "'date_range' must be one of {}",
+                DATE_RANGES.join(", ")
+            ))
+        }
+    };
+    Ok(Some(format!("created_at_i>{}", now - days * 24 * 60 * 60)))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "hn_get_comments" => hn_get_comments(&args),
+        "hn_get_item" => hn_get_item(&args),
+        "hn_new_stories" => hn_new_stories(&args),
+        "hn_search" => hn_search(&args),
+        "hn_top_stories" => hn_top_stories(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn hn_get_comments(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "story_id");
+    let Some(story_id) = get_int_arg(args, "story_id") else {
+        return Ok(error_result(
+            "Error: 'story_id' must be an integer".to_string(),
+        ));
+    };
+    let max_depth = get_bounded_int_arg(args, "max_depth", DEFAULT_MAX_DEPTH, MAX_DEPTH)?;
+    let max_comments =
+        get_bounded_int_arg(args, "max_comments", DEFAULT_MAX_COMMENTS, MAX_COMMENTS)?;
+
+    let story = get_item(story_id)?.ok_or_else(|| anyhow!("item {story_id} not found"))?;
+    let mut budget = max_comments as usize;
+    let comments = comment_tree(&story["kids"], 1, max_depth, &mut budget, &mut get_item)?;
+
+    json_result(&json!({
+        "story": story_summary(&story),
+        "comments": comments,
+        "truncated": budget == 0,
+    }))
+}
+
+fn hn_get_item(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "id");
+    let Some(id) = get_int_arg(args, "id") else {
+        return Ok(error_result("Error: 'id' must be an integer".to_string()));
+    };
+    let include_comments = get_bool_arg(args, "include_comments").unwrap_or(true);
+
+    let mut item = get_item(id)?.ok_or_else(|| anyhow!("item {id} not found"))?;
+    if include_comments {
+        // Only the direct comments; hn_get_comments returns the whole tree
+        let mut budget = DEFAULT_MAX_COMMENTS as usize;
+        let comments = comment_tree(&item["kids"], 1, 1, &mut budget, &mut get_item)?;
+        item["comments"] = json!(comments);
+    }
+    item["time"] = format_time(&item["time"]);
+
+    json_result(&item)
+}
+
+fn hn_new_stories(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let limit = get_bounded_int_arg(args, "limit", DEFAULT_LIMIT, MAX_LIMIT)?;
+    json_result(&json!(stories("newstories", limit as usize)?))
+}
+
+fn hn_search(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "query");
+    let query = get_required_string_arg(args, "query")?;
+    let date_range = get_string_arg(args, "date_range").unwrap_or("all");
+    let date_filter = date_filter(date_range, Utc::now().timestamp())?;
+    let limit = get_bounded_int_arg(args, "limit", DEFAULT_LIMIT, MAX_LIMIT)?;
+
+    let mut url = format!(
+        "{ALGOLIA_API_URL}/search?query={}&tags=story&hitsPerPage={limit}",
+        urlencoding::encode(query)
+    );
+    if let Some(date_filter) = date_filter {
+        url.push_str(&format!(
+            "&numericFilters={}",
+            urlencoding::encode(&date_filter)
+        ));
+    }
+    let response = http_get(url)?;
+
+    let stories: Vec<Value> = response["hits"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|hit| {
+            json!({
+                "id": hit["objectID"].as_str().and_then(|id| id.parse::<i64>().ok()),
+                "title": hit["title"],
+                "url": hit["url"],
+                "score": hit["points"],
+                "by": hit["author"],
+                "descendants": hit["num_comments"],
+                "time": hit["created_at"],
+            })
+        })
+        .collect();
+
+    json_result(&json!({
+        "total_hits": response["nbHits"],
+        "stories": stories,
+    }))
+}
+
+fn hn_top_stories(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let limit = get_bounded_int_arg(args, "limit", DEFAULT_LIMIT, MAX_LIMIT)?;
+    json_result(&json!(stories("topstories", limit as usize)?))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn limit_property(description: &str) -> Value {
+    json!({
+        "type": "integer",
+        "minimum": 1,
+        "maximum": MAX_LIMIT,
+        "description": format!("{description} Defaults to {DEFAULT_LIMIT}."),
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the comments of a story or comment as a nested tree of replies.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "story_id": {
+                            "type": "integer",
+                            "description": "The ID of the story, or of a comment to get its replies.",
+                        },
+                        "max_depth": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": MAX_DEPTH,
+                            "description": format!("Number of levels of replies to return. Defaults to {DEFAULT_MAX_DEPTH}."),
+                        },
+                        "max_comments": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": MAX_COMMENTS,
+                            "description": format!("Maximum number of comments to fetch, depth-first. Defaults to {DEFAULT_MAX_COMMENTS}."),
+                        },
+                    }),
+                    &["story_id"],
+                ),
+                name: "hn_get_comments".to_string(),
+                title: Some("Get Comments".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns a Hacker News item, such as a story, comment, job or poll, with its direct comments.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "id": {
+                            "type": "integer",
+                            "description": "The ID of the item.",
+                        },
+                        "include_comments": {
+                            "type": "boolean",
+                            "description": "Whether to include the direct comments of the item. Defaults to true.",
+                        },
+                    }),
+                    &["id"],
+                ),
+                name: "hn_get_item".to_string(),
+                title: Some("Get Item".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the newest stories on Hacker News.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "limit": limit_property("Number of stories to return."),
+                    }),
+                    &[],
+                ),
+                name: "hn_new_stories".to_string(),
+                title: Some("New Stories".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Searches Hacker News stories by relevance using the Algolia search API.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "query": {
+                            "type": "string",
+                            "description": "The search terms.",
+                        },
+                        "date_range": {
+                            "type": "string",
+                            "enum": DATE_RANGES,
+                            "description": "Only return stories created in this period. Defaults to all.",
+                        },
+                        "limit": limit_property("Number of stories to return."),
+                    }),
+                    &["query"],
+                ),
+                name: "hn_search".to_string(),
+                title: Some("Search Stories".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the top stories on the Hacker News front page.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "limit": limit_property("Number of stories to return."),
+                    }),
+                    &[],
+                ),
+                name: "hn_top_stories".to_string(),
+                title: Some("Top Stories".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn thread() -> HashMap<i64, Value> {
+        HashMap::from([
+            (
+                2,
+                json!({"id": 2, "by": "alice", "time": 1700000000, "text": "First", "kids": [4, 5]}),
+            ),
+            (3, json!({"id": 3, "deleted": true, "time": 1700000100})),
+            (
+                4,
+                json!({"id": 4, "by": "bob", "time": 1700000200, "text": "Reply", "kids": [6]}),
+            ),
+            (
+                5,
+                json!({"id": 5, "by": "carol", "time": 1700000300, "text": "Flagged", "dead": true}),
+            ),
+            (
+                6,
+                json!({"id": 6, "by": "dave", "time": 1700000400, "text": "Nested"}),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_comment_tree() {
+        let items = thread();
+        let mut fetch = |id: i64| Ok(items.get(&id).cloned());
+        let mut budget = 100;
+        let comments = comment_tree(&json!([2, 3]), 1, 2, &mut budget, &mut fetch).unwrap();
+
+        assert_eq!(
+            comments,
+            [json!({
+                "id": 2,
+                "by": "alice",
+                "time": "2023-11-14T22:13:20+00:00",
+                "text": "First",
+                "reply_count": 2,
+                "replies": [{
+                    "id": 4,
+                    "by": "bob",
+                    "time": "2023-11-14T22:16:40+00:00",
+                    "text": "Reply",
+                    "reply_count": 1,
+                }],
+            })]
+        );
+        assert_eq!(budget, 96);
+    }
+
+    #[test]
+    fn test_comment_tree_budget() {
+        let items = thread();
+        let mut fetch = |id: i64| Ok(items.get(&id).cloned());
+        let mut budget = 2;
+        let comments = comment_tree(&json!([2, 3]), 1, 10, &mut budget, &mut fetch).unwrap();
+
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0]["replies"][0]["id"], 4);
+        assert!(comments[0]["replies"][0].get("replies").is_none());
+        assert_eq!(budget, 0);
+    }
+
+    #[test]
+    fn test_date_filter() {
+        assert_eq!(date_filter("all", 1700000000).unwrap(), None);
+        assert_eq!(
+            date_filter("past_week", 1700000000).unwrap(),
+            Some("created_at_i>1699395200".to_string())
+        );
+        assert!(date_filter("yesterday", 1700000000).is_err());
+    }
+
+    #[test]
+    fn test_story_summary() {
+        let item = json!({
+            "id": 8863,
+            "type": "story",
+            "title": "My YC app: Dropbox",
+            "url": "http://www.getdropbox.com/u/2/screencast.html",
+            "score": 111,
+            "by": "dhouston",
+            "descendants": 71,
+            "time": 1175714200,
+            "kids": [8952, 9224],
+        });
+        assert_eq!(
+            story_summary(&item),
+            json!({
+                "id": 8863,
+                "title": "My YC app: Dropbox",
+                "url": "http://www.getdropbox.com/u/2/screencast.html",
+                "score": 111,
+                "by": "dhouston",
+                "descendants": 71,
+                "time": "2007-04-04T19:16:40+00:00",
+            })
+        );
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("hn_top_stories", json!({"limit": 0})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "hn_get_comments",
+            json!({"story_id": 8863, "max_depth": 11}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("hn_get_item", json!({"id": "8863"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("hn_search", json!({"query": "rust", "date_range": "today"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "hn_get_comments",
+                "hn_get_item",
+                "hn_new_stories",
+                "hn_search",
+                "hn_top_stories"
+            ]
+        );
+    }
+}
@@ -0,0 +1,579 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{http::request, HttpRequest};
+use hmac::{digest::KeyInit, Hmac, Mac};
+use md5::Md5;
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use subtle::ConstantTimeEq;
+
+const ALGORITHMS: [&str; 5] = ["md5", "sha1", "sha256", "sha512", "blake3"];
+
+// BLAKE3 has its own keyed mode rather than being used with HMAC.
+const HMAC_ALGORITHMS: [&str; 4] = ["md5", "sha1", "sha256", "sha512"];
+
+const MIN_BCRYPT_COST: u32 = 4;
+
+// Hashing takes twice as long for each step of the cost, so higher costs would run for
+// minutes in Wasm.
+const MAX_BCRYPT_COST: u32 = 16;
+
+// bcrypt ignores everything after the first 72 bytes of a password.
+const MAX_BCRYPT_PASSWORD_BYTES: usize = 72;
+
+fn get_algorithm_arg<'a>(args: &'a Map<String, Value>, supported: &[&str]) -> Result<&'a str> {
+    let algorithm = get_string_arg(args, "algorithm").unwrap_or("sha256");
+    if !supported.contains(&algorithm) {
+        return Err(anyhow!(
+            "unsupported algorithm '{algorithm}', use one of {}",
+            supported.join(", ")
+        ));
+    }
+    Ok(algorithm)
+}
+
+fn digest(algorithm: &str, data: &[u8]) -> Result<Vec<u8>> {
+    Ok(match algorithm {
+        "blake3" => blake3::hash(data).as_bytes().to_vec(),
+        "md5" => Md5::digest(data).to_vec(),
+        "sha1" => Sha1::digest(data).to_vec(),
+        "sha256" => Sha256::digest(data).to_vec(),
+        "sha512" => Sha512::digest(data).to_vec(),
+        _ => return Err(anyhow!("unsupported algorithm '{algorithm}'")),
+    })
+}
+
+fn hmac_digest(algorithm: &str, key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    fn sign<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+        let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(message);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    Ok(match algorithm {
+        "md5" => sign::<Hmac<Md5>>(key, message),
+        "sha1" => sign::<Hmac<Sha1>>(key, message),
+        "sha256" => sign::<Hmac<Sha256>>(key, message),
+        "sha512" => sign::<Hmac<Sha512>>(key, message),
+        _ => return Err(anyhow!("unsupported HMAC algorithm '{algorithm}'")),
+    })
+}
+
+// Compares the digest of `input` with a hex digest in constant time, so the time taken
+// doesn't reveal how many leading bytes match.
+fn hash_matches(algorithm: &str, input: &[u8], expected_hash: &str) -> Result<bool> {
+    let expected = hex::decode(expected_hash.trim())
+        .map_err(|e| anyhow!("'expected_hash' must be a hex digest: {e}"))?;
+    let actual = digest(algorithm, input)?;
+    Ok(actual.ct_eq(&expected).into())
+}
+
+fn download(url: &str) -> Result<Vec<u8>> {
+    let req = HttpRequest::new(url)
+        .with_method("GET")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    let res = request::<()>(&req, None)?;
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(
+            "downloading {url} failed with status {}",
+            res.status_code()
+        ));
+    }
+    Ok(res.body())
+}
+
+fn check_bcrypt_password(password: &str) -> Result<()> {
+    if password.len() > MAX_BCRYPT_PASSWORD_BYTES {
+        return Err(anyhow!(
+            "'password' is {} bytes, but bcrypt only uses the first {MAX_BCRYPT_PASSWORD_BYTES}",
+            password.len()
+        ));
+    }
+    Ok(())
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn text_result(text: String) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "bcrypt_hash" => bcrypt_hash(&args),
+        "bcrypt_verify" => bcrypt_verify(&args),
+        "hash_compare" => hash_compare(&args),
+        "hash_file_url" => hash_file_url(&args),
+        "hash_text" => hash_text(&args),
+        "hmac_sign" => hmac_sign(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn bcrypt_hash(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "password");
+    let password = get_required_string_arg(args, "password")?;
+    check_bcrypt_password(password)?;
+    let cost = match get_int_arg(args, "cost") {
+        Some(cost) if (MIN_BCRYPT_COST as i64..=MAX_BCRYPT_COST as i64).contains(&cost) => {
+            cost as u32
+        }
+        Some(_) => {
+            return Err(anyhow!(
+                "'cost' must be between {MIN_BCRYPT_COST} and {MAX_BCRYPT_COST}"
+            ))
+        }
+        None => bcrypt::DEFAULT_COST,
+    };
+
+    text_result(bcrypt::hash(password, cost)?)
+}
+
+fn bcrypt_verify(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "password", "hash");
+    let password = get_required_string_arg(args, "password")?;
+    let hash = get_required_string_arg(args, "hash")?;
+
+    let valid = bcrypt::verify(password, hash.trim())
+        .map_err(|e| anyhow!("'hash' is not a bcrypt hash: {e}"))?;
+    json_result(&json!({ "valid": valid }))
+}
+
+fn hash_compare(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input", "expected_hash");
+    let input = get_required_string_arg(args, "input")?;
+    let expected_hash = get_required_string_arg(args, "expected_hash")?;
+    let algorithm = get_algorithm_arg(args, &ALGORITHMS)?;
+
+    let matches = hash_matches(algorithm, input.as_bytes(), expected_hash)?;
+    json_result(&json!({ "matches": matches }))
+}
+
+fn hash_file_url(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "url");
+    let url = get_required_string_arg(args, "url")?;
+    let algorithm = get_algorithm_arg(args, &ALGORITHMS)?;
+
+    let data = download(url)?;
+    json_result(&json!({
+        "algorithm": algorithm,
+        "digest": hex::encode(digest(algorithm, &data)?),
+        "size": data.len(),
+    }))
+}
+
+fn hash_text(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "input");
+    let input = get_required_string_arg(args, "input")?;
+    let algorithm = get_algorithm_arg(args, &ALGORITHMS)?;
+
+    text_result(hex::encode(digest(algorithm, input.as_bytes())?))
+}
+
+fn hmac_sign(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "key", "message");
+    let key = get_required_string_arg(args, "key")?;
+    let message = get_required_string_arg(args, "message")?;
+    let algorithm = get_algorithm_arg(args, &HMAC_ALGORITHMS)?;
+
+    text_result(hex::encode(hmac_digest(
+        algorithm,
+        key.as_bytes(),
+        message.as_bytes(),
+    )?))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn algorithm_property(algorithms: &[&str]) -> Value {
+    json!({
+        "type": "string",
+        "description": "The hash algorithm. Defaults to sha256.",
+        "enum": algorithms,
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    idempotent_hint: Some(false),
+
+                    ..annotations().unwrap_or_default()
+                }),
+                description: Some("Hashes a password with bcrypt and a random salt, for storing it.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "cost": {
+                            "type": "integer",
+                            "description": "The bcrypt cost, from 4 to 16. Each step doubles the time hashing takes. Defaults to 12.",
+                        },
+                        "password": {
+                            "type": "string",
+                            "description": "The password, at most 72 bytes.",
+                        },
+                    }),
+                    &["password"],
+                ),
+                name: "bcrypt_hash".to_string(),
+                title: Some("Bcrypt Hash".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Checks a password against a bcrypt hash.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "hash": {
+                            "type": "string",
+                            "description": "The bcrypt hash, like '$2b$12$...'.",
+                        },
+                        "password": {
+                            "type": "string",
+                            "description": "The password to check.",
+                        },
+                    }),
+                    &["password", "hash"],
+                ),
+                name: "bcrypt_verify".to_string(),
+                title: Some("Bcrypt Verify".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Checks whether the hash of a text matches an expected hex digest, comparing in constant time.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "algorithm": algorithm_property(&ALGORITHMS),
+                        "expected_hash": {
+                            "type": "string",
+                            "description": "The expected hex digest.",
+                        },
+                        "input": {
+                            "type": "string",
+                            "description": "The text to hash.",
+                        },
+                    }),
+                    &["input", "expected_hash"],
+                ),
+                name: "hash_compare".to_string(),
+                title: Some("Compare Hash".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    open_world_hint: Some(true),
+
+                    ..annotations().unwrap_or_default()
+                }),
+                description: Some("Downloads a file and returns its hex digest and size, for checking a download against a published checksum.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "algorithm": algorithm_property(&ALGORITHMS),
+                        "url": {
+                            "type": "string",
+                            "description": "The URL of the file. Its host must be in the plugin's allowed_hosts.",
+                        },
+                    }),
+                    &["url"],
+                ),
+                name: "hash_file_url".to_string(),
+                title: Some("Hash File URL".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Hashes the UTF-8 bytes of a text, and returns the hex digest.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "algorithm": algorithm_property(&ALGORITHMS),
+                        "input": {
+                            "type": "string",
+                            "description": "The text to hash.",
+                        },
+                    }),
+                    &["input"],
+                ),
+                name: "hash_text".to_string(),
+                title: Some("Hash Text".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Signs a message with HMAC, like webhook signatures, and returns the hex signature.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "algorithm": algorithm_property(&HMAC_ALGORITHMS),
+                        "key": {
+                            "type": "string",
+                            "description": "The secret key.",
+                        },
+                        "message": {
+                            "type": "string",
+                            "description": "The message to sign.",
+                        },
+                    }),
+                    &["key", "message"],
+                ),
+                name: "hmac_sign".to_string(),
+                title: Some("HMAC Sign".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn result_text(result: &CallToolResult) -> &str {
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        &content.text
+    }
+
+    #[test]
+    fn test_digest() {
+        let hex_digest = |algorithm| hex::encode(digest(algorithm, b"abc").unwrap());
+        assert_eq!(hex_digest("md5"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(
+            hex_digest("sha1"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            hex_digest("sha256"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex_digest("sha512"),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            hex_digest("blake3"),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        assert!(digest("crc32", b"abc").is_err());
+    }
+
+    #[test]
+    fn test_hmac_digest() {
+        let message = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            hex::encode(hmac_digest("sha256", b"key", message).unwrap()),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+        assert_eq!(
+            hex::encode(hmac_digest("md5", b"key", message).unwrap()),
+            "80070713463e7749b90c2dc24911e275"
+        );
+    }
+
+    #[test]
+    fn test_hash_matches() {
+        let sha256 = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        assert!(hash_matches("sha256", b"abc", sha256).unwrap());
+        assert!(!hash_matches("sha256", b"abd", sha256).unwrap());
+        // A digest of another algorithm has a different length
+        assert!(!hash_matches("sha256", b"abc", "900150983cd24fb0d6963f7d28e17f72").unwrap());
+        assert!(hash_matches("sha256", b"abc", "not hex").is_err());
+    }
+
+    #[test]
+    fn test_bcrypt() {
+        let result = call("bcrypt_hash", json!({"password": "hunter2", "cost": 4})).unwrap();
+        let hash = result_text(&result).to_string();
+        assert!(hash.starts_with("$2b$04$"), "{hash}");
+
+        let result = call(
+            "bcrypt_verify",
+            json!({"password": "hunter2", "hash": hash}),
+        )
+        .unwrap();
+        assert_eq!(result_text(&result), r#"{"valid":true}"#);
+        let result = call(
+            "bcrypt_verify",
+            json!({"password": "hunter3", "hash": hash}),
+        )
+        .unwrap();
+        assert_eq!(result_text(&result), r#"{"valid":false}"#);
+
+        let result = call("bcrypt_hash", json!({"password": "a".repeat(73)})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let result = call("bcrypt_hash", json!({"password": "hunter2", "cost": 20})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let result = call(
+            "bcrypt_verify",
+            json!({"password": "hunter2", "hash": "abc"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("hash_text", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("hash_text", json!({"input": "abc", "algorithm": "crc32"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "hmac_sign",
+            json!({"key": "key", "message": "abc", "algorithm": "blake3"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "bcrypt_hash",
+                "bcrypt_verify",
+                "hash_compare",
+                "hash_file_url",
+                "hash_text",
+                "hmac_sign"
+            ]
+        );
+    }
+}
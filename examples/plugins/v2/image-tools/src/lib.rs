@@ -0,0 +1,665 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{imageops::FilterType, ColorType, DynamicImage, ImageFormat};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+use std::io::Cursor;
+
+// The largest width or height the tools produce, so a typo can't exhaust the plugin's
+// memory.
+const MAX_DIMENSION: u32 = 8192;
+
+const FITS: [&str; 3] = ["contain", "cover", "crop"];
+
+const OUTPUT_FORMATS: [&str; 4] = ["png", "jpeg", "webp", "gif"];
+
+fn parse_format(name: &str) -> Option<ImageFormat> {
+    match name.to_ascii_lowercase().as_str() {
+        "bmp" => Some(ImageFormat::Bmp),
+        "gif" => Some(ImageFormat::Gif),
+        "jpeg" | "jpg" => Some(ImageFormat::Jpeg),
+        "png" => Some(ImageFormat::Png),
+        "tiff" | "tif" => Some(ImageFormat::Tiff),
+        "webp" => Some(ImageFormat::WebP),
+        _ => None,
+    }
+}
+
+fn format_name(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Bmp => "bmp",
+        ImageFormat::Gif => "gif",
+        ImageFormat::Jpeg => "jpeg",
+        ImageFormat::Png => "png",
+        ImageFormat::Tiff => "tiff",
+        ImageFormat::WebP => "webp",
+        other => other.extensions_str().first().copied().unwrap_or("unknown"),
+    }
+}
+
+// The channels and bits per channel of the pixels, named like the image crate's
+// ColorType, e.g. rgba8 or l16 for 16-bit grayscale.
+fn color_space(color: ColorType) -> &'static str {
+    match color {
+        ColorType::L8 => "l8",
+        ColorType::La8 => "la8",
+        ColorType::Rgb8 => "rgb8",
+        ColorType::Rgba8 => "rgba8",
+        ColorType::L16 => "l16",
+        ColorType::La16 => "la16",
+        ColorType::Rgb16 => "rgb16",
+        ColorType::Rgba16 => "rgba16",
+        ColorType::Rgb32F => "rgb32f",
+        ColorType::Rgba32F => "rgba32f",
+        _ => "unknown",
+    }
+}
+
+// Decodes the base64 `image` argument, which may also be a data URL. Its format is
+// detected from its contents unless `format` is given.
+fn decode_image(
+    args: &Map<String, Value>,
+    format: Option<ImageFormat>,
+) -> Result<(DynamicImage, ImageFormat)> {
+    let data = get_required_string_arg(args, "image")?;
+    let data = match data.split_once(";base64,") {
+        Some((prefix, data)) if prefix.starts_with("data:") => data,
+        _ => data,
+    };
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    let bytes = STANDARD
+        .decode(data)
+        .map_err(|e| anyhow!("'image' must be base64: {e}"))?;
+
+    let format = match format {
+        Some(format) => format,
+        None => image::guess_format(&bytes)
+            .map_err(|_| anyhow!("'image' is not in a supported image format"))?,
+    };
+    let image = image::load_from_memory_with_format(&bytes, format)
+        .map_err(|e| anyhow!("decoding the {} image failed: {e}", format_name(format)))?;
+    Ok((image, format))
+}
+
+fn encode_image(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>> {
+    // JPEG has no alpha channel, the WebP and GIF encoders only take 8-bit RGB(A), and
+    // PNG has no floating point pixels.
+    let image = match (format, image.color()) {
+        (ImageFormat::Jpeg, _) => DynamicImage::ImageRgb8(image.to_rgb8()),
+        (ImageFormat::WebP | ImageFormat::Gif, _) => DynamicImage::ImageRgba8(image.to_rgba8()),
+        (_, ColorType::Rgb32F | ColorType::Rgba32F) => DynamicImage::ImageRgba16(image.to_rgba16()),
+        _ => image.clone(),
+    };
+    let mut bytes = Cursor::new(Vec::new());
+    image
+        .write_to(&mut bytes, format)
+        .map_err(|e| anyhow!("encoding the image as {} failed: {e}", format_name(format)))?;
+    Ok(bytes.into_inner())
+}
+
+fn get_dimension_arg(args: &Map<String, Value>, name: &str) -> Result<u32> {
+    match get_int_arg(args, name) {
+        Some(value) if (1..=MAX_DIMENSION as i64).contains(&value) => Ok(value as u32),
+        Some(_) => Err(anyhow!("'{name}' must be between 1 and {MAX_DIMENSION}")),
+        None => Err(anyhow!(
+            "'{name}' argument is required and must be an integer"
+        )),
+    }
+}
+
+fn get_offset_arg(args: &Map<String, Value>, name: &str) -> Result<u32> {
+    get_int_arg(args, name)
+        .and_then(|value| u32::try_from(value).ok())
+        .ok_or_else(|| anyhow!("'{name}' argument is required and must be a non-negative integer"))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+// Returns the image as a base64 image block, after a text block describing it.
+fn image_result(image: &DynamicImage, format: ImageFormat) -> Result<CallToolResult> {
+    let bytes = encode_image(image, format)?;
+    Ok(CallToolResult {
+        content: vec![
+            ContentBlock::Text(TextContent {
+                text: format!(
+                    "{}x{} {} image, {} bytes",
+                    image.width(),
+                    image.height(),
+                    format_name(format),
+                    bytes.len()
+                ),
+
+                ..Default::default()
+            }),
+            ContentBlock::Image(ImageContent {
+                data: STANDARD.encode(&bytes),
+                mime_type: format.to_mime_type().to_string(),
+
+                ..Default::default()
+            }),
+        ],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "image_convert" => image_convert(&args),
+        "image_crop" => image_crop(&args),
+        "image_info" => image_info(&args),
+        "image_resize" => image_resize(&args),
+        "image_thumbnail" => image_thumbnail(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn image_convert(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "image", "to_format");
+    let to_format = get_required_string_arg(args, "to_format")?;
+    let to_format = parse_format(to_format)
+        .filter(|format| OUTPUT_FORMATS.contains(&format_name(*format)))
+        .ok_or_else(|| {
+            anyhow!(
+                "unsupported to_format '{to_format}', use one of {}",
+                OUTPUT_FORMATS.join(", ")
+            )
+        })?;
+    let from_format = match get_string_arg(args, "from_format") {
+        Some(name) => {
+            Some(parse_format(name).ok_or_else(|| anyhow!("unsupported from_format '{name}'"))?)
+        }
+        None => None,
+    };
+
+    let (image, _) = decode_image(args, from_format)?;
+    image_result(&image, to_format)
+}
+
+fn image_crop(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "image", "x", "y", "width", "height");
+    let x = get_offset_arg(args, "x")?;
+    let y = get_offset_arg(args, "y")?;
+    let width = get_dimension_arg(args, "width")?;
+    let height = get_dimension_arg(args, "height")?;
+
+    let (image, _) = decode_image(args, None)?;
+    if x as u64 + width as u64 > image.width() as u64
+        || y as u64 + height as u64 > image.height() as u64
+    {
+        return Err(anyhow!(
+            "the {width}x{height} area at ({x}, {y}) is outside the {}x{} image",
+            image.width(),
+            image.height()
+        ));
+    }
+    image_result(&image.crop_imm(x, y, width, height), ImageFormat::Png)
+}
+
+fn image_info(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "image");
+    let (image, format) = decode_image(args, None)?;
+    json_result(&json!({
+        "width": image.width(),
+        "height": image.height(),
+        "format": format_name(format),
+        "color_space": color_space(image.color()),
+    }))
+}
+
+fn image_resize(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "image", "width", "height");
+    let width = get_dimension_arg(args, "width")?;
+    let height = get_dimension_arg(args, "height")?;
+    let fit = get_string_arg(args, "fit").unwrap_or("contain");
+    if !FITS.contains(&fit) {
+        return Err(anyhow!(
+            "unsupported fit '{fit}', use one of {}",
+            FITS.join(", ")
+        ));
+    }
+
+    let (image, _) = decode_image(args, None)?;
+    let resized = match fit {
+        "cover" => image.resize_to_fill(width, height, FilterType::Lanczos3),
+        // Cuts the center out of the image, without scaling it
+        "crop" => {
+            let width = width.min(image.width());
+            let height = height.min(image.height());
+            image.crop_imm(
+                (image.width() - width) / 2,
+                (image.height() - height) / 2,
+                width,
+                height,
+            )
+        }
+        _ => image.resize(width, height, FilterType::Lanczos3),
+    };
+    image_result(&resized, ImageFormat::Png)
+}
+
+fn image_thumbnail(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "image", "max_dimension");
+    let max_dimension = get_dimension_arg(args, "max_dimension")?;
+
+    let (image, _) = decode_image(args, None)?;
+    // Images that already fit are not scaled up
+    let thumbnail = if image.width() <= max_dimension && image.height() <= max_dimension {
+        image
+    } else {
+        image.thumbnail(max_dimension, max_dimension)
+    };
+    image_result(&thumbnail, ImageFormat::Png)
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn image_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "The base64-encoded image, or a data URL. PNG, JPEG, WebP, GIF, BMP and TIFF images are supported.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Converts an image to PNG, JPEG, WebP or GIF, and returns it base64-encoded. Transparency is lost in JPEG, and WebP images are lossless.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "from_format": {
+                            "type": "string",
+                            "description": "The format of the image. Detected from its contents by default.",
+                            "enum": ["png", "jpeg", "webp", "gif", "bmp", "tiff"],
+                        },
+                        "image": image_property(),
+                        "to_format": {
+                            "type": "string",
+                            "description": "The format to convert to.",
+                            "enum": OUTPUT_FORMATS,
+                        },
+                    }),
+                    &["image", "to_format"],
+                ),
+                name: "image_convert".to_string(),
+                title: Some("Convert Image".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Crops an area out of an image, and returns it as a base64-encoded PNG.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "height": {
+                            "type": "integer",
+                            "description": "The height of the area in pixels.",
+                        },
+                        "image": image_property(),
+                        "width": {
+                            "type": "integer",
+                            "description": "The width of the area in pixels.",
+                        },
+                        "x": {
+                            "type": "integer",
+                            "description": "The left edge of the area, in pixels from the left of the image.",
+                        },
+                        "y": {
+                            "type": "integer",
+                            "description": "The top edge of the area, in pixels from the top of the image.",
+                        },
+                    }),
+                    &["image", "x", "y", "width", "height"],
+                ),
+                name: "image_crop".to_string(),
+                title: Some("Crop Image".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the width, height, format and color space of an image.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "image": image_property(),
+                    }),
+                    &["image"],
+                ),
+                name: "image_info".to_string(),
+                title: Some("Image Info".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Resizes an image to a width and height, and returns it as a base64-encoded PNG.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "fit": {
+                            "type": "string",
+                            "description": "How the image fits the size. 'contain' scales it to fit inside, keeping its aspect ratio, 'cover' scales it to fill the size and crops what overflows, and 'crop' cuts the center out without scaling. Defaults to contain.",
+                            "enum": FITS,
+                        },
+                        "height": {
+                            "type": "integer",
+                            "description": "The height in pixels, at most 8192.",
+                        },
+                        "image": image_property(),
+                        "width": {
+                            "type": "integer",
+                            "description": "The width in pixels, at most 8192.",
+                        },
+                    }),
+                    &["image", "width", "height"],
+                ),
+                name: "image_resize".to_string(),
+                title: Some("Resize Image".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Scales an image down so neither side is longer than a maximum, keeping its aspect ratio, and returns it as a base64-encoded PNG.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "image": image_property(),
+                        "max_dimension": {
+                            "type": "integer",
+                            "description": "The longest the width or height may be, in pixels. Smaller images are returned unscaled.",
+                        },
+                    }),
+                    &["image", "max_dimension"],
+                ),
+                name: "image_thumbnail".to_string(),
+                title: Some("Image Thumbnail".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn result_text(result: &CallToolResult) -> &str {
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        &content.text
+    }
+
+    fn result_image(result: &CallToolResult) -> (DynamicImage, String) {
+        let ContentBlock::Image(content) = &result.content[1] else {
+            panic!("expected image content");
+        };
+        let bytes = STANDARD.decode(&content.data).unwrap();
+        (
+            image::load_from_memory(&bytes).unwrap(),
+            content.mime_type.clone(),
+        )
+    }
+
+    // A base64-encoded, semi-transparent 40x20 PNG.
+    fn png() -> String {
+        let image = RgbaImage::from_fn(40, 20, |x, y| Rgba([x as u8 * 6, y as u8 * 12, 0, 128]));
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        STANDARD.encode(bytes.into_inner())
+    }
+
+    #[test]
+    fn test_image_resize() {
+        let size = |fit: &str| {
+            let result = call(
+                "image_resize",
+                json!({"image": png(), "width": 10, "height": 10, "fit": fit}),
+            )
+            .unwrap();
+            let (image, mime_type) = result_image(&result);
+            assert_eq!(mime_type, "image/png");
+            (image.width(), image.height())
+        };
+        assert_eq!(size("contain"), (10, 5));
+        assert_eq!(size("cover"), (10, 10));
+        assert_eq!(size("crop"), (10, 10));
+
+        let result = call(
+            "image_resize",
+            json!({"image": png(), "width": 10, "height": 10, "fit": "stretch"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let result = call(
+            "image_resize",
+            json!({"image": png(), "width": 0, "height": 10}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_image_convert() {
+        for (to_format, mime_type) in [
+            ("jpeg", "image/jpeg"),
+            ("webp", "image/webp"),
+            ("gif", "image/gif"),
+        ] {
+            let result = call(
+                "image_convert",
+                json!({"image": png(), "to_format": to_format}),
+            )
+            .unwrap();
+            let (image, actual) = result_image(&result);
+            assert_eq!(actual, mime_type);
+            assert_eq!((image.width(), image.height()), (40, 20));
+        }
+
+        let result = call("image_convert", json!({"image": png(), "to_format": "bmp"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let result = call(
+            "image_convert",
+            json!({"image": png(), "from_format": "jpeg", "to_format": "png"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_image_crop() {
+        let result = call(
+            "image_crop",
+            json!({"image": png(), "x": 30, "y": 5, "width": 10, "height": 15}),
+        )
+        .unwrap();
+        let (image, _) = result_image(&result);
+        assert_eq!((image.width(), image.height()), (10, 15));
+
+        let result = call(
+            "image_crop",
+            json!({"image": png(), "x": 31, "y": 5, "width": 10, "height": 15}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let result = call(
+            "image_crop",
+            json!({"image": png(), "x": -1, "y": 5, "width": 10, "height": 15}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_image_thumbnail() {
+        let result = call(
+            "image_thumbnail",
+            json!({"image": png(), "max_dimension": 10}),
+        )
+        .unwrap();
+        let (image, _) = result_image(&result);
+        assert_eq!((image.width(), image.height()), (10, 5));
+
+        let result = call(
+            "image_thumbnail",
+            json!({"image": png(), "max_dimension": 100}),
+        )
+        .unwrap();
+        let (image, _) = result_image(&result);
+        assert_eq!((image.width(), image.height()), (40, 20));
+    }
+
+    #[test]
+    fn test_image_info() {
+        let result = call("image_info", json!({"image": png()})).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(result_text(&result)).unwrap(),
+            json!({"width": 40, "height": 20, "format": "png", "color_space": "rgba8"})
+        );
+
+        let data_url = format!("data:image/png;base64,{}", png());
+        let result = call("image_info", json!({"image": data_url})).unwrap();
+        assert_eq!(result.is_error, None);
+
+        let result = call("image_info", json!({"image": "not base64!"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let result = call("image_info", json!({"image": STANDARD.encode("text")})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "image_convert",
+                "image_crop",
+                "image_info",
+                "image_resize",
+                "image_thumbnail"
+            ]
+        );
+    }
+}
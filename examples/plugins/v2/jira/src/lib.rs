@@ -0,0 +1,815 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const DEFAULT_MAX_RESULTS: i64 = 50;
+
+// Jira Cloud returns at most 100 items per page
+const PAGE_SIZE: i64 = 100;
+
+struct JiraConfig {
+    authorization: String,
+    url: String,
+}
+
+fn get_jira_config() -> Result<JiraConfig> {
+    let url = config::get("JIRA_URL")?
+        .ok_or_else(|| anyhow!("JIRA_URL configuration is required but not set"))?;
+    let email = config::get("JIRA_EMAIL")?
+        .ok_or_else(|| anyhow!("JIRA_EMAIL configuration is required but not set"))?;
+    let token = config::get("JIRA_API_TOKEN")?
+        .ok_or_else(|| anyhow!("JIRA_API_TOKEN configuration is required but not set"))?;
+
+    Ok(JiraConfig {
+        authorization: basic_auth(&email, &token),
+        url: url.trim_end_matches('/').to_string(),
+    })
+}
+
+fn basic_auth(email: &str, token: &str) -> String {
+    format!("Basic {}", STANDARD.encode(format!("{email}:{token}")))
+}
+
+fn encode_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{key}={}", urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// Sends a request to the Jira REST API and returns the JSON response, or `null` for
+// responses without a body such as `204 No Content`.
+fn jira_request(
+    config: &JiraConfig,
+    method: &str,
+    path: &str,
+    body: Option<&Value>,
+) -> Result<Value> {
+    let req = HttpRequest::new(format!("{}{}", config.url, path))
+        .with_method(method)
+        .with_header("Authorization", &config.authorization)
+        .with_header("Accept", "application/json")
+        .with_header("Content-Type", "application/json")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+
+    let res = request(&req, body.map(serde_json::to_vec).transpose()?)?;
+    let res_body = res.body();
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(
+            "Jira API returned {} for {} {}: {}",
+            res.status_code(),
+            method,
+            path,
+            String::from_utf8_lossy(&res_body)
+        ));
+    }
+    if res_body.is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(serde_json::from_slice(&res_body)?)
+}
+
+// Returns the `startAt` of the page after `page`, or `None` if it was the last one.
+// Jira reports the end either with `isLast` or with `total`, and may return fewer
+// items than requested even when there are more.
+fn next_start_at(page: &Value, start_at: i64, fetched: i64, page_size: i64) -> Option<i64> {
+    let next = start_at + fetched;
+    let is_last = match (
+        page.get("isLast").and_then(Value::as_bool),
+        page.get("total").and_then(Value::as_i64),
+    ) {
+        (Some(is_last), _) => is_last,
+        (None, Some(total)) => next >= total,
+        (None, None) => fetched < page_size,
+    };
+    (!is_last && fetched > 0).then_some(next)
+}
+
+// Collects up to `max_results` items from an endpoint paginated with `startAt` and
+// `maxResults`, starting at `start_at`. Returns the items and the `startAt` to continue
+// from, if there are more.
+fn get_paginated(
+    config: &JiraConfig,
+    path: &str,
+    query: &[(&str, String)],
+    items_key: &str,
+    start_at: i64,
+    max_results: i64,
+) -> Result<(Vec<Value>, Option<i64>)> {
+    let mut items = Vec::new();
+    let mut start_at = start_at;
+    loop {
+        let page_size = (max_results - items.len() as i64).min(PAGE_SIZE);
+        let mut params = query.to_vec();
+        params.push(("startAt", start_at.to_string()));
+        params.push(("maxResults", page_size.to_string()));
+
+        let page = jira_request(
+            config,
+            "GET",
+            &format!("{path}?{}", encode_query(&params)),
+            None,
+        )?;
+        let page_items = page
+            .get(items_key)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let fetched = page_items.len() as i64;
+        items.extend(page_items);
+
+        match next_start_at(&page, start_at, fetched, page_size) {
+            Some(next) if (items.len() as i64) < max_results => start_at = next,
+            next => return Ok((items, next)),
+        }
+    }
+}
+
+// Converts plain text to an Atlassian Document Format document, one paragraph per
+// non-empty line, as the v3 REST API requires for descriptions and comments.
+fn to_adf(text: &str) -> Value {
+    let paragraphs: Vec<Value> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            json!({
+                "type": "paragraph",
+                "content": [{"type": "text", "text": line}],
+            })
+        })
+        .collect();
+    json!({"type": "doc", "version": 1, "content": paragraphs})
+}
+
+fn get_max_results(args: &Map<String, Value>) -> i64 {
+    get_int_arg(args, "max_results")
+        .filter(|max_results| *max_results > 0)
+        .unwrap_or(DEFAULT_MAX_RESULTS)
+}
+
+fn get_start_at(args: &Map<String, Value>) -> i64 {
+    get_int_arg(args, "start_at").unwrap_or(0).max(0)
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    text_result(serde_json::to_string(value)?)
+}
+
+fn text_result(text: String) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "jira_add_comment" => add_comment(&args),
+        "jira_create_issue" => create_issue(&args),
+        "jira_get_issue" => get_issue(&args),
+        "jira_get_sprint_issues" => get_sprint_issues(&args),
+        "jira_list_projects" => list_projects(&args),
+        "jira_search_jql" => search_jql(&args),
+        "jira_transition_issue" => transition_issue(&args),
+        "jira_update_issue" => update_issue(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn add_comment(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "issue_key", "body");
+    let issue_key = get_required_string_arg(args, "issue_key")?;
+    let body = get_required_string_arg(args, "body")?;
+
+    let config = get_jira_config()?;
+    let comment = jira_request(
+        &config,
+        "POST",
+        &format!(
+            "/rest/api/3/issue/{}/comment",
+            urlencoding::encode(issue_key)
+        ),
+        Some(&json!({"body": to_adf(body)})),
+    )?;
+    json_result(&comment)
+}
+
+fn create_issue(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "project_key", "issue_type", "summary");
+    let mut fields = Map::from_iter([
+        (
+            "project".to_string(),
+            json!({"key": get_required_string_arg(args, "project_key")?}),
+        ),
+        (
+            "issuetype".to_string(),
+            json!({"name": get_required_string_arg(args, "issue_type")?}),
+        ),
+        (
+            "summary".to_string(),
+            json!(get_required_string_arg(args, "summary")?),
+        ),
+    ]);
+    if let Some(description) = get_string_arg(args, "description") {
+        fields.insert("description".to_string(), to_adf(description));
+    }
+    if let Some(labels) = get_array_arg(args, "labels") {
+        fields.insert("labels".to_string(), Value::Array(labels.clone()));
+    }
+    if let Some(assignee) = get_string_arg(args, "assignee") {
+        fields.insert("assignee".to_string(), json!({"accountId": assignee}));
+    }
+
+    let config = get_jira_config()?;
+    let issue = jira_request(
+        &config,
+        "POST",
+        "/rest/api/3/issue",
+        Some(&json!({"fields": fields})),
+    )?;
+    json_result(&issue)
+}
+
+fn get_issue(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "issue_key");
+    let issue_key = get_required_string_arg(args, "issue_key")?;
+    let mut query = vec![];
+    if let Some(fields) = get_string_arg(args, "fields") {
+        query.push(("fields", fields.to_string()));
+    }
+    if let Some(expand) = get_string_arg(args, "expand") {
+        query.push(("expand", expand.to_string()));
+    }
+
+    let mut path = format!("/rest/api/3/issue/{}", urlencoding::encode(issue_key));
+    if !query.is_empty() {
+        path = format!("{path}?{}", encode_query(&query));
+    }
+    let config = get_jira_config()?;
+    json_result(&jira_request(&config, "GET", &path, None)?)
+}
+
+fn get_sprint_issues(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "sprint_id");
+    let sprint_id = match args.get("sprint_id") {
+        Some(Value::Number(id)) => id.to_string(),
+        Some(Value::String(id)) => id.clone(),
+        _ => {
+            return Ok(error_result(
+                "Error: 'sprint_id' must be a number".to_string(),
+            ))
+        }
+    };
+    let mut query = vec![];
+    if let Some(jql) = get_string_arg(args, "jql") {
+        query.push(("jql", jql.to_string()));
+    }
+
+    let config = get_jira_config()?;
+    let (issues, next_start_at) = get_paginated(
+        &config,
+        &format!(
+            "/rest/agile/1.0/sprint/{}/issue",
+            urlencoding::encode(&sprint_id)
+        ),
+        &query,
+        "issues",
+        get_start_at(args),
+        get_max_results(args),
+    )?;
+    json_result(&json!({"issues": issues, "next_start_at": next_start_at}))
+}
+
+fn list_projects(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let mut query = vec![];
+    if let Some(search) = get_string_arg(args, "query") {
+        query.push(("query", search.to_string()));
+    }
+
+    let config = get_jira_config()?;
+    let (projects, next_start_at) = get_paginated(
+        &config,
+        "/rest/api/3/project/search",
+        &query,
+        "values",
+        get_start_at(args),
+        get_max_results(args),
+    )?;
+    json_result(&json!({"projects": projects, "next_start_at": next_start_at}))
+}
+
+fn search_jql(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "jql");
+    let mut query = vec![("jql", get_required_string_arg(args, "jql")?.to_string())];
+    if let Some(fields) = get_string_arg(args, "fields") {
+        query.push(("fields", fields.to_string()));
+    }
+
+    let config = get_jira_config()?;
+    let (issues, next_start_at) = get_paginated(
+        &config,
+        "/rest/api/3/search",
+        &query,
+        "issues",
+        get_start_at(args),
+        get_max_results(args),
+    )?;
+    json_result(&json!({"issues": issues, "next_start_at": next_start_at}))
+}
+
+fn transition_issue(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "issue_key", "transition_id");
+    let issue_key = get_required_string_arg(args, "issue_key")?;
+    let transition_id = match args.get("transition_id") {
+        Some(Value::Number(id)) => id.to_string(),
+        Some(Value::String(id)) => id.clone(),
+        _ => {
+            return Ok(error_result(
+                "Error: 'transition_id' must be a string".to_string(),
+            ))
+        }
+    };
+
+    let config = get_jira_config()?;
+    jira_request(
+        &config,
+        "POST",
+        &format!(
+            "/rest/api/3/issue/{}/transitions",
+            urlencoding::encode(issue_key)
+        ),
+        Some(&json!({"transition": {"id": transition_id}})),
+    )?;
+    text_result(format!(
+        "Issue {issue_key} transitioned with transition {transition_id}"
+    ))
+}
+
+fn update_issue(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "issue_key", "fields");
+    let issue_key = get_required_string_arg(args, "issue_key")?;
+    let Some(mut fields) = args.get("fields").and_then(Value::as_object).cloned() else {
+        return Ok(error_result(
+            "Error: 'fields' must be an object".to_string(),
+        ));
+    };
+    // Accept plain text descriptions like jira_create_issue does
+    if let Some(Value::String(description)) = fields.get("description") {
+        let description = to_adf(description);
+        fields.insert("description".to_string(), description);
+    }
+
+    let config = get_jira_config()?;
+    jira_request(
+        &config,
+        "PUT",
+        &format!("/rest/api/3/issue/{}", urlencoding::encode(issue_key)),
+        Some(&json!({"fields": fields})),
+    )?;
+    text_result(format!("Issue {issue_key} updated"))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn read_only_annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn write_annotations(destructive: bool, idempotent: bool) -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(destructive),
+        idempotent_hint: Some(idempotent),
+        open_world_hint: Some(true),
+        read_only_hint: Some(false),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn pagination_properties(properties: Value) -> Value {
+    let mut properties = properties.as_object().cloned().unwrap_or_default();
+    properties.insert(
+        "max_results".to_string(),
+        json!({
+            "type": "integer",
+            "description": format!("Maximum number of items to return. Defaults to {DEFAULT_MAX_RESULTS}."),
+        }),
+    );
+    properties.insert(
+        "start_at".to_string(),
+        json!({
+            "type": "integer",
+            "description": "Index of the first item to return, e.g. the next_start_at of a previous call. Defaults to 0.",
+        }),
+    );
+    Value::Object(properties)
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: write_annotations(false, false),
+                description: Some("Adds a comment to a Jira issue.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "issue_key": {
+                            "type": "string",
+                            "description": "The issue key, e.g. 'PROJ-123'.",
+                        },
+                        "body": {
+                            "type": "string",
+                            "description": "The comment text. Each line becomes a paragraph.",
+                        },
+                    }),
+                    &["issue_key", "body"],
+                ),
+                name: "jira_add_comment".to_string(),
+                title: Some("Add Comment".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: write_annotations(false, false),
+                description: Some("Creates a Jira issue and returns its id and key.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "project_key": {
+                            "type": "string",
+                            "description": "The key of the project to create the issue in, e.g. 'PROJ'.",
+                        },
+                        "issue_type": {
+                            "type": "string",
+                            "description": "The name of the issue type, e.g. 'Task', 'Bug' or 'Story'.",
+                        },
+                        "summary": {
+                            "type": "string",
+                            "description": "The issue summary.",
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "The issue description. Each line becomes a paragraph.",
+                        },
+                        "labels": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Labels to add to the issue.",
+                        },
+                        "assignee": {
+                            "type": "string",
+                            "description": "The account ID of the user to assign the issue to.",
+                        },
+                    }),
+                    &["project_key", "issue_type", "summary"],
+                ),
+                name: "jira_create_issue".to_string(),
+                title: Some("Create Issue".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Returns the full JSON of a Jira issue.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "issue_key": {
+                            "type": "string",
+                            "description": "The issue key, e.g. 'PROJ-123'.",
+                        },
+                        "fields": {
+                            "type": "string",
+                            "description": "Comma-separated list of fields to return. Defaults to all fields.",
+                        },
+                        "expand": {
+                            "type": "string",
+                            "description": "Comma-separated list of entities to expand, e.g. 'changelog,transitions'.",
+                        },
+                    }),
+                    &["issue_key"],
+                ),
+                name: "jira_get_issue".to_string(),
+                title: Some("Get Issue".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Lists the issues in a sprint. Returns next_start_at when there are more issues.".to_string()),
+                input_schema: tool_schema(
+                    pagination_properties(json!({
+                        "sprint_id": {
+                            "type": "integer",
+                            "description": "The ID of the sprint.",
+                        },
+                        "jql": {
+                            "type": "string",
+                            "description": "JQL to filter the sprint's issues by.",
+                        },
+                    })),
+                    &["sprint_id"],
+                ),
+                name: "jira_get_sprint_issues".to_string(),
+                title: Some("Get Sprint Issues".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Lists the Jira projects visible to the user. Returns next_start_at when there are more projects.".to_string()),
+                input_schema: tool_schema(
+                    pagination_properties(json!({
+                        "query": {
+                            "type": "string",
+                            "description": "Only return projects whose key or name contains this text.",
+                        },
+                    })),
+                    &[],
+                ),
+                name: "jira_list_projects".to_string(),
+                title: Some("List Projects".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Searches issues with JQL. Returns next_start_at when there are more matching issues.".to_string()),
+                input_schema: tool_schema(
+                    pagination_properties(json!({
+                        "jql": {
+                            "type": "string",
+                            "description": "The JQL query, e.g. 'project = PROJ AND status = \"In Progress\"'.",
+                        },
+                        "fields": {
+                            "type": "string",
+                            "description": "Comma-separated list of fields to return for each issue.",
+                        },
+                    })),
+                    &["jql"],
+                ),
+                name: "jira_search_jql".to_string(),
+                title: Some("Search Issues with JQL".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: write_annotations(false, false),
+                description: Some("Moves a Jira issue through a workflow transition. The available transition IDs are returned by jira_get_issue with expand 'transitions'.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "issue_key": {
+                            "type": "string",
+                            "description": "The issue key, e.g. 'PROJ-123'.",
+                        },
+                        "transition_id": {
+                            "type": "string",
+                            "description": "The ID of the transition to perform.",
+                        },
+                    }),
+                    &["issue_key", "transition_id"],
+                ),
+                name: "jira_transition_issue".to_string(),
+                title: Some("Transition Issue".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: write_annotations(true, true),
+                description: Some("Updates fields of a Jira issue.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "issue_key": {
+                            "type": "string",
+                            "description": "The issue key, e.g. 'PROJ-123'.",
+                        },
+                        "fields": {
+                            "type": "object",
+                            "description": "The fields to set, keyed by field ID, e.g. {\"summary\": \"New summary\", \"labels\": [\"backend\"]}. A plain text description is converted to Atlassian Document Format.",
+                        },
+                    }),
+                    &["issue_key", "fields"],
+                ),
+                name: "jira_update_issue".to_string(),
+                title: Some("Update Issue".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_basic_auth() {
+        assert_eq!(
+            basic_auth("me@example.com", "token"),
+            "Basic bWVAZXhhbXBsZS5jb206dG9rZW4="
+        );
+    }
+
+    #[test]
+    fn test_encode_query() {
+        assert_eq!(
+            encode_query(&[
+                ("jql", "project = PROJ".to_string()),
+                ("startAt", "0".to_string()),
+            ]),
+            "jql=project%20%3D%20PROJ&startAt=0"
+        );
+    }
+
+    #[test]
+    fn test_next_start_at() {
+        // Paginated with total
+        assert_eq!(
+            next_start_at(&json!({"total": 120}), 0, 100, 100),
+            Some(100)
+        );
+        assert_eq!(next_start_at(&json!({"total": 120}), 100, 20, 100), None);
+        // Jira may return fewer items than requested
+        assert_eq!(next_start_at(&json!({"total": 120}), 0, 50, 100), Some(50));
+        // Paginated with isLast
+        assert_eq!(
+            next_start_at(&json!({"isLast": false}), 0, 50, 50),
+            Some(50)
+        );
+        assert_eq!(next_start_at(&json!({"isLast": true}), 50, 50, 50), None);
+        // Neither, so a short page is the last one
+        assert_eq!(next_start_at(&json!({}), 0, 10, 50), None);
+        assert_eq!(next_start_at(&json!({"total": 120}), 0, 0, 100), None);
+    }
+
+    #[test]
+    fn test_to_adf() {
+        assert_eq!(
+            to_adf("First line\n\nSecond line"),
+            json!({
+                "type": "doc",
+                "version": 1,
+                "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "First line"}]},
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Second line"}]},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_call_tool_missing_arguments() {
+        let result = call("jira_create_issue", json!({"project_key": "PROJ"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        match &result.content[0] {
+            ContentBlock::Text(text) => assert!(text.text.contains("issue_type, summary")),
+            _ => panic!("Expected text content"),
+        }
+
+        let result = call("jira_get_issue", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call(
+            "jira_update_issue",
+            json!({"issue_key": "PROJ-1", "fields": "summary"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "jira_add_comment",
+                "jira_create_issue",
+                "jira_get_issue",
+                "jira_get_sprint_issues",
+                "jira_list_projects",
+                "jira_search_jql",
+                "jira_transition_issue",
+                "jira_update_issue",
+            ]
+        );
+        for tool in &result.tools {
+            let annotations = tool.annotations.as_ref().unwrap();
+            assert_eq!(annotations.open_world_hint, Some(true));
+        }
+    }
+}
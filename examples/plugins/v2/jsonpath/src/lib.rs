@@ -0,0 +1,515 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+use serde_json_path::{JsonPath, PathElement};
+
+// A step of the normalized path to a node, owned so that nodes can be modified after
+// they are located.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Step {
+    Index(usize),
+    Name(String),
+}
+
+// Parses the `json_string` argument, also accepting JSON that was passed as a value
+// rather than serialized into a string.
+fn get_json_string_arg(args: &Map<String, Value>) -> Result<Value> {
+    match args.get("json_string") {
+        Some(Value::String(json)) => {
+            serde_json::from_str(json).map_err(|e| anyhow!("'json_string' is not valid JSON: {e}"))
+        }
+        Some(value) => Ok(value.clone()),
+        None => Err(anyhow!("'json_string' is required")),
+    }
+}
+
+fn parse_path(path: &str) -> Result<JsonPath> {
+    JsonPath::parse(path).map_err(|e| anyhow!("invalid JSONPath expression '{path}': {e}"))
+}
+
+fn locate(value: &Value, path: &JsonPath) -> Vec<Vec<Step>> {
+    path.query_located(value)
+        .locations()
+        .map(|location| {
+            location
+                .iter()
+                .map(|element| match element {
+                    PathElement::Name(name) => Step::Name(name.to_string()),
+                    PathElement::Index(index) => Step::Index(*index),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn node_mut<'a>(value: &'a mut Value, steps: &[Step]) -> Option<&'a mut Value> {
+    steps.iter().try_fold(value, |node, step| match step {
+        Step::Name(name) => node.get_mut(name),
+        Step::Index(index) => node.get_mut(index),
+    })
+}
+
+// Splits a path whose last segment is a member name, like `$.a.b` or `$.a['b c']`, into
+// the path of the parent and the name.
+fn split_last_member(path: &str) -> Option<(&str, String)> {
+    let path = path.trim();
+    for quote in ['\'', '"'] {
+        if let Some(rest) = path.strip_suffix(&format!("{quote}]")) {
+            let (parent, name) = rest.rsplit_once(&format!("[{quote}"))?;
+            return Some((parent, name.to_string()));
+        }
+    }
+    let (parent, name) = path.rsplit_once('.')?;
+    let is_member = !name.is_empty()
+        && !parent.is_empty()
+        && !parent.ends_with('.')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    is_member.then(|| (parent, name.to_string()))
+}
+
+// Replaces the nodes matching `path` with `new_value`. When nothing matches and the
+// path ends with a member name, the member is added to the objects its parent path
+// matches instead, so `$.config.timeout` can create `timeout`.
+fn set(value: &mut Value, path: &str, new_value: &Value) -> Result<usize> {
+    let locations = locate(value, &parse_path(path)?);
+    if !locations.is_empty() {
+        for location in &locations {
+            if let Some(node) = node_mut(value, location) {
+                *node = new_value.clone();
+            }
+        }
+        return Ok(locations.len());
+    }
+
+    let Some((parent_path, name)) = split_last_member(path) else {
+        return Ok(0);
+    };
+    let mut count = 0;
+    for location in locate(value, &parse_path(parent_path)?) {
+        if let Some(Value::Object(parent)) = node_mut(value, &location) {
+            parent.insert(name.clone(), new_value.clone());
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+// Removes the nodes matching `path`. Later array elements are removed before earlier
+// ones, and descendants before their ancestors, so every location stays valid.
+fn delete(value: &mut Value, path: &str) -> Result<usize> {
+    let mut locations = locate(value, &parse_path(path)?);
+    if locations.iter().any(Vec::is_empty) {
+        return Err(anyhow!("the root of the document can't be deleted"));
+    }
+    locations.sort();
+    locations.reverse();
+
+    let mut count = 0;
+    for location in &locations {
+        let (last, parent) = location.split_last().expect("the root was rejected above");
+        let removed = match (node_mut(value, parent), last) {
+            (Some(Value::Object(parent)), Step::Name(name)) => parent.remove(name).is_some(),
+            (Some(Value::Array(parent)), Step::Index(index)) if *index < parent.len() => {
+                parent.remove(*index);
+                true
+            }
+            _ => false,
+        };
+        if removed {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "jsonpath_delete" => jsonpath_delete(&args),
+        "jsonpath_query" => jsonpath_query(&args),
+        "jsonpath_set" => jsonpath_set(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn jsonpath_delete(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "json_string", "path");
+    let mut value = get_json_string_arg(args)?;
+    let path = get_required_string_arg(args, "path")?;
+
+    let deleted = delete(&mut value, path)?;
+    json_result(&json!({
+        "deleted": deleted,
+        "json": value,
+    }))
+}
+
+fn jsonpath_query(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "json_string", "path");
+    let value = get_json_string_arg(args)?;
+    let path = parse_path(get_required_string_arg(args, "path")?)?;
+
+    let nodes = path.query_located(&value);
+    json_result(&json!({
+        "count": nodes.len(),
+        "values": nodes.nodes().collect::<Vec<_>>(),
+        "paths": nodes.locations().map(|location| location.to_string()).collect::<Vec<_>>(),
+    }))
+}
+
+fn jsonpath_set(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "json_string", "path", "value");
+    let mut value = get_json_string_arg(args)?;
+    let path = get_required_string_arg(args, "path")?;
+
+    let updated = set(&mut value, path, &args["value"])?;
+    if updated == 0 {
+        return Ok(error_result(format!(
+            "Error: '{path}' matched nothing to set"
+        )));
+    }
+    json_result(&json!({
+        "updated": updated,
+        "json": value,
+    }))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+// Adds the `json_string` and `path` properties shared by all tools.
+fn document_properties(properties: Value, path_description: &str) -> Value {
+    let mut properties = properties.as_object().cloned().unwrap_or_default();
+    properties.insert(
+        "json_string".to_string(),
+        json!({
+            "type": "string",
+            "description": "The JSON document.",
+        }),
+    );
+    properties.insert(
+        "path".to_string(),
+        json!({
+            "type": "string",
+            "description": path_description,
+        }),
+    );
+    Value::Object(properties)
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Removes the nodes matching a JSONPath expression from a JSON document, and returns the modified document.".to_string()),
+                input_schema: tool_schema(
+                    document_properties(
+                        json!({}),
+                        "The JSONPath expression (RFC 9535) of the nodes to remove, like '$.items[?@.archived == true]'.",
+                    ),
+                    &["json_string", "path"],
+                ),
+                name: "jsonpath_delete".to_string(),
+                title: Some("Delete JSON Nodes".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Extracts the values matching a JSONPath expression from a JSON document, with their normalized paths.".to_string()),
+                input_schema: tool_schema(
+                    document_properties(
+                        json!({}),
+                        "The JSONPath expression (RFC 9535), like '$.store.book[*].author' or '$..price'.",
+                    ),
+                    &["json_string", "path"],
+                ),
+                name: "jsonpath_query".to_string(),
+                title: Some("Query JSON".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Replaces the nodes matching a JSONPath expression in a JSON document with a value, and returns the modified document. A path ending with a member name that matches nothing, like '$.config.timeout', adds the member.".to_string()),
+                input_schema: tool_schema(
+                    document_properties(
+                        json!({
+                            "value": {
+                                "description": "The JSON value to set.",
+                            },
+                        }),
+                        "The JSONPath expression (RFC 9535) of the nodes to replace, like '$.items[*].status'.",
+                    ),
+                    &["json_string", "path", "value"],
+                ),
+                name: "jsonpath_set".to_string(),
+                title: Some("Set JSON Nodes".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn result_json(result: &CallToolResult) -> Value {
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        serde_json::from_str(&content.text).unwrap()
+    }
+
+    fn store() -> Value {
+        json!({
+            "store": {
+                "book": [
+                    {"author": "Nigel Rees", "price": 8.95},
+                    {"author": "Evelyn Waugh", "price": 12.99},
+                    {"author": "Herman Melville", "price": 8.99},
+                ],
+                "bicycle": {"color": "red", "price": 19.95},
+            },
+        })
+    }
+
+    #[test]
+    fn test_jsonpath_query() {
+        let result = call(
+            "jsonpath_query",
+            json!({
+                "json_string": store().to_string(),
+                "path": "$.store.book[*].author",
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            result_json(&result),
+            json!({
+                "count": 3,
+                "values": ["Nigel Rees", "Evelyn Waugh", "Herman Melville"],
+                "paths": [
+                    "$['store']['book'][0]['author']",
+                    "$['store']['book'][1]['author']",
+                    "$['store']['book'][2]['author']",
+                ],
+            })
+        );
+
+        // The document may also be passed as JSON rather than a string
+        let result = call(
+            "jsonpath_query",
+            json!({"json_string": store(), "path": "$.store.bicycle.color"}),
+        )
+        .unwrap();
+        assert_eq!(result_json(&result)["values"], json!(["red"]));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut value = store();
+        assert_eq!(set(&mut value, "$..price", &json!(0)).unwrap(), 4);
+        assert_eq!(value["store"]["book"][1]["price"], 0);
+        assert_eq!(value["store"]["bicycle"]["price"], 0);
+
+        assert_eq!(
+            set(&mut value, "$.store.book[*].isbn", &json!("unknown")).unwrap(),
+            3
+        );
+        assert_eq!(value["store"]["book"][2]["isbn"], "unknown");
+
+        assert_eq!(
+            set(&mut value, "$.store['opening hours']", &json!("9-5")).unwrap(),
+            1
+        );
+        assert_eq!(value["store"]["opening hours"], "9-5");
+
+        assert_eq!(set(&mut value, "$.missing.member", &json!(1)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut value = store();
+        assert_eq!(
+            delete(&mut value, "$.store.book[?@.price < 10]").unwrap(),
+            2
+        );
+        assert_eq!(
+            value["store"]["book"],
+            json!([{"author": "Evelyn Waugh", "price": 12.99}])
+        );
+
+        let mut value = store();
+        assert_eq!(delete(&mut value, "$..price").unwrap(), 4);
+        assert_eq!(value["store"]["bicycle"], json!({"color": "red"}));
+
+        let mut value = store();
+        assert_eq!(delete(&mut value, "$.store..*").unwrap(), 13);
+        assert_eq!(value, json!({"store": {}}));
+
+        assert!(delete(&mut value, "$").is_err());
+    }
+
+    #[test]
+    fn test_split_last_member() {
+        assert_eq!(split_last_member("$.a.b"), Some(("$.a", "b".to_string())));
+        assert_eq!(
+            split_last_member("$.a['b c']"),
+            Some(("$.a", "b c".to_string()))
+        );
+        assert_eq!(split_last_member("$..b"), None);
+        assert_eq!(split_last_member("$.a[0]"), None);
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call(
+            "jsonpath_query",
+            json!({"json_string": "{}", "path": "$.store.book[*"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "jsonpath_query",
+            json!({"json_string": "{not json", "path": "$"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "jsonpath_set",
+            json!({"json_string": "{}", "path": "$[0]", "value": 1}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["jsonpath_delete", "jsonpath_query", "jsonpath_set"]);
+    }
+}
@@ -0,0 +1,682 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+const DEFAULT_LIMIT: i64 = 50;
+
+const ISSUE_FIELDS: &str = r#"
+    id
+    identifier
+    title
+    description
+    priority
+    priorityLabel
+    url
+    createdAt
+    updatedAt
+    state { id name type }
+    team { id key name }
+    assignee { id name email }
+    labels { nodes { id name } }
+"#;
+
+fn get_api_key() -> Result<String> {
+    config::get("LINEAR_API_KEY")?
+        .ok_or_else(|| anyhow!("LINEAR_API_KEY configuration is required but not set"))
+}
+
+// Returns the messages of the `errors` array of a GraphQL response, if there are any.
+// Linear reports most failures this way, usually with a `200 OK` status.
+fn graphql_errors(response: &Value) -> Option<String> {
+    let errors = response.get("errors")?.as_array()?;
+    if errors.is_empty() {
+        return None;
+    }
+    Some(
+        errors
+            .iter()
+            .map(|error| {
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| error.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+// Sends a GraphQL query to the Linear API and returns its `data`.
+fn graphql(query: &str, variables: Value) -> Result<Value> {
+    let api_key = get_api_key()?;
+    let req = HttpRequest::new(LINEAR_API_URL)
+        .with_method("POST")
+        .with_header("Authorization", api_key)
+        .with_header("Content-Type", "application/json")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    let body = json!({"query": query, "variables": variables});
+
+    let res = request(&req, Some(serde_json::to_vec(&body)?))?;
+    let res_body = res.body();
+    let response: Value = serde_json::from_slice(&res_body).map_err(|_| {
+        anyhow!(
+            "Linear API returned {}: {}",
+            res.status_code(),
+            String::from_utf8_lossy(&res_body)
+        )
+    })?;
+    if let Some(errors) = graphql_errors(&response) {
+        return Err(anyhow!("Linear API returned errors: {errors}"));
+    }
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(
+            "Linear API returned {}: {}",
+            res.status_code(),
+            String::from_utf8_lossy(&res_body)
+        ));
+    }
+    response
+        .get("data")
+        .cloned()
+        .ok_or_else(|| anyhow!("Linear API returned no data"))
+}
+
+// Builds an `IssueFilter` from the optional filter arguments of linear_list_issues.
+fn issue_filter(args: &Map<String, Value>) -> Value {
+    let mut filter = Map::new();
+    if let Some(team_key) = get_string_arg(args, "team_key") {
+        filter.insert("team".to_string(), json!({"key": {"eq": team_key}}));
+    }
+    if let Some(state) = get_string_arg(args, "state") {
+        filter.insert(
+            "state".to_string(),
+            json!({"name": {"eqIgnoreCase": state}}),
+        );
+    }
+    if let Some(priority) = get_int_arg(args, "priority") {
+        filter.insert("priority".to_string(), json!({"eq": priority}));
+    }
+    if let Some(assignee) = get_string_arg(args, "assignee") {
+        // Accept either an email address or a user ID
+        let assignee_filter = if assignee.contains('@') {
+            json!({"email": {"eq": assignee}})
+        } else {
+            json!({"id": {"eq": assignee}})
+        };
+        filter.insert("assignee".to_string(), assignee_filter);
+    }
+    Value::Object(filter)
+}
+
+// Copies the optional arguments in `fields` to a GraphQL input object, renaming each
+// from the snake_case argument to the camelCase field.
+fn issue_input(args: &Map<String, Value>, fields: &[(&str, &str)]) -> Map<String, Value> {
+    fields
+        .iter()
+        .filter_map(|(arg, field)| {
+            args.get(*arg)
+                .filter(|value| !value.is_null())
+                .map(|value| (field.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+const ISSUE_INPUT_FIELDS: [(&str, &str); 7] = [
+    ("title", "title"),
+    ("description", "description"),
+    ("team_id", "teamId"),
+    ("state_id", "stateId"),
+    ("priority", "priority"),
+    ("assignee_id", "assigneeId"),
+    ("label_ids", "labelIds"),
+];
+
+fn get_limit(args: &Map<String, Value>) -> i64 {
+    get_int_arg(args, "limit")
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_LIMIT)
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "linear_add_comment" => add_comment(&args),
+        "linear_create_issue" => create_issue(&args),
+        "linear_get_issue" => get_issue(&args),
+        "linear_list_issues" => list_issues(&args),
+        "linear_list_states" => list_states(&args),
+        "linear_list_teams" => list_teams(&args),
+        "linear_update_issue" => update_issue(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn add_comment(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "issue_id", "body");
+    let data = graphql(
+        r#"
+        mutation CreateComment($input: CommentCreateInput!) {
+            commentCreate(input: $input) {
+                success
+                comment { id body url createdAt }
+            }
+        }
+        "#,
+        json!({
+            "input": {
+                "issueId": get_required_string_arg(args, "issue_id")?,
+                "body": get_required_string_arg(args, "body")?,
+            }
+        }),
+    )?;
+    json_result(&data["commentCreate"])
+}
+
+fn create_issue(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "title", "team_id");
+    let data = graphql(
+        &format!(
+            r#"
+            mutation CreateIssue($input: IssueCreateInput!) {{
+                issueCreate(input: $input) {{
+                    success
+                    issue {{ {ISSUE_FIELDS} }}
+                }}
+            }}
+            "#
+        ),
+        json!({"input": issue_input(args, &ISSUE_INPUT_FIELDS)}),
+    )?;
+    json_result(&data["issueCreate"])
+}
+
+fn get_issue(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "issue_id");
+    let data = graphql(
+        &format!(
+            r#"
+            query GetIssue($id: String!) {{
+                issue(id: $id) {{
+                    {ISSUE_FIELDS}
+                    comments {{ nodes {{ id body createdAt user {{ id name }} }} }}
+                }}
+            }}
+            "#
+        ),
+        json!({"id": get_required_string_arg(args, "issue_id")?}),
+    )?;
+    json_result(&data["issue"])
+}
+
+fn list_issues(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let data = graphql(
+        &format!(
+            r#"
+            query ListIssues($filter: IssueFilter, $first: Int, $after: String) {{
+                issues(filter: $filter, first: $first, after: $after) {{
+                    nodes {{ {ISSUE_FIELDS} }}
+                    pageInfo {{ hasNextPage endCursor }}
+                }}
+            }}
+            "#
+        ),
+        json!({
+            "filter": issue_filter(args),
+            "first": get_limit(args),
+            "after": get_string_arg(args, "cursor"),
+        }),
+    )?;
+    json_result(&data["issues"])
+}
+
+fn list_states(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let filter = match get_string_arg(args, "team_key") {
+        Some(team_key) => json!({"team": {"key": {"eq": team_key}}}),
+        None => json!({}),
+    };
+    let data = graphql(
+        r#"
+        query ListStates($filter: WorkflowStateFilter) {
+            workflowStates(filter: $filter, first: 250) {
+                nodes { id name type position team { id key } }
+            }
+        }
+        "#,
+        json!({"filter": filter}),
+    )?;
+    json_result(&data["workflowStates"]["nodes"])
+}
+
+fn list_teams(_args: &Map<String, Value>) -> Result<CallToolResult> {
+    let data = graphql(
+        r#"
+        query ListTeams {
+            teams(first: 250) {
+                nodes { id key name description }
+            }
+        }
+        "#,
+        json!({}),
+    )?;
+    json_result(&data["teams"]["nodes"])
+}
+
+fn update_issue(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "issue_id");
+    let input = issue_input(args, &ISSUE_INPUT_FIELDS);
+    if input.is_empty() {
+        return Ok(error_result(
+            "Error: at least one field to update is required".to_string(),
+        ));
+    }
+    let data = graphql(
+        &format!(
+            r#"
+            mutation UpdateIssue($id: String!, $input: IssueUpdateInput!) {{
+                issueUpdate(id: $id, input: $input) {{
+                    success
+                    issue {{ {ISSUE_FIELDS} }}
+                }}
+            }}
+            "#
+        ),
+        json!({
+            "id": get_required_string_arg(args, "issue_id")?,
+            "input": input,
+        }),
+    )?;
+    json_result(&data["issueUpdate"])
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations(read_only: bool, idempotent: bool) -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(idempotent),
+        open_world_hint: Some(true),
+        read_only_hint: Some(read_only),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn issue_input_properties() -> Value {
+    json!({
+        "title": {
+            "type": "string",
+            "description": "The issue title.",
+        },
+        "description": {
+            "type": "string",
+            "description": "The issue description, in Markdown.",
+        },
+        "team_id": {
+            "type": "string",
+            "description": "The ID of the team the issue belongs to. See linear_list_teams.",
+        },
+        "state_id": {
+            "type": "string",
+            "description": "The ID of the workflow state. See linear_list_states.",
+        },
+        "priority": {
+            "type": "integer",
+            "description": "The priority: 0 = none, 1 = urgent, 2 = high, 3 = medium, 4 = low.",
+        },
+        "assignee_id": {
+            "type": "string",
+            "description": "The ID of the user to assign the issue to.",
+        },
+        "label_ids": {
+            "type": "array",
+            "items": {"type": "string"},
+            "description": "The IDs of the labels of the issue.",
+        },
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    let mut update_properties = issue_input_properties();
+    update_properties["issue_id"] = json!({
+        "type": "string",
+        "description": "The ID or identifier of the issue, e.g. 'ENG-123'.",
+    });
+
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(false, false),
+                description: Some("Adds a comment to a Linear issue.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "issue_id": {
+                            "type": "string",
+                            "description": "The ID or identifier of the issue, e.g. 'ENG-123'.",
+                        },
+                        "body": {
+                            "type": "string",
+                            "description": "The comment, in Markdown.",
+                        },
+                    }),
+                    &["issue_id", "body"],
+                ),
+                name: "linear_add_comment".to_string(),
+                title: Some("Add Comment".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, false),
+                description: Some("Creates a Linear issue.".to_string()),
+                input_schema: tool_schema(issue_input_properties(), &["title", "team_id"]),
+                name: "linear_create_issue".to_string(),
+                title: Some("Create Issue".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, true),
+                description: Some("Returns a Linear issue with its comments.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "issue_id": {
+                            "type": "string",
+                            "description": "The ID or identifier of the issue, e.g. 'ENG-123'.",
+                        },
+                    }),
+                    &["issue_id"],
+                ),
+                name: "linear_get_issue".to_string(),
+                title: Some("Get Issue".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, true),
+                description: Some("Lists Linear issues, optionally filtered by team, state, priority and assignee. Returns pageInfo.endCursor to pass as cursor when there are more issues.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "team_key": {
+                            "type": "string",
+                            "description": "Only list issues of the team with this key, e.g. 'ENG'.",
+                        },
+                        "state": {
+                            "type": "string",
+                            "description": "Only list issues in the workflow state with this name, e.g. 'In Progress'.",
+                        },
+                        "priority": {
+                            "type": "integer",
+                            "description": "Only list issues with this priority: 0 = none, 1 = urgent, 2 = high, 3 = medium, 4 = low.",
+                        },
+                        "assignee": {
+                            "type": "string",
+                            "description": "Only list issues assigned to the user with this email address or ID.",
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": format!("Maximum number of issues to return. Defaults to {DEFAULT_LIMIT}."),
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "The endCursor of a previous call, to get the next page.",
+                        },
+                    }),
+                    &[],
+                ),
+                name: "linear_list_issues".to_string(),
+                title: Some("List Issues".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, true),
+                description: Some("Lists the workflow states, e.g. 'Todo' or 'Done', and their IDs.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "team_key": {
+                            "type": "string",
+                            "description": "Only list the states of the team with this key, e.g. 'ENG'.",
+                        },
+                    }),
+                    &[],
+                ),
+                name: "linear_list_states".to_string(),
+                title: Some("List Workflow States".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, true),
+                description: Some("Lists the Linear teams and their IDs.".to_string()),
+                input_schema: tool_schema(json!({}), &[]),
+                name: "linear_list_teams".to_string(),
+                title: Some("List Teams".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, true),
+                description: Some("Updates a Linear issue. Only the given fields are changed.".to_string()),
+                input_schema: tool_schema(update_properties, &["issue_id"]),
+                name: "linear_update_issue".to_string(),
+                title: Some("Update Issue".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn args(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_graphql_errors() {
+        assert_eq!(graphql_errors(&json!({"data": {"teams": {}}})), None);
+        assert_eq!(graphql_errors(&json!({"errors": []})), None);
+        assert_eq!(
+            graphql_errors(&json!({
+                "data": null,
+                "errors": [
+                    {"message": "Entity not found"},
+                    {"message": "Argument Validation Error"},
+                ],
+            })),
+            Some("Entity not found; Argument Validation Error".to_string())
+        );
+    }
+
+    #[test]
+    fn test_issue_filter() {
+        assert_eq!(issue_filter(&Map::new()), json!({}));
+        assert_eq!(
+            issue_filter(&args(json!({
+                "team_key": "ENG",
+                "state": "In Progress",
+                "priority": 1,
+                "assignee": "me@example.com",
+            }))),
+            json!({
+                "team": {"key": {"eq": "ENG"}},
+                "state": {"name": {"eqIgnoreCase": "In Progress"}},
+                "priority": {"eq": 1},
+                "assignee": {"email": {"eq": "me@example.com"}},
+            })
+        );
+        assert_eq!(
+            issue_filter(&args(json!({"assignee": "user-id"}))),
+            json!({"assignee": {"id": {"eq": "user-id"}}})
+        );
+    }
+
+    #[test]
+    fn test_issue_input() {
+        assert_eq!(
+            Value::Object(issue_input(
+                &args(json!({
+                    "issue_id": "ENG-1",
+                    "title": "Fix login",
+                    "team_id": "team",
+                    "label_ids": ["bug"],
+                    "priority": null,
+                })),
+                &ISSUE_INPUT_FIELDS,
+            )),
+            json!({"title": "Fix login", "teamId": "team", "labelIds": ["bug"]})
+        );
+    }
+
+    #[test]
+    fn test_call_tool_missing_arguments() {
+        let result = call("linear_create_issue", json!({"title": "Fix login"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        match &result.content[0] {
+            ContentBlock::Text(text) => assert!(text.text.contains("team_id")),
+            _ => panic!("Expected text content"),
+        }
+
+        let result = call("linear_update_issue", json!({"issue_id": "ENG-1"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "linear_add_comment",
+                "linear_create_issue",
+                "linear_get_issue",
+                "linear_list_issues",
+                "linear_list_states",
+                "linear_list_teams",
+                "linear_update_issue",
+            ]
+        );
+        let update = &result.tools[6];
+        assert!(update
+            .input_schema
+            .properties
+            .as_ref()
+            .unwrap()
+            .contains_key("issue_id"));
+    }
+}
@@ -0,0 +1,555 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use pdk::types::*;
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+// Returns the parser options for the extensions named in the `extensions` argument.
+fn parse_extensions(args: &Map<String, Value>) -> Result<Options> {
+    let mut options = Options::empty();
+    for extension in get_array_arg(args, "extensions")
+        .cloned()
+        .unwrap_or_default()
+    {
+        options.insert(match extension.as_str() {
+            Some("tables") => Options::ENABLE_TABLES,
+            Some("footnotes") => Options::ENABLE_FOOTNOTES,
+            Some("strikethrough") => Options::ENABLE_STRIKETHROUGH,
+            Some("tasklists") => Options::ENABLE_TASKLISTS,
+            _ => {
+                return Err(anyhow!(
+                    "unknown extension {extension}, expected one of tables, footnotes, strikethrough or tasklists"
+                ))
+            }
+        });
+    }
+    Ok(options)
+}
+
+// The extensions used by the tools that analyze rather than render Markdown, so that
+// e.g. a link in a table or a struck through header is still found.
+fn all_extensions() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+}
+
+fn to_html(markdown: &str, options: Options) -> String {
+    let mut output = String::new();
+    html::push_html(&mut output, Parser::new_ext(markdown, options));
+    output
+}
+
+fn to_text(markdown: &str) -> String {
+    let mut text = String::new();
+    for event in Parser::new_ext(markdown, all_extensions()) {
+        match event {
+            Event::Text(content) | Event::Code(content) => text.push_str(&content),
+            Event::SoftBreak => text.push(' '),
+            Event::HardBreak => text.push('\n'),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::TableRow
+                | TagEnd::TableHead,
+            ) => {
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            Event::End(TagEnd::TableCell) => text.push('\t'),
+            _ => {}
+        }
+    }
+    // Drop the tab after the last cell of each row
+    text.replace("\t\n", "\n").trim_end().to_string()
+}
+
+fn extract_links(markdown: &str) -> Vec<Value> {
+    let mut links = Vec::new();
+    // The URL and title of the link being read, and its text so far
+    let mut current: Option<(String, String, String)> = None;
+    for event in Parser::new_ext(markdown, all_extensions()) {
+        match event {
+            Event::Start(Tag::Link {
+                dest_url, title, ..
+            }) => current = Some((dest_url.to_string(), title.to_string(), String::new())),
+            Event::Text(content) | Event::Code(content) => {
+                if let Some((_, _, text)) = current.as_mut() {
+                    text.push_str(&content);
+                }
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((url, title, text)) = current.take() {
+                    let mut link = json!({"text": text, "url": url});
+                    if !title.is_empty() {
+                        link["title"] = json!(title);
+                    }
+                    links.push(link);
+                }
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+// Returns the GitHub-style anchor of a header: lowercase, without punctuation, and
+// with spaces replaced by hyphens.
+fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+fn extract_headers(markdown: &str) -> Vec<Value> {
+    let mut headers = Vec::new();
+    let mut anchors: HashMap<String, usize> = HashMap::new();
+    // The level and text of the header being read
+    let mut current: Option<(u8, String)> = None;
+    for event in Parser::new_ext(markdown, all_extensions()) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some((level as u8, String::new()))
+            }
+            Event::Text(content) | Event::Code(content) => {
+                if let Some((_, text)) = current.as_mut() {
+                    text.push_str(&content);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, text)) = current.take() {
+                    // Like GitHub, suffix repeated anchors with -1, -2, ...
+                    let slug = slugify(&text);
+                    let count = anchors.entry(slug.clone()).or_insert(0);
+                    let anchor = match *count {
+                        0 => slug,
+                        n => format!("{slug}-{n}"),
+                    };
+                    *count += 1;
+                    headers.push(json!({"level": level, "text": text.trim(), "anchor": anchor}));
+                }
+            }
+            _ => {}
+        }
+    }
+    headers
+}
+
+// Returns the rows of each table as objects keyed by the header cells.
+fn extract_tables(markdown: &str) -> Vec<Vec<Value>> {
+    let mut tables = Vec::new();
+    let mut header: Vec<String> = Vec::new();
+    let mut rows: Vec<Value> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut cell: Option<String> = None;
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES) {
+        match event {
+            Event::Start(Tag::TableCell) => cell = Some(String::new()),
+            Event::Text(content) | Event::Code(content) => {
+                if let Some(cell) = cell.as_mut() {
+                    cell.push_str(&content);
+                }
+            }
+            Event::End(TagEnd::TableCell) => row.extend(cell.take()),
+            Event::End(TagEnd::TableHead) => header = std::mem::take(&mut row),
+            Event::End(TagEnd::TableRow) => {
+                let values = std::mem::take(&mut row);
+                let object: Map<String, Value> = header
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| {
+                        let value = values.get(i).cloned().unwrap_or_default();
+                        (name.clone(), Value::String(value.trim().to_string()))
+                    })
+                    .collect();
+                rows.push(Value::Object(object));
+            }
+            Event::End(TagEnd::Table) => tables.push(std::mem::take(&mut rows)),
+            _ => {}
+        }
+    }
+    tables
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    text_result(serde_json::to_string(value)?)
+}
+
+fn text_result(text: String) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "md_extract_headers" => md_extract_headers(&args),
+        "md_extract_links" => md_extract_links(&args),
+        "md_table_to_json" => md_table_to_json(&args),
+        "md_to_html" => md_to_html(&args),
+        "md_to_text" => md_to_text(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn md_extract_headers(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "markdown");
+    json_result(&json!(extract_headers(get_required_string_arg(
+        args, "markdown"
+    )?)))
+}
+
+fn md_extract_links(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "markdown");
+    json_result(&json!(extract_links(get_required_string_arg(
+        args, "markdown"
+    )?)))
+}
+
+fn md_table_to_json(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "markdown");
+    let mut tables = extract_tables(get_required_string_arg(args, "markdown")?);
+    let index = get_int_arg(args, "index").unwrap_or(0);
+    if tables.is_empty() {
+        return Ok(error_result("Error: no table found".to_string()));
+    }
+    if index < 0 || index as usize >= tables.len() {
+        return Ok(error_result(format!(
+            "Error: 'index' must be between 0 and {}",
+            tables.len() - 1
+        )));
+    }
+    json_result(&json!(tables.swap_remove(index as usize)))
+}
+
+fn md_to_html(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "markdown");
+    let options = parse_extensions(args)?;
+    text_result(to_html(get_required_string_arg(args, "markdown")?, options))
+}
+
+fn md_to_text(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "markdown");
+    text_result(to_text(get_required_string_arg(args, "markdown")?))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn markdown_schema(properties: Value) -> ToolSchema {
+    let mut properties = properties.as_object().cloned().unwrap_or_default();
+    properties.insert(
+        "markdown".to_string(),
+        json!({
+            "type": "string",
+            "description": "The Markdown text.",
+        }),
+    );
+    ToolSchema {
+        properties: Some(properties),
+        required: Some(vec!["markdown".to_string()]),
+
+        ..Default::default()
+    }
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the headers of a Markdown document with their level and the anchor they link to, e.g. for a table of contents.".to_string()),
+                input_schema: markdown_schema(json!({})),
+                name: "md_extract_headers".to_string(),
+                title: Some("Extract Headers".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the text and URL of each link in a Markdown document.".to_string()),
+                input_schema: markdown_schema(json!({})),
+                name: "md_extract_links".to_string(),
+                title: Some("Extract Links".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Converts a Markdown table to a JSON array with an object per row, keyed by the header cells.".to_string()),
+                input_schema: markdown_schema(json!({
+                    "index": {
+                        "type": "integer",
+                        "description": "Which table to convert if there are several, starting at 0. Defaults to 0.",
+                    },
+                })),
+                name: "md_table_to_json".to_string(),
+                title: Some("Table to JSON".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Renders Markdown to HTML, following CommonMark.".to_string()),
+                input_schema: markdown_schema(json!({
+                    "extensions": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "enum": ["tables", "footnotes", "strikethrough", "tasklists"],
+                        },
+                        "description": "Extensions to CommonMark to enable.",
+                    },
+                })),
+                name: "md_to_html".to_string(),
+                title: Some("Markdown to HTML".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Converts Markdown to plain text, stripping all formatting.".to_string()),
+                input_schema: markdown_schema(json!({})),
+                name: "md_to_text".to_string(),
+                title: Some("Markdown to Text".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn text(result: &CallToolResult) -> &str {
+        match &result.content[0] {
+            ContentBlock::Text(text) => &text.text,
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[test]
+    fn test_md_to_html() {
+        let result = call(
+            "md_to_html",
+            json!({"markdown": "# Title\n\n~~old~~ *new*"}),
+        )
+        .unwrap();
+        assert_eq!(
+            text(&result),
+            "<h1>Title</h1>\n<p>~~old~~ <em>new</em></p>\n"
+        );
+
+        let result = call(
+            "md_to_html",
+            json!({"markdown": "~~old~~ *new*", "extensions": ["strikethrough"]}),
+        )
+        .unwrap();
+        assert_eq!(text(&result), "<p><del>old</del> <em>new</em></p>\n");
+
+        let result = call(
+            "md_to_html",
+            json!({"markdown": "text", "extensions": ["smartypants"]}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            to_text("# Title\n\nSome **bold** and `code`,\nwrapped.\n\n- one\n- [two](https://example.com)\n"),
+            "Title\nSome bold and code, wrapped.\none\ntwo"
+        );
+        assert_eq!(to_text("| a | b |\n|---|---|\n| 1 | 2 |\n"), "a\tb\n1\t2");
+    }
+
+    #[test]
+    fn test_extract_links() {
+        assert_eq!(
+            extract_links(
+                "See [the docs](https://example.com/docs \"Docs\") and [`api`](https://example.com/api).\n\n<https://example.com>"
+            ),
+            vec![
+                json!({"text": "the docs", "url": "https://example.com/docs", "title": "Docs"}),
+                json!({"text": "api", "url": "https://example.com/api"}),
+                json!({"text": "https://example.com", "url": "https://example.com"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_headers() {
+        assert_eq!(
+            extract_headers(
+                "# Getting Started!\n\n## Install `hyper-mcp`\n\n## Usage\n\n## Usage\n"
+            ),
+            vec![
+                json!({"level": 1, "text": "Getting Started!", "anchor": "getting-started"}),
+                json!({"level": 2, "text": "Install hyper-mcp", "anchor": "install-hyper-mcp"}),
+                json!({"level": 2, "text": "Usage", "anchor": "usage"}),
+                json!({"level": 2, "text": "Usage", "anchor": "usage-1"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_md_table_to_json() {
+        let markdown =
+            "| Name | Age |\n|------|----:|\n| Alice | 30 |\n| Bob |\n\n| Key |\n|-----|\n| a |\n";
+        assert_eq!(
+            extract_tables(markdown),
+            vec![
+                vec![
+                    json!({"Name": "Alice", "Age": "30"}),
+                    json!({"Name": "Bob", "Age": ""}),
+                ],
+                vec![json!({"Key": "a"})],
+            ]
+        );
+
+        let result = call(
+            "md_table_to_json",
+            json!({"markdown": markdown, "index": 1}),
+        )
+        .unwrap();
+        assert_eq!(text(&result), r#"[{"Key":"a"}]"#);
+
+        let result = call(
+            "md_table_to_json",
+            json!({"markdown": markdown, "index": 2}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("md_table_to_json", json!({"markdown": "no table"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "md_extract_headers",
+                "md_extract_links",
+                "md_table_to_json",
+                "md_to_html",
+                "md_to_text",
+            ]
+        );
+    }
+}
@@ -0,0 +1,686 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const NOTION_API_URL: &str = "https://api.notion.com/v1";
+
+const NOTION_VERSION: &str = "2022-06-28";
+
+// Notion accepts at most 100 blocks per request
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+fn get_api_key() -> Result<String> {
+    config::get("NOTION_API_KEY")?
+        .ok_or_else(|| anyhow!("NOTION_API_KEY configuration is required but not set"))
+}
+
+// Formats a Notion error response, which looks like
+// `{"object": "error", "status": 404, "code": "object_not_found", "message": "..."}`.
+fn notion_error(status: u16, body: &[u8]) -> String {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(error) if error.get("message").is_some() => format!(
+            "Notion API returned {} ({}): {}",
+            status,
+            error["code"].as_str().unwrap_or("unknown"),
+            error["message"].as_str().unwrap_or_default()
+        ),
+        _ => format!(
+            "Notion API returned {}: {}",
+            status,
+            String::from_utf8_lossy(body)
+        ),
+    }
+}
+
+fn notion_request(method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+    let api_key = get_api_key()?;
+    let req = HttpRequest::new(format!("{NOTION_API_URL}{path}"))
+        .with_method(method)
+        .with_header("Authorization", format!("Bearer {api_key}"))
+        .with_header("Notion-Version", NOTION_VERSION)
+        .with_header("Content-Type", "application/json")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+
+    let res = request(&req, body.map(serde_json::to_vec).transpose()?)?;
+    let res_body = res.body();
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(notion_error(res.status_code(), &res_body)));
+    }
+    Ok(serde_json::from_slice(&res_body)?)
+}
+
+// Returns the JSON argument `name`, which may also be passed pre-serialized as a string.
+fn get_json_arg(args: &Map<String, Value>, name: &str) -> Result<Option<Value>> {
+    match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(json)) => serde_json::from_str(json)
+            .map(Some)
+            .map_err(|e| anyhow!("'{name}' is not valid JSON: {e}")),
+        Some(value) => Ok(Some(value.clone())),
+    }
+}
+
+fn get_blocks_arg(args: &Map<String, Value>, name: &str) -> Result<Vec<Value>> {
+    match get_json_arg(args, name)? {
+        None => Ok(vec![]),
+        Some(Value::Array(blocks)) => Ok(blocks),
+        Some(_) => Err(anyhow!("'{name}' must be an array of Notion blocks")),
+    }
+}
+
+fn get_object_arg(args: &Map<String, Value>, name: &str) -> Result<Option<Map<String, Value>>> {
+    match get_json_arg(args, name)? {
+        None => Ok(None),
+        Some(Value::Object(object)) => Ok(Some(object)),
+        Some(_) => Err(anyhow!("'{name}' must be an object")),
+    }
+}
+
+fn rich_text(text: &str) -> Value {
+    json!([{"type": "text", "text": {"content": text}}])
+}
+
+// Builds the body of a create page request. The title goes into the `title` property
+// of a page under a page, or into `title_property` of a page in a database.
+fn create_page_body(
+    parent_id: &str,
+    parent_type: &str,
+    title: &str,
+    title_property: Option<&str>,
+    properties: Option<Map<String, Value>>,
+    children: Vec<Value>,
+) -> Result<Value> {
+    let (parent, default_title_property) = match parent_type {
+        "page" => (json!({"page_id": parent_id}), "title"),
+        "database" => (json!({"database_id": parent_id}), "Name"),
+        _ => return Err(anyhow!("'parent_type' must be 'page' or 'database'")),
+    };
+    let mut properties = properties.unwrap_or_default();
+    properties.insert(
+        title_property.unwrap_or(default_title_property).to_string(),
+        json!({"title": rich_text(title)}),
+    );
+
+    let mut body = json!({"parent": parent, "properties": properties});
+    if !children.is_empty() {
+        body["children"] = Value::Array(children);
+    }
+    Ok(body)
+}
+
+fn pagination_body(args: &Map<String, Value>, body: &mut Map<String, Value>) {
+    if let Some(page_size) = get_int_arg(args, "page_size") {
+        body.insert("page_size".to_string(), json!(page_size.clamp(1, 100)));
+    }
+    if let Some(start_cursor) = get_string_arg(args, "start_cursor") {
+        body.insert("start_cursor".to_string(), json!(start_cursor));
+    }
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "notion_append_blocks" => append_blocks(&args),
+        "notion_create_page" => create_page(&args),
+        "notion_get_page" => get_page(&args),
+        "notion_query_database" => query_database(&args),
+        "notion_search" => search(&args),
+        "notion_update_page" => update_page(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn append_blocks(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "block_id", "blocks");
+    let block_id = get_required_string_arg(args, "block_id")?;
+    let blocks = get_blocks_arg(args, "blocks")?;
+    if blocks.is_empty() {
+        return Ok(error_result(
+            "Error: 'blocks' must not be empty".to_string(),
+        ));
+    }
+
+    let mut results = Vec::new();
+    for chunk in blocks.chunks(MAX_BLOCKS_PER_REQUEST) {
+        let response = notion_request(
+            "PATCH",
+            &format!("/blocks/{block_id}/children"),
+            Some(&json!({"children": chunk})),
+        )?;
+        if let Some(Value::Array(appended)) = response.get("results") {
+            results.extend(appended.iter().cloned());
+        }
+    }
+    json_result(&json!({"results": results}))
+}
+
+fn create_page(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "parent_id", "title");
+    let mut body = create_page_body(
+        get_required_string_arg(args, "parent_id")?,
+        get_string_arg(args, "parent_type").unwrap_or("page"),
+        get_required_string_arg(args, "title")?,
+        get_string_arg(args, "title_property"),
+        get_object_arg(args, "properties")?,
+        get_blocks_arg(args, "content")?,
+    )?;
+
+    // Pages with more blocks than a single request allows are created first and
+    // the remaining blocks appended afterwards
+    let mut remaining = Vec::new();
+    if let Some(Value::Array(children)) = body.get_mut("children") {
+        if children.len() > MAX_BLOCKS_PER_REQUEST {
+            remaining = children.split_off(MAX_BLOCKS_PER_REQUEST);
+        }
+    }
+
+    let page = notion_request("POST", "/pages", Some(&body))?;
+    if !remaining.is_empty() {
+        let page_id = page["id"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Notion API returned a page without an id"))?;
+        for chunk in remaining.chunks(MAX_BLOCKS_PER_REQUEST) {
+            notion_request(
+                "PATCH",
+                &format!("/blocks/{page_id}/children"),
+                Some(&json!({"children": chunk})),
+            )?;
+        }
+    }
+    json_result(&page)
+}
+
+fn get_page(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "page_id");
+    let page_id = get_required_string_arg(args, "page_id")?;
+    let mut page = notion_request("GET", &format!("/pages/{page_id}"), None)?;
+    if get_bool_arg(args, "include_content").unwrap_or(false) {
+        let children = notion_request("GET", &format!("/blocks/{page_id}/children"), None)?;
+        page["content"] = children;
+    }
+    json_result(&page)
+}
+
+fn query_database(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "database_id");
+    let database_id = get_required_string_arg(args, "database_id")?;
+    let mut body = Map::new();
+    if let Some(filter) = get_json_arg(args, "filter")? {
+        body.insert("filter".to_string(), filter);
+    }
+    if let Some(sorts) = get_json_arg(args, "sorts")? {
+        body.insert("sorts".to_string(), sorts);
+    }
+    pagination_body(args, &mut body);
+
+    let results = notion_request(
+        "POST",
+        &format!("/databases/{database_id}/query"),
+        Some(&Value::Object(body)),
+    )?;
+    json_result(&results)
+}
+
+fn search(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let mut body = Map::new();
+    if let Some(query) = get_string_arg(args, "query") {
+        body.insert("query".to_string(), json!(query));
+    }
+    if let Some(object) = get_string_arg(args, "filter") {
+        if object != "page" && object != "database" {
+            return Ok(error_result(
+                "Error: 'filter' must be 'page' or 'database'".to_string(),
+            ));
+        }
+        body.insert(
+            "filter".to_string(),
+            json!({"property": "object", "value": object}),
+        );
+    }
+    pagination_body(args, &mut body);
+
+    let results = notion_request("POST", "/search", Some(&Value::Object(body)))?;
+    json_result(&results)
+}
+
+fn update_page(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "page_id");
+    let page_id = get_required_string_arg(args, "page_id")?;
+    let mut body = Map::new();
+    if let Some(properties) = get_object_arg(args, "properties")? {
+        body.insert("properties".to_string(), Value::Object(properties));
+    }
+    if let Some(archived) = get_bool_arg(args, "archived") {
+        body.insert("archived".to_string(), json!(archived));
+    }
+    if body.is_empty() {
+        return Ok(error_result(
+            "Error: 'properties' or 'archived' is required".to_string(),
+        ));
+    }
+
+    let page = notion_request(
+        "PATCH",
+        &format!("/pages/{page_id}"),
+        Some(&Value::Object(body)),
+    )?;
+    json_result(&page)
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations(read_only: bool, idempotent: bool) -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(idempotent),
+        open_world_hint: Some(true),
+        read_only_hint: Some(read_only),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn pagination_properties(properties: Value) -> Value {
+    let mut properties = properties.as_object().cloned().unwrap_or_default();
+    properties.insert(
+        "page_size".to_string(),
+        json!({
+            "type": "integer",
+            "description": "Maximum number of results to return, up to 100.",
+        }),
+    );
+    properties.insert(
+        "start_cursor".to_string(),
+        json!({
+            "type": "string",
+            "description": "The next_cursor of a previous call, to get the next page.",
+        }),
+    );
+    Value::Object(properties)
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(false, false),
+                description: Some("Appends blocks to a Notion page or block.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "block_id": {
+                            "type": "string",
+                            "description": "The ID of the page or block to append to.",
+                        },
+                        "blocks": {
+                            "type": "array",
+                            "items": {"type": "object"},
+                            "description": "Notion block objects, e.g. [{\"object\": \"block\", \"type\": \"paragraph\", \"paragraph\": {\"rich_text\": [{\"type\": \"text\", \"text\": {\"content\": \"Hello\"}}]}}].",
+                        },
+                    }),
+                    &["block_id", "blocks"],
+                ),
+                name: "notion_append_blocks".to_string(),
+                title: Some("Append Blocks".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, false),
+                description: Some(
+                    "Creates a Notion page under a page or in a database.".to_string(),
+                ),
+                input_schema: tool_schema(
+                    json!({
+                        "parent_id": {
+                            "type": "string",
+                            "description": "The ID of the parent page or database.",
+                        },
+                        "parent_type": {
+                            "type": "string",
+                            "enum": ["page", "database"],
+                            "description": "Whether the parent is a page or a database. Defaults to 'page'.",
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "The page title.",
+                        },
+                        "title_property": {
+                            "type": "string",
+                            "description": "The name of the title property of the database. Defaults to 'Name'.",
+                        },
+                        "properties": {
+                            "type": "object",
+                            "description": "Other Notion property values of a page in a database, keyed by property name.",
+                        },
+                        "content": {
+                            "type": "array",
+                            "items": {"type": "object"},
+                            "description": "Notion block objects for the page content.",
+                        },
+                    }),
+                    &["parent_id", "title"],
+                ),
+                name: "notion_create_page".to_string(),
+                title: Some("Create Page".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, true),
+                description: Some("Returns a Notion page and its properties.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "page_id": {
+                            "type": "string",
+                            "description": "The ID of the page.",
+                        },
+                        "include_content": {
+                            "type": "boolean",
+                            "description": "Also return the first page of the page's blocks. Defaults to false.",
+                        },
+                    }),
+                    &["page_id"],
+                ),
+                name: "notion_get_page".to_string(),
+                title: Some("Get Page".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, true),
+                description: Some("Queries the pages of a Notion database.".to_string()),
+                input_schema: tool_schema(
+                    pagination_properties(json!({
+                        "database_id": {
+                            "type": "string",
+                            "description": "The ID of the database.",
+                        },
+                        "filter": {
+                            "type": "object",
+                            "description": "A Notion database filter object, e.g. {\"property\": \"Status\", \"status\": {\"equals\": \"Done\"}}.",
+                        },
+                        "sorts": {
+                            "type": "array",
+                            "items": {"type": "object"},
+                            "description": "Notion sort objects, e.g. [{\"property\": \"Due\", \"direction\": \"ascending\"}].",
+                        },
+                    })),
+                    &["database_id"],
+                ),
+                name: "notion_query_database".to_string(),
+                title: Some("Query Database".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, true),
+                description: Some(
+                    "Searches the pages and databases shared with the integration by title."
+                        .to_string(),
+                ),
+                input_schema: tool_schema(
+                    pagination_properties(json!({
+                        "query": {
+                            "type": "string",
+                            "description": "The text to search titles for. Returns everything if not set.",
+                        },
+                        "filter": {
+                            "type": "string",
+                            "enum": ["page", "database"],
+                            "description": "Only return pages or only databases.",
+                        },
+                    })),
+                    &[],
+                ),
+                name: "notion_search".to_string(),
+                title: Some("Search".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, true),
+                description: Some(
+                    "Updates the properties of a Notion page, or archives it.".to_string(),
+                ),
+                input_schema: tool_schema(
+                    json!({
+                        "page_id": {
+                            "type": "string",
+                            "description": "The ID of the page.",
+                        },
+                        "properties": {
+                            "type": "object",
+                            "description": "Notion property values to set, keyed by property name.",
+                        },
+                        "archived": {
+                            "type": "boolean",
+                            "description": "Set to true to archive the page, or false to restore it.",
+                        },
+                    }),
+                    &["page_id"],
+                ),
+                name: "notion_update_page".to_string(),
+                title: Some("Update Page".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn args(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_get_json_arg() {
+        let args = args(json!({
+            "object": {"property": "Done"},
+            "serialized": "[{\"type\": \"divider\"}]",
+            "invalid": "[{",
+        }));
+        assert_eq!(
+            get_json_arg(&args, "object").unwrap(),
+            Some(json!({"property": "Done"}))
+        );
+        assert_eq!(
+            get_blocks_arg(&args, "serialized").unwrap(),
+            vec![json!({"type": "divider"})]
+        );
+        assert!(get_json_arg(&args, "invalid").is_err());
+        assert!(get_blocks_arg(&args, "object").is_err());
+        assert_eq!(get_json_arg(&args, "missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_create_page_body() {
+        let blocks = vec![json!({"type": "divider", "divider": {}})];
+        assert_eq!(
+            create_page_body("parent", "page", "Notes", None, None, blocks.clone()).unwrap(),
+            json!({
+                "parent": {"page_id": "parent"},
+                "properties": {
+                    "title": {"title": [{"type": "text", "text": {"content": "Notes"}}]},
+                },
+                "children": blocks,
+            })
+        );
+
+        let properties = args(json!({"Status": {"status": {"name": "Done"}}}));
+        assert_eq!(
+            create_page_body("db", "database", "Task", None, Some(properties), vec![]).unwrap(),
+            json!({
+                "parent": {"database_id": "db"},
+                "properties": {
+                    "Name": {"title": [{"type": "text", "text": {"content": "Task"}}]},
+                    "Status": {"status": {"name": "Done"}},
+                },
+            })
+        );
+
+        assert!(create_page_body("parent", "block", "Notes", None, None, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_notion_error() {
+        assert_eq!(
+            notion_error(
+                404,
+                br#"{"object": "error", "status": 404, "code": "object_not_found", "message": "Could not find page"}"#
+            ),
+            "Notion API returned 404 (object_not_found): Could not find page"
+        );
+        assert_eq!(
+            notion_error(502, b"Bad Gateway"),
+            "Notion API returned 502: Bad Gateway"
+        );
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("notion_append_blocks", json!({"block_id": "id"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "notion_append_blocks",
+            json!({"block_id": "id", "blocks": []}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("notion_update_page", json!({"page_id": "id"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("notion_search", json!({"filter": "block"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "notion_append_blocks",
+                "notion_create_page",
+                "notion_get_page",
+                "notion_query_database",
+                "notion_search",
+                "notion_update_page",
+            ]
+        );
+    }
+}
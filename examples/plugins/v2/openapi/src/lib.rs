@@ -0,0 +1,704 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{http::request, var, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+// The plugin variable holding the loaded spec as JSON, so it is only downloaded and
+// parsed once.
+const SPEC_VAR: &str = "spec";
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+// Converts YAML to JSON. Keys like the `200` of responses are integers in YAML, which
+// JSON objects can't have, so scalar keys are turned into strings.
+fn yaml_to_json(value: serde_yaml::Value) -> Result<Value> {
+    use serde_yaml::Value as Yaml;
+
+    Ok(match value {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(b),
+        Yaml::Number(n) => serde_json::to_value(n)?,
+        Yaml::String(s) => Value::String(s),
+        Yaml::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect::<Result<_>>()?)
+        }
+        Yaml::Mapping(mapping) => {
+            let mut object = Map::new();
+            for (key, value) in mapping {
+                let key = match key {
+                    Yaml::String(s) => s,
+                    Yaml::Number(n) => n.to_string(),
+                    Yaml::Bool(b) => b.to_string(),
+                    key => return Err(anyhow!("unsupported YAML key {key:?}")),
+                };
+                object.insert(key, yaml_to_json(value)?);
+            }
+            Value::Object(object)
+        }
+        Yaml::Tagged(tagged) => yaml_to_json(tagged.value)?,
+    })
+}
+
+// Parses an OpenAPI 3.x spec in JSON or YAML.
+fn parse_spec(body: &[u8]) -> Result<Value> {
+    let spec = match serde_json::from_slice(body) {
+        Ok(spec) => spec,
+        Err(_) => {
+            let mut yaml: serde_yaml::Value = serde_yaml::from_slice(body)
+                .map_err(|e| anyhow!("the spec is neither JSON nor YAML: {e}"))?;
+            yaml.apply_merge()?;
+            yaml_to_json(yaml)?
+        }
+    };
+    match spec.get("openapi").and_then(Value::as_str) {
+        Some(version) if version.starts_with("3.") => Ok(spec),
+        Some(version) => Err(anyhow!("OpenAPI {version} is not supported, only 3.x")),
+        None if spec.get("swagger").is_some() => Err(anyhow!(
+            "Swagger 2.0 specs are not supported, convert the spec to OpenAPI 3 first"
+        )),
+        None => Err(anyhow!(
+            "this is not an OpenAPI spec, it has no 'openapi' version"
+        )),
+    }
+}
+
+fn paths(spec: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    spec.get("paths")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+fn schemas(spec: &Value) -> Option<&Map<String, Value>> {
+    spec.pointer("/components/schemas")
+        .and_then(Value::as_object)
+}
+
+// Returns the operations of the spec in the order of its paths, optionally only those
+// with `tag`.
+fn operations(spec: &Value, tag: Option<&str>) -> Vec<Value> {
+    let mut operations = Vec::new();
+    for (path, item) in paths(spec) {
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let tags = operation.get("tags").and_then(Value::as_array);
+            if let Some(tag) = tag {
+                if !tags.is_some_and(|tags| tags.iter().any(|t| t.as_str() == Some(tag))) {
+                    continue;
+                }
+            }
+            operations.push(json!({
+                "method": method.to_uppercase(),
+                "path": path,
+                "summary": operation.get("summary"),
+                "operation_id": operation.get("operationId"),
+            }));
+        }
+    }
+    operations
+}
+
+// Replaces local `$ref`s like `#/components/parameters/limit` with what they point to.
+// Schema references are kept, as schemas can be large and recursive and are available
+// from `openapi_get_schema`, and so are references back to a parent, which would
+// never end.
+fn resolve_refs(value: &Value, spec: &Value, parents: &mut Vec<String>) -> Value {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get("$ref") {
+                if reference.starts_with("#/components/schemas/") || parents.contains(reference) {
+                    return value.clone();
+                }
+                // External references can't be followed
+                let Some(target) = reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| spec.pointer(pointer))
+                else {
+                    return value.clone();
+                };
+                parents.push(reference.clone());
+                let resolved = resolve_refs(target, spec, parents);
+                parents.pop();
+                return resolved;
+            }
+            Value::Object(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), resolve_refs(value, spec, parents)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_refs(item, spec, parents))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+// Returns an operation with its references resolved, and with the parameters shared by
+// all operations of the path added unless the operation overrides them.
+fn operation(spec: &Value, method: &str, path: &str) -> Result<Value> {
+    let method = method.to_lowercase();
+    if !METHODS.contains(&method.as_str()) {
+        return Err(anyhow!(
+            "'{method}' is not an HTTP method, use one of {}",
+            METHODS.join(", ")
+        ));
+    }
+    let item = spec
+        .get("paths")
+        .and_then(|paths| paths.get(path))
+        .ok_or_else(|| {
+            anyhow!("path '{path}' not found, use openapi_list_paths to see the paths")
+        })?;
+    let item = resolve_refs(item, spec, &mut Vec::new());
+    let mut operation = item.get(&method).cloned().ok_or_else(|| {
+        let methods: Vec<String> = METHODS
+            .iter()
+            .filter(|method| item.get(*method).is_some())
+            .map(|method| method.to_uppercase())
+            .collect();
+        anyhow!(
+            "path '{path}' has no {} operation, only {}",
+            method.to_uppercase(),
+            methods.join(", ")
+        )
+    })?;
+
+    let key = |parameter: &Value| (parameter.get("name").cloned(), parameter.get("in").cloned());
+    let mut parameters: Vec<Value> = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let shared = item.get("parameters").and_then(Value::as_array);
+    for parameter in shared.into_iter().flatten() {
+        if !parameters.iter().any(|p| key(p) == key(parameter)) {
+            parameters.push(parameter.clone());
+        }
+    }
+    if !parameters.is_empty() {
+        operation["parameters"] = Value::Array(parameters);
+    }
+
+    let mut result = json!({
+        "method": method.to_uppercase(),
+        "path": path,
+    });
+    if let (Value::Object(result), Value::Object(operation)) = (&mut result, operation) {
+        result.extend(operation);
+    }
+    Ok(result)
+}
+
+fn spec_summary(spec: &Value) -> Value {
+    json!({
+        "openapi": spec.get("openapi"),
+        "title": spec.pointer("/info/title"),
+        "version": spec.pointer("/info/version"),
+        "description": spec.pointer("/info/description"),
+        "servers": spec
+            .get("servers")
+            .and_then(Value::as_array)
+            .map(|servers| servers.iter().filter_map(|server| server.get("url")).collect::<Vec<_>>()),
+        "paths": paths(spec).count(),
+        "operations": operations(spec, None).len(),
+        "schemas": schemas(spec).map_or(0, Map::len),
+    })
+}
+
+fn load_spec() -> Result<Value> {
+    let spec: String =
+        var::get(SPEC_VAR)?.ok_or_else(|| anyhow!("no spec is loaded, call openapi_load first"))?;
+    Ok(serde_json::from_str(&spec)?)
+}
+
+fn download(url: &str) -> Result<Vec<u8>> {
+    let req = HttpRequest::new(url)
+        .with_method("GET")
+        .with_header("Accept", "application/json, application/yaml")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    let res = request::<()>(&req, None)?;
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(
+            "downloading {url} failed with status {}",
+            res.status_code()
+        ));
+    }
+    Ok(res.body())
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "openapi_get_operation" => openapi_get_operation(&args),
+        "openapi_get_schema" => openapi_get_schema(&args),
+        "openapi_list_paths" => openapi_list_paths(&args),
+        "openapi_list_schemas" => openapi_list_schemas(&args),
+        "openapi_load" => openapi_load(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn openapi_get_operation(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "method", "path");
+    let method = get_required_string_arg(args, "method")?;
+    let path = get_required_string_arg(args, "path")?;
+
+    json_result(&operation(&load_spec()?, method, path)?)
+}
+
+fn openapi_get_schema(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "name");
+    let name = get_required_string_arg(args, "name")?;
+
+    let spec = load_spec()?;
+    let schema = schemas(&spec)
+        .and_then(|schemas| schemas.get(name))
+        .ok_or_else(|| {
+            anyhow!("schema '{name}' not found, use openapi_list_schemas to see the schemas")
+        })?;
+    json_result(schema)
+}
+
+fn openapi_list_paths(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let tag = get_string_arg(args, "tag");
+    json_result(&Value::Array(operations(&load_spec()?, tag)))
+}
+
+fn openapi_list_schemas(_args: &Map<String, Value>) -> Result<CallToolResult> {
+    let spec = load_spec()?;
+    let names: Vec<&String> = schemas(&spec).into_iter().flat_map(Map::keys).collect();
+    json_result(&json!(names))
+}
+
+fn openapi_load(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "url");
+    let url = get_required_string_arg(args, "url")?;
+
+    let spec = parse_spec(&download(url)?)?;
+    var::set(SPEC_VAR, serde_json::to_string(&spec)?).map_err(|e| {
+        anyhow!("caching the spec failed, it may be larger than the host allows plugin variables to be: {e}")
+    })?;
+    json_result(&spec_summary(&spec))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns an operation of the loaded OpenAPI spec with its parameters, request body and responses. References to parameters, request bodies and responses are resolved, while schemas are left as references to look up with openapi_get_schema.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "method": {
+                            "type": "string",
+                            "description": "The HTTP method, like GET.",
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "The path as written in the spec, like /pets/{petId}.",
+                        },
+                    }),
+                    &["method", "path"],
+                ),
+                name: "openapi_get_operation".to_string(),
+                title: Some("Get Operation".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns a component schema of the loaded OpenAPI spec.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "name": {
+                            "type": "string",
+                            "description": "The schema name, like Pet for #/components/schemas/Pet.",
+                        },
+                    }),
+                    &["name"],
+                ),
+                name: "openapi_get_schema".to_string(),
+                title: Some("Get Schema".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Lists the operations of the loaded OpenAPI spec with their method, path, summary and operation ID.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "tag": {
+                            "type": "string",
+                            "description": "Only list operations with this tag.",
+                        },
+                    }),
+                    &[],
+                ),
+                name: "openapi_list_paths".to_string(),
+                title: Some("List Paths".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Lists the names of the component schemas of the loaded OpenAPI spec.".to_string()),
+                input_schema: tool_schema(json!({}), &[]),
+                name: "openapi_list_schemas".to_string(),
+                title: Some("List Schemas".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    open_world_hint: Some(true),
+                    read_only_hint: Some(false),
+
+                    ..annotations().unwrap_or_default()
+                }),
+                description: Some("Downloads an OpenAPI 3.x spec in JSON or YAML and loads it for the other openapi tools, replacing any spec loaded before. Returns a summary of the API.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "url": {
+                            "type": "string",
+                            "description": "The URL of the spec, like https://petstore3.swagger.io/api/v3/openapi.json.",
+                        },
+                    }),
+                    &["url"],
+                ),
+                name: "openapi_load".to_string(),
+                title: Some("Load OpenAPI Spec".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+servers:
+  - url: https://petstore.example.com/v1
+paths:
+  /pets:
+    get:
+      summary: List pets
+      operationId: listPets
+      tags: [pets]
+      parameters:
+        - $ref: "#/components/parameters/limit"
+      responses:
+        200:
+          description: A list of pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: "#/components/schemas/Pet"
+    post:
+      summary: Create a pet
+      tags: [admin]
+      requestBody:
+        $ref: "#/components/requestBodies/Pet"
+      responses:
+        201:
+          description: Created
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema:
+          type: string
+      - name: verbose
+        in: query
+        schema:
+          type: boolean
+    get:
+      summary: Get a pet
+      parameters:
+        - name: verbose
+          in: query
+          description: Include the owner
+          schema:
+            type: boolean
+      responses:
+        default:
+          $ref: "#/components/responses/Error"
+components:
+  parameters:
+    limit:
+      name: limit
+      in: query
+      schema:
+        type: integer
+  requestBodies:
+    Pet:
+      content:
+        application/json:
+          schema:
+            $ref: "#/components/schemas/Pet"
+  responses:
+    Error:
+      description: An error
+      links:
+        self:
+          $ref: "#/components/responses/Error"
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+    Error:
+      type: object
+"##;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = parse_spec(PETSTORE.as_bytes()).unwrap();
+        assert_eq!(
+            spec.pointer("/paths/~1pets/get/responses/200/description"),
+            Some(&json!("A list of pets"))
+        );
+
+        let json = serde_json::to_vec(&spec).unwrap();
+        assert_eq!(parse_spec(&json).unwrap(), spec);
+
+        let error = parse_spec(br#"{"swagger": "2.0"}"#).unwrap_err();
+        assert!(error.to_string().contains("Swagger 2.0"));
+        assert!(parse_spec(br#"{"openapi": "4.0.0"}"#).is_err());
+        assert!(parse_spec(b"title: not a spec").is_err());
+        assert!(parse_spec(b"{[").is_err());
+    }
+
+    #[test]
+    fn test_spec_summary() {
+        let spec = parse_spec(PETSTORE.as_bytes()).unwrap();
+        assert_eq!(
+            spec_summary(&spec),
+            json!({
+                "openapi": "3.0.3",
+                "title": "Petstore",
+                "version": "1.0.0",
+                "description": null,
+                "servers": ["https://petstore.example.com/v1"],
+                "paths": 2,
+                "operations": 3,
+                "schemas": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_operations() {
+        let spec = parse_spec(PETSTORE.as_bytes()).unwrap();
+        assert_eq!(
+            operations(&spec, None),
+            [
+                json!({"method": "GET", "path": "/pets", "summary": "List pets", "operation_id": "listPets"}),
+                json!({"method": "POST", "path": "/pets", "summary": "Create a pet", "operation_id": null}),
+                json!({"method": "GET", "path": "/pets/{petId}", "summary": "Get a pet", "operation_id": null}),
+            ]
+        );
+        assert_eq!(operations(&spec, Some("admin")).len(), 1);
+        assert!(operations(&spec, Some("unknown")).is_empty());
+    }
+
+    #[test]
+    fn test_operation() {
+        let spec = parse_spec(PETSTORE.as_bytes()).unwrap();
+
+        let list = operation(&spec, "get", "/pets").unwrap();
+        assert_eq!(list["method"], "GET");
+        assert_eq!(list["parameters"][0]["name"], "limit");
+        // Schema references are kept
+        assert_eq!(
+            list["responses"]["200"]["content"]["application/json"]["schema"]["items"],
+            json!({"$ref": "#/components/schemas/Pet"})
+        );
+
+        let create = operation(&spec, "POST", "/pets").unwrap();
+        assert_eq!(
+            create["requestBody"]["content"]["application/json"]["schema"],
+            json!({"$ref": "#/components/schemas/Pet"})
+        );
+
+        let get = operation(&spec, "get", "/pets/{petId}").unwrap();
+        let parameters = get["parameters"].as_array().unwrap();
+        assert_eq!(parameters.len(), 2);
+        assert_eq!(parameters[0]["description"], "Include the owner");
+        assert_eq!(parameters[1]["name"], "petId");
+        // A reference back to a parent is kept rather than followed forever
+        assert_eq!(get["responses"]["default"]["description"], "An error");
+        assert_eq!(
+            get["responses"]["default"]["links"]["self"],
+            json!({"$ref": "#/components/responses/Error"})
+        );
+
+        assert!(operation(&spec, "delete", "/pets").is_err());
+        assert!(operation(&spec, "fetch", "/pets").is_err());
+        assert!(operation(&spec, "get", "/owners").is_err());
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("openapi_get_operation", json!({"method": "GET"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("openapi_load", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "openapi_get_operation",
+                "openapi_get_schema",
+                "openapi_list_paths",
+                "openapi_list_schemas",
+                "openapi_load"
+            ]
+        );
+    }
+}
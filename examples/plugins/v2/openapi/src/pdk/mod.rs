@@ -0,0 +1,3 @@
+pub mod exports;
+pub mod imports;
+pub mod types;
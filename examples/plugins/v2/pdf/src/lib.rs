@@ -0,0 +1,574 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use lopdf::{Dictionary, Document, Object};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+// Decodes the base64 `data` argument, which may also be a data URL, and parses the PDF.
+// PDFs that need a password are rejected, as there is no way to pass one.
+fn load_pdf(args: &Map<String, Value>) -> Result<Document> {
+    let data = get_required_string_arg(args, "data")?;
+    let data = match data.split_once(";base64,") {
+        Some((prefix, data)) if prefix.starts_with("data:") => data,
+        _ => data,
+    };
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    let bytes = STANDARD
+        .decode(data)
+        .map_err(|e| anyhow!("'data' must be base64: {e}"))?;
+    let encrypted = || anyhow!("the PDF is encrypted with a password, which is not supported");
+
+    // lopdf decrypts PDFs with an empty user password while loading and removes their
+    // Encrypt entry, and fails to load or leaves the entry in place for the others.
+    // `is_encrypted` only sees an entry that refers to an object, so the trailer is
+    // checked for any Encrypt entry.
+    let document = match Document::load_mem(&bytes) {
+        Ok(document) => document,
+        Err(_) if bytes.windows(8).any(|window| window == b"/Encrypt") => return Err(encrypted()),
+        Err(e) => return Err(anyhow!("'data' is not a valid PDF: {e}")),
+    };
+    if document.trailer.has(b"Encrypt") {
+        return Err(encrypted());
+    }
+    Ok(document)
+}
+
+fn get_page_number_arg(args: &Map<String, Value>, page_count: usize) -> Result<u32> {
+    match get_int_arg(args, "page_number") {
+        Some(page) if (1..=page_count as i64).contains(&page) => Ok(page as u32),
+        Some(page) => Err(anyhow!(
+            "page {page} does not exist, the PDF has {page_count} pages"
+        )),
+        None => Err(anyhow!(
+            "'page_number' argument is required and must be an integer"
+        )),
+    }
+}
+
+fn extract_text(document: &Document, pages: &[u32]) -> Result<String> {
+    document
+        .extract_text(pages)
+        .map_err(|e| anyhow!("extracting the text failed: {e}"))
+}
+
+// Decodes a PDF text string, which is UTF-16BE when it starts with a byte order mark, and
+// otherwise PDFDocEncoding, which matches Latin-1 for printable characters.
+fn text_string(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFE, 0xFF, utf16 @ ..] => {
+            let units: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xEF, 0xBB, 0xBF, utf8 @ ..] => String::from_utf8_lossy(utf8).into_owned(),
+        _ => bytes.iter().map(|&byte| byte as char).collect(),
+    }
+}
+
+// Converts a PDF date like "D:20240115103000+01'00'" to RFC 3339. Missing parts default to
+// the start of the period, and dates without a timezone are returned without an offset.
+fn pdf_date(date: &str) -> Option<String> {
+    let date = date.strip_prefix("D:").unwrap_or(date);
+    let digits = date.bytes().take_while(u8::is_ascii_digit).count();
+    if digits < 4 {
+        return None;
+    }
+    let part = |start: usize, default: &'static str| {
+        date.get(start..start + 2)
+            .filter(|_| digits >= start + 2)
+            .unwrap_or(default)
+    };
+    let mut rfc3339 = format!(
+        "{}-{}-{}T{}:{}:{}",
+        &date[..4],
+        part(4, "01"),
+        part(6, "01"),
+        part(8, "00"),
+        part(10, "00"),
+        part(12, "00")
+    );
+
+    let zone = &date[digits.min(14)..];
+    match zone.as_bytes() {
+        [b'Z', ..] => rfc3339.push('Z'),
+        [sign @ (b'+' | b'-'), ..] => {
+            let offset: Vec<&str> = zone[1..]
+                .split('\'')
+                .filter(|part| !part.is_empty())
+                .collect();
+            rfc3339.push_str(&format!(
+                "{}{}:{}",
+                *sign as char,
+                offset.first()?,
+                offset.get(1).unwrap_or(&"00")
+            ));
+        }
+        _ => {}
+    }
+    Some(rfc3339)
+}
+
+// The document information dictionary, if the PDF has one.
+fn info_dictionary(document: &Document) -> Option<&Dictionary> {
+    match document.trailer.get(b"Info").ok()? {
+        Object::Reference(id) => document.get_dictionary(*id).ok(),
+        Object::Dictionary(dictionary) => Some(dictionary),
+        _ => None,
+    }
+}
+
+fn info_string(info: Option<&Dictionary>, key: &[u8]) -> Option<String> {
+    match info?.get(key).ok()? {
+        Object::String(bytes, _) => Some(text_string(bytes)),
+        _ => None,
+    }
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn text_result(text: String) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "pdf_extract_metadata" => pdf_extract_metadata(&args),
+        "pdf_extract_page" => pdf_extract_page(&args),
+        "pdf_extract_text" => pdf_extract_text(&args),
+        "pdf_get_page_count" => pdf_get_page_count(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn pdf_extract_metadata(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "data");
+    let document = load_pdf(args)?;
+
+    let info = info_dictionary(&document);
+    // Kept as is when it isn't a valid PDF date
+    let creation_date =
+        info_string(info, b"CreationDate").map(|date| pdf_date(&date).unwrap_or(date));
+    json_result(&json!({
+        "title": info_string(info, b"Title"),
+        "author": info_string(info, b"Author"),
+        "subject": info_string(info, b"Subject"),
+        "creator": info_string(info, b"Creator"),
+        "creation_date": creation_date,
+        "page_count": document.get_pages().len(),
+    }))
+}
+
+fn pdf_extract_page(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "data", "page_number");
+    let document = load_pdf(args)?;
+    let page_number = get_page_number_arg(args, document.get_pages().len())?;
+
+    text_result(extract_text(&document, &[page_number])?)
+}
+
+fn pdf_extract_text(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "data");
+    let document = load_pdf(args)?;
+
+    let pages: Vec<u32> = document.get_pages().into_keys().collect();
+    text_result(extract_text(&document, &pages)?)
+}
+
+fn pdf_get_page_count(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "data");
+    let document = load_pdf(args)?;
+
+    text_result(document.get_pages().len().to_string())
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn data_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "The base64-encoded PDF, or a data URL.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the title, author, subject, creator, creation date and page count of a PDF. Missing fields are null.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "data": data_property(),
+                    }),
+                    &["data"],
+                ),
+                name: "pdf_extract_metadata".to_string(),
+                title: Some("Extract PDF Metadata".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Extracts the plain text of one page of a PDF.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "data": data_property(),
+                        "page_number": {
+                            "type": "integer",
+                            "description": "The page, starting from 1.",
+                        },
+                    }),
+                    &["data", "page_number"],
+                ),
+                name: "pdf_extract_page".to_string(),
+                title: Some("Extract PDF Page".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Extracts the plain text of every page of a PDF. Scanned pages without a text layer have no text.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "data": data_property(),
+                    }),
+                    &["data"],
+                ),
+                name: "pdf_extract_text".to_string(),
+                title: Some("Extract PDF Text".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the number of pages of a PDF.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "data": data_property(),
+                    }),
+                    &["data"],
+                ),
+                name: "pdf_get_page_count".to_string(),
+                title: Some("Get PDF Page Count".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{
+        content::{Content, Operation},
+        dictionary, Stream,
+    };
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn result_text(result: &CallToolResult) -> &str {
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        &content.text
+    }
+
+    // A PDF with a page showing each of `texts`, and a document information dictionary.
+    fn document(texts: &[&str]) -> Document {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Courier",
+        });
+        let resources_id = document.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let mut kids = Vec::new();
+        for text in texts {
+            let content = Content {
+                operations: vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 24.into()]),
+                    Operation::new("Td", vec![100.into(), 600.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(*text)]),
+                    Operation::new("ET", vec![]),
+                ],
+            };
+            let content_id =
+                document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            let page_id = document.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(Object::from(page_id));
+        }
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Count" => kids.len() as i64,
+                "Kids" => kids,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        document.trailer.set("Root", catalog_id);
+        let info_id = document.add_object(dictionary! {
+            "Title" => Object::string_literal("Quarterly Report"),
+            // "Jürgen" as UTF-16BE
+            "Author" => Object::String(
+                vec![0xFE, 0xFF, 0, b'J', 0, 0xFC, 0, b'r', 0, b'g', 0, b'e', 0, b'n'],
+                lopdf::StringFormat::Hexadecimal,
+            ),
+            "CreationDate" => Object::string_literal("D:20240115103000+01'00'"),
+        });
+        document.trailer.set("Info", info_id);
+        document
+    }
+
+    fn encode(mut document: Document) -> String {
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+        STANDARD.encode(bytes)
+    }
+
+    #[test]
+    fn test_pdf_date() {
+        assert_eq!(
+            pdf_date("D:20240115103000+01'00'").as_deref(),
+            Some("2024-01-15T10:30:00+01:00")
+        );
+        assert_eq!(
+            pdf_date("D:20240115103000Z").as_deref(),
+            Some("2024-01-15T10:30:00Z")
+        );
+        assert_eq!(pdf_date("D:202401").as_deref(), Some("2024-01-01T00:00:00"));
+        assert_eq!(pdf_date("yesterday"), None);
+    }
+
+    #[test]
+    fn test_text_string() {
+        assert_eq!(text_string(b"Report"), "Report");
+        assert_eq!(text_string(&[0xFE, 0xFF, 0, b'J', 0, 0xFC]), "Jü");
+        assert_eq!(text_string(&[b'J', 0xFC]), "Jü");
+    }
+
+    #[test]
+    fn test_pdf_extract_text() {
+        let data = encode(document(&["First page", "Second page"]));
+
+        let result = call("pdf_extract_text", json!({"data": data})).unwrap();
+        let text = result_text(&result);
+        assert!(text.contains("First page"), "{text}");
+        assert!(text.contains("Second page"), "{text}");
+
+        let result = call("pdf_extract_page", json!({"data": data, "page_number": 2})).unwrap();
+        let text = result_text(&result);
+        assert!(text.contains("Second page"), "{text}");
+        assert!(!text.contains("First page"), "{text}");
+
+        let result = call("pdf_extract_page", json!({"data": data, "page_number": 3})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_pdf_get_page_count() {
+        let data = encode(document(&["One", "Two", "Three"]));
+        let result = call("pdf_get_page_count", json!({"data": data})).unwrap();
+        assert_eq!(result_text(&result), "3");
+    }
+
+    #[test]
+    fn test_pdf_extract_metadata() {
+        let data = encode(document(&["One"]));
+        let result = call("pdf_extract_metadata", json!({"data": data})).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(result_text(&result)).unwrap(),
+            json!({
+                "title": "Quarterly Report",
+                "author": "Jürgen",
+                "subject": null,
+                "creator": null,
+                "creation_date": "2024-01-15T10:30:00+01:00",
+                "page_count": 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_pdf() {
+        let result = call("pdf_get_page_count", json!({"data": "not base64!"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let result = call(
+            "pdf_get_page_count",
+            json!({"data": STANDARD.encode("not a PDF")}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let mut encrypted = document(&["Secret"]);
+        encrypted.trailer.set(
+            "Encrypt",
+            dictionary! {
+                "Filter" => "Standard",
+                "V" => 1,
+                "R" => 2,
+                "O" => Object::string_literal(vec![1u8; 32]),
+                "U" => Object::string_literal(vec![2u8; 32]),
+                "P" => -4,
+            },
+        );
+        let result = call("pdf_extract_text", json!({"data": encode(encrypted)})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        assert!(result_text(&result).contains("encrypted"));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "pdf_extract_metadata",
+                "pdf_extract_page",
+                "pdf_extract_text",
+                "pdf_get_page_count"
+            ]
+        );
+    }
+}
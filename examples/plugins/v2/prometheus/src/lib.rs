@@ -0,0 +1,596 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+fn get_prometheus_url() -> Result<String> {
+    let url = config::get("PROMETHEUS_URL")?
+        .ok_or_else(|| anyhow!("PROMETHEUS_URL configuration is required but not set"))?;
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+fn encode_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                urlencoding::encode(key),
+                urlencoding::encode(value)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// Returns the `data` of a Prometheus API response, or its error. Responses look like
+// `{"status": "success", "data": ...}` or
+// `{"status": "error", "errorType": "bad_data", "error": "..."}`.
+fn response_data(status: u16, body: &[u8]) -> Result<Value> {
+    let response: Value = serde_json::from_slice(body).map_err(|_| {
+        anyhow!(
+            "Prometheus returned {status}: {}",
+            String::from_utf8_lossy(body)
+        )
+    })?;
+    match response["status"].as_str() {
+        Some("success") => Ok(response["data"].clone()),
+        _ => Err(anyhow!(
+            "Prometheus returned {status} ({}): {}",
+            response["errorType"].as_str().unwrap_or("unknown"),
+            response["error"].as_str().unwrap_or_default()
+        )),
+    }
+}
+
+fn prometheus_get(path: &str, query: &[(&str, String)]) -> Result<Value> {
+    let mut url = format!("{}{path}", get_prometheus_url()?);
+    if !query.is_empty() {
+        url = format!("{url}?{}", encode_query(query));
+    }
+    let mut req = HttpRequest::new(url)
+        .with_method("GET")
+        .with_header("Accept", "application/json")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    if let Some(token) = config::get("PROMETHEUS_BEARER_TOKEN")? {
+        req = req.with_header("Authorization", format!("Bearer {token}"));
+    }
+
+    let res = request::<()>(&req, None)?;
+    response_data(res.status_code(), &res.body())
+}
+
+// Appends a `match[]` parameter for each series selector in the `match` argument.
+fn push_matchers(args: &Map<String, Value>, query: &mut Vec<(&str, String)>) {
+    let matchers = match args.get("match") {
+        Some(Value::String(matcher)) => vec![matcher.clone()],
+        Some(Value::Array(matchers)) => matchers
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => vec![],
+    };
+    query.extend(matchers.into_iter().map(|matcher| ("match[]", matcher)));
+}
+
+// Appends the optional `start` and `end` arguments.
+fn push_time_range(args: &Map<String, Value>, query: &mut Vec<(&str, String)>) {
+    for name in ["start", "end"] {
+        if let Some(time) = get_time_arg(args, name) {
+            query.push((name, time));
+        }
+    }
+}
+
+// Returns a time argument, which may be an RFC 3339 timestamp or a Unix timestamp.
+fn get_time_arg(args: &Map<String, Value>, name: &str) -> Option<String> {
+    match args.get(name)? {
+        Value::String(time) => Some(time.clone()),
+        Value::Number(time) => Some(time.to_string()),
+        _ => None,
+    }
+}
+
+// Collects the firing and pending alerts from the rule groups of `GET /api/v1/rules`.
+fn active_alerts(rules: &Value) -> Vec<Value> {
+    let groups = rules["groups"].as_array().cloned().unwrap_or_default();
+    groups
+        .iter()
+        .flat_map(|group| {
+            let group_name = group["name"].clone();
+            group["rules"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|rule| rule["type"] == "alerting")
+                .flat_map(move |rule| {
+                    let group_name = group_name.clone();
+                    rule["alerts"]
+                        .as_array()
+                        .cloned()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(move |alert| {
+                            json!({
+                                "alertname": rule["name"],
+                                "group": group_name,
+                                "state": alert["state"],
+                                "labels": alert["labels"],
+                                "annotations": alert["annotations"],
+                                "active_at": alert["activeAt"],
+                                "value": alert["value"],
+                            })
+                        })
+                })
+        })
+        .filter(|alert| alert["state"] == "firing" || alert["state"] == "pending")
+        .collect()
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "prom_alert_rules" => alert_rules(&args),
+        "prom_label_values" => label_values(&args),
+        "prom_labels" => labels(&args),
+        "prom_query_instant" => query_instant(&args),
+        "prom_query_range" => query_range(&args),
+        "prom_series" => series(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn alert_rules(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let rules = prometheus_get("/api/v1/rules", &[("type", "alert".to_string())])?;
+    if get_bool_arg(args, "include_rules").unwrap_or(false) {
+        return json_result(&json!({
+            "alerts": active_alerts(&rules),
+            "groups": rules["groups"],
+        }));
+    }
+    json_result(&json!({"alerts": active_alerts(&rules)}))
+}
+
+fn label_values(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "label");
+    let label = get_required_string_arg(args, "label")?;
+    let mut query = vec![];
+    push_matchers(args, &mut query);
+    push_time_range(args, &mut query);
+
+    let values = prometheus_get(
+        &format!("/api/v1/label/{}/values", urlencoding::encode(label)),
+        &query,
+    )?;
+    json_result(&values)
+}
+
+fn labels(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let mut query = vec![];
+    push_matchers(args, &mut query);
+    push_time_range(args, &mut query);
+    json_result(&prometheus_get("/api/v1/labels", &query)?)
+}
+
+fn query_instant(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "query");
+    let mut query = vec![("query", get_required_string_arg(args, "query")?.to_string())];
+    if let Some(time) = get_time_arg(args, "time") {
+        query.push(("time", time));
+    }
+    json_result(&prometheus_get("/api/v1/query", &query)?)
+}
+
+fn query_range(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "query", "start", "end", "step");
+    let mut query = vec![("query", get_required_string_arg(args, "query")?.to_string())];
+    push_time_range(args, &mut query);
+    match get_time_arg(args, "step") {
+        Some(step) => query.push(("step", step)),
+        None => {
+            return Ok(error_result(
+                "Error: 'step' must be a duration like '1m' or a number of seconds".to_string(),
+            ))
+        }
+    }
+    json_result(&prometheus_get("/api/v1/query_range", &query)?)
+}
+
+fn series(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "match");
+    let mut query = vec![];
+    push_matchers(args, &mut query);
+    if query.is_empty() {
+        return Ok(error_result(
+            "Error: 'match' must contain at least one series selector".to_string(),
+        ));
+    }
+    push_time_range(args, &mut query);
+    json_result(&prometheus_get("/api/v1/series", &query)?)
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn read_only_annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+// Adds the `match`, `start` and `end` properties shared by the metadata tools.
+fn metadata_properties(properties: Value, match_description: &str) -> Value {
+    let mut properties = properties.as_object().cloned().unwrap_or_default();
+    properties.insert(
+        "match".to_string(),
+        json!({
+            "type": "array",
+            "items": {"type": "string"},
+            "description": match_description,
+        }),
+    );
+    properties.insert(
+        "start".to_string(),
+        json!({
+            "type": "string",
+            "description": "Start of the time range, as an RFC 3339 or Unix timestamp.",
+        }),
+    );
+    properties.insert(
+        "end".to_string(),
+        json!({
+            "type": "string",
+            "description": "End of the time range, as an RFC 3339 or Unix timestamp.",
+        }),
+    );
+    Value::Object(properties)
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Returns the firing and pending alerts of all alerting rules.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "include_rules": {
+                            "type": "boolean",
+                            "description": "Also return the alerting rule groups, including rules without active alerts. Defaults to false.",
+                        },
+                    }),
+                    &[],
+                ),
+                name: "prom_alert_rules".to_string(),
+                title: Some("Active Alerts".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Returns the values of a label, e.g. all values of 'job'.".to_string()),
+                input_schema: tool_schema(
+                    metadata_properties(
+                        json!({
+                            "label": {
+                                "type": "string",
+                                "description": "The label name.",
+                            },
+                        }),
+                        "Only return values of series matching these selectors, e.g. ['up{job=\"api\"}'].",
+                    ),
+                    &["label"],
+                ),
+                name: "prom_label_values".to_string(),
+                title: Some("Label Values".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Returns the label names.".to_string()),
+                input_schema: tool_schema(
+                    metadata_properties(
+                        json!({}),
+                        "Only return labels of series matching these selectors, e.g. ['up'].",
+                    ),
+                    &[],
+                ),
+                name: "prom_labels".to_string(),
+                title: Some("Labels".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Evaluates a PromQL expression at a single point in time.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "query": {
+                            "type": "string",
+                            "description": "The PromQL expression, e.g. 'sum(rate(http_requests_total[5m])) by (status)'.",
+                        },
+                        "time": {
+                            "type": "string",
+                            "description": "The evaluation time, as an RFC 3339 or Unix timestamp. Defaults to now.",
+                        },
+                    }),
+                    &["query"],
+                ),
+                name: "prom_query_instant".to_string(),
+                title: Some("Instant Query".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Evaluates a PromQL expression over a range of time.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "query": {
+                            "type": "string",
+                            "description": "The PromQL expression.",
+                        },
+                        "start": {
+                            "type": "string",
+                            "description": "Start of the range, as an RFC 3339 or Unix timestamp.",
+                        },
+                        "end": {
+                            "type": "string",
+                            "description": "End of the range, as an RFC 3339 or Unix timestamp.",
+                        },
+                        "step": {
+                            "type": "string",
+                            "description": "The resolution, as a duration like '1m' or a number of seconds.",
+                        },
+                    }),
+                    &["query", "start", "end", "step"],
+                ),
+                name: "prom_query_range".to_string(),
+                title: Some("Range Query".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: read_only_annotations(),
+                description: Some("Returns the series matching a set of selectors.".to_string()),
+                input_schema: tool_schema(
+                    metadata_properties(
+                        json!({}),
+                        "Series selectors, e.g. ['up', 'process_start_time_seconds{job=\"prometheus\"}'].",
+                    ),
+                    &["match"],
+                ),
+                name: "prom_series".to_string(),
+                title: Some("Series".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_response_data() {
+        assert_eq!(
+            response_data(
+                200,
+                br#"{"status": "success", "data": {"resultType": "vector", "result": []}}"#
+            )
+            .unwrap(),
+            json!({"resultType": "vector", "result": []})
+        );
+
+        let error = response_data(
+            400,
+            br#"{"status": "error", "errorType": "bad_data", "error": "parse error"}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Prometheus returned 400 (bad_data): parse error"
+        );
+
+        assert!(response_data(502, b"Bad Gateway").is_err());
+    }
+
+    #[test]
+    fn test_push_matchers() {
+        let args = json!({"match": ["up", "node_load1{instance=\"a\"}"]});
+        let mut query = vec![];
+        push_matchers(args.as_object().unwrap(), &mut query);
+        assert_eq!(
+            encode_query(&query),
+            "match%5B%5D=up&match%5B%5D=node_load1%7Binstance%3D%22a%22%7D"
+        );
+
+        let args = json!({"match": "up"});
+        let mut query = vec![];
+        push_matchers(args.as_object().unwrap(), &mut query);
+        assert_eq!(query, vec![("match[]", "up".to_string())]);
+    }
+
+    #[test]
+    fn test_active_alerts() {
+        let rules = json!({
+            "groups": [{
+                "name": "node",
+                "rules": [
+                    {
+                        "type": "alerting",
+                        "name": "HighLoad",
+                        "alerts": [
+                            {
+                                "state": "firing",
+                                "labels": {"instance": "a"},
+                                "annotations": {"summary": "High load"},
+                                "activeAt": "2024-01-15T10:30:00Z",
+                                "value": "4.2e+00",
+                            },
+                            {"state": "inactive", "labels": {"instance": "b"}},
+                        ],
+                    },
+                    {"type": "alerting", "name": "Quiet", "alerts": []},
+                    {"type": "recording", "name": "job:load:avg"},
+                ],
+            }],
+        });
+
+        assert_eq!(
+            active_alerts(&rules),
+            vec![json!({
+                "alertname": "HighLoad",
+                "group": "node",
+                "state": "firing",
+                "labels": {"instance": "a"},
+                "annotations": {"summary": "High load"},
+                "active_at": "2024-01-15T10:30:00Z",
+                "value": "4.2e+00",
+            })]
+        );
+        assert!(active_alerts(&json!({"groups": []})).is_empty());
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("prom_query_range", json!({"query": "up"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("prom_series", json!({"match": []})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "prom_alert_rules",
+                "prom_label_values",
+                "prom_labels",
+                "prom_query_instant",
+                "prom_query_range",
+                "prom_series",
+            ]
+        );
+    }
+}
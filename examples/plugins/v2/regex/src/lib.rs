@@ -0,0 +1,582 @@
+mod pdk;
+
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use extism_pdk::config;
+use pdk::types::*;
+use regex::{Captures, Regex};
+use serde_json::{json, Map, Value};
+
+// The most matches a tool returns or replaces, so huge results can't exhaust the
+// plugin's memory.
+const MAX_MATCHES: usize = 10_000;
+
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+
+// Returns how long a tool may spend matching, from `REGEX_TIMEOUT_MS` in the plugin's
+// `runtime_config.env_vars`.
+fn get_timeout() -> Result<Duration> {
+    let timeout_ms = match config::get("REGEX_TIMEOUT_MS")? {
+        Some(timeout_ms) => timeout_ms
+            .parse()
+            .map_err(|_| anyhow!("REGEX_TIMEOUT_MS must be a number of milliseconds"))?,
+        None => DEFAULT_TIMEOUT_MS,
+    };
+    Ok(Duration::from_millis(timeout_ms))
+}
+
+// Compiles `pattern`, reporting syntax errors with the line and column they are at.
+fn compile(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| match e {
+        regex::Error::Syntax(message) => syntax_error(pattern, &message),
+        regex::Error::CompiledTooBig(limit) => {
+            anyhow!("the pattern is too big, it compiles to more than {limit} bytes")
+        }
+        e => anyhow!(e),
+    })
+}
+
+// `regex` only reports syntax errors as a formatted message, so the pattern is parsed
+// again with `regex-syntax`, which `regex` uses, to get the position of the error.
+fn syntax_error(pattern: &str, message: &str) -> anyhow::Error {
+    let (span, kind) = match regex_syntax::Parser::new().parse(pattern) {
+        Err(regex_syntax::Error::Parse(e)) => (*e.span(), e.kind().to_string()),
+        Err(regex_syntax::Error::Translate(e)) => (*e.span(), e.kind().to_string()),
+        _ => return anyhow!("invalid regex '{pattern}': {message}"),
+    };
+    anyhow!(
+        "invalid regex '{pattern}' at line {}, column {}: {kind}",
+        span.start.line,
+        span.start.column
+    )
+}
+
+// Collects up to `MAX_MATCHES` items from `matches`, failing once `timeout` has
+// passed. Returns whether there were more matches.
+fn collect_matches<T>(
+    matches: impl Iterator<Item = T>,
+    timeout: Duration,
+) -> Result<(Vec<T>, bool)> {
+    let start = Instant::now();
+    let mut collected = Vec::new();
+    for item in matches {
+        if collected.len() == MAX_MATCHES {
+            return Ok((collected, true));
+        }
+        if start.elapsed() > timeout {
+            return Err(anyhow!(
+                "matching timed out after {} ms, with {} matches found",
+                timeout.as_millis(),
+                collected.len()
+            ));
+        }
+        collected.push(item);
+    }
+    Ok((collected, false))
+}
+
+fn match_json(captures: &Captures) -> Value {
+    let whole = captures.get(0).expect("group 0 is always the whole match");
+    let groups: Vec<Option<&str>> = captures
+        .iter()
+        .skip(1)
+        .map(|group| group.map(|group| group.as_str()))
+        .collect();
+    json!({
+        "match": whole.as_str(),
+        "start": whole.start(),
+        "end": whole.end(),
+        "groups": groups,
+    })
+}
+
+// Returns the named groups of the first match, with `null` for groups that didn't
+// participate in it.
+fn named_groups(regex: &Regex, text: &str) -> Option<Map<String, Value>> {
+    let captures = regex.captures(text)?;
+    Some(
+        regex
+            .capture_names()
+            .flatten()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    json!(captures.name(name).map(|group| group.as_str())),
+                )
+            })
+            .collect(),
+    )
+}
+
+// Replaces the first match of `regex`, or up to `MAX_MATCHES` of them, expanding `$1`
+// and `${name}` in `replacement`. Returns the new text and the number of replacements.
+fn replace(
+    regex: &Regex,
+    text: &str,
+    replacement: &str,
+    all: bool,
+    timeout: Duration,
+) -> Result<(String, usize)> {
+    let limit = if all { MAX_MATCHES } else { 1 };
+    let (matches, _) = collect_matches(regex.captures_iter(text).take(limit), timeout)?;
+
+    let mut replaced = String::with_capacity(text.len());
+    let mut last = 0;
+    for captures in &matches {
+        let whole = captures.get(0).expect("group 0 is always the whole match");
+        replaced.push_str(&text[last..whole.start()]);
+        captures.expand(replacement, &mut replaced);
+        last = whole.end();
+    }
+    replaced.push_str(&text[last..]);
+    Ok((replaced, matches.len()))
+}
+
+// Splits `text` on the matches of `regex`. After `MAX_MATCHES` splits the rest of the
+// text is the last part, and the returned flag is set.
+fn split<'t>(regex: &Regex, text: &'t str, timeout: Duration) -> Result<(Vec<&'t str>, bool)> {
+    let (matches, truncated) = collect_matches(regex.find_iter(text), timeout)?;
+
+    let mut parts = Vec::with_capacity(matches.len() + 1);
+    let mut last = 0;
+    for found in matches {
+        parts.push(&text[last..found.start()]);
+        last = found.end();
+    }
+    parts.push(&text[last..]);
+    Ok((parts, truncated))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "regex_find_all" => regex_find_all(&args),
+        "regex_match" => regex_match(&args),
+        "regex_named_groups" => regex_named_groups(&args),
+        "regex_replace" => regex_replace(&args),
+        "regex_split" => regex_split(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn regex_find_all(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "pattern", "text");
+    let regex = compile(get_required_string_arg(args, "pattern")?)?;
+    let text = get_required_string_arg(args, "text")?;
+
+    let (matches, truncated) = collect_matches(regex.captures_iter(text), get_timeout()?)?;
+    json_result(&json!({
+        "count": matches.len(),
+        "truncated": truncated,
+        "matches": matches.iter().map(match_json).collect::<Vec<_>>(),
+    }))
+}
+
+fn regex_match(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "pattern", "text");
+    let regex = compile(get_required_string_arg(args, "pattern")?)?;
+    let text = get_required_string_arg(args, "text")?;
+
+    let (matches, truncated) = collect_matches(regex.find_iter(text), get_timeout()?)?;
+    let positions: Vec<Value> = matches
+        .iter()
+        .map(|found| json!({"start": found.start(), "end": found.end()}))
+        .collect();
+    json_result(&json!({
+        "is_match": !positions.is_empty(),
+        "positions": positions,
+        "truncated": truncated,
+    }))
+}
+
+fn regex_named_groups(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "pattern", "text");
+    let regex = compile(get_required_string_arg(args, "pattern")?)?;
+    let text = get_required_string_arg(args, "text")?;
+
+    if regex.capture_names().flatten().next().is_none() {
+        return Err(anyhow!(
+            "the pattern has no named groups, add some like (?<year>\\d{{4}})"
+        ));
+    }
+    let groups = named_groups(&regex, text);
+    json_result(&json!({
+        "is_match": groups.is_some(),
+        "groups": groups,
+    }))
+}
+
+fn regex_replace(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "pattern", "text", "replacement");
+    let regex = compile(get_required_string_arg(args, "pattern")?)?;
+    let text = get_required_string_arg(args, "text")?;
+    let replacement = get_required_string_arg(args, "replacement")?;
+    let all = get_bool_arg(args, "all").unwrap_or(true);
+
+    let (replaced, replacements) = replace(&regex, text, replacement, all, get_timeout()?)?;
+    json_result(&json!({
+        "text": replaced,
+        "replacements": replacements,
+    }))
+}
+
+fn regex_split(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "pattern", "text");
+    let regex = compile(get_required_string_arg(args, "pattern")?)?;
+    let text = get_required_string_arg(args, "text")?;
+
+    let (parts, truncated) = split(&regex, text, get_timeout()?)?;
+    json_result(&json!({
+        "parts": parts,
+        "truncated": truncated,
+    }))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn pattern_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "The regular expression, in Rust regex syntax. Flags are set inline, like (?i) for case-insensitive matching or (?m) for ^ and $ to match at line breaks. Lookaround and backreferences are not supported.",
+    })
+}
+
+fn text_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "The text to search.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Finds all matches of a regular expression in a text, with their byte offsets and capture groups.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "pattern": pattern_property(),
+                        "text": text_property(),
+                    }),
+                    &["pattern", "text"],
+                ),
+                name: "regex_find_all".to_string(),
+                title: Some("Find All Matches".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Tests whether a regular expression matches a text, and returns the byte offsets of the matches.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "pattern": pattern_property(),
+                        "text": text_property(),
+                    }),
+                    &["pattern", "text"],
+                ),
+                name: "regex_match".to_string(),
+                title: Some("Match Regex".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Extracts the named capture groups, like (?<year>\\d{4}), of the first match of a regular expression in a text.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "pattern": pattern_property(),
+                        "text": text_property(),
+                    }),
+                    &["pattern", "text"],
+                ),
+                name: "regex_named_groups".to_string(),
+                title: Some("Extract Named Groups".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Replaces the matches of a regular expression in a text.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "all": {
+                            "type": "boolean",
+                            "description": "Whether to replace all matches rather than only the first. Defaults to true.",
+                        },
+                        "pattern": pattern_property(),
+                        "replacement": {
+                            "type": "string",
+                            "description": "The replacement, where $1 or ${name} insert capture groups and $$ inserts a literal $.",
+                        },
+                        "text": text_property(),
+                    }),
+                    &["pattern", "text", "replacement"],
+                ),
+                name: "regex_replace".to_string(),
+                title: Some("Replace Matches".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Splits a text on the matches of a regular expression.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "pattern": pattern_property(),
+                        "text": text_property(),
+                    }),
+                    &["pattern", "text"],
+                ),
+                name: "regex_split".to_string(),
+                title: Some("Split Text".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_compile_error_position() {
+        let error = compile("(?<year>\\d{4}-(\\d{2})").unwrap_err().to_string();
+        assert!(error.contains("line 1, column 1"), "{error}");
+        assert!(error.contains("unclosed group"), "{error}");
+
+        let error = compile("ab[c").unwrap_err().to_string();
+        assert!(error.contains("line 1, column 3"), "{error}");
+
+        let error = compile("a\\").unwrap_err().to_string();
+        assert!(error.contains("line 1"), "{error}");
+    }
+
+    #[test]
+    fn test_collect_matches() {
+        let regex = compile("a").unwrap();
+        let text = "a".repeat(MAX_MATCHES + 5);
+        let (matches, truncated) = collect_matches(regex.find_iter(&text), TIMEOUT).unwrap();
+        assert_eq!(matches.len(), MAX_MATCHES);
+        assert!(truncated);
+
+        let (matches, truncated) = collect_matches(regex.find_iter("banana"), TIMEOUT).unwrap();
+        assert_eq!(matches.len(), 3);
+        assert!(!truncated);
+
+        assert!(collect_matches(regex.find_iter(&text), Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_match_json() {
+        let regex = compile(r"(\w+)@(\w+)?\.com").unwrap();
+        let captures = regex.captures("mail: jane@.com").unwrap();
+        assert_eq!(
+            match_json(&captures),
+            json!({"match": "jane@.com", "start": 6, "end": 15, "groups": ["jane", null]})
+        );
+    }
+
+    #[test]
+    fn test_named_groups() {
+        let regex = compile(r"(?<year>\d{4})-(?<month>\d{2})(-(?<day>\d{2}))?").unwrap();
+        let groups = named_groups(&regex, "released 2024-03").unwrap();
+        assert_eq!(
+            Value::Object(groups),
+            json!({"year": "2024", "month": "03", "day": null})
+        );
+        assert!(named_groups(&regex, "no date").is_none());
+    }
+
+    #[test]
+    fn test_replace() {
+        let regex = compile(r"(?<first>\w+) (?<last>\w+)").unwrap();
+        assert_eq!(
+            replace(
+                &regex,
+                "Ada Lovelace, Alan Turing",
+                "${last} $first",
+                true,
+                TIMEOUT
+            )
+            .unwrap(),
+            ("Lovelace Ada, Turing Alan".to_string(), 2)
+        );
+        assert_eq!(
+            replace(&regex, "Ada Lovelace, Alan Turing", "$$", false, TIMEOUT).unwrap(),
+            ("$, Alan Turing".to_string(), 1)
+        );
+
+        let regex = compile("x").unwrap();
+        assert_eq!(
+            replace(&regex, "abc", "y", true, TIMEOUT).unwrap(),
+            ("abc".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let regex = compile(r"\s*[,;]\s*").unwrap();
+        assert_eq!(
+            split(&regex, "a, b ;c,", TIMEOUT).unwrap(),
+            (vec!["a", "b", "c", ""], false)
+        );
+
+        let regex = compile(",").unwrap();
+        let text = ",".repeat(MAX_MATCHES + 1) + "rest";
+        let (parts, truncated) = split(&regex, &text, TIMEOUT).unwrap();
+        assert_eq!(parts.len(), MAX_MATCHES + 1);
+        assert_eq!(parts[MAX_MATCHES], ",rest");
+        assert!(truncated);
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("regex_match", json!({"pattern": "a"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("regex_find_all", json!({"pattern": "(", "text": "a"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("regex_named_groups", json!({"pattern": "(a)", "text": "a"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "regex_find_all",
+                "regex_match",
+                "regex_named_groups",
+                "regex_replace",
+                "regex_split"
+            ]
+        );
+    }
+}
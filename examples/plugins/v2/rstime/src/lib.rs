@@ -2,6 +2,10 @@ mod pdk;
 
 use anyhow::{Result, anyhow};
 use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Months, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone,
+    Utc, Weekday,
+};
 use extism_pdk::{HttpRequest, Memory, http::request};
 use pdk::types::*;
 use serde_json::{Map, Value, json};
@@ -9,13 +13,11 @@ use serde_json::{Map, Value, json};
 pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
     match input.request.name.as_str() {
         "get_time" => {
-            let tz = match input
-                .request
-                .arguments
-                .as_ref()
-                .and_then(|args| args.get("timezone"))
-                .and_then(|v| v.as_str())
-            {
+            let args = input.request.arguments.clone().unwrap_or_default();
+            let timezone = get_string_arg(&args, "timezone")
+                .map(String::from)
+                .or_else(elicit_timezone);
+            let tz = match timezone {
                 Some(timezone) => match timezone.parse::<chrono_tz::Tz>() {
                     Ok(tz) => tz,
                     Err(e) => {
@@ -49,27 +51,9 @@ pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
             })
         }
         "parse_time" => {
-            let time_str = match input
-                .request
-                .arguments
-                .as_ref()
-                .and_then(|args| args.get("time"))
-                .and_then(|v| v.as_str())
-            {
-                Some(t) => t,
-                None => {
-                    return Ok(CallToolResult {
-                        content: vec![ContentBlock::Text(TextContent {
-                            text: "Error: 'time' argument is required".to_string(),
-
-                            ..Default::default()
-                        })],
-                        is_error: Some(true),
-
-                        ..Default::default()
-                    });
-                }
-            };
+            let args = input.request.arguments.clone().unwrap_or_default();
+            require_args!(args, "time");
+            let time_str = get_required_string_arg(&args, "time")?;
             match chrono::DateTime::parse_from_rfc2822(time_str) {
                 Ok(dt) => Ok(CallToolResult {
                     content: vec![ContentBlock::Text(TextContent {
@@ -96,10 +80,384 @@ pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
                 }),
             }
         }
+        "time_diff" => {
+            let args = input.request.arguments.clone().unwrap_or_default();
+            require_args!(args, "time1", "time2");
+            let time1 = match parse_time_arg(&args, "time1")? {
+                Ok(time) => time,
+                Err(error) => return Ok(error),
+            };
+            let time2 = match parse_time_arg(&args, "time2")? {
+                Ok(time) => time,
+                Err(error) => return Ok(error),
+            };
+            let diff = time_diff(time1.with_timezone(&Utc), time2.with_timezone(&Utc));
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent {
+                    text: diff.to_string(),
+
+                    ..Default::default()
+                })],
+                structured_content: diff.as_object().cloned(),
+
+                ..Default::default()
+            })
+        }
+        "time_add" => {
+            let args = input.request.arguments.clone().unwrap_or_default();
+            require_args!(args, "time", "duration");
+            let time = match parse_time_arg(&args, "time")? {
+                Ok(time) => time,
+                Err(error) => return Ok(error),
+            };
+            let result = duration_arg(&args)
+                .and_then(|duration| {
+                    time.checked_add_signed(duration)
+                        .ok_or_else(|| "the resulting time is out of range".to_string())
+                })
+                .map(|time| time.to_rfc2822());
+            match result {
+                Ok(time) => Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent {
+                        text: time.clone(),
+
+                        ..Default::default()
+                    })],
+                    structured_content: Some(Map::from_iter([(
+                        "time".to_string(),
+                        Value::String(time),
+                    )])),
+
+                    ..Default::default()
+                }),
+                Err(e) => Ok(error_result(format!("Error: Invalid duration: {}", e))),
+            }
+        }
+        "time_convert_timezone" => {
+            let args = input.request.arguments.clone().unwrap_or_default();
+            require_args!(args, "time", "to_timezone");
+            let to_timezone = get_required_string_arg(&args, "to_timezone")?;
+            let to_tz = match to_timezone.parse::<chrono_tz::Tz>() {
+                Ok(tz) => tz,
+                Err(e) => {
+                    return Ok(error_result(format!(
+                        "Error: Invalid timezone '{}': {}",
+                        to_timezone, e
+                    )));
+                }
+            };
+            let time = match local_time_arg(&args)? {
+                Ok(time) => time,
+                Err(error) => return Ok(error),
+            };
+            let converted = time.with_timezone(&to_tz).to_rfc2822();
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent {
+                    text: converted.clone(),
+
+                    ..Default::default()
+                })],
+                structured_content: Some(Map::from_iter([
+                    ("time".to_string(), Value::String(converted)),
+                    (
+                        "timezone".to_string(),
+                        Value::String(to_tz.name().to_string()),
+                    ),
+                ])),
+
+                ..Default::default()
+            })
+        }
+        "business_hours_check" => {
+            let args = input.request.arguments.clone().unwrap_or_default();
+            require_args!(args, "timezone", "schedule");
+            let timezone = get_required_string_arg(&args, "timezone")?;
+            let tz = match timezone.parse::<chrono_tz::Tz>() {
+                Ok(tz) => tz,
+                Err(e) => {
+                    return Ok(error_result(format!(
+                        "Error: Invalid timezone '{}': {}",
+                        timezone, e
+                    )));
+                }
+            };
+            let check_time = match get_string_arg(&args, "check_time") {
+                Some(_) => match parse_time_arg(&args, "check_time")? {
+                    Ok(time) => time.with_timezone(&Utc),
+                    Err(error) => return Ok(error),
+                },
+                None => Utc::now(),
+            };
+            let schedule = match schedule_arg(&args) {
+                Ok(schedule) => schedule,
+                Err(e) => return Ok(error_result(format!("Error: Invalid schedule: {}", e))),
+            };
+            let result = business_hours(tz, check_time, &schedule);
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent {
+                    text: result.to_string(),
+
+                    ..Default::default()
+                })],
+                structured_content: result.as_object().cloned(),
+
+                ..Default::default()
+            })
+        }
         _ => Err(anyhow!("Unknown tool: {}", input.request.name)),
     }
 }
 
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+// Parses the RFC2822 argument `name`. An unparsable time is returned as an error result
+// for the tool, while a missing or non-string argument fails the call.
+fn parse_time_arg(
+    args: &Map<String, Value>,
+    name: &str,
+) -> Result<std::result::Result<DateTime<FixedOffset>, CallToolResult>> {
+    let time = get_required_string_arg(args, name)?;
+    Ok(DateTime::parse_from_rfc2822(time)
+        .map_err(|e| error_result(format!("Error parsing {}: {}", name, e))))
+}
+
+// Parses the `time` argument of time_convert_timezone, either an RFC2822 time or a
+// wall-clock time such as `2024-01-15 10:30` in `from_timezone`.
+fn local_time_arg(
+    args: &Map<String, Value>,
+) -> Result<std::result::Result<DateTime<FixedOffset>, CallToolResult>> {
+    let time = get_required_string_arg(args, "time")?;
+    if let Ok(time) = DateTime::parse_from_rfc2822(time) {
+        return Ok(Ok(time));
+    }
+
+    let Some(naive) = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(time, format).ok()) else {
+        return Ok(Err(error_result(format!(
+            "Error parsing time: '{}' is neither RFC2822 nor 'YYYY-MM-DD HH:MM[:SS]'",
+            time
+        ))));
+    };
+    let Some(from_timezone) = get_string_arg(args, "from_timezone") else {
+        return Ok(Err(error_result(
+            "Error: from_timezone is required when the time has no UTC offset".to_string(),
+        )));
+    };
+    let from_tz = match from_timezone.parse::<chrono_tz::Tz>() {
+        Ok(tz) => tz,
+        Err(e) => {
+            return Ok(Err(error_result(format!(
+                "Error: Invalid timezone '{}': {}",
+                from_timezone, e
+            ))));
+        }
+    };
+    // Ambiguous times, when clocks go back, resolve to the earlier one
+    Ok(from_tz
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.fixed_offset())
+        .ok_or_else(|| {
+            error_result(format!(
+                "Error: {} does not exist in {}, as clocks go forward",
+                time, from_timezone
+            ))
+        }))
+}
+
+// Reads the `duration` object of time_add as a TimeDelta. Each of days, hours, minutes
+// and seconds is an optional integer and may be negative.
+fn duration_arg(args: &Map<String, Value>) -> std::result::Result<TimeDelta, String> {
+    let duration = args
+        .get("duration")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "duration must be an object".to_string())?;
+    let mut total: i64 = 0;
+    for (unit, seconds) in [
+        ("days", 86400),
+        ("hours", 3600),
+        ("minutes", 60),
+        ("seconds", 1),
+    ] {
+        let value = match duration.get(unit) {
+            None | Some(Value::Null) => 0,
+            Some(value) => value
+                .as_i64()
+                .ok_or_else(|| format!("{} must be an integer", unit))?,
+        };
+        total = value
+            .checked_mul(seconds)
+            .and_then(|value| total.checked_add(value))
+            .ok_or_else(|| "duration is out of range".to_string())?;
+    }
+    TimeDelta::try_seconds(total).ok_or_else(|| "duration is out of range".to_string())
+}
+
+// Reads the `schedule` object of business_hours_check, mapping day names to their
+// opening hours. Days that are null or missing are closed.
+fn schedule_arg(
+    args: &Map<String, Value>,
+) -> std::result::Result<std::collections::HashMap<Weekday, (NaiveTime, NaiveTime)>, String> {
+    let schedule = args
+        .get("schedule")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "schedule must be an object".to_string())?;
+    let mut hours = std::collections::HashMap::new();
+    for (day, value) in schedule {
+        let weekday = day
+            .parse::<Weekday>()
+            .map_err(|_| format!("'{}' is not a day of the week", day))?;
+        if value.is_null() {
+            continue;
+        }
+        let time = |name: &str| {
+            value
+                .get(name)
+                .and_then(Value::as_str)
+                .and_then(|time| NaiveTime::parse_from_str(time, "%H:%M").ok())
+                .ok_or_else(|| format!("{}.{} must be a time as HH:MM", day, name))
+        };
+        hours.insert(weekday, (time("start")?, time("end")?));
+    }
+    Ok(hours)
+}
+
+// Returns whether `now` is within the opening hours of `schedule` in `tz`, and when it
+// next opens otherwise. Hours whose end is not after their start close the next day.
+fn business_hours(
+    tz: chrono_tz::Tz,
+    now: DateTime<Utc>,
+    schedule: &std::collections::HashMap<Weekday, (NaiveTime, NaiveTime)>,
+) -> Value {
+    // Wall-clock times skipped when clocks go forward fall after the transition
+    let to_utc = |local: NaiveDateTime| match tz.from_local_datetime(&local).earliest() {
+        Some(time) => time.with_timezone(&Utc),
+        None => Utc.from_utc_datetime(&(local - tz.offset_from_utc_datetime(&local).fix())),
+    };
+
+    let today = now.with_timezone(&tz).date_naive();
+    // Yesterday's hours may run past midnight, and the same weekday next week is the
+    // last chance to open
+    for offset in -1..=7 {
+        let date = today + TimeDelta::days(offset);
+        let Some(&(start, end)) = schedule.get(&date.weekday()) else {
+            continue;
+        };
+        let opens = to_utc(date.and_time(start));
+        let closes = if end > start {
+            to_utc(date.and_time(end))
+        } else {
+            to_utc((date + TimeDelta::days(1)).and_time(end))
+        };
+
+        if opens <= now && now < closes {
+            return json!({
+                "is_business_hours": true,
+                "next_open": now.with_timezone(&tz).to_rfc2822(),
+                "time_until_open_secs": 0,
+            });
+        }
+        if opens > now {
+            return json!({
+                "is_business_hours": false,
+                "next_open": opens.with_timezone(&tz).to_rfc2822(),
+                "time_until_open_secs": (opens - now).num_seconds(),
+            });
+        }
+    }
+
+    json!({
+        "is_business_hours": false,
+        "next_open": null,
+        "time_until_open_secs": null,
+    })
+}
+
+// Calendar difference from `from` to `to`, negative when `to` is earlier. Whole months
+// are counted first, so 31 Jan to 1 Mar is 1 month and 1 day (in a non-leap year).
+fn time_diff(from: DateTime<Utc>, to: DateTime<Utc>) -> Value {
+    let total_seconds = (to - from).num_seconds();
+    let (start, end, sign) = if to >= from {
+        (from, to, 1)
+    } else {
+        (to, from, -1)
+    };
+
+    let mut months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    let anchor = loop {
+        match start.checked_add_months(Months::new(months as u32)) {
+            Some(anchor) if anchor <= end => break anchor,
+            _ => months -= 1,
+        }
+    };
+    let rest = end - anchor;
+
+    json!({
+        "years": sign * (months / 12) as i64,
+        "months": sign * (months % 12) as i64,
+        "days": sign * rest.num_days(),
+        "hours": sign * (rest.num_hours() % 24),
+        "minutes": sign * (rest.num_minutes() % 60),
+        "seconds": sign * (rest.num_seconds() % 60),
+        "total_seconds": total_seconds,
+    })
+}
+
+// Ask the user which timezone they want when get_time is called without one.
+//
+// Returns None when the client does not support elicitation or the user declines, in which case UTC is used.
+#[cfg(target_arch = "wasm32")]
+fn elicit_timezone() -> Option<String> {
+    let result = pdk::imports::create_elicitation(ElicitRequestParamWithTimeout {
+        message: "Which timezone would you like?".to_string(),
+        requested_schema: Schema {
+            properties: std::collections::HashMap::from([(
+                "timezone".to_string(),
+                PrimitiveSchemaDefinition::String(StringSchema {
+                    description: Some(
+                        "The timezone to get the current time for, e.g. 'America/New_York'"
+                            .to_string(),
+                    ),
+
+                    ..Default::default()
+                }),
+            )]),
+            required: Some(vec!["timezone".to_string()]),
+
+            ..Default::default()
+        },
+        timeout: None,
+    })
+    .ok()?;
+    match (result.action, result.content?.remove("timezone")?) {
+        (ElicitResultAction::Accept, ElicitResultContentValue::String(timezone)) => Some(timezone),
+        _ => None,
+    }
+}
+
+// Host functions cannot be linked into native test builds, so tests always fall back to UTC.
+#[cfg(not(target_arch = "wasm32"))]
+fn elicit_timezone() -> Option<String> {
+    None
+}
+
 // Provide completion suggestions for a partially-typed input.
 //
 // This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
@@ -122,6 +480,13 @@ pub(crate) fn complete(input: CompleteRequest) -> Result<CompleteResult> {
             ));
         }
 
+        Reference::Tool(tool_ref) if tool_ref.name.as_str() != "get_time" => {
+            return Err(anyhow!(
+                "Completion for tool not implemented: {}",
+                tool_ref.name
+            ));
+        }
+
         _ => {}
     };
 
@@ -227,6 +592,8 @@ pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResu
                 ..Default::default()
             }]),
         }],
+
+        ..Default::default()
     })
 }
 
@@ -265,13 +632,20 @@ pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
     Ok(ListToolsResult {
         tools: vec![
             Tool {
-                annotations: None,
-                description: Some("Returns the current time in the specified timezone. If no timezone is specified then UTC is used.".to_string()),
+                annotations: Some(ToolAnnotations {
+                    destructive_hint: Some(false),
+                    idempotent_hint: Some(true),
+                    open_world_hint: Some(false),
+                    read_only_hint: Some(true),
+
+                    ..Default::default()
+                }),
+                description: Some("Returns the current time in the specified timezone. If no timezone is specified the user is asked for one, falling back to UTC.".to_string()),
                 input_schema: ToolSchema {
                     properties: Some(Map::from_iter([
                         ("timezone".to_string(), json!({
                             "type": "string",
-                            "description": "The timezone to get the current time for, e.g. 'America/New_York'. Defaults to 'UTC' if not provided.",
+                            "description": "The timezone to get the current time for, e.g. 'America/New_York'. If not provided the user is asked which timezone they want, defaulting to 'UTC' if they decline.",
                         })),
                     ])),
 
@@ -292,7 +666,14 @@ pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
                 title: Some("Get Current Time".to_string()),
             },
             Tool {
-                annotations: None,
+                annotations: Some(ToolAnnotations {
+                    destructive_hint: Some(false),
+                    idempotent_hint: Some(true),
+                    open_world_hint: Some(false),
+                    read_only_hint: Some(true),
+
+                    ..Default::default()
+                }),
                 description: Some("Parses a time string in RFC2822 format and returns the corresponding timestamp in UTC.".to_string()),
                 input_schema: ToolSchema {
                     properties: Some(Map::from_iter([
@@ -318,8 +699,206 @@ pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
                     ..Default::default()
                 }),
                 title: Some("Parse Time from RFC2822".to_string()),
-            }
+            },
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    destructive_hint: Some(false),
+                    idempotent_hint: Some(true),
+                    open_world_hint: Some(false),
+                    read_only_hint: Some(true),
+
+                    ..Default::default()
+                }),
+                description: Some("Returns the difference from time1 to time2, both in RFC2822 format, in years, months, days, hours, minutes and seconds, and in total seconds. The values are negative when time2 is earlier.".to_string()),
+                input_schema: ToolSchema {
+                    properties: Some(Map::from_iter([
+                        ("time1".to_string(), json!({
+                            "type": "string",
+                            "description": "The start time in RFC2822 format.",
+                        })),
+                        ("time2".to_string(), json!({
+                            "type": "string",
+                            "description": "The end time in RFC2822 format.",
+                        })),
+                    ])),
+                    required: Some(vec!["time1".to_string(), "time2".to_string()]),
+
+                    ..Default::default()
+                },
+                name: "time_diff".to_string(),
+                output_schema: Some(ToolSchema {
+                    properties: Some(Map::from_iter(
+                        ["years", "months", "days", "hours", "minutes", "seconds", "total_seconds"]
+                            .map(|unit| (unit.to_string(), json!({ "type": "integer" }))),
+                    )),
+                    required: Some(
+                        ["years", "months", "days", "hours", "minutes", "seconds", "total_seconds"]
+                            .map(String::from)
+                            .to_vec(),
+                    ),
+
+                    ..Default::default()
+                }),
+                title: Some("Time Difference".to_string()),
+            },
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    destructive_hint: Some(false),
+                    idempotent_hint: Some(true),
+                    open_world_hint: Some(false),
+                    read_only_hint: Some(true),
+
+                    ..Default::default()
+                }),
+                description: Some("Adds a duration to a time in RFC2822 format and returns the resulting time in RFC2822 format, with the same UTC offset.".to_string()),
+                input_schema: ToolSchema {
+                    properties: Some(Map::from_iter([
+                        ("time".to_string(), json!({
+                            "type": "string",
+                            "description": "The time in RFC2822 format.",
+                        })),
+                        ("duration".to_string(), json!({
+                            "type": "object",
+                            "description": "The duration to add. Each unit is optional and negative values subtract.",
+                            "properties": {
+                                "days": { "type": "integer" },
+                                "hours": { "type": "integer" },
+                                "minutes": { "type": "integer" },
+                                "seconds": { "type": "integer" },
+                            },
+                        })),
+                    ])),
+                    required: Some(vec!["time".to_string(), "duration".to_string()]),
+
+                    ..Default::default()
+                },
+                name: "time_add".to_string(),
+                output_schema: Some(ToolSchema {
+                    properties: Some(Map::from_iter([
+                        ("time".to_string(), json!({
+                            "type": "string",
+                            "description": "The resulting time in RFC2822 format.",
+                        })),
+                    ])),
+                    required: Some(vec!["time".to_string()]),
+
+                    ..Default::default()
+                }),
+                title: Some("Add Duration to Time".to_string()),
+            },
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    destructive_hint: Some(false),
+                    idempotent_hint: Some(true),
+                    open_world_hint: Some(false),
+                    read_only_hint: Some(true),
+
+                    ..Default::default()
+                }),
+                description: Some("Converts a time to another timezone and returns it in RFC2822 format. The time is either in RFC2822 format or a wall-clock time such as '2024-01-15 10:30' in from_timezone.".to_string()),
+                input_schema: ToolSchema {
+                    properties: Some(Map::from_iter([
+                        ("time".to_string(), json!({
+                            "type": "string",
+                            "description": "The time to convert, in RFC2822 format or as 'YYYY-MM-DD HH:MM[:SS]'.",
+                        })),
+                        ("from_timezone".to_string(), json!({
+                            "type": "string",
+                            "description": "The timezone of a time without UTC offset, e.g. 'America/New_York'. Required unless the time is in RFC2822 format.",
+                        })),
+                        ("to_timezone".to_string(), json!({
+                            "type": "string",
+                            "description": "The timezone to convert to, e.g. 'Asia/Tokyo'.",
+                        })),
+                    ])),
+                    required: Some(vec!["time".to_string(), "to_timezone".to_string()]),
+
+                    ..Default::default()
+                },
+                name: "time_convert_timezone".to_string(),
+                output_schema: Some(ToolSchema {
+                    properties: Some(Map::from_iter([
+                        ("time".to_string(), json!({
+                            "type": "string",
+                            "description": "The converted time in RFC2822 format.",
+                        })),
+                        ("timezone".to_string(), json!({
+                            "type": "string",
+                            "description": "The timezone converted to.",
+                        })),
+                    ])),
+                    required: Some(vec!["time".to_string(), "timezone".to_string()]),
+
+                    ..Default::default()
+                }),
+                title: Some("Convert Timezone".to_string()),
+            },
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    destructive_hint: Some(false),
+                    idempotent_hint: Some(false),
+                    open_world_hint: Some(false),
+                    read_only_hint: Some(true),
+
+                    ..Default::default()
+                }),
+                description: Some("Checks whether a time, by default now, is within business hours given a weekly schedule in a timezone, and when business next opens otherwise.".to_string()),
+                input_schema: ToolSchema {
+                    properties: Some(Map::from_iter([
+                        ("timezone".to_string(), json!({
+                            "type": "string",
+                            "description": "The timezone of the schedule, e.g. 'America/New_York'.",
+                        })),
+                        ("check_time".to_string(), json!({
+                            "type": "string",
+                            "description": "The time to check in RFC2822 format. Defaults to now.",
+                        })),
+                        ("schedule".to_string(), json!({
+                            "type": "object",
+                            "description": "The opening hours of each day of the week, keyed by day name such as 'monday', as {\"start\": \"HH:MM\", \"end\": \"HH:MM\"}, or null when closed. Missing days are closed. Hours whose end is not after their start close the next day.",
+                            "additionalProperties": {
+                                "type": ["object", "null"],
+                                "properties": {
+                                    "start": { "type": "string" },
+                                    "end": { "type": "string" },
+                                },
+                                "required": ["start", "end"],
+                            },
+                        })),
+                    ])),
+                    required: Some(vec!["timezone".to_string(), "schedule".to_string()]),
+
+                    ..Default::default()
+                },
+                name: "business_hours_check".to_string(),
+                output_schema: Some(ToolSchema {
+                    properties: Some(Map::from_iter([
+                        ("is_business_hours".to_string(), json!({
+                            "type": "boolean",
+                            "description": "Whether the time is within business hours.",
+                        })),
+                        ("next_open".to_string(), json!({
+                            "type": ["string", "null"],
+                            "description": "When business next opens in RFC2822 format, the checked time itself during business hours, or null if the schedule has no opening hours.",
+                        })),
+                        ("time_until_open_secs".to_string(), json!({
+                            "type": ["integer", "null"],
+                            "description": "Seconds until next_open.",
+                        })),
+                    ])),
+                    required: Some(vec![
+                        "is_business_hours".to_string(),
+                        "next_open".to_string(),
+                        "time_until_open_secs".to_string(),
+                    ]),
+
+                    ..Default::default()
+                }),
+                title: Some("Check Business Hours".to_string()),
+            },
         ],
+
+        ..Default::default()
     })
 }
 
@@ -497,6 +1076,241 @@ mod tests {
         assert!(result.is_error == Some(true));
     }
 
+    fn call(name: &str, args: Value) -> CallToolResult {
+        let input = CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: args.as_object().cloned(),
+            },
+        };
+        call_tool(input).expect("call_tool should succeed")
+    }
+
+    #[test]
+    fn test_call_tool_time_diff() {
+        let result = call(
+            "time_diff",
+            json!({
+                "time1": "15 Jan 2024 10:00:00 +0000",
+                "time2": "18 Mar 2025 13:30:15 +0100",
+            }),
+        );
+        assert!(result.is_error.is_none());
+        assert_eq!(
+            Value::Object(result.structured_content.unwrap()),
+            json!({
+                "years": 1,
+                "months": 2,
+                "days": 3,
+                "hours": 2,
+                "minutes": 30,
+                "seconds": 15,
+                "total_seconds": 36988215,
+            })
+        );
+
+        let result = call(
+            "time_diff",
+            json!({
+                "time1": "18 Mar 2025 12:30:15 +0000",
+                "time2": "15 Jan 2024 10:00:00 +0000",
+            }),
+        );
+        let diff = result.structured_content.unwrap();
+        assert_eq!(diff["years"], -1);
+        assert_eq!(diff["days"], -3);
+        assert_eq!(diff["total_seconds"], -36988215);
+
+        let result = call(
+            "time_diff",
+            json!({ "time1": "not a time", "time2": "15 Jan 2024 10:00:00 +0000" }),
+        );
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_time_diff_end_of_month() {
+        let from = DateTime::parse_from_rfc2822("31 Jan 2025 00:00:00 +0000").unwrap();
+        let to = DateTime::parse_from_rfc2822("1 Mar 2025 00:00:00 +0000").unwrap();
+        let diff = time_diff(from.with_timezone(&Utc), to.with_timezone(&Utc));
+        assert_eq!(diff["months"], 1);
+        assert_eq!(diff["days"], 1);
+    }
+
+    #[test]
+    fn test_call_tool_time_add() {
+        let result = call(
+            "time_add",
+            json!({
+                "time": "15 Jan 2024 10:00:00 +0200",
+                "duration": { "days": 1, "hours": 2, "minutes": 30 },
+            }),
+        );
+        assert!(result.is_error.is_none());
+        assert_eq!(
+            result.structured_content.unwrap()["time"],
+            "Tue, 16 Jan 2024 12:30:00 +0200"
+        );
+
+        let result = call(
+            "time_add",
+            json!({
+                "time": "15 Jan 2024 10:00:00 +0000",
+                "duration": { "seconds": -60 },
+            }),
+        );
+        assert_eq!(
+            result.structured_content.unwrap()["time"],
+            "Mon, 15 Jan 2024 09:59:00 +0000"
+        );
+
+        let result = call(
+            "time_add",
+            json!({ "time": "15 Jan 2024 10:00:00 +0000", "duration": { "days": "one" } }),
+        );
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_time_convert_timezone() {
+        let result = call(
+            "time_convert_timezone",
+            json!({ "time": "15 Jan 2024 10:00:00 +0000", "to_timezone": "Asia/Tokyo" }),
+        );
+        assert!(result.is_error.is_none());
+        let converted = result.structured_content.unwrap();
+        assert_eq!(converted["time"], "Mon, 15 Jan 2024 19:00:00 +0900");
+        assert_eq!(converted["timezone"], "Asia/Tokyo");
+
+        let result = call(
+            "time_convert_timezone",
+            json!({
+                "time": "2024-07-15 10:00",
+                "from_timezone": "America/New_York",
+                "to_timezone": "UTC",
+            }),
+        );
+        assert_eq!(
+            result.structured_content.unwrap()["time"],
+            "Mon, 15 Jul 2024 14:00:00 +0000"
+        );
+
+        // The wall-clock time needs a timezone
+        let result = call(
+            "time_convert_timezone",
+            json!({ "time": "2024-07-15 10:00", "to_timezone": "UTC" }),
+        );
+        assert_eq!(result.is_error, Some(true));
+
+        // 02:30 is skipped when clocks go forward
+        let result = call(
+            "time_convert_timezone",
+            json!({
+                "time": "2024-03-10 02:30",
+                "from_timezone": "America/New_York",
+                "to_timezone": "UTC",
+            }),
+        );
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_business_hours_check() {
+        let weekdays = json!({
+            "monday": { "start": "09:00", "end": "17:00" },
+            "tuesday": { "start": "09:00", "end": "17:00" },
+            "wednesday": { "start": "09:00", "end": "17:00" },
+            "thursday": { "start": "09:00", "end": "17:00" },
+            "friday": { "start": "09:00", "end": "17:00" },
+            "saturday": null,
+        });
+
+        // Monday 10:00 in New York
+        let result = call(
+            "business_hours_check",
+            json!({
+                "timezone": "America/New_York",
+                "check_time": "15 Jan 2024 15:00:00 +0000",
+                "schedule": weekdays,
+            }),
+        );
+        let check = result.structured_content.unwrap();
+        assert_eq!(check["is_business_hours"], true);
+        assert_eq!(check["time_until_open_secs"], 0);
+
+        // Friday 18:00 in New York opens again on Monday
+        let result = call(
+            "business_hours_check",
+            json!({
+                "timezone": "America/New_York",
+                "check_time": "19 Jan 2024 23:00:00 +0000",
+                "schedule": weekdays,
+            }),
+        );
+        let check = result.structured_content.unwrap();
+        assert_eq!(check["is_business_hours"], false);
+        assert_eq!(check["next_open"], "Mon, 22 Jan 2024 09:00:00 -0500");
+        assert_eq!(check["time_until_open_secs"], 63 * 3600);
+    }
+
+    #[test]
+    fn test_call_tool_business_hours_check_overnight() {
+        // Sunday 01:00 in New York, during Saturday's night shift
+        let result = call(
+            "business_hours_check",
+            json!({
+                "timezone": "America/New_York",
+                "check_time": "21 Jan 2024 06:00:00 +0000",
+                "schedule": { "sat": { "start": "22:00", "end": "02:00" } },
+            }),
+        );
+        assert_eq!(
+            result.structured_content.unwrap()["is_business_hours"],
+            true
+        );
+
+        let result = call(
+            "business_hours_check",
+            json!({ "timezone": "UTC", "schedule": {} }),
+        );
+        let check = result.structured_content.unwrap();
+        assert_eq!(check["is_business_hours"], false);
+        assert!(check["next_open"].is_null());
+    }
+
+    #[test]
+    fn test_call_tool_business_hours_check_invalid_schedule() {
+        let result = call(
+            "business_hours_check",
+            json!({ "timezone": "UTC", "schedule": { "someday": null } }),
+        );
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "business_hours_check",
+            json!({ "timezone": "UTC", "schedule": { "monday": { "start": "9am", "end": "5pm" } } }),
+        );
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_argument_helpers() {
+        let mut args = Map::new();
+        args.insert("name".to_string(), Value::String("utc".to_string()));
+        args.insert("count".to_string(), json!(3));
+        args.insert("enabled".to_string(), Value::Bool(true));
+        args.insert("items".to_string(), json!(["a", "b"]));
+
+        assert_eq!(get_string_arg(&args, "name"), Some("utc"));
+        assert_eq!(get_string_arg(&args, "count"), None);
+        assert_eq!(get_int_arg(&args, "count"), Some(3));
+        assert_eq!(get_bool_arg(&args, "enabled"), Some(true));
+        assert_eq!(get_array_arg(&args, "items").map(Vec::len), Some(2));
+        assert_eq!(get_required_string_arg(&args, "name").unwrap(), "utc");
+        assert!(get_required_string_arg(&args, "missing").is_err());
+    }
+
     #[test]
     fn test_call_tool_unknown_tool() {
         let input = CallToolRequest {
@@ -538,6 +1352,44 @@ mod tests {
         assert!(result.completion.total.is_some());
     }
 
+    #[test]
+    fn test_complete_tool_argument() {
+        // Test complete function for the timezone argument of the get_time tool
+        let input = CompleteRequest {
+            context: PluginRequestContext::default(),
+            request: CompleteRequestParam {
+                r#ref: Reference::Tool(ToolReference {
+                    name: "get_time".to_string(),
+                    r#type: ToolReferenceType::Tool,
+                }),
+                argument: CompleteRequestParamArgument {
+                    name: "timezone".to_string(),
+                    value: "utc".to_string(),
+                },
+                context: None,
+            },
+        };
+
+        let result = complete(input).expect("complete should succeed");
+        assert!(result.completion.values.contains(&"UTC".to_string()));
+
+        let input = CompleteRequest {
+            context: PluginRequestContext::default(),
+            request: CompleteRequestParam {
+                r#ref: Reference::Tool(ToolReference {
+                    name: "other_tool".to_string(),
+                    r#type: ToolReferenceType::Tool,
+                }),
+                argument: CompleteRequestParamArgument {
+                    name: "timezone".to_string(),
+                    value: "utc".to_string(),
+                },
+                context: None,
+            },
+        };
+        assert!(complete(input).is_err());
+    }
+
     #[test]
     fn test_complete_with_america_query() {
         // Test complete function with America timezone prefix
@@ -855,9 +1707,13 @@ mod tests {
         let input = ListToolsRequest::default();
         let result = list_tools(input).expect("list_tools should succeed");
 
-        assert_eq!(result.tools.len(), 2);
+        assert_eq!(result.tools.len(), 6);
         assert_eq!(result.tools[0].name, "get_time");
         assert_eq!(result.tools[1].name, "parse_time");
+        assert_eq!(result.tools[2].name, "time_diff");
+        assert_eq!(result.tools[3].name, "time_add");
+        assert_eq!(result.tools[4].name, "time_convert_timezone");
+        assert_eq!(result.tools[5].name, "business_hours_check");
 
         assert!(result.tools[0].description.is_some());
         assert!(result.tools[0].input_schema.properties.is_some());
@@ -866,6 +1722,15 @@ mod tests {
         assert!(result.tools[1].description.is_some());
         assert!(result.tools[1].input_schema.properties.is_some());
         assert!(result.tools[1].output_schema.is_some());
+
+        for tool in &result.tools {
+            let annotations = tool
+                .annotations
+                .as_ref()
+                .expect("tool should have annotations");
+            assert_eq!(annotations.read_only_hint, Some(true));
+            assert_eq!(annotations.destructive_hint, Some(false));
+        }
     }
 
     #[test]
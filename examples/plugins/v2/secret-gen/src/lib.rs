@@ -0,0 +1,670 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use chrono::Utc;
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+const DIGITS: &str = "0123456789";
+
+const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+const DEFAULT_PASSWORD_LENGTH: i64 = 20;
+
+const MAX_PASSWORD_LENGTH: i64 = 256;
+
+const MAX_UUID_COUNT: i64 = 1000;
+
+const DEFAULT_TOKEN_BYTES: i64 = 32;
+
+const MAX_TOKEN_BYTES: i64 = 1024;
+
+const ENCODINGS: [&str; 4] = ["hex", "base64", "base64url", "base58"];
+
+// Entropy comes from the host through WASI's `random_get`, which Extism provides.
+fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow!("failed to get random bytes: {e}"))?;
+    Ok(bytes)
+}
+
+// Returns a uniformly random number below `n`, rejecting the values that would make
+// `% n` favor small numbers.
+fn random_below(n: usize) -> Result<usize> {
+    let n = n as u32;
+    let limit = u32::MAX - u32::MAX % n;
+    loop {
+        let value = u32::from_le_bytes(random_bytes()?);
+        if value < limit {
+            return Ok((value % n) as usize);
+        }
+    }
+}
+
+fn random_char(chars: &[char]) -> Result<char> {
+    Ok(chars[random_below(chars.len())?])
+}
+
+// Generates a password with at least one character of each class, shuffled so their
+// positions are random too.
+fn generate(classes: &[Vec<char>], length: usize) -> Result<String> {
+    let all: Vec<char> = classes.iter().flatten().copied().collect();
+    let mut password = classes
+        .iter()
+        .map(|class| random_char(class))
+        .collect::<Result<Vec<_>>>()?;
+    while password.len() < length {
+        password.push(random_char(&all)?);
+    }
+    // Fisher-Yates shuffle
+    for i in (1..password.len()).rev() {
+        password.swap(i, random_below(i + 1)?);
+    }
+    Ok(password.into_iter().collect())
+}
+
+fn format_uuid(bytes: [u8; 16]) -> String {
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+// Sets the version and RFC 9562 variant bits.
+fn set_version(mut bytes: [u8; 16], version: u8) -> [u8; 16] {
+    bytes[6] = (bytes[6] & 0x0f) | (version << 4);
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes
+}
+
+fn uuid_v4(random: [u8; 16]) -> String {
+    format_uuid(set_version(random, 4))
+}
+
+// Lays out a UUIDv7: a 48-bit Unix timestamp in milliseconds, then a 12-bit counter that
+// orders the UUIDs generated in the same millisecond, then random bits.
+fn uuid_v7(timestamp_ms: u64, counter: u16, random: [u8; 8]) -> String {
+    let mut bytes = [0; 16];
+    bytes[..6].copy_from_slice(&timestamp_ms.to_be_bytes()[2..]);
+    bytes[6..8].copy_from_slice(&(counter & 0x0fff).to_be_bytes());
+    bytes[8..].copy_from_slice(&random);
+    format_uuid(set_version(bytes, 7))
+}
+
+fn uuids_v7(count: usize, mut timestamp_ms: u64) -> Result<Vec<String>> {
+    // Start the counter in the lower half so it rarely overflows within a batch
+    let mut counter = u16::from_be_bytes(random_bytes()?) & 0x07ff;
+    let mut uuids = Vec::with_capacity(count);
+    for _ in 0..count {
+        uuids.push(uuid_v7(timestamp_ms, counter, random_bytes()?));
+        counter += 1;
+        if counter > 0x0fff {
+            timestamp_ms += 1;
+            counter = 0;
+        }
+    }
+    Ok(uuids)
+}
+
+fn encode_token(bytes: &[u8], encoding: &str) -> Result<String> {
+    match encoding {
+        "hex" => Ok(hex::encode(bytes)),
+        "base64" => Ok(STANDARD.encode(bytes)),
+        "base64url" => Ok(URL_SAFE_NO_PAD.encode(bytes)),
+        "base58" => Ok(bs58::encode(bytes).into_string()),
+        _ => Err(anyhow!(
+            "'encoding' must be one of {}",
+            ENCODINGS.join(", ")
+        )),
+    }
+}
+
+// Estimates the entropy of a password from the size of the character classes it uses,
+// as if each character were picked at random. Passwords made of words or patterns have
+// much less entropy than this.
+fn estimate(password: &str) -> Value {
+    let chars: Vec<char> = password.chars().collect();
+    let classes = [
+        (LOWERCASE, "lowercase"),
+        (UPPERCASE, "uppercase"),
+        (DIGITS, "digits"),
+        (SYMBOLS, "symbols"),
+    ];
+    let mut pool = 0;
+    let mut used = Vec::new();
+    for (class, name) in classes {
+        if chars.iter().any(|c| class.contains(*c)) {
+            pool += class.len();
+            used.push(name);
+        }
+    }
+    // Characters outside ASCII, counted as a class of 100 for want of a better guess
+    if chars.iter().any(|c| !c.is_ascii()) {
+        pool += 100;
+        used.push("other");
+    }
+
+    let bits = if pool == 0 {
+        0.0
+    } else {
+        chars.len() as f64 * (pool as f64).log2()
+    };
+    let strength = match bits {
+        bits if bits < 28.0 => "very weak",
+        bits if bits < 36.0 => "weak",
+        bits if bits < 60.0 => "reasonable",
+        bits if bits < 128.0 => "strong",
+        _ => "very strong",
+    };
+    json!({
+        "entropy_bits": (bits * 100.0).round() / 100.0,
+        "length": chars.len(),
+        "character_classes": used,
+        "pool_size": pool,
+        "strength": strength,
+    })
+}
+
+fn get_bounded_int_arg(
+    args: &Map<String, Value>,
+    name: &str,
+    default: i64,
+    max: i64,
+) -> Result<i64> {
+    let value = get_int_arg(args, name).unwrap_or(default);
+    if !(1..=max).contains(&value) {
+        return Err(anyhow!("'{name}' must be between 1 and {max}"));
+    }
+    Ok(value)
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "estimate_entropy" => estimate_entropy(&args),
+        "generate_password" => generate_password(&args),
+        "generate_token" => generate_token(&args),
+        "generate_uuid_v4" => generate_uuid_v4(&args),
+        "generate_uuid_v7" => generate_uuid_v7(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn estimate_entropy(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "password");
+    let password = get_required_string_arg(args, "password")?;
+    json_result(&estimate(password))
+}
+
+fn generate_password(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let length =
+        get_bounded_int_arg(args, "length", DEFAULT_PASSWORD_LENGTH, MAX_PASSWORD_LENGTH)? as usize;
+    let exclude_chars = get_string_arg(args, "exclude_chars").unwrap_or_default();
+    let classes: Vec<Vec<char>> = [
+        ("include_lowercase", LOWERCASE),
+        ("include_uppercase", UPPERCASE),
+        ("include_digits", DIGITS),
+        ("include_symbols", SYMBOLS),
+    ]
+    .into_iter()
+    .filter(|(name, _)| get_bool_arg(args, name).unwrap_or(true))
+    .map(|(_, class)| {
+        class
+            .chars()
+            .filter(|c| !exclude_chars.contains(*c))
+            .collect::<Vec<_>>()
+    })
+    .filter(|class| !class.is_empty())
+    .collect();
+
+    if classes.is_empty() {
+        return Ok(error_result(
+            "Error: no characters are left to generate a password from".to_string(),
+        ));
+    }
+    if length < classes.len() {
+        return Ok(error_result(format!(
+            "Error: 'length' must be at least {} to include a character of each class",
+            classes.len()
+        )));
+    }
+
+    let pool: usize = classes.iter().map(Vec::len).sum();
+    let password = generate(&classes, length)?;
+    json_result(&json!({
+        "password": password,
+        "entropy_bits": ((length as f64 * (pool as f64).log2()) * 100.0).round() / 100.0,
+    }))
+}
+
+fn generate_token(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let size = get_bounded_int_arg(args, "bytes", DEFAULT_TOKEN_BYTES, MAX_TOKEN_BYTES)? as usize;
+    let encoding = get_string_arg(args, "encoding").unwrap_or("hex");
+
+    let mut bytes = vec![0; size];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow!("failed to get random bytes: {e}"))?;
+    json_result(&json!({
+        "token": encode_token(&bytes, encoding)?,
+        "encoding": encoding,
+        "entropy_bits": size * 8,
+    }))
+}
+
+fn generate_uuid_v4(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let count = get_bounded_int_arg(args, "count", 1, MAX_UUID_COUNT)?;
+    let uuids = (0..count)
+        .map(|_| Ok(uuid_v4(random_bytes()?)))
+        .collect::<Result<Vec<_>>>()?;
+    json_result(&json!(uuids))
+}
+
+fn generate_uuid_v7(args: &Map<String, Value>) -> Result<CallToolResult> {
+    let count = get_bounded_int_arg(args, "count", 1, MAX_UUID_COUNT)?;
+    let uuids = uuids_v7(count as usize, Utc::now().timestamp_millis() as u64)?;
+    json_result(&json!(uuids))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(false),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn count_property() -> Value {
+    json!({
+        "type": "integer",
+        "minimum": 1,
+        "maximum": MAX_UUID_COUNT,
+        "description": "Number of UUIDs to generate. Defaults to 1.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: Some(ToolAnnotations {
+                    idempotent_hint: Some(true),
+
+                    ..annotations().unwrap_or_default()
+                }),
+                description: Some("Estimates the entropy of a password in bits from the character classes it uses, with a strength rating. This is an upper bound: passwords made of words or patterns are much weaker.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "password": {
+                            "type": "string",
+                            "description": "The password to rate.",
+                        },
+                    }),
+                    &["password"],
+                ),
+                name: "estimate_entropy".to_string(),
+                title: Some("Estimate Entropy".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Generates a random password with at least one character of each included class, and returns its entropy in bits.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "length": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": MAX_PASSWORD_LENGTH,
+                            "description": format!("Length of the password. Defaults to {DEFAULT_PASSWORD_LENGTH}."),
+                        },
+                        "include_uppercase": {
+                            "type": "boolean",
+                            "description": "Whether to include uppercase letters. Defaults to true.",
+                        },
+                        "include_lowercase": {
+                            "type": "boolean",
+                            "description": "Whether to include lowercase letters. Defaults to true.",
+                        },
+                        "include_digits": {
+                            "type": "boolean",
+                            "description": "Whether to include digits. Defaults to true.",
+                        },
+                        "include_symbols": {
+                            "type": "boolean",
+                            "description": format!("Whether to include the symbols {SYMBOLS}. Defaults to true."),
+                        },
+                        "exclude_chars": {
+                            "type": "string",
+                            "description": "Characters to never use, like ambiguous ones ('0O1lI') or ones that need escaping ('\"\\'`$').",
+                        },
+                    }),
+                    &[],
+                ),
+                name: "generate_password".to_string(),
+                title: Some("Generate Password".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Generates a random token, such as an API key or secret.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "bytes": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": MAX_TOKEN_BYTES,
+                            "description": format!("Number of random bytes. Defaults to {DEFAULT_TOKEN_BYTES}."),
+                        },
+                        "encoding": {
+                            "type": "string",
+                            "enum": ENCODINGS,
+                            "description": "How to encode the bytes, where base64url is URL-safe without padding. Defaults to hex.",
+                        },
+                    }),
+                    &[],
+                ),
+                name: "generate_token".to_string(),
+                title: Some("Generate Token".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Generates random version 4 UUIDs.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "count": count_property(),
+                    }),
+                    &[],
+                ),
+                name: "generate_uuid_v4".to_string(),
+                title: Some("Generate UUIDv4".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Generates version 7 UUIDs, which start with a timestamp so they sort in the order they were generated, like database keys.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "count": count_property(),
+                    }),
+                    &[],
+                ),
+                name: "generate_uuid_v7".to_string(),
+                title: Some("Generate UUIDv7".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn result_json(result: &CallToolResult) -> Value {
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        serde_json::from_str(&content.text).unwrap()
+    }
+
+    #[test]
+    fn test_random_below() {
+        for n in [1, 2, 7, 94] {
+            assert!((0..100).all(|_| random_below(n).unwrap() < n));
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let classes: Vec<Vec<char>> = [DIGITS, "ab"]
+            .iter()
+            .map(|class| class.chars().collect())
+            .collect();
+        for _ in 0..20 {
+            let password = generate(&classes, 3).unwrap();
+            assert_eq!(password.chars().count(), 3);
+            assert!(password.chars().any(|c| c.is_ascii_digit()));
+            assert!(password.chars().any(|c| c == 'a' || c == 'b'));
+        }
+    }
+
+    #[test]
+    fn test_uuid_v4() {
+        let uuid = uuid_v4([0xff; 16]);
+        assert_eq!(uuid, "ffffffff-ffff-4fff-bfff-ffffffffffff");
+        assert_eq!(uuid_v4([0; 16]), "00000000-0000-4000-8000-000000000000");
+    }
+
+    #[test]
+    fn test_uuid_v7() {
+        assert_eq!(
+            uuid_v7(
+                0x017F22E279B0,
+                0x0cc3,
+                [0x18, 0xc4, 0xdc, 0x0c, 0x0c, 0x07, 0x39, 0x8f]
+            ),
+            "017f22e2-79b0-7cc3-98c4-dc0c0c07398f"
+        );
+
+        let uuids = uuids_v7(50, 1_700_000_000_000).unwrap();
+        let mut sorted = uuids.clone();
+        sorted.sort();
+        assert_eq!(uuids, sorted);
+        assert!(uuids.iter().all(|uuid| uuid.starts_with("018bcfe5-6800-7")));
+    }
+
+    #[test]
+    fn test_encode_token() {
+        let bytes = [0x00, 0x01, 0xfe, 0xff];
+        assert_eq!(encode_token(&bytes, "hex").unwrap(), "0001feff");
+        assert_eq!(encode_token(&bytes, "base64").unwrap(), "AAH+/w==");
+        assert_eq!(encode_token(&bytes, "base64url").unwrap(), "AAH-_w");
+        assert_eq!(encode_token(&bytes, "base58").unwrap(), "1ftS");
+        assert!(encode_token(&bytes, "base32").is_err());
+    }
+
+    #[test]
+    fn test_estimate() {
+        let estimate = estimate("password");
+        assert_eq!(estimate["entropy_bits"], 37.6);
+        assert_eq!(estimate["pool_size"], 26);
+        assert_eq!(estimate["strength"], "reasonable");
+
+        let estimate = super::estimate("Tr0ub4dor&3");
+        assert_eq!(estimate["pool_size"], 94);
+        assert_eq!(
+            estimate["character_classes"],
+            json!(["lowercase", "uppercase", "digits", "symbols"])
+        );
+        assert_eq!(estimate["entropy_bits"], 72.1);
+
+        assert_eq!(super::estimate("")["strength"], "very weak");
+    }
+
+    #[test]
+    fn test_generate_password() {
+        let result = call(
+            "generate_password",
+            json!({"length": 32, "include_symbols": false, "exclude_chars": "0O1lI"}),
+        )
+        .unwrap();
+        let password = result_json(&result)["password"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(password.len(), 32);
+        assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert!(!password.contains(['0', 'O', '1', 'l', 'I']));
+    }
+
+    #[test]
+    fn test_generate_token() {
+        let result = call("generate_token", json!({"bytes": 16, "encoding": "hex"})).unwrap();
+        assert_eq!(result_json(&result)["token"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("generate_password", json!({"length": 0})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "generate_password",
+            json!({"include_uppercase": false, "include_digits": false, "include_symbols": false, "exclude_chars": LOWERCASE}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("generate_password", json!({"length": 3})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("generate_uuid_v4", json!({"count": 1001})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("generate_token", json!({"encoding": "base32"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "estimate_entropy",
+                "generate_password",
+                "generate_token",
+                "generate_uuid_v4",
+                "generate_uuid_v7"
+            ]
+        );
+    }
+}
@@ -0,0 +1,603 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+const SSL_LABS_API_URL: &str = "https://api.ssllabs.com/api/v3";
+
+const DEFAULT_PORT: u16 = 443;
+
+const EXPIRY_WARNING_DAYS: i64 = 30;
+
+const MAX_BULK_HOSTNAMES: usize = 25;
+
+// The leaf certificate of a host, as returned by an `SSL_CHECKER_URL` service.
+#[derive(Debug, Deserialize, PartialEq)]
+struct Certificate {
+    subject: String,
+    issuer: String,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    #[serde(default)]
+    san_list: Vec<String>,
+    chain_length: Option<usize>,
+}
+
+// The outcome of a check, as SSL Labs assessments take a few minutes the first time a
+// host is checked.
+#[derive(Debug, PartialEq)]
+enum Check {
+    Ready(Certificate),
+    InProgress(String),
+}
+
+fn validate_hostname(hostname: &str) -> Result<&str> {
+    let valid = !hostname.is_empty()
+        && hostname.len() <= 253
+        && hostname.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if !valid {
+        return Err(anyhow!(
+            "'{hostname}' is not a valid hostname, which looks like example.com without a scheme or path"
+        ));
+    }
+    Ok(hostname)
+}
+
+// Splits a `host` or `host:port` entry of `ssl_check_bulk`.
+fn parse_host(entry: &str) -> Result<(&str, u16)> {
+    match entry.trim().rsplit_once(':') {
+        Some((hostname, port)) => {
+            let port = port
+                .parse()
+                .ok()
+                .filter(|port| *port > 0)
+                .ok_or_else(|| anyhow!("'{port}' is not a valid port"))?;
+            Ok((validate_hostname(hostname)?, port))
+        }
+        None => Ok((validate_hostname(entry.trim())?, DEFAULT_PORT)),
+    }
+}
+
+fn http_get(url: String) -> Result<Value> {
+    let req = HttpRequest::new(url)
+        .with_method("GET")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    let res = request::<()>(&req, None)?;
+    let body = res.body();
+    if !(200..300).contains(&res.status_code()) {
+        return Err(anyhow!(
+            "{} returned {}: {}",
+            req.url,
+            res.status_code(),
+            String::from_utf8_lossy(&body)
+        ));
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn ssllabs_time(millis: &Value) -> Result<DateTime<Utc>> {
+    millis
+        .as_i64()
+        .and_then(DateTime::from_timestamp_millis)
+        .ok_or_else(|| anyhow!("SSL Labs returned an invalid certificate date"))
+}
+
+// Reads the leaf certificate of the first endpoint of an SSL Labs assessment made with
+// `all=done`, whose certificates are listed once in `certs` and referenced by ID from
+// each endpoint's chains.
+fn ssllabs_check(report: &Value) -> Result<Check> {
+    match report["status"].as_str() {
+        Some("READY") => {}
+        Some("ERROR") => {
+            return Err(anyhow!(
+                "SSL Labs could not check the host: {}",
+                report["statusMessage"].as_str().unwrap_or("unknown error")
+            ))
+        }
+        status => {
+            return Ok(Check::InProgress(format!(
+                "SSL Labs is assessing the host ({}), retry in a minute",
+                status.unwrap_or("starting")
+            )))
+        }
+    }
+
+    let endpoint = &report["endpoints"][0];
+    let cert_ids = endpoint["details"]["certChains"][0]["certIds"]
+        .as_array()
+        .filter(|ids| !ids.is_empty())
+        .ok_or_else(|| {
+            anyhow!(
+                "SSL Labs returned no certificate: {}",
+                endpoint["statusMessage"]
+                    .as_str()
+                    .unwrap_or("unknown error")
+            )
+        })?;
+    let leaf = report["certs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|cert| cert["id"] == cert_ids[0])
+        .ok_or_else(|| anyhow!("SSL Labs returned no details for the leaf certificate"))?;
+
+    Ok(Check::Ready(Certificate {
+        subject: leaf["subject"].as_str().unwrap_or_default().to_string(),
+        issuer: leaf["issuerSubject"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        not_before: ssllabs_time(&leaf["notBefore"])?,
+        not_after: ssllabs_time(&leaf["notAfter"])?,
+        san_list: leaf["altNames"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|name| name.as_str().map(str::to_string))
+            .collect(),
+        chain_length: Some(cert_ids.len()),
+    }))
+}
+
+fn check(hostname: &str, port: u16) -> Result<Check> {
+    if let Some(checker_url) = config::get("SSL_CHECKER_URL")? {
+        let certificate = http_get(format!(
+            "{}?host={hostname}&port={port}",
+            checker_url.trim_end_matches('/')
+        ))?;
+        return Ok(Check::Ready(serde_json::from_value(certificate)?));
+    }
+
+    if port != DEFAULT_PORT {
+        return Err(anyhow!(
+            "SSL Labs only checks port {DEFAULT_PORT}, set SSL_CHECKER_URL to check other ports"
+        ));
+    }
+    let report = http_get(format!(
+        "{SSL_LABS_API_URL}/analyze?host={hostname}&fromCache=on&maxAge=24&all=done"
+    ))?;
+    ssllabs_check(&report)
+}
+
+fn report(hostname: &str, port: u16, check: Check, now: DateTime<Utc>) -> Value {
+    let certificate = match check {
+        Check::Ready(certificate) => certificate,
+        Check::InProgress(message) => {
+            return json!({
+                "hostname": hostname,
+                "port": port,
+                "status": "in_progress",
+                "message": message,
+            })
+        }
+    };
+
+    let days_until_expiry = (certificate.not_after - now).num_days();
+    let is_expired = certificate.not_after <= now;
+    let expiry_warning = days_until_expiry < EXPIRY_WARNING_DAYS;
+    let mut report = json!({
+        "hostname": hostname,
+        "port": port,
+        "status": "ready",
+        "subject": certificate.subject,
+        "issuer": certificate.issuer,
+        "not_before": certificate.not_before.to_rfc3339(),
+        "not_after": certificate.not_after.to_rfc3339(),
+        "days_until_expiry": days_until_expiry,
+        "san_list": certificate.san_list,
+        "is_expired": is_expired,
+        "is_not_yet_valid": certificate.not_before > now,
+        "chain_length": certificate.chain_length,
+        "expiry_warning": expiry_warning,
+    });
+    if is_expired {
+        report["warning"] = json!("The certificate has expired");
+    } else if expiry_warning {
+        report["warning"] = json!(format!(
+            "The certificate expires in {days_until_expiry} days, which is under {EXPIRY_WARNING_DAYS}"
+        ));
+    }
+    report
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "ssl_check_bulk" => ssl_check_bulk(&args),
+        "ssl_check_cert" => ssl_check_cert(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn ssl_check_bulk(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "hostnames");
+    let Some(entries) = get_array_arg(args, "hostnames") else {
+        return Ok(error_result(
+            "Error: 'hostnames' must be an array of hostnames".to_string(),
+        ));
+    };
+    if entries.is_empty() || entries.len() > MAX_BULK_HOSTNAMES {
+        return Ok(error_result(format!(
+            "Error: 'hostnames' must contain between 1 and {MAX_BULK_HOSTNAMES} hostnames"
+        )));
+    }
+
+    // A failed check gets an error in place, without failing the others
+    let now = Utc::now();
+    let reports: Vec<Value> = entries
+        .iter()
+        .map(|entry| {
+            entry
+                .as_str()
+                .ok_or_else(|| anyhow!("'hostnames' must only contain strings"))
+                .and_then(parse_host)
+                .and_then(|(hostname, port)| {
+                    Ok(report(hostname, port, check(hostname, port)?, now))
+                })
+                .unwrap_or_else(|e| json!({"hostname": entry, "error": e.to_string()}))
+        })
+        .collect();
+
+    json_result(&json!(reports))
+}
+
+fn ssl_check_cert(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "hostname");
+    let hostname = validate_hostname(get_required_string_arg(args, "hostname")?.trim())?;
+    let port = match get_int_arg(args, "port") {
+        Some(port) => u16::try_from(port)
+            .ok()
+            .filter(|port| *port > 0)
+            .ok_or_else(|| anyhow!("'port' must be between 1 and 65535"))?,
+        None => DEFAULT_PORT,
+    };
+
+    let check = check(hostname, port)?;
+    json_result(&report(hostname, port, check, Utc::now()))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some(format!("Checks the TLS certificates of up to {MAX_BULK_HOSTNAMES} hosts, returning a report or an error for each, in the order given.")),
+                input_schema: tool_schema(
+                    json!({
+                        "hostnames": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "maxItems": MAX_BULK_HOSTNAMES,
+                            "description": format!("The hostnames to check, optionally with a port like 'example.com:8443'. The port defaults to {DEFAULT_PORT}."),
+                        },
+                    }),
+                    &["hostnames"],
+                ),
+                name: "ssl_check_bulk".to_string(),
+                title: Some("Check Certificates".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some(format!("Checks the TLS certificate of a host, returning its subject, issuer, validity dates, days until expiry, subject alternative names and chain length. Sets 'expiry_warning' if it expires in under {EXPIRY_WARNING_DAYS} days.")),
+                input_schema: tool_schema(
+                    json!({
+                        "hostname": {
+                            "type": "string",
+                            "description": "The hostname, like example.com, without a scheme or path.",
+                        },
+                        "port": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": 65535,
+                            "description": format!("The port. Defaults to {DEFAULT_PORT}."),
+                        },
+                    }),
+                    &["hostname"],
+                ),
+                name: "ssl_check_cert".to_string(),
+                title: Some("Check Certificate".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn time(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn certificate(not_after: &str) -> Certificate {
+        Certificate {
+            subject: "CN=example.com".to_string(),
+            issuer: "CN=R11, O=Let's Encrypt, C=US".to_string(),
+            not_before: time("2024-05-01T00:00:00Z"),
+            not_after: time(not_after),
+            san_list: vec!["example.com".to_string(), "www.example.com".to_string()],
+            chain_length: Some(2),
+        }
+    }
+
+    #[test]
+    fn test_validate_hostname() {
+        assert!(validate_hostname("example.com").is_ok());
+        assert!(validate_hostname("my-host.sub.example.co.uk").is_ok());
+        assert!(validate_hostname("https://example.com").is_err());
+        assert!(validate_hostname("example.com/path").is_err());
+        assert!(validate_hostname("-bad.example.com").is_err());
+        assert!(validate_hostname("example..com").is_err());
+        assert!(validate_hostname("").is_err());
+    }
+
+    #[test]
+    fn test_parse_host() {
+        assert_eq!(parse_host("example.com").unwrap(), ("example.com", 443));
+        assert_eq!(
+            parse_host(" example.com:8443 ").unwrap(),
+            ("example.com", 8443)
+        );
+        assert!(parse_host("example.com:0").is_err());
+        assert!(parse_host("example.com:https").is_err());
+    }
+
+    #[test]
+    fn test_ssllabs_check() {
+        let report = json!({
+            "host": "example.com",
+            "status": "READY",
+            "endpoints": [{
+                "ipAddress": "93.184.215.14",
+                "statusMessage": "Ready",
+                "details": {"certChains": [{"certIds": ["leaf", "intermediate"]}]},
+            }],
+            "certs": [
+                {
+                    "id": "intermediate",
+                    "subject": "CN=R11, O=Let's Encrypt, C=US",
+                    "notBefore": 1709683200000_i64,
+                    "notAfter": 1836000000000_i64,
+                },
+                {
+                    "id": "leaf",
+                    "subject": "CN=example.com",
+                    "issuerSubject": "CN=R11, O=Let's Encrypt, C=US",
+                    "notBefore": 1714521600000_i64,
+                    "notAfter": 1722297600000_i64,
+                    "altNames": ["example.com", "www.example.com"],
+                },
+            ],
+        });
+        assert_eq!(
+            ssllabs_check(&report).unwrap(),
+            Check::Ready(certificate("2024-07-30T00:00:00Z"))
+        );
+
+        let report = json!({"host": "example.com", "status": "IN_PROGRESS"});
+        assert!(matches!(
+            ssllabs_check(&report).unwrap(),
+            Check::InProgress(_)
+        ));
+
+        let report = json!({"status": "ERROR", "statusMessage": "Unable to resolve domain name"});
+        let error = ssllabs_check(&report).unwrap_err().to_string();
+        assert!(error.contains("Unable to resolve domain name"));
+    }
+
+    #[test]
+    fn test_checker_certificate() {
+        let parsed: Certificate = serde_json::from_value(json!({
+            "subject": "CN=example.com",
+            "issuer": "CN=R11, O=Let's Encrypt, C=US",
+            "not_before": "2024-05-01T00:00:00Z",
+            "not_after": "2024-07-30T00:00:00Z",
+            "san_list": ["example.com", "www.example.com"],
+            "chain_length": 2,
+        }))
+        .unwrap();
+        assert_eq!(parsed, certificate("2024-07-30T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_report() {
+        let now = time("2024-07-10T12:00:00Z");
+
+        let report = super::report(
+            "example.com",
+            443,
+            Check::Ready(certificate("2024-09-01T00:00:00Z")),
+            now,
+        );
+        assert_eq!(report["days_until_expiry"], 52);
+        assert_eq!(report["is_expired"], false);
+        assert_eq!(report["expiry_warning"], false);
+        assert_eq!(report["chain_length"], 2);
+        assert_eq!(report["not_after"], "2024-09-01T00:00:00+00:00");
+        assert!(report.get("warning").is_none());
+
+        let report = super::report(
+            "example.com",
+            443,
+            Check::Ready(certificate("2024-07-30T00:00:00Z")),
+            now,
+        );
+        assert_eq!(report["days_until_expiry"], 19);
+        assert_eq!(report["expiry_warning"], true);
+        assert!(report["warning"].as_str().unwrap().contains("19 days"));
+
+        let report = super::report(
+            "example.com",
+            443,
+            Check::Ready(certificate("2024-07-01T00:00:00Z")),
+            now,
+        );
+        assert_eq!(report["is_expired"], true);
+        assert_eq!(report["warning"], "The certificate has expired");
+
+        let report = super::report(
+            "example.com",
+            443,
+            Check::InProgress("retry".to_string()),
+            now,
+        );
+        assert_eq!(report["status"], "in_progress");
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("ssl_check_cert", json!({"hostname": "https://example.com"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "ssl_check_cert",
+            json!({"hostname": "example.com", "port": 70000}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("ssl_check_bulk", json!({"hostnames": []})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["ssl_check_bulk", "ssl_check_cert"]);
+    }
+}
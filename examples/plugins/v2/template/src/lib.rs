@@ -0,0 +1,446 @@
+mod pdk;
+mod variables;
+
+use anyhow::{anyhow, Result};
+use handlebars::Handlebars;
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+use std::error::Error;
+use tera::{Context, Tera};
+
+const ENGINES: [&str; 2] = ["handlebars", "tera"];
+
+// Formats an error with its sources, which is where Tera puts the line and column of a
+// syntax error.
+fn error_chain(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(error) = source {
+        message.push_str(&format!("\n{error}"));
+        source = error.source();
+    }
+    message
+}
+
+fn get_object_arg<'a>(args: &'a Map<String, Value>, name: &str) -> Result<&'a Value> {
+    match args.get(name) {
+        Some(value @ Value::Object(_)) => Ok(value),
+        _ => Err(anyhow!("'{name}' must be a JSON object")),
+    }
+}
+
+fn render_handlebars(
+    template: &str,
+    variables: &Value,
+    strict: bool,
+    escape_html: bool,
+) -> Result<String> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(strict);
+    if !escape_html {
+        handlebars.register_escape_fn(handlebars::no_escape);
+    }
+    handlebars
+        .render_template(template, variables)
+        .map_err(|e| anyhow!("{}", error_chain(&e)))
+}
+
+fn render_tera(template: &str, context: &Value, escape_html: bool) -> Result<String> {
+    let context = Context::from_value(context.clone())?;
+    Tera::one_off(template, &context, escape_html).map_err(|e| anyhow!("{}", error_chain(&e)))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn text_result(text: String) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "template_list_variables" => template_list_variables(&args),
+        "template_render_handlebars" => template_render_handlebars(&args),
+        "template_render_tera" => template_render_tera(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn template_list_variables(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "template");
+    let template = get_required_string_arg(args, "template")?;
+    let engine = get_string_arg(args, "engine").unwrap_or("handlebars");
+
+    let variables = match engine {
+        "handlebars" => variables::handlebars_variables(template),
+        "tera" => variables::tera_variables(template),
+        _ => {
+            return Ok(error_result(format!(
+                "Error: 'engine' must be one of {}",
+                ENGINES.join(", ")
+            )))
+        }
+    };
+    json_result(&json!({
+        "engine": engine,
+        "variables": variables,
+    }))
+}
+
+fn template_render_handlebars(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "template");
+    let template = get_required_string_arg(args, "template")?;
+    let empty = json!({});
+    let variables = match args.get("variables") {
+        Some(_) => get_object_arg(args, "variables")?,
+        None => &empty,
+    };
+    let strict = get_bool_arg(args, "strict").unwrap_or(false);
+    let escape_html = get_bool_arg(args, "escape_html").unwrap_or(false);
+
+    text_result(render_handlebars(template, variables, strict, escape_html)?)
+}
+
+fn template_render_tera(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "template");
+    let template = get_required_string_arg(args, "template")?;
+    let empty = json!({});
+    let context = match args.get("context") {
+        Some(_) => get_object_arg(args, "context")?,
+        None => &empty,
+    };
+    let escape_html = get_bool_arg(args, "escape_html").unwrap_or(false);
+
+    text_result(render_tera(template, context, escape_html)?)
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn escape_html_property() -> Value {
+    json!({
+        "type": "boolean",
+        "description": "Whether to HTML-escape the values of variables, for templates that produce HTML. Defaults to false.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Lists the top-level variables a Handlebars or Tera template reads, for example to build the schema of its variables. Variables of loops and blocks like `each` are not included.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "template": {
+                            "type": "string",
+                            "description": "The template.",
+                        },
+                        "engine": {
+                            "type": "string",
+                            "enum": ENGINES,
+                            "description": "The template syntax. Defaults to handlebars.",
+                        },
+                    }),
+                    &["template"],
+                ),
+                name: "template_list_variables".to_string(),
+                title: Some("List Template Variables".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Renders a Handlebars template with variables. Errors include the line and column of the problem.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "template": {
+                            "type": "string",
+                            "description": "The Handlebars template, like 'Hello {{name}}!'.",
+                        },
+                        "variables": {
+                            "type": "object",
+                            "description": "The variables of the template.",
+                        },
+                        "strict": {
+                            "type": "boolean",
+                            "description": "Whether to fail on variables that are missing instead of rendering them empty. Defaults to false.",
+                        },
+                        "escape_html": escape_html_property(),
+                    }),
+                    &["template"],
+                ),
+                name: "template_render_handlebars".to_string(),
+                title: Some("Render Handlebars Template".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Renders a Tera (Jinja2-like) template with a context. Syntax errors include the line and column of the problem, and missing variables are errors.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "template": {
+                            "type": "string",
+                            "description": "The Tera template, like 'Hello {{ name | upper }}!'.",
+                        },
+                        "context": {
+                            "type": "object",
+                            "description": "The variables of the template.",
+                        },
+                        "escape_html": escape_html_property(),
+                    }),
+                    &["template"],
+                ),
+                name: "template_render_tera".to_string(),
+                title: Some("Render Tera Template".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_render_handlebars() {
+        let variables = json!({"name": "<World>", "items": ["a", "b"]});
+        assert_eq!(
+            render_handlebars(
+                "Hello {{name}}!{{#each items}} {{this}}{{/each}}",
+                &variables,
+                false,
+                false
+            )
+            .unwrap(),
+            "Hello <World>! a b"
+        );
+        assert_eq!(
+            render_handlebars("Hello {{name}}!", &variables, false, true).unwrap(),
+            "Hello &lt;World&gt;!"
+        );
+        assert_eq!(
+            render_handlebars("Hello {{missing}}!", &variables, false, false).unwrap(),
+            "Hello !"
+        );
+
+        let error = render_handlebars("Hello {{missing}}!", &variables, true, false)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("missing"), "{error}");
+
+        let error = render_handlebars("Hello\n{{name}!", &variables, false, false)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains('2'), "{error}");
+    }
+
+    #[test]
+    fn test_render_tera() {
+        let context = json!({"name": "<World>", "items": ["a", "b"]});
+        assert_eq!(
+            render_tera(
+                "Hello {{ name | lower }}!{% for item in items %} {{ item }}{% endfor %}",
+                &context,
+                false
+            )
+            .unwrap(),
+            "Hello <world>! a b"
+        );
+        assert_eq!(
+            render_tera("Hello {{ name }}!", &context, true).unwrap(),
+            "Hello &lt;World&gt;!"
+        );
+
+        let error = render_tera("Hello {{ missing }}!", &context, false)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("missing"), "{error}");
+
+        let error = render_tera("Hello\n{{ name }!", &context, false)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("2:"), "{error}");
+    }
+
+    #[test]
+    fn test_template_list_variables() {
+        let result = call(
+            "template_list_variables",
+            json!({"template": "{% for x in xs %}{{ x }}{% endfor %}", "engine": "tera"}),
+        )
+        .unwrap();
+        let ContentBlock::Text(content) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        assert_eq!(
+            serde_json::from_str::<Value>(&content.text).unwrap(),
+            json!({"engine": "tera", "variables": ["xs"]})
+        );
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call(
+            "template_render_handlebars",
+            json!({"template": "{{a}}", "variables": [1, 2]}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "template_render_tera",
+            json!({"template": "{{ a }}", "context": "a=1"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "template_list_variables",
+            json!({"template": "{{a}}", "engine": "jinja"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("template_render_tera", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "template_list_variables",
+                "template_render_handlebars",
+                "template_render_tera"
+            ]
+        );
+    }
+}
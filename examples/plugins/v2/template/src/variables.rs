@@ -0,0 +1,372 @@
+//! Finds the top-level variables a template reads from its context.
+//!
+//! This is a lexical scan rather than a parse, so it also works on templates that don't
+//! compile yet. Variables used inside `each`/`with` blocks, loop variables and variables
+//! set by the template itself are not part of the context, so they aren't returned.
+
+use std::collections::{BTreeSet, HashSet};
+
+const HANDLEBARS_KEYWORDS: [&str; 7] =
+    ["else", "if", "unless", "true", "false", "null", "undefined"];
+
+const TERA_KEYWORDS: [&str; 11] = [
+    "and", "or", "not", "in", "is", "true", "false", "True", "False", "none", "None",
+];
+
+// Returns the contents of the `{{ }}` tags of a Handlebars template, where comments like
+// `{{!-- a }} b --}}` may contain `}}`.
+fn handlebars_tags(template: &str) -> Vec<&str> {
+    let mut tags = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start + 2..];
+        let close = if rest.starts_with("!--") {
+            "--}}"
+        } else {
+            "}}"
+        };
+        let Some(end) = rest.find(close) else {
+            break;
+        };
+        tags.push(&rest[..end]);
+        rest = &rest[end + close.len()..];
+    }
+    tags
+}
+
+// Splits a Handlebars expression into tokens, leaving out string literals and the helper
+// names of subexpressions like `(eq a b)`.
+fn handlebars_tokens(expression: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.char_indices().peekable();
+    let mut helper_next = false;
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                for (_, next) in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                }
+                helper_next = false;
+            }
+            '(' => helper_next = true,
+            ')' => {}
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(index, next)) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '(' | ')' | '"' | '\'') {
+                        break;
+                    }
+                    end = index + next.len_utf8();
+                    chars.next();
+                }
+                if !helper_next {
+                    tokens.push(&expression[start..end]);
+                }
+                helper_next = false;
+            }
+        }
+    }
+    tokens
+}
+
+// Returns the top-level variable a Handlebars path like `user.name` refers to, given how
+// many open blocks change the context. Paths can escape those blocks with `../` or
+// `@root.`.
+fn handlebars_variable(
+    token: &str,
+    scoped_depth: usize,
+    locals: &HashSet<String>,
+) -> Option<String> {
+    // Hash arguments like `sep=separator`
+    let mut path = token.split_once('=').map_or(token, |(_, value)| value);
+    let mut depth = scoped_depth;
+    if let Some(root_path) = path.strip_prefix("@root.") {
+        path = root_path;
+        depth = 0;
+    } else if path.starts_with('@') {
+        return None;
+    }
+    while let Some(parent_path) = path.strip_prefix("../") {
+        path = parent_path;
+        depth = depth.saturating_sub(1);
+    }
+    path = path
+        .strip_prefix("this.")
+        .or_else(|| path.strip_prefix("./"))
+        .unwrap_or(path);
+
+    let name = path.split(['.', '/', '[']).next()?;
+    let is_literal = name.is_empty()
+        || name == "this"
+        || name.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        || HANDLEBARS_KEYWORDS.contains(&name);
+    (!is_literal && depth == 0 && !locals.contains(name)).then(|| name.to_string())
+}
+
+/// Returns the top-level variables of a Handlebars template, sorted.
+pub fn handlebars_variables(template: &str) -> Vec<String> {
+    let mut variables = BTreeSet::new();
+    let mut locals = HashSet::new();
+    // Whether each open block changes the context of its contents, like `each` and `with`
+    let mut blocks: Vec<bool> = Vec::new();
+
+    for tag in handlebars_tags(template) {
+        // Remove the third brace of `{{{raw}}}` and whitespace control like `{{~name~}}`
+        let tag = tag.trim_start_matches('{').trim_matches('~').trim();
+        if tag.starts_with(['!', '>']) {
+            continue;
+        }
+        if tag.starts_with('/') {
+            blocks.pop();
+            continue;
+        }
+        let (is_block, expression) = match tag.strip_prefix(['#', '^']) {
+            Some(expression) => (true, expression.trim()),
+            None => (false, tag),
+        };
+        if expression.is_empty() {
+            continue;
+        }
+        if is_block && expression.starts_with(['>', '*']) {
+            // Partial blocks and inline partials
+            blocks.push(false);
+            continue;
+        }
+
+        let mut tokens = handlebars_tokens(expression);
+        // Block parameters like `{{#each items as |item index|}}`
+        if let Some(position) = tokens.iter().position(|token| *token == "as") {
+            locals.extend(
+                tokens
+                    .drain(position..)
+                    .skip(1)
+                    .map(|token| token.trim_matches('|').to_string())
+                    .filter(|token| !token.is_empty()),
+            );
+        }
+
+        // Tags with parameters start with a helper name, like `if` in `{{#if ready}}`
+        let parameters = if is_block || tokens.len() > 1 {
+            tokens.get(1..).unwrap_or_default()
+        } else {
+            &tokens[..]
+        };
+        let scoped_depth = blocks.iter().filter(|scoped| **scoped).count();
+        variables.extend(
+            parameters
+                .iter()
+                .filter_map(|token| handlebars_variable(token, scoped_depth, &locals)),
+        );
+
+        if is_block {
+            blocks.push(matches!(tokens.first(), Some(&"each") | Some(&"with")));
+        }
+    }
+    variables.into_iter().collect()
+}
+
+// Adds the variables of a Tera expression like `user.name | default(value=fallback)`,
+// skipping filters, tests, function names and keyword argument names.
+fn tera_expression_variables(
+    expression: &str,
+    locals: &HashSet<String>,
+    variables: &mut BTreeSet<String>,
+) {
+    let chars: Vec<char> = expression.chars().collect();
+    let next_significant = |from: usize| chars[from..].iter().copied().find(|c| !c.is_whitespace());
+    let mut previous = None;
+    let mut after_is = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if matches!(c, '"' | '\'' | '`') {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += 1;
+            }
+            i += 1;
+            previous = Some(c);
+            continue;
+        }
+        if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            previous = Some('0');
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | ':'))
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let is_filter = previous == Some('|');
+            let is_call = next_significant(i) == Some('(');
+            let is_keyword_argument = next_significant(i) == Some('=')
+                && chars[i..].iter().skip_while(|c| c.is_whitespace()).nth(1) != Some(&'=');
+            let is_keyword = TERA_KEYWORDS.contains(&word.as_str());
+            // Namespaced macros like `macros::input`
+            let is_macro = word.contains("::");
+            if !(is_filter || after_is || is_call || is_keyword_argument || is_keyword || is_macro)
+            {
+                let name = word.split('.').next().unwrap_or_default();
+                if !locals.contains(name) {
+                    variables.insert(name.to_string());
+                }
+            }
+            // Tests follow `is` or `is not`, like `is defined`
+            after_is = word == "is" || (after_is && word == "not");
+            previous = Some('a');
+            continue;
+        }
+        if !c.is_whitespace() {
+            previous = Some(c);
+        }
+        i += 1;
+    }
+}
+
+/// Returns the top-level variables of a Tera template, sorted.
+pub fn tera_variables(template: &str) -> Vec<String> {
+    let mut variables = BTreeSet::new();
+    let mut locals: HashSet<String> = HashSet::from(["loop".to_string()]);
+    let mut raw = false;
+
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let close = match after.chars().next() {
+            Some('{') => "}}",
+            Some('%') => "%}",
+            Some('#') => "#}",
+            _ => {
+                rest = after;
+                continue;
+            }
+        };
+        let Some(end) = after[1..].find(close) else {
+            break;
+        };
+        let kind = &after[..1];
+        // Remove whitespace control like `{%- if x -%}`
+        let content = after[1..end + 1].trim_matches('-').trim();
+        rest = &after[end + 1 + close.len()..];
+
+        let (keyword, arguments) = content
+            .split_once(char::is_whitespace)
+            .unwrap_or((content, ""));
+        if raw {
+            raw = !(kind == "%" && keyword == "endraw");
+            continue;
+        }
+        match kind {
+            "{" => tera_expression_variables(content, &locals, &mut variables),
+            "%" => match keyword {
+                "raw" => raw = true,
+                "for" => {
+                    if let Some((names, iterable)) = arguments.split_once(" in ") {
+                        tera_expression_variables(iterable, &locals, &mut variables);
+                        locals.extend(names.split(',').map(|name| name.trim().to_string()));
+                    }
+                }
+                "if" | "elif" => tera_expression_variables(arguments, &locals, &mut variables),
+                "set" | "set_global" => {
+                    if let Some((name, value)) = arguments.split_once('=') {
+                        tera_expression_variables(value, &locals, &mut variables);
+                        locals.insert(name.trim().to_string());
+                    }
+                }
+                "macro" => {
+                    let parameters = arguments
+                        .split_once('(')
+                        .and_then(|(_, parameters)| parameters.rsplit_once(')'))
+                        .map_or("", |(parameters, _)| parameters);
+                    locals.extend(parameters.split(',').map(|parameter| {
+                        parameter
+                            .split_once('=')
+                            .map_or(parameter, |(name, _)| name)
+                            .trim()
+                            .to_string()
+                    }));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    variables.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handlebars_variables() {
+        let template = r#"
+            {{!-- {{ignored}} --}}
+            Hello {{~ user.name ~}}, you have {{{count}}} messages.
+            {{#if (gt count 0)}}
+              {{#each messages as |message|}}
+                {{@index}}: {{message.subject}} from {{sender}} for {{../user.email}}
+              {{/each}}
+            {{else if archived}}
+              {{> empty-inbox}}
+            {{/if}}
+            {{join tags sep=separator}} {{format "literal" 42 true}}
+            {{#with settings}}{{theme}} {{@root.locale}}{{/with}}
+        "#;
+        assert_eq!(
+            handlebars_variables(template),
+            [
+                "archived",
+                "count",
+                "locale",
+                "messages",
+                "separator",
+                "settings",
+                "tags",
+                "user"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tera_variables() {
+        let template = r#"
+            {# {{ ignored }} #}
+            {% import "macros.html" as macros %}
+            Hello {{- user.name | title -}}!
+            {% set greeting = prefix ~ " there" %}{{ greeting }}
+            {% for item in items | sort(attribute="name") %}
+              {{ loop.index }}. {{ item.name | truncate(length=max_length) }}
+              {{ macros::badge(label=item.label, color=theme.color) }}
+            {% endfor %}
+            {% if total is defined and total > 10 %}{{ total }}{% elif missing is not defined %}{% endif %}
+            {% raw %}{{ not_a_variable }}{% endraw %}
+            {% for key, value in settings %}{{ key }}={{ value }}{% endfor %}
+            {{ now() | date(format="%Y") }} {{ prices[currency] }}
+        "#;
+        assert_eq!(
+            tera_variables(template),
+            [
+                "currency",
+                "items",
+                "max_length",
+                "missing",
+                "prefix",
+                "prices",
+                "settings",
+                "theme",
+                "total",
+                "user"
+            ]
+        );
+    }
+}
@@ -0,0 +1,558 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+use std::sync::Mutex;
+
+// Token from the last AppRole login, reused until Vault rejects it
+static APPROLE_TOKEN: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, PartialEq)]
+enum KvVersion {
+    V1,
+    V2,
+}
+
+#[derive(Debug, PartialEq)]
+enum KvOperation {
+    Read,
+    Write,
+    Delete,
+    List,
+}
+
+fn get_vault_addr() -> Result<String> {
+    let addr = config::get("VAULT_ADDR")?
+        .ok_or_else(|| anyhow!("VAULT_ADDR configuration is required but not set"))?;
+    Ok(addr.trim_end_matches('/').to_string())
+}
+
+fn send(
+    addr: &str,
+    token: Option<&str>,
+    method: &str,
+    path: &str,
+    body: Option<&Value>,
+) -> Result<(u16, Vec<u8>)> {
+    let mut req = HttpRequest::new(format!("{addr}/v1/{path}"))
+        .with_method(method)
+        .with_header("Content-Type", "application/json")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    if let Some(token) = token {
+        req = req.with_header("X-Vault-Token", token);
+    }
+    let res = request(&req, body.map(serde_json::to_vec).transpose()?)?;
+    Ok((res.status_code(), res.body()))
+}
+
+// Formats a Vault error response, which looks like `{"errors": ["permission denied"]}`.
+fn vault_error(status: u16, body: &[u8]) -> String {
+    let errors = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|error| error["errors"].as_array().cloned())
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+        .filter(|errors| !errors.is_empty())
+        .unwrap_or_else(|| String::from_utf8_lossy(body).to_string());
+    format!("Vault returned {status}: {errors}")
+}
+
+fn approle_login(addr: &str, role_id: &str, secret_id: &str) -> Result<String> {
+    let (status, body) = send(
+        addr,
+        None,
+        "POST",
+        "auth/approle/login",
+        Some(&json!({"role_id": role_id, "secret_id": secret_id})),
+    )?;
+    if !(200..300).contains(&status) {
+        return Err(anyhow!(
+            "AppRole login failed: {}",
+            vault_error(status, &body)
+        ));
+    }
+    let response: Value = serde_json::from_slice(&body)?;
+    response["auth"]["client_token"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("AppRole login returned no client token"))
+}
+
+// Returns the token to authenticate with: `VAULT_TOKEN` if set, otherwise one obtained
+// by logging in with `VAULT_ROLE_ID` and `VAULT_SECRET_ID`.
+fn get_token(addr: &str, renew: bool) -> Result<String> {
+    if let Some(token) = config::get("VAULT_TOKEN")? {
+        return Ok(token);
+    }
+    let (Some(role_id), Some(secret_id)) = (
+        config::get("VAULT_ROLE_ID")?,
+        config::get("VAULT_SECRET_ID")?,
+    ) else {
+        return Err(anyhow!(
+            "VAULT_TOKEN, or VAULT_ROLE_ID and VAULT_SECRET_ID, configuration is required but not set"
+        ));
+    };
+
+    let mut cached = APPROLE_TOKEN.lock().unwrap();
+    if let Some(token) = cached.as_ref().filter(|_| !renew) {
+        return Ok(token.clone());
+    }
+    let token = approle_login(addr, &role_id, &secret_id)?;
+    *cached = Some(token.clone());
+    Ok(token)
+}
+
+// Sends an authenticated request to Vault and returns the JSON response, or `null` for
+// responses without a body.
+fn vault_request(method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+    let addr = get_vault_addr()?;
+    let token = get_token(&addr, false)?;
+    let (mut status, mut res_body) = send(&addr, Some(&token), method, path, body)?;
+    // An AppRole token may have expired, so log in again once
+    if status == 403 && config::get("VAULT_TOKEN")?.is_none() {
+        let token = get_token(&addr, true)?;
+        (status, res_body) = send(&addr, Some(&token), method, path, body)?;
+    }
+
+    if !(200..300).contains(&status) {
+        return Err(anyhow!(vault_error(status, &res_body)));
+    }
+    if res_body.is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(serde_json::from_slice(&res_body)?)
+}
+
+// Splits a secret path like `secret/app/db` into its mount, `secret`, and the path
+// within the mount, `app/db`.
+fn split_mount(path: &str) -> Result<(&str, &str)> {
+    let path = path.trim_matches('/');
+    match path.split_once('/') {
+        Some((mount, rest)) if !mount.is_empty() => Ok((mount, rest)),
+        None if !path.is_empty() => Ok((path, "")),
+        _ => Err(anyhow!(
+            "'path' must start with the mount of a KV engine, e.g. 'secret/my-app'"
+        )),
+    }
+}
+
+fn kv_version(mount: &str) -> Result<KvVersion> {
+    let tune = vault_request("GET", &format!("sys/mounts/{mount}/tune"), None)
+        .map_err(|e| anyhow!("failed to detect the KV version of mount '{mount}': {e}"))?;
+    Ok(match tune["options"]["version"].as_str() {
+        Some("2") => KvVersion::V2,
+        _ => KvVersion::V1,
+    })
+}
+
+// Returns the API path of a KV operation. KV v2 keeps secrets under `data/` and their
+// versions and keys under `metadata/`, while KV v1 uses the secret path as is.
+fn kv_path(mount: &str, path: &str, version: &KvVersion, operation: &KvOperation) -> String {
+    let prefix = match (version, operation) {
+        (KvVersion::V1, _) => None,
+        (KvVersion::V2, KvOperation::List) => Some("metadata"),
+        (KvVersion::V2, _) => Some("data"),
+    };
+    [
+        Some(mount),
+        prefix,
+        Some(path).filter(|path| !path.is_empty()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+fn resolve_kv_path(path: &str, operation: &KvOperation) -> Result<(KvVersion, String)> {
+    let (mount, path) = split_mount(path)?;
+    let version = kv_version(mount)?;
+    let api_path = kv_path(mount, path, &version, operation);
+    Ok((version, api_path))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    text_result(serde_json::to_string(value)?)
+}
+
+fn text_result(text: String) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "vault_delete_secret" => delete_secret(&args),
+        "vault_generate_credentials" => generate_credentials(&args),
+        "vault_list_secrets" => list_secrets(&args),
+        "vault_read_secret" => read_secret(&args),
+        "vault_write_secret" => write_secret(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn delete_secret(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "path");
+    let path = get_required_string_arg(args, "path")?;
+    let (_, api_path) = resolve_kv_path(path, &KvOperation::Delete)?;
+    vault_request("DELETE", &api_path, None)?;
+    text_result(format!("Deleted secret {path}"))
+}
+
+fn generate_credentials(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "path");
+    let path = get_required_string_arg(args, "path")?.trim_matches('/');
+    let response = vault_request("GET", path, None)?;
+    json_result(&json!({
+        "data": response["data"],
+        "lease_id": response["lease_id"],
+        "lease_duration": response["lease_duration"],
+        "renewable": response["renewable"],
+    }))
+}
+
+fn list_secrets(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "path");
+    let path = get_required_string_arg(args, "path")?;
+    let (_, api_path) = resolve_kv_path(path, &KvOperation::List)?;
+    // Vault answers LIST requests, which plugins cannot send, to GET with list=true
+    let response = vault_request("GET", &format!("{api_path}?list=true"), None)?;
+    json_result(&response["data"]["keys"])
+}
+
+fn read_secret(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "path");
+    let path = get_required_string_arg(args, "path")?;
+    let (version, mut api_path) = resolve_kv_path(path, &KvOperation::Read)?;
+    if let (KvVersion::V2, Some(secret_version)) = (&version, get_int_arg(args, "version")) {
+        api_path = format!("{api_path}?version={secret_version}");
+    }
+
+    let response = vault_request("GET", &api_path, None)?;
+    match version {
+        KvVersion::V1 => json_result(&response["data"]),
+        KvVersion::V2 => json_result(&json!({
+            "data": response["data"]["data"],
+            "metadata": response["data"]["metadata"],
+        })),
+    }
+}
+
+fn write_secret(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "path", "data");
+    let path = get_required_string_arg(args, "path")?;
+    let Some(data) = args.get("data").filter(|data| data.is_object()) else {
+        return Ok(error_result(
+            "Error: 'data' must be an object of key/value pairs".to_string(),
+        ));
+    };
+
+    let (version, api_path) = resolve_kv_path(path, &KvOperation::Write)?;
+    let body = match version {
+        KvVersion::V1 => data.clone(),
+        KvVersion::V2 => json!({"data": data}),
+    };
+    let response = vault_request("POST", &api_path, Some(&body))?;
+    match version {
+        KvVersion::V1 => text_result(format!("Wrote secret {path}")),
+        // KV v2 returns the metadata of the new version
+        KvVersion::V2 => json_result(&response["data"]),
+    }
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations(read_only: bool, destructive: bool, idempotent: bool) -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(destructive),
+        idempotent_hint: Some(idempotent),
+        open_world_hint: Some(true),
+        read_only_hint: Some(read_only),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+fn path_schema(description: &str) -> Value {
+    json!({
+        "path": {
+            "type": "string",
+            "description": description,
+        },
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    let secret_path = "The path of the secret, starting with the mount of the KV engine, e.g. 'secret/my-app/db'. Works with both KV v1 and v2.";
+
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(false, true, true),
+                description: Some("Deletes a secret from a KV engine. On KV v2 this deletes the latest version, which can be undeleted.".to_string()),
+                input_schema: tool_schema(path_schema(secret_path), &["path"]),
+                name: "vault_delete_secret".to_string(),
+                title: Some("Delete Secret".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, false, false),
+                description: Some("Generates dynamic credentials, e.g. a database username and password, and returns them with their lease.".to_string()),
+                input_schema: tool_schema(
+                    path_schema("The credentials path of a secrets engine, e.g. 'database/creds/readonly'."),
+                    &["path"],
+                ),
+                name: "vault_generate_credentials".to_string(),
+                title: Some("Generate Credentials".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, false, true),
+                description: Some("Lists the keys under a path of a KV engine. Keys ending with '/' are folders.".to_string()),
+                input_schema: tool_schema(
+                    path_schema("The path to list, starting with the mount of the KV engine, e.g. 'secret/my-app'."),
+                    &["path"],
+                ),
+                name: "vault_list_secrets".to_string(),
+                title: Some("List Secrets".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(true, false, true),
+                description: Some("Reads the key/value pairs of a secret from a KV engine.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "path": {
+                            "type": "string",
+                            "description": secret_path,
+                        },
+                        "version": {
+                            "type": "integer",
+                            "description": "The version to read from a KV v2 engine. Defaults to the latest version.",
+                        },
+                    }),
+                    &["path"],
+                ),
+                name: "vault_read_secret".to_string(),
+                title: Some("Read Secret".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(false, true, true),
+                description: Some("Writes key/value pairs to a secret in a KV engine, replacing its current data.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "path": {
+                            "type": "string",
+                            "description": secret_path,
+                        },
+                        "data": {
+                            "type": "object",
+                            "description": "The key/value pairs of the secret.",
+                        },
+                    }),
+                    &["path", "data"],
+                ),
+                name: "vault_write_secret".to_string(),
+                title: Some("Write Secret".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_split_mount() {
+        assert_eq!(split_mount("secret/app/db").unwrap(), ("secret", "app/db"));
+        assert_eq!(split_mount("/secret/app/").unwrap(), ("secret", "app"));
+        assert_eq!(split_mount("secret").unwrap(), ("secret", ""));
+        assert!(split_mount("").is_err());
+        assert!(split_mount("/").is_err());
+    }
+
+    #[test]
+    fn test_kv_path() {
+        assert_eq!(
+            kv_path("secret", "app/db", &KvVersion::V1, &KvOperation::Read),
+            "secret/app/db"
+        );
+        assert_eq!(
+            kv_path("secret", "app/db", &KvVersion::V1, &KvOperation::List),
+            "secret/app/db"
+        );
+        assert_eq!(
+            kv_path("secret", "app/db", &KvVersion::V2, &KvOperation::Read),
+            "secret/data/app/db"
+        );
+        assert_eq!(
+            kv_path("secret", "app/db", &KvVersion::V2, &KvOperation::Write),
+            "secret/data/app/db"
+        );
+        assert_eq!(
+            kv_path("secret", "app/db", &KvVersion::V2, &KvOperation::Delete),
+            "secret/data/app/db"
+        );
+        assert_eq!(
+            kv_path("secret", "", &KvVersion::V2, &KvOperation::List),
+            "secret/metadata"
+        );
+    }
+
+    #[test]
+    fn test_vault_error() {
+        assert_eq!(
+            vault_error(403, br#"{"errors": ["permission denied"]}"#),
+            "Vault returned 403: permission denied"
+        );
+        assert_eq!(
+            vault_error(503, b"Vault is sealed"),
+            "Vault returned 503: Vault is sealed"
+        );
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("vault_read_secret", json!({})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "vault_write_secret",
+            json!({"path": "secret/app", "data": "password"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "vault_delete_secret",
+                "vault_generate_credentials",
+                "vault_list_secrets",
+                "vault_read_secret",
+                "vault_write_secret",
+            ]
+        );
+    }
+}
@@ -0,0 +1,627 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, FixedOffset};
+use extism_pdk::{config, http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+const OPENWEATHERMAP_API_URL: &str = "https://api.openweathermap.org/data/2.5";
+
+const UNITS: [&str; 3] = ["metric", "imperial", "standard"];
+
+const MAX_FORECAST_DAYS: i64 = 5;
+
+#[derive(Debug, PartialEq)]
+enum Location {
+    City(String),
+    Coordinates(f64, f64),
+}
+
+impl Location {
+    fn query(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Location::City(name) => vec![("q", name.clone())],
+            Location::Coordinates(lat, lon) => {
+                vec![("lat", lat.to_string()), ("lon", lon.to_string())]
+            }
+        }
+    }
+}
+
+// Parses a location, which is either `lat,lon` or a city name like `London,GB`.
+fn parse_location(location: &str) -> Result<Location> {
+    let location = location.trim();
+    if location.is_empty() {
+        return Err(anyhow!("'location' must not be empty"));
+    }
+    if let Some((lat, lon)) = location.split_once(',') {
+        if let (Ok(lat), Ok(lon)) = (lat.trim().parse::<f64>(), lon.trim().parse::<f64>()) {
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+                return Err(anyhow!(
+                    "coordinates must be between -90 and 90 latitude and -180 and 180 longitude"
+                ));
+            }
+            return Ok(Location::Coordinates(lat, lon));
+        }
+    }
+    Ok(Location::City(location.to_string()))
+}
+
+fn get_units(args: &Map<String, Value>) -> Result<&str> {
+    let units = get_string_arg(args, "units").unwrap_or("metric");
+    if !UNITS.contains(&units) {
+        return Err(anyhow!(
+            "'units' must be one of metric, imperial or standard"
+        ));
+    }
+    Ok(units)
+}
+
+fn openweathermap_get(path: &str, mut query: Vec<(&str, String)>) -> Result<Value> {
+    let api_key = config::get("OPENWEATHERMAP_API_KEY")?
+        .ok_or_else(|| anyhow!("OPENWEATHERMAP_API_KEY configuration is required but not set"))?;
+    query.push(("appid", api_key));
+    let query = query
+        .iter()
+        .map(|(key, value)| format!("{key}={}", urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let req = HttpRequest::new(format!("{OPENWEATHERMAP_API_URL}{path}?{query}"))
+        .with_method("GET")
+        .with_header("User-Agent", "hyper-mcp/0.1.0");
+    let res = request::<()>(&req, None)?;
+    let body = res.body();
+    if !(200..300).contains(&res.status_code()) {
+        // Errors look like `{"cod": "404", "message": "city not found"}`
+        let message = serde_json::from_slice::<Value>(&body)
+            .ok()
+            .and_then(|error| error["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| String::from_utf8_lossy(&body).to_string());
+        return Err(anyhow!(
+            "OpenWeatherMap returned {}: {}",
+            res.status_code(),
+            message
+        ));
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+// Returns the local time of a Unix timestamp, given the offset of the location's
+// timezone from UTC in seconds.
+fn local_time(timestamp: i64, offset_secs: i64) -> Option<DateTime<FixedOffset>> {
+    let offset = FixedOffset::east_opt(offset_secs as i32)?;
+    Some(DateTime::from_timestamp(timestamp, 0)?.with_timezone(&offset))
+}
+
+fn format_local_time(timestamp: &Value, offset_secs: i64) -> Value {
+    timestamp
+        .as_i64()
+        .and_then(|timestamp| local_time(timestamp, offset_secs))
+        .map(|time| json!(time.to_rfc3339()))
+        .unwrap_or(Value::Null)
+}
+
+fn temperature_unit(units: &str) -> &'static str {
+    match units {
+        "imperial" => "°F",
+        "standard" => "K",
+        _ => "°C",
+    }
+}
+
+fn wind_speed_unit(units: &str) -> &'static str {
+    match units {
+        "imperial" => "mph",
+        _ => "m/s",
+    }
+}
+
+fn current_conditions(weather: &Value, units: &str) -> Value {
+    let offset = weather["timezone"].as_i64().unwrap_or(0);
+    json!({
+        "location": weather["name"],
+        "country": weather["sys"]["country"],
+        "coordinates": weather["coord"],
+        "description": weather["weather"][0]["description"],
+        "temperature": weather["main"]["temp"],
+        "feels_like": weather["main"]["feels_like"],
+        "temperature_min": weather["main"]["temp_min"],
+        "temperature_max": weather["main"]["temp_max"],
+        "temperature_unit": temperature_unit(units),
+        "humidity": weather["main"]["humidity"],
+        "pressure": weather["main"]["pressure"],
+        "wind_speed": weather["wind"]["speed"],
+        "wind_speed_unit": wind_speed_unit(units),
+        "wind_direction": weather["wind"]["deg"],
+        "cloudiness": weather["clouds"]["all"],
+        "visibility": weather["visibility"],
+        "sunrise": format_local_time(&weather["sys"]["sunrise"], offset),
+        "sunset": format_local_time(&weather["sys"]["sunset"], offset),
+        "time": format_local_time(&weather["dt"], offset),
+    })
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// Groups the 3-hour intervals of a `/forecast` response into a summary per local day,
+// for at most `days` days.
+fn daily_summaries(forecast: &Value, days: usize, units: &str) -> Vec<Value> {
+    let offset = forecast["city"]["timezone"].as_i64().unwrap_or(0);
+    let mut intervals_by_date: BTreeMap<String, Vec<&Value>> = BTreeMap::new();
+    for interval in forecast["list"].as_array().into_iter().flatten() {
+        let Some(time) = interval["dt"]
+            .as_i64()
+            .and_then(|dt| local_time(dt, offset))
+        else {
+            continue;
+        };
+        intervals_by_date
+            .entry(time.format("%Y-%m-%d").to_string())
+            .or_default()
+            .push(interval);
+    }
+
+    intervals_by_date
+        .into_iter()
+        .take(days)
+        .map(|(date, intervals)| {
+            let values = |f: fn(&Value) -> Option<f64>| -> Vec<f64> {
+                intervals
+                    .iter()
+                    .filter_map(|interval| f(interval))
+                    .collect()
+            };
+            let temperatures_min = values(|interval| interval["main"]["temp_min"].as_f64());
+            let temperatures_max = values(|interval| interval["main"]["temp_max"].as_f64());
+            let humidities = values(|interval| interval["main"]["humidity"].as_f64());
+            let wind_speeds = values(|interval| interval["wind"]["speed"].as_f64());
+            let precipitation = values(|interval| interval["pop"].as_f64());
+
+            // The most common description of the day, preferring the earliest on ties
+            let mut descriptions: Vec<(&str, usize)> = Vec::new();
+            for interval in &intervals {
+                if let Some(description) = interval["weather"][0]["description"].as_str() {
+                    match descriptions.iter_mut().find(|(d, _)| *d == description) {
+                        Some((_, count)) => *count += 1,
+                        None => descriptions.push((description, 1)),
+                    }
+                }
+            }
+            let description = descriptions
+                .iter()
+                .rev()
+                .max_by_key(|(_, count)| *count)
+                .map(|(description, _)| *description);
+
+            json!({
+                "date": date,
+                "description": description,
+                "temperature_min": temperatures_min.iter().copied().reduce(f64::min),
+                "temperature_max": temperatures_max.iter().copied().reduce(f64::max),
+                "temperature_unit": temperature_unit(units),
+                "humidity": (!humidities.is_empty())
+                    .then(|| round(humidities.iter().sum::<f64>() / humidities.len() as f64)),
+                "wind_speed_max": wind_speeds.iter().copied().reduce(f64::max),
+                "wind_speed_unit": wind_speed_unit(units),
+                "precipitation_probability": precipitation.iter().copied().reduce(f64::max),
+            })
+        })
+        .collect()
+}
+
+fn air_quality(pollution: &Value) -> Result<Value> {
+    let current = pollution["list"]
+        .get(0)
+        .ok_or_else(|| anyhow!("OpenWeatherMap returned no air quality data"))?;
+    let aqi = current["main"]["aqi"].as_i64();
+    let label = match aqi {
+        Some(1) => "Good",
+        Some(2) => "Fair",
+        Some(3) => "Moderate",
+        Some(4) => "Poor",
+        Some(5) => "Very Poor",
+        _ => "Unknown",
+    };
+    Ok(json!({
+        "aqi": aqi,
+        "aqi_label": label,
+        "components": current["components"],
+        "components_unit": "μg/m³",
+        "time": format_local_time(&current["dt"], 0),
+    }))
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "weather_air_quality" => weather_air_quality(&args),
+        "weather_current" => weather_current(&args),
+        "weather_forecast" => weather_forecast(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn weather_air_quality(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "lat", "lon");
+    let (Some(lat), Some(lon)) = (args["lat"].as_f64(), args["lon"].as_f64()) else {
+        return Ok(error_result(
+            "Error: 'lat' and 'lon' must be numbers".to_string(),
+        ));
+    };
+    let location = parse_location(&format!("{lat},{lon}"))?;
+
+    let pollution = openweathermap_get("/air_pollution", location.query())?;
+    json_result(&air_quality(&pollution)?)
+}
+
+fn weather_current(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "location");
+    let location = parse_location(get_required_string_arg(args, "location")?)?;
+    let units = get_units(args)?;
+
+    let mut query = location.query();
+    query.push(("units", units.to_string()));
+    let weather = openweathermap_get("/weather", query)?;
+    json_result(&current_conditions(&weather, units))
+}
+
+fn weather_forecast(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "location");
+    let location = parse_location(get_required_string_arg(args, "location")?)?;
+    let units = get_units(args)?;
+    let days = get_int_arg(args, "days").unwrap_or(MAX_FORECAST_DAYS);
+    if !(1..=MAX_FORECAST_DAYS).contains(&days) {
+        return Ok(error_result(format!(
+            "Error: 'days' must be between 1 and {MAX_FORECAST_DAYS}"
+        )));
+    }
+
+    let mut query = location.query();
+    query.push(("units", units.to_string()));
+    let forecast = openweathermap_get("/forecast", query)?;
+    let offset = forecast["city"]["timezone"].as_i64().unwrap_or(0);
+    json_result(&json!({
+        "location": forecast["city"]["name"],
+        "country": forecast["city"]["country"],
+        "coordinates": forecast["city"]["coord"],
+        "sunrise": format_local_time(&forecast["city"]["sunrise"], offset),
+        "sunset": format_local_time(&forecast["city"]["sunset"], offset),
+        "days": daily_summaries(&forecast, days as usize, units),
+    }))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+// Adds the `location` and `units` properties shared by the weather tools.
+fn location_properties(properties: Value) -> Value {
+    let mut properties = properties.as_object().cloned().unwrap_or_default();
+    properties.insert(
+        "location".to_string(),
+        json!({
+            "type": "string",
+            "description": "A city name, optionally with a country code like 'London,GB', or coordinates as 'lat,lon' like '51.51,-0.13'.",
+        }),
+    );
+    properties.insert(
+        "units".to_string(),
+        json!({
+            "type": "string",
+            "enum": UNITS,
+            "description": "metric for Celsius and m/s, imperial for Fahrenheit and mph, or standard for Kelvin and m/s. Defaults to metric.",
+        }),
+    );
+    Value::Object(properties)
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the air quality index (1 = Good to 5 = Very Poor) and pollutant concentrations at a location.".to_string()),
+                input_schema: tool_schema(
+                    json!({
+                        "lat": {
+                            "type": "number",
+                            "description": "The latitude.",
+                        },
+                        "lon": {
+                            "type": "number",
+                            "description": "The longitude.",
+                        },
+                    }),
+                    &["lat", "lon"],
+                ),
+                name: "weather_air_quality".to_string(),
+                title: Some("Air Quality".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the current weather at a location: temperature, humidity, wind, description, sunrise and sunset.".to_string()),
+                input_schema: tool_schema(location_properties(json!({})), &["location"]),
+                name: "weather_current".to_string(),
+                title: Some("Current Weather".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns a daily weather forecast for up to 5 days at a location, summarized from 3-hour intervals.".to_string()),
+                input_schema: tool_schema(
+                    location_properties(json!({
+                        "days": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": MAX_FORECAST_DAYS,
+                            "description": format!("Number of days to forecast, from 1 to {MAX_FORECAST_DAYS}. Defaults to {MAX_FORECAST_DAYS}."),
+                        },
+                    })),
+                    &["location"],
+                ),
+                name: "weather_forecast".to_string(),
+                title: Some("Weather Forecast".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    fn interval(dt: i64, temp_min: f64, temp_max: f64, description: &str) -> Value {
+        json!({
+            "dt": dt,
+            "main": {"temp_min": temp_min, "temp_max": temp_max, "humidity": 80},
+            "wind": {"speed": temp_max / 10.0},
+            "pop": 0.2,
+            "weather": [{"description": description}],
+        })
+    }
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!(
+            parse_location("51.51, -0.13").unwrap(),
+            Location::Coordinates(51.51, -0.13)
+        );
+        assert_eq!(
+            parse_location("London,GB").unwrap(),
+            Location::City("London,GB".to_string())
+        );
+        assert_eq!(
+            parse_location(" Tokyo ").unwrap(),
+            Location::City("Tokyo".to_string())
+        );
+        assert!(parse_location("91,0").is_err());
+        assert!(parse_location("").is_err());
+    }
+
+    #[test]
+    fn test_current_conditions() {
+        let weather = json!({
+            "name": "London",
+            "timezone": 3600,
+            "dt": 1718445600,
+            "sys": {"country": "GB", "sunrise": 1718423000, "sunset": 1718482800},
+            "main": {"temp": 18.5, "feels_like": 18.0, "humidity": 72, "pressure": 1012},
+            "wind": {"speed": 4.1, "deg": 230},
+            "weather": [{"description": "light rain"}],
+        });
+        let conditions = current_conditions(&weather, "imperial");
+        assert_eq!(conditions["location"], "London");
+        assert_eq!(conditions["description"], "light rain");
+        assert_eq!(conditions["temperature"], 18.5);
+        assert_eq!(conditions["temperature_unit"], "°F");
+        assert_eq!(conditions["wind_speed_unit"], "mph");
+        assert_eq!(conditions["sunrise"], "2024-06-15T04:43:20+01:00");
+        assert_eq!(conditions["sunset"], "2024-06-15T21:20:00+01:00");
+    }
+
+    #[test]
+    fn test_daily_summaries() {
+        // 2024-06-15T00:00:00Z, in a timezone 2 hours ahead of UTC
+        let midnight = 1718409600;
+        let forecast = json!({
+            "city": {"timezone": 7200},
+            "list": [
+                // 2024-06-14T23:00:00+02:00
+                interval(midnight - 3 * 3600, 14.0, 15.0, "clear sky"),
+                interval(midnight, 12.0, 13.0, "clear sky"),
+                interval(midnight + 3 * 3600, 13.0, 19.0, "light rain"),
+                interval(midnight + 6 * 3600, 16.0, 22.0, "light rain"),
+                // 2024-06-16T01:00:00+02:00
+                interval(midnight + 23 * 3600, 11.0, 12.0, "few clouds"),
+            ],
+        });
+
+        let days = daily_summaries(&forecast, 5, "metric");
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0]["date"], "2024-06-14");
+        assert_eq!(
+            days[1],
+            json!({
+                "date": "2024-06-15",
+                "description": "light rain",
+                "temperature_min": 12.0,
+                "temperature_max": 22.0,
+                "temperature_unit": "°C",
+                "humidity": 80.0,
+                "wind_speed_max": 2.2,
+                "wind_speed_unit": "m/s",
+                "precipitation_probability": 0.2,
+            })
+        );
+        assert_eq!(days[2]["date"], "2024-06-16");
+
+        assert_eq!(daily_summaries(&forecast, 1, "metric").len(), 1);
+    }
+
+    #[test]
+    fn test_air_quality() {
+        let pollution = json!({
+            "list": [{
+                "dt": 1718445600,
+                "main": {"aqi": 2},
+                "components": {"pm2_5": 5.2, "o3": 68.7},
+            }],
+        });
+        let quality = air_quality(&pollution).unwrap();
+        assert_eq!(quality["aqi"], 2);
+        assert_eq!(quality["aqi_label"], "Fair");
+        assert_eq!(quality["components"]["pm2_5"], 5.2);
+
+        assert!(air_quality(&json!({"list": []})).is_err());
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call(
+            "weather_current",
+            json!({"location": "London", "units": "kelvin"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("weather_forecast", json!({"location": "London", "days": 7})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("weather_air_quality", json!({"lat": "51.5", "lon": 0})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["weather_air_quality", "weather_current", "weather_forecast"]
+        );
+    }
+}
@@ -0,0 +1,751 @@
+mod pdk;
+
+use anyhow::{anyhow, Result};
+use extism_pdk::{http::request, HttpRequest};
+use pdk::types::*;
+use serde_json::{json, Map, Value};
+
+const DEFAULT_LANGUAGE: &str = "en";
+
+const USER_AGENT: &str = "hyper-mcp/0.1.0 (https://github.com/tuananh/hyper-mcp)";
+
+const DEFAULT_SEARCH_LIMIT: i64 = 10;
+
+const MAX_SEARCH_LIMIT: i64 = 50;
+
+const DEFAULT_LINKS_LIMIT: i64 = 100;
+
+const MAX_LINKS_LIMIT: i64 = 500;
+
+// Returns the language edition to use, like `en` or `zh-yue`, which becomes part of the
+// host name.
+fn get_language(args: &Map<String, Value>) -> Result<&str> {
+    let language = get_string_arg(args, "language").unwrap_or(DEFAULT_LANGUAGE);
+    if language.is_empty()
+        || language.len() > 12
+        || !language.chars().all(|c| c.is_ascii_lowercase() || c == '-')
+    {
+        return Err(anyhow!(
+            "'language' must be a Wikipedia language code like en or de, got '{language}'"
+        ));
+    }
+    Ok(language)
+}
+
+fn get_limit(args: &Map<String, Value>, default: i64, max: i64) -> Result<i64> {
+    let limit = get_int_arg(args, "limit").unwrap_or(default);
+    if !(1..=max).contains(&limit) {
+        return Err(anyhow!("'limit' must be between 1 and {max}"));
+    }
+    Ok(limit)
+}
+
+// Encodes a title for a REST API path, where spaces are written as underscores.
+fn title_path(title: &str) -> String {
+    urlencoding::encode(&title.trim().replace(' ', "_")).into_owned()
+}
+
+fn wikipedia_get(url: String) -> Result<Value> {
+    let req = HttpRequest::new(url)
+        .with_method("GET")
+        .with_header("Accept", "application/json")
+        .with_header("User-Agent", USER_AGENT);
+    let res = request::<()>(&req, None)?;
+    let body = res.body();
+    if !(200..300).contains(&res.status_code()) {
+        // REST API errors look like `{"title": "Not found.", "detail": "..."}`
+        let message = serde_json::from_slice::<Value>(&body)
+            .ok()
+            .and_then(|error| {
+                error["detail"]
+                    .as_str()
+                    .or(error["title"].as_str())
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| String::from_utf8_lossy(&body).to_string());
+        return Err(anyhow!(
+            "Wikipedia returned {}: {}",
+            res.status_code(),
+            message
+        ));
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn rest_get(language: &str, path: &str) -> Result<Value> {
+    wikipedia_get(format!(
+        "https://{language}.wikipedia.org/api/rest_v1{path}"
+    ))
+}
+
+// Calls the MediaWiki Action API, which reports errors with a 200 status and an
+// `error` object.
+fn action_get(language: &str, params: &[(&str, &str)]) -> Result<Value> {
+    let query = params
+        .iter()
+        .chain(&[("format", "json"), ("formatversion", "2")])
+        .map(|(name, value)| format!("{name}={}", urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let response = wikipedia_get(format!(
+        "https://{language}.wikipedia.org/w/api.php?{query}"
+    ))?;
+    if let Some(error) = response.get("error") {
+        return Err(anyhow!(
+            "Wikipedia returned {}: {}",
+            error["code"].as_str().unwrap_or("error"),
+            error["info"].as_str().unwrap_or_default()
+        ));
+    }
+    Ok(response)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = entity.strip_prefix('#')?;
+                let code = match code.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+// Removes citation markers like `[12]` left behind by stripped references.
+fn remove_citations(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        cleaned.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match rest[1..].find(']') {
+            Some(end) if end > 0 && rest[1..end + 1].chars().all(|c| c.is_ascii_digit()) => {
+                rest = &rest[end + 2..];
+            }
+            _ => {
+                cleaned.push('[');
+                rest = &rest[1..];
+            }
+        }
+    }
+    cleaned.push_str(rest);
+    cleaned
+}
+
+// Converts HTML to plain text: tags are removed along with the contents of `style` and
+// `script` elements, block elements start new lines and entities are decoded.
+fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if !tag.starts_with('/') && matches!(name.as_str(), "style" | "script") {
+            // Skip to the closing tag, which is then removed like any other
+            rest = match rest.to_ascii_lowercase().find(&format!("</{name}")) {
+                Some(close) => &rest[close..],
+                None => "",
+            };
+            continue;
+        }
+        if matches!(
+            name.as_str(),
+            "br" | "div"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "li"
+                | "ol"
+                | "p"
+                | "table"
+                | "tr"
+                | "ul"
+        ) {
+            text.push('\n');
+        }
+    }
+    text.push_str(rest);
+
+    let text = remove_citations(&decode_entities(&text));
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        // Keep single blank lines between paragraphs
+        if !line.is_empty() || lines.last().is_some_and(|last| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+// Returns the index of the section named `name`, ignoring case and markup, or an error
+// listing the available sections.
+fn find_section(sections: &[Value], name: &str) -> Result<String> {
+    let name = name.trim().to_lowercase();
+    let titles: Vec<String> = sections
+        .iter()
+        .map(|section| strip_html(section["line"].as_str().unwrap_or_default()))
+        .collect();
+    sections
+        .iter()
+        .zip(&titles)
+        .find(|(_, title)| title.to_lowercase() == name)
+        .and_then(|(section, _)| section["index"].as_str().map(str::to_string))
+        .ok_or_else(|| {
+            anyhow!(
+                "section '{name}' not found, available sections are: {}",
+                titles.join(", ")
+            )
+        })
+}
+
+fn json_result(value: &Value) -> Result<CallToolResult> {
+    Ok(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: serde_json::to_string(value)?,
+
+            ..Default::default()
+        })],
+
+        ..Default::default()
+    })
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text,
+
+            ..Default::default()
+        })],
+        is_error: Some(true),
+
+        ..Default::default()
+    }
+}
+
+pub(crate) fn call_tool(input: CallToolRequest) -> Result<CallToolResult> {
+    let args = input.request.arguments.clone().unwrap_or_default();
+    let result = match input.request.name.as_str() {
+        "wiki_get_links" => wiki_get_links(&args),
+        "wiki_get_section" => wiki_get_section(&args),
+        "wiki_get_summary" => wiki_get_summary(&args),
+        "wiki_search" => wiki_search(&args),
+        _ => return Err(anyhow!("Unknown tool: {}", input.request.name)),
+    };
+    Ok(result.unwrap_or_else(|e| error_result(format!("Error: {e}"))))
+}
+
+fn wiki_get_links(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "title");
+    let title = get_required_string_arg(args, "title")?;
+    let language = get_language(args)?;
+    let limit = get_limit(args, DEFAULT_LINKS_LIMIT, MAX_LINKS_LIMIT)? as usize;
+
+    let mut links: Vec<Value> = Vec::new();
+    let mut continuation: Vec<(String, String)> = Vec::new();
+    while links.len() < limit {
+        let mut params = vec![
+            ("action", "query"),
+            ("prop", "links"),
+            ("titles", title),
+            ("plnamespace", "0"),
+            ("pllimit", "max"),
+            ("redirects", "1"),
+        ];
+        params.extend(
+            continuation
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        );
+        let response = action_get(language, &params)?;
+
+        let page = &response["query"]["pages"][0];
+        if page["missing"].as_bool().unwrap_or(false) {
+            return Err(anyhow!("article '{title}' not found"));
+        }
+        links.extend(
+            page["links"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|link| link["title"].as_str())
+                .map(|title| json!(title)),
+        );
+
+        // Results continue with the parameters of the `continue` object
+        let Some(next) = response["continue"].as_object() else {
+            break;
+        };
+        continuation = next
+            .iter()
+            .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+            .collect();
+    }
+    links.truncate(limit);
+
+    json_result(&json!({
+        "title": title,
+        "links": links,
+    }))
+}
+
+fn wiki_get_section(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "title", "section_name");
+    let title = get_required_string_arg(args, "title")?;
+    let section_name = get_required_string_arg(args, "section_name")?;
+    let language = get_language(args)?;
+
+    let response = action_get(
+        language,
+        &[
+            ("action", "parse"),
+            ("page", title),
+            ("prop", "sections"),
+            ("redirects", "1"),
+        ],
+    )?;
+    let sections = response["parse"]["sections"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let index = find_section(&sections, section_name)?;
+
+    let response = action_get(
+        language,
+        &[
+            ("action", "parse"),
+            ("page", title),
+            ("section", index.as_str()),
+            ("prop", "text"),
+            ("disableeditsection", "1"),
+            ("redirects", "1"),
+        ],
+    )?;
+    let html = response["parse"]["text"].as_str().unwrap_or_default();
+
+    json_result(&json!({
+        "title": response["parse"]["title"],
+        "section": section_name,
+        "content": strip_html(html),
+    }))
+}
+
+fn wiki_get_summary(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "title");
+    let title = get_required_string_arg(args, "title")?;
+    let language = get_language(args)?;
+
+    let summary = rest_get(
+        language,
+        &format!("/page/summary/{}?redirect=true", title_path(title)),
+    )?;
+    let extract = summary["extract_html"]
+        .as_str()
+        .or(summary["extract"].as_str())
+        .map(strip_html)
+        .unwrap_or_default();
+    let first_paragraph = extract.split("\n\n").next().unwrap_or_default().to_string();
+
+    let response = action_get(
+        language,
+        &[
+            ("action", "query"),
+            ("prop", "categories"),
+            ("titles", summary["title"].as_str().unwrap_or(title)),
+            ("clshow", "!hidden"),
+            ("cllimit", "max"),
+            ("redirects", "1"),
+        ],
+    )?;
+    // Category titles have a localized namespace prefix like `Category:`
+    let categories: Vec<&str> = response["query"]["pages"][0]["categories"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|category| category["title"].as_str())
+        .map(|category| category.split_once(':').map_or(category, |(_, name)| name))
+        .collect();
+
+    json_result(&json!({
+        "title": summary["title"],
+        "description": summary["description"],
+        "extract": first_paragraph,
+        "thumbnail_url": summary["thumbnail"]["source"],
+        "url": summary["content_urls"]["desktop"]["page"],
+        "categories": categories,
+    }))
+}
+
+fn wiki_search(args: &Map<String, Value>) -> Result<CallToolResult> {
+    require_args!(args, "query");
+    let query = get_required_string_arg(args, "query")?;
+    let language = get_language(args)?;
+    let limit = get_limit(args, DEFAULT_SEARCH_LIMIT, MAX_SEARCH_LIMIT)?.to_string();
+
+    let response = action_get(
+        language,
+        &[
+            ("action", "query"),
+            ("list", "search"),
+            ("srsearch", query),
+            ("srlimit", limit.as_str()),
+        ],
+    )?;
+    let results: Vec<Value> = response["query"]["search"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|result| {
+            json!({
+                "title": result["title"],
+                "snippet": strip_html(result["snippet"].as_str().unwrap_or_default()),
+                "pageid": result["pageid"],
+            })
+        })
+        .collect();
+
+    json_result(&json!({
+        "total_hits": response["query"]["searchinfo"]["totalhits"],
+        "results": results,
+    }))
+}
+
+// Provide completion suggestions for a partially-typed input.
+//
+// This function is called when the user requests autocompletion. The plugin should analyze the partial input and return matching completion suggestions based on the reference (prompt or resource) and argument context.
+pub(crate) fn complete(_input: CompleteRequest) -> Result<CompleteResult> {
+    Ok(CompleteResult::default())
+}
+
+// Retrieve a specific prompt by name.
+//
+// This function is called when the user requests a specific prompt. The plugin should return the prompt details including messages and optional description.
+pub(crate) fn get_prompt(_input: GetPromptRequest) -> Result<GetPromptResult> {
+    Err(anyhow!("get_prompt not implemented"))
+}
+
+// List all available prompts.
+//
+// This function should return a list of prompts that the plugin provides. Each prompt should include its name and a brief description of what it does. Supports pagination via cursor.
+pub(crate) fn list_prompts(_input: ListPromptsRequest) -> Result<ListPromptsResult> {
+    Ok(ListPromptsResult::default())
+}
+
+// List all available resource templates.
+//
+// This function should return a list of resource templates that the plugin provides. Templates are URI patterns that can match multiple resources. Supports pagination via cursor.
+pub(crate) fn list_resource_templates(
+    _input: ListResourceTemplatesRequest,
+) -> Result<ListResourceTemplatesResult> {
+    Ok(ListResourceTemplatesResult::default())
+}
+
+// List all available resources.
+//
+// This function should return a list of resources that the plugin provides. Resources are URI-based references to files, data, or services. Supports pagination via cursor.
+pub(crate) fn list_resources(_input: ListResourcesRequest) -> Result<ListResourcesResult> {
+    Ok(ListResourcesResult::default())
+}
+
+fn annotations() -> Option<ToolAnnotations> {
+    Some(ToolAnnotations {
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(true),
+        read_only_hint: Some(true),
+
+        ..Default::default()
+    })
+}
+
+fn tool_schema(properties: Value, required: &[&str]) -> ToolSchema {
+    ToolSchema {
+        properties: properties.as_object().cloned(),
+        required: (!required.is_empty())
+            .then(|| required.iter().map(|name| name.to_string()).collect()),
+
+        ..Default::default()
+    }
+}
+
+// Adds the `language` property shared by all tools.
+fn language_properties(properties: Value) -> Value {
+    let mut properties = properties.as_object().cloned().unwrap_or_default();
+    properties.insert(
+        "language".to_string(),
+        json!({
+            "type": "string",
+            "description": format!("The language edition of Wikipedia, like en, de or ja. Defaults to {DEFAULT_LANGUAGE}."),
+        }),
+    );
+    Value::Object(properties)
+}
+
+fn title_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "The title of the article, like 'Alan Turing'. Redirects are followed.",
+    })
+}
+
+// List all available tools.
+//
+// This function should return a list of all tools that the plugin provides. Each tool should include its name, description, and input schema. Supports pagination via cursor.
+pub(crate) fn list_tools(_input: ListToolsRequest) -> Result<ListToolsResult> {
+    Ok(ListToolsResult {
+        tools: vec![
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the titles of the articles linked from a Wikipedia article.".to_string()),
+                input_schema: tool_schema(
+                    language_properties(json!({
+                        "title": title_property(),
+                        "limit": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": MAX_LINKS_LIMIT,
+                            "description": format!("Maximum number of links to return. Defaults to {DEFAULT_LINKS_LIMIT}."),
+                        },
+                    })),
+                    &["title"],
+                ),
+                name: "wiki_get_links".to_string(),
+                title: Some("Get Article Links".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the plain text content of a section of a Wikipedia article, found by its heading.".to_string()),
+                input_schema: tool_schema(
+                    language_properties(json!({
+                        "title": title_property(),
+                        "section_name": {
+                            "type": "string",
+                            "description": "The heading of the section, like 'Early life'. Case is ignored.",
+                        },
+                    })),
+                    &["title", "section_name"],
+                ),
+                name: "wiki_get_section".to_string(),
+                title: Some("Get Article Section".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Returns the first paragraph of a Wikipedia article as plain text, with its short description, thumbnail URL and categories.".to_string()),
+                input_schema: tool_schema(
+                    language_properties(json!({
+                        "title": title_property(),
+                    })),
+                    &["title"],
+                ),
+                name: "wiki_get_summary".to_string(),
+                title: Some("Get Article Summary".to_string()),
+
+                ..Default::default()
+            },
+            Tool {
+                annotations: annotations(),
+                description: Some("Searches Wikipedia articles, returning their titles, page IDs and matching snippets.".to_string()),
+                input_schema: tool_schema(
+                    language_properties(json!({
+                        "query": {
+                            "type": "string",
+                            "description": "The search terms.",
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": MAX_SEARCH_LIMIT,
+                            "description": format!("Maximum number of results to return. Defaults to {DEFAULT_SEARCH_LIMIT}."),
+                        },
+                    })),
+                    &["query"],
+                ),
+                name: "wiki_search".to_string(),
+                title: Some("Search Wikipedia".to_string()),
+
+                ..Default::default()
+            },
+        ],
+
+        ..Default::default()
+    })
+}
+
+// Notification that the list of roots has changed.
+//
+// This is an optional notification handler. If implemented, the plugin will be notified whenever the roots list changes on the client side. This allows plugins to react to changes in the file system roots or other root resources.
+pub(crate) fn on_roots_list_changed(_input: PluginNotificationContext) -> Result<()> {
+    Ok(())
+}
+
+// Read the contents of a resource by its URI.
+//
+// This function is called when the user wants to read the contents of a specific resource. The plugin should retrieve and return the resource data with appropriate MIME type information.
+pub(crate) fn read_resource(_input: ReadResourceRequest) -> Result<ReadResourceResult> {
+    Err(anyhow!("read_resource not implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> Result<CallToolResult> {
+        call_tool(CallToolRequest {
+            context: PluginRequestContext::default(),
+            request: CallToolRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_strip_html() {
+        assert_eq!(
+            strip_html(
+                "<p><b>Alan Turing</b> was an English <a href=\"/wiki/Mathematician\">mathematician</a>.<sup class=\"reference\">[1]</sup></p>\n<p>He was born in London.</p>"
+            ),
+            "Alan Turing was an English mathematician.\n\nHe was born in London."
+        );
+        assert_eq!(
+            strip_html("<style>.mw-parser-output { color: red }</style><p>Text</p><script>alert(1)</script>"),
+            "Text"
+        );
+        assert_eq!(
+            strip_html("an <span class=\"searchmatch\">Enigma</span> machine"),
+            "an Enigma machine"
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("Fish &amp; chips &lt;3 &#8211; caf&#xE9;&nbsp;&quot;ok&quot;"),
+            "Fish & chips <3 – café \"ok\""
+        );
+        assert_eq!(
+            decode_entities("AT&T & co &unknown;"),
+            "AT&T & co &unknown;"
+        );
+    }
+
+    #[test]
+    fn test_remove_citations() {
+        assert_eq!(
+            remove_citations("A fact.[1][23] A [note] and []."),
+            "A fact. A [note] and []."
+        );
+    }
+
+    #[test]
+    fn test_find_section() {
+        let sections = json!([
+            {"index": "1", "line": "Early life and education"},
+            {"index": "2", "line": "<i>Enigma</i> codebreaking"},
+        ]);
+        let sections = sections.as_array().unwrap();
+        assert_eq!(
+            find_section(sections, "early life and education").unwrap(),
+            "1"
+        );
+        assert_eq!(find_section(sections, "Enigma codebreaking").unwrap(), "2");
+
+        let error = find_section(sections, "Legacy").unwrap_err().to_string();
+        assert!(error.contains("Early life and education, Enigma codebreaking"));
+    }
+
+    #[test]
+    fn test_title_path() {
+        assert_eq!(title_path("Alan Turing"), "Alan_Turing");
+        assert_eq!(title_path("AC/DC"), "AC%2FDC");
+    }
+
+    #[test]
+    fn test_call_tool_invalid_arguments() {
+        let result = call("wiki_search", json!({"query": "turing", "language": "EN"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call(
+            "wiki_search",
+            json!({"query": "turing", "language": "evil.example.com/"}),
+        )
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("wiki_search", json!({"query": "turing", "limit": 100})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let result = call("wiki_get_section", json!({"title": "Alan Turing"})).unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_call_tool_unknown_tool() {
+        assert!(call("unknown_tool", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_list_tools() {
+        let result = list_tools(ListToolsRequest::default()).unwrap();
+        let names: Vec<&str> = result.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "wiki_get_links",
+                "wiki_get_section",
+                "wiki_get_summary",
+                "wiki_search"
+            ]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::service::ServerState;
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
+use rmcp::model::ProtocolVersion;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// Builds the `/health`, `/ready` and `/info` endpoints of the streamable-http
+/// transport, for liveness and readiness probes. They report the plugins the sessions
+/// sharing `state` serve, as of each request.
+pub fn admin_router(state: Arc<ServerState>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/info", get(info))
+        .route("/ready", get(ready))
+        .with_state(state)
+}
+
+async fn health(State(state): State<Arc<ServerState>>) -> Json<Value> {
+    Json(json!({ "status": "ok", "plugins_loaded": state.loaded_plugins().len() }))
+}
+
+async fn info(State(state): State<Arc<ServerState>>) -> Json<Value> {
+    Json(json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "protocol_version": ProtocolVersion::LATEST,
+        "plugins": state.loaded_plugins(),
+    }))
+}
+
+/// Ready while at least one loaded plugin accepts calls, i.e. its circuit breaker is
+/// not open.
+async fn ready(State(state): State<Arc<ServerState>>) -> (StatusCode, Json<Value>) {
+    let plugins_loaded = state.loaded_plugins().len();
+    let plugins_available = state.available_plugins().len();
+    let (status, message) = if plugins_loaded == 0 {
+        (StatusCode::SERVICE_UNAVAILABLE, "no plugins loaded")
+    } else if plugins_available == 0 {
+        (StatusCode::SERVICE_UNAVAILABLE, "all plugin circuits open")
+    } else {
+        (StatusCode::OK, "ok")
+    };
+    let body = json!({
+        "status": message,
+        "plugins_loaded": plugins_loaded,
+        "plugins_available": plugins_available,
+    });
+    (status, Json(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CircuitBreakerConfig, Config, PluginConfig, PluginName};
+    use axum::{
+        body::{Body, to_bytes},
+        http::Request,
+    };
+    use std::str::FromStr;
+    use tower::ServiceExt;
+
+    async fn get_json(router: Router, uri: &str) -> (StatusCode, Value) {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn plugins() -> Vec<PluginName> {
+        vec![
+            PluginName::from_str("gitlab").unwrap(),
+            PluginName::from_str("rstime").unwrap(),
+        ]
+    }
+
+    fn server_state(plugins: Vec<PluginName>) -> Arc<ServerState> {
+        let config = Config {
+            circuit_breaker: CircuitBreakerConfig {
+                failure_threshold: 1,
+                reset_timeout_secs: 60,
+            },
+            plugins: plugins
+                .iter()
+                .map(|plugin_name| {
+                    let plugin_config = PluginConfig {
+                        url: url::Url::parse(&format!("file:///{plugin_name}.wasm")).unwrap(),
+                        digest: None,
+                        pinned_digest: None,
+                        enabled: true,
+                        tags: vec![],
+                        runtime_config: None,
+                    };
+                    (plugin_name.clone(), plugin_config)
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let state = Arc::new(ServerState::new(&config).unwrap());
+        state.set_loaded_plugins(plugins);
+        state
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let (status, body) = get_json(admin_router(server_state(plugins())), "/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "status": "ok", "plugins_loaded": 2 }));
+    }
+
+    #[tokio::test]
+    async fn test_ready() {
+        let state = server_state(plugins());
+        let (status, body) = get_json(admin_router(state.clone()), "/ready").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["plugins_loaded"], 2);
+        assert_eq!(body["plugins_available"], 2);
+
+        // Readiness follows the circuit breakers
+        state
+            .circuit_breaker(&plugins()[0])
+            .unwrap()
+            .record_failure();
+        let (status, body) = get_json(admin_router(state.clone()), "/ready").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["plugins_available"], 1);
+        state
+            .circuit_breaker(&plugins()[1])
+            .unwrap()
+            .record_failure();
+        let (status, body) = get_json(admin_router(state), "/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "all plugin circuits open");
+
+        let (status, body) = get_json(admin_router(server_state(vec![])), "/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["plugins_loaded"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_info() {
+        let (status, body) = get_json(admin_router(server_state(plugins())), "/info").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(body["protocol_version"], json!(ProtocolVersion::LATEST));
+        assert_eq!(body["plugins"], json!(["gitlab", "rstime"]));
+    }
+}
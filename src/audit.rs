@@ -0,0 +1,209 @@
+use crate::{
+    config::{AuditLogConfig, PluginName},
+    wasm::sha256_digest,
+};
+use anyhow::{Context, Result};
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, JsonObject},
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    sync::Mutex,
+    time::Duration,
+};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+struct AuditRecord<'a> {
+    timestamp: String,
+    session_id: Uuid,
+    plugin: &'a PluginName,
+    tool: &'a str,
+    arguments_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arguments: Option<&'a JsonObject>,
+    is_error: bool,
+    duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+}
+
+/// Appends a JSON line for every tool call to the file configured in `audit_log`.
+///
+/// Arguments are only recorded as a SHA-256 hash unless `include_arguments` is set, so
+/// operators can spot unusual calls without logging sensitive data.
+#[derive(Debug)]
+pub struct AuditLog {
+    config: AuditLogConfig,
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    pub fn open(config: &AuditLogConfig) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)
+            .with_context(|| format!("Failed to open audit log at {}", config.path.display()))?;
+        Ok(Self {
+            config: config.clone(),
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn record(
+        &self,
+        session_id: Uuid,
+        plugin: &PluginName,
+        tool: &str,
+        arguments: Option<&JsonObject>,
+        result: &Result<CallToolResult, McpError>,
+        duration: Duration,
+    ) {
+        let record = AuditRecord {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            session_id,
+            plugin,
+            tool,
+            arguments_hash: sha256_digest(&serde_json::to_vec(&arguments).unwrap_or_default()),
+            arguments: arguments.filter(|_| self.config.include_arguments),
+            is_error: match result {
+                Ok(result) => result.is_error.unwrap_or(false),
+                Err(_) => true,
+            },
+            duration_ms: duration.as_millis() as u64,
+            result: self
+                .config
+                .include_result
+                .then(|| match result {
+                    Ok(result) => serde_json::to_value(result),
+                    Err(e) => serde_json::to_value(e),
+                })
+                .and_then(Result::ok),
+        };
+
+        let mut line = match serde_json::to_vec(&record) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::error!("Failed to serialize audit record for {plugin}-{tool}: {e}");
+                return;
+            }
+        };
+        line.push(b'\n');
+        // Write the whole line at once so records from concurrent calls never interleave
+        if let Err(e) = self.file.lock().unwrap().write_all(&line) {
+            tracing::error!(
+                "Failed to write audit record to {}: {e}",
+                self.config.path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::Content;
+    use serde_json::json;
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    fn read_records(config: &AuditLogConfig) -> Vec<Value> {
+        std::fs::read_to_string(&config.path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_audit_log_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = AuditLogConfig {
+            path: temp_dir.path().join("audit.jsonl"),
+            include_arguments: false,
+            include_result: false,
+        };
+        let audit_log = AuditLog::open(&config).unwrap();
+        let session_id = Uuid::new_v4();
+        let plugin = PluginName::from_str("gitlab").unwrap();
+        let arguments = json!({"project_id": "42"}).as_object().unwrap().clone();
+
+        audit_log.record(
+            session_id,
+            &plugin,
+            "gl_get_issue",
+            Some(&arguments),
+            &Ok(CallToolResult::success(vec![Content::text("issue")])),
+            Duration::from_millis(12),
+        );
+        audit_log.record(
+            session_id,
+            &plugin,
+            "gl_get_issue",
+            None,
+            &Err(McpError::internal_error("wasm trap", None)),
+            Duration::from_millis(3),
+        );
+
+        let records = read_records(&config);
+        assert_eq!(records.len(), 2);
+
+        let record = &records[0];
+        assert_eq!(record["session_id"], session_id.to_string());
+        assert_eq!(record["plugin"], "gitlab");
+        assert_eq!(record["tool"], "gl_get_issue");
+        assert_eq!(
+            record["arguments_hash"],
+            sha256_digest(br#"{"project_id":"42"}"#)
+        );
+        assert_eq!(record["is_error"], false);
+        assert_eq!(record["duration_ms"], 12);
+        assert!(record["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert!(record.get("arguments").is_none());
+        assert!(record.get("result").is_none());
+
+        assert_eq!(records[1]["is_error"], true);
+        assert_eq!(records[1]["arguments_hash"], sha256_digest(b"null"));
+    }
+
+    #[test]
+    fn test_audit_log_include_arguments_and_result() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = AuditLogConfig {
+            path: temp_dir.path().join("audit.jsonl"),
+            include_arguments: true,
+            include_result: true,
+        };
+        let audit_log = AuditLog::open(&config).unwrap();
+        let arguments = json!({"project_id": "42"}).as_object().unwrap().clone();
+
+        audit_log.record(
+            Uuid::new_v4(),
+            &PluginName::from_str("gitlab").unwrap(),
+            "gl_get_issue",
+            Some(&arguments),
+            &Ok(CallToolResult::error(vec![Content::text("not found")])),
+            Duration::ZERO,
+        );
+
+        let records = read_records(&config);
+        assert_eq!(records[0]["arguments"], json!({"project_id": "42"}));
+        assert_eq!(records[0]["is_error"], true);
+        assert_eq!(records[0]["result"]["content"][0]["text"], "not found");
+    }
+
+    #[test]
+    fn test_audit_log_open_failure() {
+        let config = AuditLogConfig {
+            path: "/nonexistent/dir/audit.jsonl".into(),
+            include_arguments: false,
+            include_result: false,
+        };
+        assert!(AuditLog::open(&config).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use crate::config::{CircuitBreakerConfig, PluginName};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through and failures are counted.
+    Closed,
+    /// Calls are rejected until the reset timeout has passed.
+    Open,
+    /// A single trial call is let through to decide whether to close the circuit again.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct CircuitBreakerState {
+    failures: u32,
+    // Start of the failure window while closed, or when the circuit opened.
+    since: Instant,
+    state: CircuitState,
+}
+
+/// Stops calling a plugin that keeps failing, e.g. because of wasm traps or running out
+/// of memory. After `failure_threshold` failures within `reset_timeout` the circuit
+/// opens and calls are rejected. Once `reset_timeout` has passed a trial call is let
+/// through, and its outcome decides whether the circuit closes or opens again.
+#[derive(Debug)]
+pub struct PluginCircuitBreaker {
+    failure_threshold: u32,
+    plugin_name: PluginName,
+    reset_timeout: Duration,
+    state: Mutex<CircuitBreakerState>,
+}
+
+impl PluginCircuitBreaker {
+    pub fn new(plugin_name: PluginName, failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold,
+            plugin_name,
+            reset_timeout,
+            state: Mutex::new(CircuitBreakerState {
+                failures: 0,
+                since: Instant::now(),
+                state: CircuitState::Closed,
+            }),
+        }
+    }
+
+    pub fn from_config(plugin_name: PluginName, config: &CircuitBreakerConfig) -> Self {
+        Self::new(
+            plugin_name,
+            config.failure_threshold,
+            Duration::from_secs(config.reset_timeout_secs),
+        )
+    }
+
+    /// Whether a call may go through. Every allowed call must be followed by
+    /// `record_success`, `record_failure` or `record_cancelled`.
+    pub fn allow_call(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.state {
+            CircuitState::Closed => true,
+            CircuitState::Open if state.since.elapsed() >= self.reset_timeout => {
+                tracing::info!(
+                    "Circuit breaker for plugin {} is half-open, trying a call",
+                    self.plugin_name
+                );
+                state.state = CircuitState::HalfOpen;
+                true
+            }
+            // Either still open, or a trial call is already in flight
+            CircuitState::Open | CircuitState::HalfOpen => false,
+        }
+    }
+
+    pub fn record_failure(&self) {
+        if self.failure_threshold == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        match state.state {
+            CircuitState::Closed => {
+                if state.failures == 0 || state.since.elapsed() >= self.reset_timeout {
+                    state.failures = 0;
+                    state.since = Instant::now();
+                }
+                state.failures += 1;
+                if state.failures >= self.failure_threshold {
+                    tracing::info!(
+                        "Circuit breaker for plugin {} opened after {} failures",
+                        self.plugin_name,
+                        state.failures
+                    );
+                    state.state = CircuitState::Open;
+                    state.since = Instant::now();
+                }
+            }
+            CircuitState::HalfOpen => {
+                tracing::info!(
+                    "Circuit breaker for plugin {} opened again, trial call failed",
+                    self.plugin_name
+                );
+                state.state = CircuitState::Open;
+                state.since = Instant::now();
+            }
+            CircuitState::Open => {}
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.state != CircuitState::Closed {
+            tracing::info!("Circuit breaker for plugin {} closed", self.plugin_name);
+        }
+        state.state = CircuitState::Closed;
+        state.failures = 0;
+    }
+
+    /// Whether calls are currently rejected. Unlike `allow_call` this never lets a trial
+    /// call through.
+    pub fn is_open(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.state == CircuitState::Open && state.since.elapsed() < self.reset_timeout
+    }
+
+    /// Records a call that was cancelled before it finished. A cancellation says nothing
+    /// about the health of the plugin, so it is not counted, and a trial call gives its
+    /// slot back to the next call.
+    pub fn record_cancelled(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.state == CircuitState::HalfOpen {
+            // `since` is still when the circuit opened, so the next call is let through
+            state.state = CircuitState::Open;
+        }
+    }
+
+    #[cfg(test)]
+    pub fn state(&self) -> CircuitState {
+        self.state.lock().unwrap().state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn create_breaker(failure_threshold: u32, reset_timeout: Duration) -> PluginCircuitBreaker {
+        PluginCircuitBreaker::new(
+            PluginName::from_str("test_plugin").unwrap(),
+            failure_threshold,
+            reset_timeout,
+        )
+    }
+
+    #[test]
+    fn test_circuit_breaker_opens_after_threshold() {
+        let breaker = create_breaker(3, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert!(breaker.allow_call());
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+        }
+
+        assert!(breaker.allow_call());
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_call());
+    }
+
+    #[test]
+    fn test_circuit_breaker_success_resets_failures() {
+        let breaker = create_breaker(2, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_circuit_breaker_forgets_old_failures() {
+        let breaker = create_breaker(2, Duration::from_millis(20));
+
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(30));
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_circuit_breaker_half_open() {
+        let breaker = create_breaker(1, Duration::from_millis(20));
+
+        breaker.record_failure();
+        assert!(!breaker.allow_call());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.allow_call());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        // Only one trial call at a time
+        assert!(!breaker.allow_call());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_call());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.allow_call());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow_call());
+    }
+
+    #[test]
+    fn test_circuit_breaker_is_open() {
+        let breaker = create_breaker(1, Duration::from_millis(20));
+        assert!(!breaker.is_open());
+
+        breaker.record_failure();
+        assert!(breaker.is_open());
+
+        // Open circuits are ready for a trial call once the timeout has passed
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!breaker.is_open());
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_circuit_breaker_cancelled() {
+        let breaker = create_breaker(2, Duration::from_millis(20));
+
+        // Cancelled calls neither count as failures nor reset them
+        breaker.record_failure();
+        breaker.record_cancelled();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A cancelled trial call lets the next call try again
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.allow_call());
+        breaker.record_cancelled();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.allow_call());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    }
+
+    #[test]
+    fn test_circuit_breaker_disabled() {
+        let breaker = create_breaker(0, Duration::from_secs(60));
+
+        for _ in 0..10 {
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow_call());
+    }
+}
@@ -1,12 +1,27 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 pub const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1:3001";
 
+// e.g. `0.1.8 (abc1234 2025-07-28T10:00:00.000000000Z, rustc 1.90.0)`, set by build.rs
+const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("VERGEN_GIT_SHA"),
+    " ",
+    env!("VERGEN_BUILD_TIMESTAMP"),
+    ", rustc ",
+    env!("VERGEN_RUSTC_SEMVER"),
+    ")"
+);
+
 #[derive(Parser, Clone)]
-#[command(author = "Tuan Anh Tran <me@tuananh.org>", version = env!("CARGO_PKG_VERSION"), about, long_about = None)]
+#[command(author = "Tuan Anh Tran <me@tuananh.org>", version = VERSION, about, long_about = None)]
 pub struct Cli {
-    #[arg(short, long, value_name = "FILE")]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[arg(short, long, value_name = "FILE", global = true)]
     pub config_file: Option<PathBuf>,
 
     #[arg(
@@ -74,11 +89,98 @@ pub struct Cli {
         env = "HYPER_MCP_CERT_URL"
     )]
     pub cert_url: Option<String>,
+
+    #[arg(
+        long = "strict-output-validation",
+        help = "Return an error to the client when a tool's structured content does not match its output schema. Will override the value in your config file if set.",
+        env = "HYPER_MCP_STRICT_OUTPUT_VALIDATION"
+    )]
+    pub strict_output_validation: Option<bool>,
+
+    #[arg(
+        long = "health-check-interval",
+        value_name = "SECONDS",
+        help = "Interval in seconds between health check pings sent to connected clients, 0 disables them. Will override the value in your config file if set.",
+        env = "HYPER_MCP_HEALTH_CHECK_INTERVAL"
+    )]
+    pub health_check_interval: Option<u64>,
+
+    #[arg(
+        long = "no-compile-cache",
+        num_args = 0..=1,
+        default_missing_value = "true",
+        help = "Compile plugins from scratch on every start instead of reusing compiled modules from the cache. Will override the value in your config file if set.",
+        env = "HYPER_MCP_NO_COMPILE_CACHE"
+    )]
+    pub no_compile_cache: Option<bool>,
+
+    #[arg(
+        long = "log-redact-args",
+        num_args = 0..=1,
+        default_missing_value = "true",
+        help = "Log only the argument names of tool calls to plugins with debug_log_calls enabled, not their values. Will override the value in your config file if set.",
+        env = "HYPER_MCP_LOG_REDACT_ARGS"
+    )]
+    pub log_redact_args: Option<bool>,
+
+    #[arg(
+        long = "no-admin-endpoints",
+        help = "Do not serve the /health, /ready and /info endpoints of the streamable-http transport.",
+        env = "HYPER_MCP_NO_ADMIN_ENDPOINTS"
+    )]
+    pub no_admin_endpoints: bool,
+
+    #[arg(
+        long = "disable-tag",
+        value_name = "TAG",
+        value_delimiter = ',',
+        help = "Do not load the plugins with this tag, e.g. `write` for a read-only instance. Can be repeated.",
+        env = "HYPER_MCP_DISABLE_TAGS"
+    )]
+    pub disable_tags: Vec<String>,
+
+    #[arg(
+        long = "tls-cert",
+        value_name = "FILE",
+        requires = "tls_key",
+        help = "PEM certificate chain to serve the streamable-http transport over HTTPS.",
+        env = "HYPER_MCP_TLS_CERT"
+    )]
+    pub tls_cert: Option<PathBuf>,
+
+    #[arg(
+        long = "tls-key",
+        value_name = "FILE",
+        requires = "tls_cert",
+        help = "PEM private key of the certificate given with --tls-cert.",
+        env = "HYPER_MCP_TLS_KEY"
+    )]
+    pub tls_key: Option<PathBuf>,
+
+    #[arg(
+        long = "tls-ca-cert",
+        value_name = "FILE",
+        requires = "tls_cert",
+        help = "PEM CA certificates that client certificates must be signed by. Clients without a valid certificate are rejected.",
+        env = "HYPER_MCP_TLS_CA_CERT"
+    )]
+    pub tls_ca_cert: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum Command {
+    /// Load the plugins in the config file and list their tools, prompts and resources
+    Plugins {
+        /// Only list the plugins with this tag
+        #[arg(long, value_name = "TAG")]
+        tag: Option<String>,
+    },
 }
 
 impl Default for Cli {
     fn default() -> Self {
         Self {
+            command: None,
             config_file: None,
             transport: "stdio".to_string(),
             bind_address: DEFAULT_BIND_ADDRESS.to_string(),
@@ -89,6 +191,15 @@ impl Default for Cli {
             cert_issuer: None,
             cert_email: None,
             cert_url: None,
+            strict_output_validation: None,
+            health_check_interval: None,
+            no_compile_cache: None,
+            log_redact_args: None,
+            no_admin_endpoints: false,
+            disable_tags: Vec::new(),
+            tls_cert: None,
+            tls_key: None,
+            tls_ca_cert: None,
         }
     }
 }
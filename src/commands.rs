@@ -0,0 +1,196 @@
+use crate::{
+    config::{Config, PluginName},
+    naming::{parse_namespaced_name, parse_namespaced_uri},
+    service::PluginService,
+};
+use anyhow::Result;
+use rmcp::service::{serve_client, serve_server};
+use std::collections::BTreeMap;
+use tokio::io::duplex;
+
+const HEADERS: [&str; 4] = ["PLUGIN", "KIND", "NAME", "DESCRIPTION"];
+
+/// Loads every plugin in `config` the same way the server does and prints a table of
+/// the tools, prompts and resources each of them exposes.
+///
+/// The listing goes through an in-process MCP session so that it reflects exactly what
+/// a client would see, including `skip_tools` and pagination. With `tag`, only the
+/// plugins with that tag are loaded and listed.
+pub async fn list_plugins(config: &Config, tag: Option<&str>) -> Result<()> {
+    let mut config = config.clone();
+    if let Some(tag) = tag {
+        config
+            .plugins
+            .retain(|_, plugin_config| plugin_config.tags.iter().any(|t| t == tag));
+    }
+    let service = PluginService::new(&config).await?;
+
+    let (server_io, client_io) = duplex(64 * 1024);
+    let (server, client) = tokio::try_join!(
+        async {
+            serve_server(service, server_io)
+                .await
+                .map_err(anyhow::Error::from)
+        },
+        async {
+            serve_client((), client_io)
+                .await
+                .map_err(anyhow::Error::from)
+        }
+    )?;
+
+    let mut rows: BTreeMap<PluginName, Vec<[String; 3]>> = config
+        .plugins
+        .keys()
+        .map(|name| (name.clone(), Vec::new()))
+        .collect();
+
+    for tool in client.list_all_tools().await? {
+        let (plugin_name, tool_name) = parse_namespaced_name(tool.name.to_string())?;
+        rows.entry(plugin_name).or_default().push([
+            "tool".to_string(),
+            tool_name,
+            tool.description.as_deref().unwrap_or_default().to_string(),
+        ]);
+    }
+    for prompt in client.list_all_prompts().await? {
+        let (plugin_name, prompt_name) = parse_namespaced_name(prompt.name.clone())?;
+        rows.entry(plugin_name).or_default().push([
+            "prompt".to_string(),
+            prompt_name,
+            prompt.description.clone().unwrap_or_default(),
+        ]);
+    }
+    for resource in client.list_all_resources().await? {
+        let (plugin_name, uri) = parse_namespaced_uri(resource.uri.clone())?;
+        rows.entry(plugin_name).or_default().push([
+            "resource".to_string(),
+            uri,
+            resource.description.clone().unwrap_or_default(),
+        ]);
+    }
+
+    client.cancel().await?;
+    server.cancel().await?;
+
+    print!("{}", format_table(&rows));
+    Ok(())
+}
+
+fn format_table(rows: &BTreeMap<PluginName, Vec<[String; 3]>>) -> String {
+    let lines: Vec<[String; 4]> = rows
+        .iter()
+        .flat_map(|(plugin_name, entries)| {
+            if entries.is_empty() {
+                return vec![[
+                    plugin_name.to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                    String::new(),
+                ]];
+            }
+            entries
+                .iter()
+                .map(|[kind, name, description]| {
+                    [
+                        plugin_name.to_string(),
+                        kind.clone(),
+                        name.clone(),
+                        // Only the first line keeps the table readable
+                        description.lines().next().unwrap_or_default().to_string(),
+                    ]
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut widths = HEADERS.map(str::len);
+    for line in &lines {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for cells in std::iter::once(HEADERS.map(String::from)).chain(lines) {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_format_table() {
+        let rows = BTreeMap::from([
+            (
+                PluginName::from_str("time").unwrap(),
+                vec![
+                    [
+                        "tool".to_string(),
+                        "get_time".to_string(),
+                        "Returns the current time\nin the given timezone".to_string(),
+                    ],
+                    [
+                        "prompt".to_string(),
+                        "get_time_with_timezone".to_string(),
+                        String::new(),
+                    ],
+                ],
+            ),
+            (PluginName::from_str("myip").unwrap(), vec![]),
+        ]);
+
+        assert_eq!(
+            format_table(&rows),
+            "PLUGIN  KIND    NAME                    DESCRIPTION\n\
+             myip    -       -\n\
+             time    tool    get_time                Returns the current time\n\
+             time    prompt  get_time_with_timezone\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_plugins_empty_config() {
+        assert!(list_plugins(&Config::default(), None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_list_plugins_load_failure() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+plugins:
+  missing:
+    url: "file:///nonexistent/plugin.wasm"
+"#,
+        )
+        .unwrap();
+        assert!(list_plugins(&config, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_plugins_tag() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+plugins:
+  missing:
+    url: "file:///nonexistent/plugin.wasm"
+    tags: ["write"]
+"#,
+        )
+        .unwrap();
+        // Plugins without the tag are not loaded
+        assert!(list_plugins(&config, Some("devops")).await.is_ok());
+        assert!(list_plugins(&config, Some("write")).await.is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use tower_http::compression::{
+    CompressionLayer, Predicate,
+    predicate::{NotForContentType, SizeAbove},
+};
+
+// Below this size the compression overhead outweighs the bytes saved.
+const MIN_COMPRESSION_SIZE: u16 = 1024;
+
+/// Builds the layer that compresses responses of the streamable-http transport with
+/// gzip or brotli, whichever the client asks for in `Accept-Encoding`.
+///
+/// This includes the server-sent event streams that carry the responses to `/mcp`
+/// requests. Their size is not known up front, so they are compressed regardless of
+/// it, and the encoder flushes whenever the stream waits for its next event, so clients
+/// still receive each event as soon as it is sent.
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().compress_when(
+        SizeAbove::new(MIN_COMPRESSION_SIZE)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, service::PluginService};
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, header},
+        routing::post,
+    };
+    use flate2::write::GzDecoder;
+    use futures::StreamExt;
+    use rmcp::transport::streamable_http_server::{
+        StreamableHttpService, session::local::LocalSessionManager,
+    };
+    use std::{io::Write, sync::Mutex, time::Duration};
+    use tower::ServiceExt;
+
+    fn router() -> Router {
+        Router::new()
+            .route("/small", post(|| async { "ok" }))
+            .route("/large", post(|| async { "a".repeat(4096) }))
+            .layer(compression_layer())
+    }
+
+    fn request(uri: &str, accept_encoding: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::ACCEPT_ENCODING, accept_encoding)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_compresses_large_responses() {
+        let response = router().oneshot(request("/large", "gzip")).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let response = router().oneshot(request("/large", "br")).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+    }
+
+    #[tokio::test]
+    async fn test_skips_small_responses() {
+        let response = router()
+            .oneshot(request("/small", "gzip, br"))
+            .await
+            .unwrap();
+        assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    }
+
+    #[tokio::test]
+    async fn test_skips_clients_without_accept_encoding() {
+        let response = router()
+            .oneshot(request("/large", "identity"))
+            .await
+            .unwrap();
+        assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    }
+
+    #[tokio::test]
+    async fn test_compresses_mcp_event_streams() {
+        let session = Mutex::new(Some(
+            PluginService::new(&Config::default())
+                .await
+                .expect("Failed to create service"),
+        ));
+        let service = StreamableHttpService::new(
+            move || {
+                session
+                    .lock()
+                    .unwrap()
+                    .take()
+                    .ok_or_else(|| std::io::Error::other("only one session"))
+            },
+            LocalSessionManager::default().into(),
+            Default::default(),
+        );
+        let router = Router::new()
+            .nest_service("/mcp", service)
+            .layer(compression_layer());
+
+        let initialize = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1.0" },
+            },
+        });
+        let request = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header(header::ACCEPT, "application/json, text/event-stream")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(initialize.to_string()))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let mut body = response.into_body().into_data_stream();
+        let mut decoder = GzDecoder::new(Vec::new());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !String::from_utf8_lossy(decoder.get_ref()).contains("serverInfo") {
+                let chunk = body.next().await.expect("stream ended").unwrap();
+                decoder.write_all(&chunk).unwrap();
+                decoder.flush().unwrap();
+            }
+        })
+        .await
+        .expect("the compressed event did not arrive");
+    }
+
+    #[tokio::test]
+    async fn test_flushes_each_event() {
+        // An event stream that stays open after its first event
+        let router = Router::new()
+            .route(
+                "/events",
+                post(|| async {
+                    let events =
+                        futures::stream::once(async { Ok::<_, std::io::Error>("data: first\n\n") })
+                            .chain(futures::stream::pending());
+                    (
+                        [(header::CONTENT_TYPE, "text/event-stream")],
+                        Body::from_stream(events),
+                    )
+                }),
+            )
+            .layer(compression_layer());
+        let response = router.oneshot(request("/events", "gzip")).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let mut body = response.into_body().into_data_stream();
+        let mut decoder = GzDecoder::new(Vec::new());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !decoder.get_ref().ends_with(b"data: first\n\n") {
+                let chunk = body.next().await.expect("stream ended").unwrap();
+                decoder.write_all(&chunk).unwrap();
+                decoder.flush().unwrap();
+            }
+        })
+        .await
+        .expect("the compressed event was not flushed");
+    }
+}
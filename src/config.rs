@@ -3,10 +3,16 @@ use anyhow::{Context, Result};
 use once_cell::sync::Lazy;
 use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, convert::TryFrom, fmt, path::PathBuf, str::FromStr};
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    convert::TryFrom,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use url::Url;
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct PluginName(String);
 
 #[derive(Clone, Debug)]
@@ -24,6 +30,11 @@ static PLUGIN_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^[A-Za-z0-9]+(?:[_][A-Za-z0-9]+)*$").expect("Failed to compile plugin name regex")
 });
 
+static ENV_VAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}")
+        .expect("Failed to compile env var regex")
+});
+
 impl PluginName {
     #[allow(dead_code)]
     pub fn as_str(&self) -> &str {
@@ -125,13 +136,133 @@ impl<'de> Deserialize<'de> for AuthConfig {
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<AuditLogConfig>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auths: Option<HashMap<Url, AuthConfig>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_ttl_hours: Option<u64>,
+
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
+
+    #[serde(default = "default_compression")]
+    pub compression: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cors: Option<CorsConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check_interval: Option<u64>,
+
+    // Config files whose plugins are merged into this config, relative to this file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<PathBuf>,
+
+    #[serde(default)]
+    pub log_redact_args: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_calls: Option<u32>,
+
+    #[serde(default)]
+    pub no_compile_cache: bool,
+
     #[serde(default)]
     pub oci: OciConfig,
 
+    #[serde(default)]
     pub plugins: HashMap<PluginName, PluginConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registries: Option<HashMap<String, RegistryConfig>>,
+
+    // Unset keeps `create_message` allowed for every plugin, as before `sampling`
+    // existed, with a deprecation warning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<bool>,
+
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+
+    #[serde(default)]
+    pub strict_output_validation: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuditLogConfig {
+    // File the audit records are appended to, one JSON object per line.
+    pub path: PathBuf,
+
+    // Log the tool arguments, otherwise only their hash is logged.
+    #[serde(default)]
+    pub include_arguments: bool,
+
+    // Log the tool result.
+    #[serde(default)]
+    pub include_result: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CircuitBreakerConfig {
+    // Number of failed calls within `reset_timeout_secs` that opens the circuit, 0 disables it.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    // Seconds an open circuit waits before letting a trial call through.
+    #[serde(default = "default_reset_timeout_secs")]
+    pub reset_timeout_secs: u64,
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_reset_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: default_failure_threshold(),
+            reset_timeout_secs: default_reset_timeout_secs(),
+        }
+    }
+}
+
+fn default_compression() -> bool {
+    true
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CorsConfig {
+    // Origins browsers may call the streamable-http transport from, or `["*"]` for any.
+    pub allowed_origins: Vec<String>,
+
+    // Request headers browsers may send, on top of the ones the MCP transport uses.
+    #[serde(default = "default_cors_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+
+    // Seconds browsers may cache the response to a preflight request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+}
+
+fn default_cors_allowed_headers() -> Vec<String> {
+    vec!["content-type".to_string(), "authorization".to_string()]
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RegistryConfig {
+    pub username: String,
+    pub password: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -154,6 +285,13 @@ pub struct OciConfig {
     pub rekor_pub_keys: Option<PathBuf>,
 
     pub use_sigstore_tuf_data: bool,
+
+    #[serde(default = "default_verify_digest")]
+    pub verify_digest: bool,
+}
+
+fn default_verify_digest() -> bool {
+    true
 }
 
 impl Default for OciConfig {
@@ -166,17 +304,65 @@ impl Default for OciConfig {
             insecure_skip_signature: false,
             rekor_pub_keys: None,
             use_sigstore_tuf_data: true,
+            verify_digest: default_verify_digest(),
         }
     }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PluginConfig {
-    #[serde(rename = "url", alias = "path")]
+    #[serde(
+        rename = "url",
+        alias = "path",
+        deserialize_with = "plugin_url_serde::deserialize"
+    )]
     pub url: Url,
+    // Expected SHA-256 digest of a plugin downloaded from an `http(s)://` URL.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub digest: Option<String>,
+    // Expected manifest digest (e.g. `sha256:...`) of an `oci://` plugin.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub pinned_digest: Option<String>,
+    // Disabled plugins are parsed but neither downloaded nor loaded.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // Labels for grouping plugins, e.g. to list them with `plugins --tag` or to
+    // disable them with `--disable-tag`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     pub runtime_config: Option<RuntimeConfig>,
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
+mod plugin_url_serde {
+    use super::*;
+    use serde::{Deserializer, de};
+
+    fn is_local_path(value: &str) -> bool {
+        value.starts_with('/') || value.starts_with("./") || value.starts_with("../")
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        if is_local_path(&value) {
+            // Paths in config files were made absolute when the file was read, other
+            // relative paths are resolved against the current working directory
+            let path = std::path::absolute(&value).map_err(de::Error::custom)?;
+            return Url::from_file_path(&path)
+                .map_err(|_| de::Error::custom(format!("invalid plugin path: {value}")));
+        }
+        Url::parse(&value).map_err(|e| {
+            de::Error::invalid_value(de::Unexpected::Str(&value), &e.to_string().as_str())
+        })
+    }
+}
+
 mod skip_serde {
     use super::*;
     use serde::{Deserializer, Serializer};
@@ -238,10 +424,199 @@ pub struct RuntimeConfig {
     // List of tools to skip loading at runtime.
     #[serde(with = "skip_serde", default)]
     pub skip_tools: Option<RegexSet>,
+    // Prepended to the names of the plugin's tools and prompts, after skip_tools and
+    // skip_prompts are applied to the original names.
+    #[serde(default)]
+    pub tool_prefix: String,
+    // Let the plugin ask the client for LLM completions, if the server allows sampling.
+    #[serde(default)]
+    pub allow_sampling: bool,
     pub allowed_hosts: Option<Vec<String>>,
     pub allowed_paths: Option<Vec<String>>,
+    // Log the tool calls to this plugin at DEBUG level.
+    #[serde(default)]
+    pub debug_log_calls: bool,
     pub env_vars: Option<HashMap<String, String>>,
+    // Maximum number of concurrent tool calls to this plugin, on top of the server-wide limit.
+    pub max_concurrent_calls: Option<u32>,
     pub memory_limit: Option<String>,
+    // Env vars that clients may override for their session in the `initialize` request.
+    pub overridable_env_vars: Option<Vec<String>>,
+}
+
+// A config file pulled in by `include`, which only contributes plugins.
+#[derive(Deserialize)]
+struct IncludedConfig {
+    #[serde(default)]
+    include: Vec<PathBuf>,
+    #[serde(default)]
+    plugins: HashMap<PluginName, PluginConfig>,
+}
+
+/// Whether `path` is the location of a plugin's env var in the config. Plugins may run
+/// without their env vars, so unset variables in them are not an error.
+fn is_plugin_env_var(path: &str) -> bool {
+    let parts = path.splitn(5, '.').collect::<Vec<_>>();
+    matches!(
+        parts.as_slice(),
+        ["plugins", _, "runtime_config", "env_vars", _]
+    )
+}
+
+/// Substitutes `${VAR}` and `${VAR:-default}` in the string values of `value` with
+/// environment variables. `path` is the location of `value` in the config, for errors.
+/// A plugin env var that refers to an unset variable keeps its value with a warning,
+/// other unset variables fail the config.
+fn interpolate_env_vars(value: &mut serde_json::Value, path: &str) -> Result<()> {
+    match value {
+        serde_json::Value::String(s) => {
+            let mut missing = None;
+            let interpolated = ENV_VAR_REGEX.replace_all(s, |caps: &regex::Captures| {
+                let name = &caps[1];
+                match (std::env::var(name), caps.get(2)) {
+                    (Ok(value), Some(_)) if !value.is_empty() => value,
+                    (Ok(value), None) => value,
+                    (_, Some(default)) => default.as_str().to_string(),
+                    (Err(_), None) => {
+                        missing.get_or_insert_with(|| name.to_string());
+                        String::new()
+                    }
+                }
+            });
+            match missing {
+                Some(name) if is_plugin_env_var(path) => {
+                    tracing::warn!(
+                        "Environment variable {name} used in config at {path} is not set, keeping the original value {s}"
+                    );
+                }
+                Some(name) => {
+                    return Err(anyhow::anyhow!(
+                        "Environment variable {name} used in config at {path} is not set"
+                    ));
+                }
+                None => *s = interpolated.into_owned(),
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                interpolate_env_vars(item, &format!("{path}[{i}]"))?;
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                interpolate_env_vars(item, &path)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn read_config_file<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read config file at {}", path.display()))?;
+
+    let mut value: serde_json::Value = match ext {
+        "json" => serde_json::from_str(&content)?,
+        "yaml" | "yml" => serde_yaml::from_str(&content)?,
+        "toml" => toml::from_str(&content)?,
+        _ => return Err(anyhow::anyhow!("Unsupported config format: {ext}")),
+    };
+    interpolate_env_vars(&mut value, "")?;
+    resolve_plugin_paths(&mut value, path)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Resolves the relative plugin paths of the config file at `path` against the
+/// directory of that file, as its `include` paths are, so that the same config loads
+/// the same plugins whatever the working directory.
+fn resolve_plugin_paths(value: &mut serde_json::Value, path: &Path) -> Result<()> {
+    let Some(plugins) = value
+        .get_mut("plugins")
+        .and_then(serde_json::Value::as_object_mut)
+    else {
+        return Ok(());
+    };
+    let config_dir = std::path::absolute(path)?
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    for plugin in plugins.values_mut() {
+        for key in ["url", "path"] {
+            if let Some(serde_json::Value::String(url)) = plugin.get_mut(key)
+                && (url.starts_with("./") || url.starts_with("../"))
+            {
+                *url = config_dir.join(&*url).display().to_string();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Merges the plugins of the files in `config.include` into `config`, following the
+/// includes of those files as well. Include paths are relative to the file that
+/// includes them. Fails on circular includes and on plugins defined more than once.
+async fn merge_includes(config: &mut Config, config_path: &Path) -> Result<()> {
+    let config_path = tokio::fs::canonicalize(config_path).await?;
+    let resolve = |from: &Path, include: &PathBuf| match from.parent() {
+        Some(dir) => dir.join(include),
+        None => include.clone(),
+    };
+    let mut pending = config
+        .include
+        .iter()
+        .rev()
+        .map(|include| (resolve(&config_path, include), vec![config_path.clone()]))
+        .collect::<Vec<_>>();
+
+    while let Some((path, mut chain)) = pending.pop() {
+        let path = tokio::fs::canonicalize(&path)
+            .await
+            .with_context(|| format!("Included config file not found at {}", path.display()))?;
+        if chain.contains(&path) {
+            chain.push(path);
+            let cycle = chain
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(anyhow::anyhow!("Circular config include: {cycle}"));
+        }
+
+        let included: IncludedConfig = read_config_file(&path).await?;
+        for (name, plugin) in included.plugins {
+            match config.plugins.entry(name) {
+                Entry::Occupied(entry) => {
+                    return Err(anyhow::anyhow!(
+                        "Plugin {} included from {} is already defined",
+                        entry.key(),
+                        path.display()
+                    ));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(plugin);
+                }
+            }
+        }
+
+        chain.push(path.clone());
+        pending.extend(
+            included
+                .include
+                .iter()
+                .rev()
+                .map(|include| (resolve(&path, include), chain.clone())),
+        );
+    }
+    Ok(())
 }
 
 pub async fn load_config(cli: &Cli) -> Result<Config> {
@@ -262,21 +637,8 @@ pub async fn load_config(cli: &Cli) -> Result<Config> {
         ));
     }
     tracing::info!("Using config file at {}", config_path.display());
-    let ext = config_path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
-
-    let content = tokio::fs::read_to_string(config_path)
-        .await
-        .with_context(|| format!("Failed to read config file at {}", config_path.display()))?;
-
-    let mut config: Config = match ext {
-        "json" => serde_json::from_str(&content)?,
-        "yaml" | "yml" => serde_yaml::from_str(&content)?,
-        "toml" => toml::from_str(&content)?,
-        _ => return Err(anyhow::anyhow!("Unsupported config format: {ext}")),
-    };
+    let mut config: Config = read_config_file(config_path).await?;
+    merge_includes(&mut config, config_path).await?;
 
     let mut oci = config.oci.clone();
 
@@ -303,6 +665,35 @@ pub async fn load_config(cli: &Cli) -> Result<Config> {
     }
     config.oci = oci;
 
+    if let Some(strict) = cli.strict_output_validation {
+        config.strict_output_validation = strict;
+    }
+    if let Some(interval) = cli.health_check_interval {
+        config.health_check_interval = Some(interval);
+    }
+    if let Some(no_compile_cache) = cli.no_compile_cache {
+        config.no_compile_cache = no_compile_cache;
+    }
+    if let Some(log_redact_args) = cli.log_redact_args {
+        config.log_redact_args = log_redact_args;
+    }
+
+    config.plugins.retain(|plugin_name, plugin_config| {
+        if !plugin_config.enabled {
+            tracing::info!("Skipping disabled plugin {plugin_name}");
+            return false;
+        }
+        if let Some(tag) = cli
+            .disable_tags
+            .iter()
+            .find(|tag| plugin_config.tags.contains(tag))
+        {
+            tracing::info!("Skipping plugin {plugin_name} with disabled tag {tag}");
+            return false;
+        }
+        true
+    });
+
     Ok(config)
 }
 
@@ -720,6 +1111,158 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_plugin_config_local_paths() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+plugins:
+  relative:
+    url: "./target/wasm32-wasip1/release/my_plugin.wasm"
+  parent:
+    path: "../plugins/other.wasm"
+  absolute:
+    url: "/opt/plugins/plugin.wasm"
+  file_url:
+    url: "file:///opt/plugins/plugin.wasm"
+"#,
+        )
+        .unwrap();
+        let cwd = std::env::current_dir().unwrap();
+
+        let url = |name: &str| {
+            config
+                .plugins
+                .get(&PluginName::from_str(name).unwrap())
+                .unwrap()
+                .url
+                .clone()
+        };
+
+        let relative = url("relative");
+        assert_eq!(relative.scheme(), "file");
+        assert_eq!(
+            relative.to_file_path().unwrap(),
+            cwd.join("target/wasm32-wasip1/release/my_plugin.wasm")
+        );
+
+        let parent = url("parent");
+        assert_eq!(parent.scheme(), "file");
+        assert!(parent.path().ends_with("/plugins/other.wasm"));
+
+        assert_eq!(url("absolute").as_str(), "file:///opt/plugins/plugin.wasm");
+        assert_eq!(url("file_url").as_str(), "file:///opt/plugins/plugin.wasm");
+    }
+
+    #[test]
+    fn test_audit_log_config() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+audit_log:
+  path: /var/log/hyper-mcp-audit.jsonl
+plugins: {}
+"#,
+        )
+        .unwrap();
+        let audit_log = config.audit_log.unwrap();
+        assert_eq!(
+            audit_log.path,
+            PathBuf::from("/var/log/hyper-mcp-audit.jsonl")
+        );
+        assert!(!audit_log.include_arguments);
+        assert!(!audit_log.include_result);
+
+        let config: Config = serde_yaml::from_str("plugins: {}").unwrap();
+        assert!(config.audit_log.is_none());
+    }
+
+    #[test]
+    fn test_circuit_breaker_config() {
+        let config: Config = serde_yaml::from_str("plugins: {}").unwrap();
+        assert_eq!(config.circuit_breaker.failure_threshold, 5);
+        assert_eq!(config.circuit_breaker.reset_timeout_secs, 60);
+
+        let config: Config = serde_yaml::from_str(
+            r#"
+circuit_breaker:
+  failure_threshold: 3
+plugins: {}
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.circuit_breaker.failure_threshold, 3);
+        assert_eq!(config.circuit_breaker.reset_timeout_secs, 60);
+    }
+
+    #[test]
+    fn test_compression_config() {
+        let config: Config = serde_yaml::from_str("plugins: {}").unwrap();
+        assert!(config.compression);
+
+        let config: Config = serde_yaml::from_str("compression: false\nplugins: {}").unwrap();
+        assert!(!config.compression);
+    }
+
+    #[test]
+    fn test_shutdown_timeout_config() {
+        let config: Config = serde_yaml::from_str("plugins: {}").unwrap();
+        assert_eq!(config.shutdown_timeout_secs, 30);
+
+        let config: Config = serde_yaml::from_str("plugins: {}\nshutdown_timeout_secs: 5").unwrap();
+        assert_eq!(config.shutdown_timeout_secs, 5);
+    }
+
+    #[test]
+    fn test_cors_config() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+cors:
+  allowed_origins: ["https://myapp.com", "http://localhost:3000"]
+  max_age_secs: 3600
+plugins: {}
+"#,
+        )
+        .unwrap();
+        let cors = config.cors.unwrap();
+        assert_eq!(
+            cors.allowed_origins,
+            ["https://myapp.com", "http://localhost:3000"]
+        );
+        assert_eq!(cors.allowed_headers, ["content-type", "authorization"]);
+        assert_eq!(cors.max_age_secs, Some(3600));
+
+        let config: Config = serde_yaml::from_str("plugins: {}").unwrap();
+        assert!(config.cors.is_none());
+    }
+
+    #[test]
+    fn test_registries_config() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+registries:
+  ghcr.io:
+    username: myuser
+    password: ghp_xxx
+  registry.mycompany.com:
+    username: robot$project
+    password: secret
+plugins: {}
+"#,
+        )
+        .unwrap();
+
+        let registries = config.registries.unwrap();
+        assert_eq!(registries.len(), 2);
+        assert_eq!(registries["ghcr.io"].username, "myuser");
+        assert_eq!(registries["ghcr.io"].password, "ghp_xxx");
+        assert_eq!(
+            registries["registry.mycompany.com"].username,
+            "robot$project"
+        );
+
+        let config: Config = serde_yaml::from_str("plugins: {}").unwrap();
+        assert!(config.registries.is_none());
+    }
+
     #[test]
     fn test_load_invalid_structure() {
         let rt = Runtime::new().unwrap();
@@ -1114,6 +1657,293 @@ plugins:
         }
     }
 
+    #[test]
+    fn test_load_config_strict_output_validation_cli_override() {
+        let rt = Runtime::new().unwrap();
+        let path = Path::new("tests/fixtures/config_with_auths.yaml");
+
+        let cli = Cli {
+            config_file: Some(path.to_path_buf()),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        assert!(
+            !config.strict_output_validation,
+            "Strict output validation should be disabled by default"
+        );
+
+        let cli = Cli {
+            config_file: Some(path.to_path_buf()),
+            strict_output_validation: Some(true),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        assert!(config.strict_output_validation);
+    }
+
+    #[test]
+    fn test_load_config_no_compile_cache_cli_override() {
+        let rt = Runtime::new().unwrap();
+        let path = Path::new("tests/fixtures/config_with_auths.yaml");
+
+        let cli = Cli {
+            config_file: Some(path.to_path_buf()),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        assert!(
+            !config.no_compile_cache,
+            "Compile cache should be enabled by default"
+        );
+
+        let cli = Cli {
+            config_file: Some(path.to_path_buf()),
+            no_compile_cache: Some(true),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        assert!(config.no_compile_cache);
+    }
+
+    #[test]
+    fn test_load_config_log_redact_args_cli_override() {
+        let rt = Runtime::new().unwrap();
+        let path = Path::new("tests/fixtures/config_with_auths.yaml");
+
+        let cli = Cli {
+            config_file: Some(path.to_path_buf()),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        assert!(
+            !config.log_redact_args,
+            "Arguments should be logged by default"
+        );
+
+        let cli = Cli {
+            config_file: Some(path.to_path_buf()),
+            log_redact_args: Some(true),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        assert!(config.log_redact_args);
+    }
+
+    #[test]
+    fn test_load_config_with_include() {
+        let rt = Runtime::new().unwrap();
+        let cli = Cli {
+            config_file: Some(PathBuf::from("tests/fixtures/config_with_include.yaml")),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+
+        let mut names = config
+            .plugins
+            .keys()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["crates_io", "gitlab", "time"]);
+
+        let gitlab = &config.plugins[&PluginName::from_str("gitlab").unwrap()];
+        assert_eq!(
+            gitlab.runtime_config.as_ref().unwrap().allowed_hosts,
+            Some(vec!["gitlab.com".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_load_config_with_disabled_plugin() {
+        let rt = Runtime::new().unwrap();
+        let cli = Cli {
+            config_file: Some(PathBuf::from(
+                "tests/fixtures/config_with_disabled_plugin.yaml",
+            )),
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        let names = config
+            .plugins
+            .keys()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["time"]);
+
+        let cli = Cli {
+            config_file: Some(PathBuf::from("tests/fixtures/config_with_include.yaml")),
+            disable_tags: vec!["write".to_string()],
+
+            ..Default::default()
+        };
+        let config = rt.block_on(load_config(&cli)).unwrap();
+        let mut names = config
+            .plugins
+            .keys()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["crates_io", "time"]);
+
+        // Disabled plugins are still validated
+        let result = serde_yaml::from_str::<Config>(
+            "plugins:\n  gitlab:\n    url: \"not a url\"\n    enabled: false\n",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_load_config_with_circular_include() {
+        let rt = Runtime::new().unwrap();
+        let cli = Cli {
+            config_file: Some(PathBuf::from(
+                "tests/fixtures/config_with_circular_include.yaml",
+            )),
+
+            ..Default::default()
+        };
+        let error = rt.block_on(load_config(&cli)).unwrap_err().to_string();
+        assert!(
+            error.starts_with("Circular config include:"),
+            "Unexpected error: {error}"
+        );
+        assert!(
+            error.ends_with("circular_a.yaml"),
+            "Unexpected error: {error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_config_relative_plugin_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let plugins_dir = dir.path().join("plugins");
+        tokio::fs::create_dir(&plugins_dir).await.unwrap();
+        let wasm_dir = dir.path().join("wasm");
+        tokio::fs::create_dir(&wasm_dir).await.unwrap();
+        let wasm_dir = std::fs::canonicalize(wasm_dir).unwrap();
+        tokio::fs::write(
+            plugins_dir.join("other.yaml"),
+            "plugins:\n  other:\n    url: \"../wasm/other.wasm\"\n",
+        )
+        .await
+        .unwrap();
+        let config_path = dir.path().join("config.yaml");
+        tokio::fs::write(
+            &config_path,
+            "include: [plugins/other.yaml]\nplugins:\n  time:\n    path: \"./wasm/time.wasm\"\n",
+        )
+        .await
+        .unwrap();
+
+        let cli = Cli {
+            config_file: Some(config_path),
+
+            ..Default::default()
+        };
+        let config = load_config(&cli).await.unwrap();
+        // Canonicalized, as the temp dir may be behind a symlink
+        let plugin_path = |name: &str| {
+            let path = config.plugins[&PluginName::from_str(name).unwrap()]
+                .url
+                .to_file_path()
+                .unwrap();
+            std::fs::canonicalize(path.parent().unwrap())
+                .unwrap()
+                .join(path.file_name().unwrap())
+        };
+        assert_eq!(plugin_path("time"), wasm_dir.join("time.wasm"));
+        assert_eq!(plugin_path("other"), wasm_dir.join("other.wasm"));
+    }
+
+    #[tokio::test]
+    async fn test_merge_includes_duplicate_plugin() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let main_path = dir.path().join("config.yaml");
+        tokio::fs::write(
+            dir.path().join("other.yaml"),
+            "plugins:\n  time:\n    url: \"oci://ghcr.io/tuananh/time-plugin:latest\"\n",
+        )
+        .await
+        .unwrap();
+        let mut config: Config = serde_yaml::from_str(
+            "include: [other.yaml]\nplugins:\n  time:\n    url: \"oci://ghcr.io/tuananh/time-plugin:latest\"\n",
+        )
+        .unwrap();
+        tokio::fs::write(&main_path, "").await.unwrap();
+
+        let error = merge_includes(&mut config, &main_path)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Plugin time included from") && error.ends_with("is already defined"),
+            "Unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_env_vars() {
+        // SAFETY: no other test reads or writes these variables
+        unsafe {
+            std::env::set_var("HYPER_MCP_TEST_TOKEN", "secret");
+            std::env::set_var("HYPER_MCP_TEST_EMPTY", "");
+            std::env::remove_var("HYPER_MCP_TEST_UNSET");
+        }
+
+        let mut value = serde_json::json!({
+            "plugins": {
+                "gitlab": {
+                    "runtime_config": {
+                        "env_vars": {
+                            "GITLAB_TOKEN": "${HYPER_MCP_TEST_TOKEN}",
+                            "GITLAB_URL": "${HYPER_MCP_TEST_UNSET:-https://gitlab.com}",
+                            "GITLAB_GROUP": "${HYPER_MCP_TEST_EMPTY:-default}",
+                            "AUTH": "Bearer ${HYPER_MCP_TEST_TOKEN}, $HOME",
+                            "OPTIONAL": "${HYPER_MCP_TEST_UNSET}",
+                        },
+                        "allowed_hosts": ["${HYPER_MCP_TEST_UNSET:-}"],
+                    }
+                }
+            },
+            "max_concurrent_calls": 5,
+        });
+        interpolate_env_vars(&mut value, "").unwrap();
+        let runtime_config = &value["plugins"]["gitlab"]["runtime_config"];
+        assert_eq!(runtime_config["env_vars"]["GITLAB_TOKEN"], "secret");
+        assert_eq!(
+            runtime_config["env_vars"]["GITLAB_URL"],
+            "https://gitlab.com"
+        );
+        assert_eq!(runtime_config["env_vars"]["GITLAB_GROUP"], "default");
+        assert_eq!(runtime_config["env_vars"]["AUTH"], "Bearer secret, $HOME");
+        // Plugin env vars keep unset references, as plugins may run without them
+        assert_eq!(
+            runtime_config["env_vars"]["OPTIONAL"],
+            "${HYPER_MCP_TEST_UNSET}"
+        );
+        assert_eq!(runtime_config["allowed_hosts"][0], "");
+        assert_eq!(value["max_concurrent_calls"], 5);
+
+        let mut value = serde_json::json!({
+            "plugins": { "gitlab": { "url": "${HYPER_MCP_TEST_UNSET}" } }
+        });
+        let error = interpolate_env_vars(&mut value, "")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "Environment variable HYPER_MCP_TEST_UNSET used in config at plugins.gitlab.url is not set"
+        );
+    }
+
     #[test]
     fn test_load_config_with_auths_json() {
         let rt = Runtime::new().unwrap();
@@ -2396,10 +3226,15 @@ plugins:
             skip_resource_templates: None,
             skip_resources: None,
             skip_tools: None,
+            tool_prefix: String::new(),
+            allow_sampling: false,
             allowed_hosts: None,
             allowed_paths: None,
+            debug_log_calls: false,
             env_vars: None,
+            max_concurrent_calls: None,
             memory_limit: None,
+            overridable_env_vars: None,
         };
 
         // Test serialization
@@ -2503,10 +3338,15 @@ plugins:
             skip_resource_templates: None,
             skip_resources: None,
             skip_tools: Some(regex_set),
+            tool_prefix: String::new(),
+            allow_sampling: false,
             allowed_hosts: None,
             allowed_paths: None,
+            debug_log_calls: false,
             env_vars: None,
+            max_concurrent_calls: None,
             memory_limit: None,
+            overridable_env_vars: None,
         };
 
         // Serialize
@@ -0,0 +1,189 @@
+use crate::config::CorsConfig;
+use anyhow::{Context, Result, bail};
+use axum::http::{HeaderName, HeaderValue, Method};
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use url::Url;
+
+// Request headers of the streamable HTTP transport, which browser clients always need
+// to be able to send.
+const MCP_REQUEST_HEADERS: [&str; 3] = ["mcp-session-id", "mcp-protocol-version", "last-event-id"];
+
+// Scripts can only read the response headers a CORS response exposes, and browser
+// clients need the session ID the server returns on `initialize`.
+const MCP_RESPONSE_HEADERS: [&str; 1] = ["mcp-session-id"];
+
+/// Builds the layer that adds CORS headers to the responses of the streamable-http
+/// transport and answers preflight `OPTIONS` requests before they reach the MCP service.
+pub fn cors_layer(config: &CorsConfig) -> Result<CorsLayer> {
+    let allow_origin = if config.allowed_origins.iter().any(|origin| origin == "*") {
+        if config.allowed_origins.len() > 1 {
+            bail!("cors.allowed_origins cannot mix \"*\" with other origins");
+        }
+        tracing::warn!(
+            "CORS allows any origin, so any website can call hyper-mcp from its visitors' browsers"
+        );
+        AllowOrigin::any()
+    } else {
+        if config.allowed_origins.is_empty() {
+            bail!("cors.allowed_origins must list at least one origin");
+        }
+        let origins = config
+            .allowed_origins
+            .iter()
+            .map(|origin| parse_origin(origin))
+            .collect::<Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    let allow_headers = config
+        .allowed_headers
+        .iter()
+        .map(String::as_str)
+        .chain(MCP_REQUEST_HEADERS)
+        .map(|name| {
+            HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("Invalid header in cors.allowed_headers: {name}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut layer = CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::POST, Method::DELETE])
+        .allow_headers(allow_headers)
+        .expose_headers(MCP_RESPONSE_HEADERS.map(HeaderName::from_static));
+    if let Some(max_age_secs) = config.max_age_secs {
+        layer = layer.max_age(Duration::from_secs(max_age_secs));
+    }
+    Ok(layer)
+}
+
+// Browsers send the `Origin` header as `scheme://host[:port]`, without a path and with
+// the default port left out, so configured origins are normalized to match it exactly.
+fn parse_origin(origin: &str) -> Result<HeaderValue> {
+    let url = Url::parse(origin)
+        .with_context(|| format!("Invalid origin in cors.allowed_origins: {origin}"))?;
+    if !url.has_host() {
+        bail!("Invalid origin in cors.allowed_origins: {origin}");
+    }
+    Ok(HeaderValue::from_str(&url.origin().ascii_serialization())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode, header},
+        routing::post,
+    };
+    use tower::ServiceExt;
+
+    fn config(allowed_origins: &[&str]) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: allowed_origins.iter().map(|o| o.to_string()).collect(),
+            allowed_headers: vec!["content-type".to_string(), "authorization".to_string()],
+            max_age_secs: Some(3600),
+        }
+    }
+
+    fn router(config: &CorsConfig) -> Router {
+        Router::new()
+            .route("/mcp", post(|| async { "ok" }))
+            .layer(cors_layer(config).unwrap())
+    }
+
+    fn preflight(origin: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/mcp")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(
+                header::ACCESS_CONTROL_REQUEST_HEADERS,
+                "content-type,mcp-session-id",
+            )
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_origin() {
+        assert_eq!(
+            parse_origin("https://myapp.com/").unwrap(),
+            "https://myapp.com"
+        );
+        assert_eq!(
+            parse_origin("https://myapp.com:443").unwrap(),
+            "https://myapp.com"
+        );
+        assert_eq!(
+            parse_origin("http://localhost:3000").unwrap(),
+            "http://localhost:3000"
+        );
+        assert!(parse_origin("myapp.com").is_err());
+        assert!(parse_origin("data:text/plain,hi").is_err());
+    }
+
+    #[test]
+    fn test_cors_layer_invalid_config() {
+        assert!(cors_layer(&config(&[])).is_err());
+        assert!(cors_layer(&config(&["*", "https://myapp.com"])).is_err());
+
+        let mut invalid_header = config(&["https://myapp.com"]);
+        invalid_header.allowed_headers = vec!["content type".to_string()];
+        assert!(cors_layer(&invalid_header).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_allowed_origin() {
+        let router = router(&config(&["https://myapp.com", "http://localhost:3000"]));
+
+        let response = router
+            .clone()
+            .oneshot(preflight("http://localhost:3000"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "http://localhost:3000"
+        );
+        let allowed_headers = headers[header::ACCESS_CONTROL_ALLOW_HEADERS]
+            .to_str()
+            .unwrap();
+        assert!(allowed_headers.contains("mcp-session-id"));
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "3600");
+
+        let response = router
+            .oneshot(preflight("https://evil.example"))
+            .await
+            .unwrap();
+        assert!(
+            !response
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_origin() {
+        let router = router(&config(&["*"]));
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/mcp")
+            .header(header::ORIGIN, "https://anywhere.example")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_EXPOSE_HEADERS],
+            "mcp-session-id"
+        );
+    }
+}
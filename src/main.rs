@@ -1,10 +1,18 @@
+mod admin;
+mod audit;
+mod circuit_breaker;
 mod cli;
+mod commands;
+mod compression;
 mod config;
+mod cors;
 mod https_auth;
 mod logging;
 mod naming;
 mod plugin;
 mod service;
+mod shutdown;
+mod tls;
 mod wasm;
 
 use anyhow::Result;
@@ -13,13 +21,29 @@ use rmcp::transport::streamable_http_server::{
     StreamableHttpService, session::local::LocalSessionManager,
 };
 use rmcp::{ServiceExt, transport::stdio};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use tokio::{runtime::Handle, task::block_in_place};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = cli::Cli::parse();
     let config = config::load_config(&cli).await?;
+
+    if let Some(cli::Command::Plugins { tag }) = &cli.command {
+        return commands::list_plugins(&config, tag.as_deref()).await;
+    }
+
+    if cli.tls_cert.is_some() && cli.transport != "streamable-http" {
+        anyhow::bail!("--tls-cert is only supported with the streamable-http transport");
+    }
+
     tracing::info!("Starting hyper-mcp server");
+    #[cfg(unix)]
+    reload_plugins_on_sighup(config.clone())?;
+    let shutdown_timeout = Duration::from_secs(config.shutdown_timeout_secs);
 
     match cli.transport.as_str() {
         "stdio" => {
@@ -31,21 +55,63 @@ async fn main() -> Result<()> {
                 .inspect_err(|e| {
                     tracing::error!("Serving error: {:?}", e);
                 })?;
-            service.waiting().await?;
+            // Keep the service running while in-flight calls drain after a signal
+            let waiting = service.waiting();
+            tokio::pin!(waiting);
+            tokio::select! {
+                result = &mut waiting => {
+                    result?;
+                }
+                signal = shutdown::signal() => {
+                    shutdown::drain_and_exit(signal, shutdown_timeout).await
+                }
+            }
         }
         "streamable-http" => {
             let bind_address = cli.bind_address.clone();
+            let compression = config.compression;
+            let cors_layer = config.cors.as_ref().map(cors::cors_layer).transpose()?;
+            let tls_config = match (&cli.tls_cert, &cli.tls_key) {
+                (Some(cert), Some(key)) => {
+                    Some(tls::tls_config(cert, key, cli.tls_ca_cert.as_deref())?)
+                }
+                _ => None,
+            };
+            let scheme = if tls_config.is_some() {
+                "https"
+            } else {
+                "http"
+            };
             tracing::info!(
-                "Starting hyper-mcp with streamable-http transport at {}/mcp",
+                "Starting hyper-mcp with streamable-http transport at {}://{}/mcp",
+                scheme,
                 bind_address
             );
+            if cli.tls_ca_cert.is_some() {
+                tracing::info!("Requiring client certificates signed by the TLS CA");
+            }
+
+            // Shared by the sessions, so that call limits, circuit breakers and the audit
+            // log are server-wide
+            let state = Arc::new(service::ServerState::new(&config)?);
+            // Sessions load their own plugins. The first session's are loaded up front, so
+            // that a plugin that fails to load stops the server and the readiness probe
+            // knows the plugins before the first client connects.
+            let first_session = Mutex::new(Some(
+                service::PluginService::with_state(&config, state.clone()).await?,
+            ));
+            let admin_state = (!cli.no_admin_endpoints).then(|| state.clone());
 
             let service = StreamableHttpService::new(
                 {
                     move || {
+                        if let Some(service) = first_session.lock().unwrap().take() {
+                            return Ok(service);
+                        }
                         block_in_place(|| {
-                            Handle::current()
-                                .block_on(async { service::PluginService::new(&config).await })
+                            Handle::current().block_on(async {
+                                service::PluginService::with_state(&config, state.clone()).await
+                            })
                         })
                         .map_err(std::io::Error::other)
                     }
@@ -54,22 +120,65 @@ async fn main() -> Result<()> {
                 Default::default(),
             );
 
-            let router = axum::Router::new().nest_service("/mcp", service);
-
-            let listener = tokio::net::TcpListener::bind(bind_address.clone()).await?;
+            let mut router = axum::Router::new().nest_service("/mcp", service);
+            if let Some(cors_layer) = cors_layer {
+                router = router.layer(cors_layer);
+            }
+            // Merged after the CORS layer, which only applies to the routes added before it
+            if let Some(state) = admin_state {
+                router = router.merge(admin::admin_router(state));
+            }
+            if compression {
+                router = router.layer(compression::compression_layer());
+            }
 
-            let _ = axum::serve(listener, router)
-                .with_graceful_shutdown(async {
-                    tokio::signal::ctrl_c().await.unwrap();
-                    tracing::info!("Received Ctrl+C, shutting down hyper-mcp server...");
-                    // Give the log a moment to flush
-                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-                    std::process::exit(0);
-                })
-                .await;
+            // Dropping the server stops accepting connections, while the tasks serving
+            // the open ones keep running until the in-flight calls have drained.
+            let serve = async {
+                if let Some(tls_config) = tls_config {
+                    let listener = std::net::TcpListener::bind(&bind_address)?;
+                    listener.set_nonblocking(true)?;
+                    axum_server::from_tcp(listener)
+                        .acceptor(tls::ClientIdentityAcceptor::new(tls_config))
+                        .serve(router.into_make_service())
+                        .await?;
+                } else {
+                    let listener = tokio::net::TcpListener::bind(bind_address.clone()).await?;
+                    axum::serve(listener, router).await?;
+                }
+                anyhow::Ok(())
+            };
+            tokio::select! {
+                result = serve => result?,
+                signal = shutdown::signal() => {
+                    shutdown::drain_and_exit(signal, shutdown_timeout).await
+                }
+            }
         }
         _ => unreachable!(),
     }
 
     Ok(())
 }
+
+/// Reloads the plugins of every session on `SIGHUP` and tells the clients that their
+/// tools may have changed.
+#[cfg(unix)]
+fn reload_plugins_on_sighup(config: config::Config) -> Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut sighup = signal(SignalKind::hangup())?;
+    tokio::spawn(async move {
+        while sighup.recv().await.is_some() {
+            tracing::info!("Received SIGHUP, reloading plugins");
+            // Loading plugins from OCI registries is not `Send`
+            block_in_place(|| {
+                Handle::current().block_on(async {
+                    service::reload_plugins(&config).await;
+                    service::notify_tools_changed().await;
+                })
+            });
+        }
+    });
+    Ok(())
+}
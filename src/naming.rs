@@ -35,6 +35,33 @@ pub fn create_namespaced_uri(plugin_name: &PluginName, uri: &str) -> Result<Stri
     Ok(uri.to_string())
 }
 
+/// Prepends `prefix` to the path of `uri`, e.g. `file:///docs/a` becomes
+/// `file:///my_docs/a`.
+pub fn add_uri_prefix(uri: &str, prefix: &str) -> Result<String> {
+    if prefix.is_empty() {
+        return Ok(uri.to_string());
+    }
+    let mut uri = Url::parse(uri)?;
+    uri.set_path(&format!("{prefix}{}", uri.path().trim_start_matches('/')));
+    Ok(uri.to_string())
+}
+
+/// Removes `prefix` from the path of `uri`, undoing [`add_uri_prefix`]. Returns `None`
+/// if the path does not start with it.
+pub fn strip_uri_prefix(uri: &str, prefix: &str) -> Option<String> {
+    if prefix.is_empty() {
+        return Some(uri.to_string());
+    }
+    let mut uri = Url::parse(uri).ok()?;
+    let path = uri
+        .path()
+        .trim_start_matches('/')
+        .strip_prefix(prefix)?
+        .to_string();
+    uri.set_path(&path);
+    Some(uri.to_string())
+}
+
 pub fn parse_namespaced_name(namespaced_name: String) -> Result<(PluginName, String)> {
     if let Some((plugin_name, tool_name)) = namespaced_name.split_once("-") {
         return Ok((PluginName::from_str(plugin_name)?, tool_name.to_string()));
@@ -162,6 +189,31 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_uri_prefix_round_trip() {
+        let prefixed = add_uri_prefix("file:///docs/readme.md", "my_").unwrap();
+        assert_eq!(prefixed, "file:///my_docs/readme.md");
+        let plugin_name = PluginName::from_str("test_plugin").unwrap();
+        let namespaced = create_namespaced_uri(&plugin_name, &prefixed).unwrap();
+        assert_eq!(namespaced, "file:///test_plugin/my_docs/readme.md");
+
+        let (_, uri) = parse_namespaced_uri(namespaced).unwrap();
+        assert_eq!(
+            strip_uri_prefix(&uri, "my_").as_deref(),
+            Some("file:///docs/readme.md")
+        );
+        assert_eq!(strip_uri_prefix("file:///docs/readme.md", "my_"), None);
+    }
+
+    #[test]
+    fn test_uri_prefix_empty() {
+        assert_eq!(add_uri_prefix("mem://notes", "").unwrap(), "mem://notes");
+        assert_eq!(
+            strip_uri_prefix("mem://notes", "").as_deref(),
+            Some("mem://notes")
+        );
+    }
+
     #[test]
     fn test_tool_name_parse_error_display() {
         let error = NamespacedNameParseError;
@@ -1,4 +1,4 @@
-use crate::config::PluginName;
+use crate::{config::PluginName, tls::ClientIdentity};
 use async_trait::async_trait;
 use rmcp::{
     ErrorData as McpError,
@@ -13,21 +13,69 @@ use std::{
     sync::{Arc, Mutex},
 };
 use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
 type PluginHandle = Arc<Mutex<extism::Plugin>>;
 
+/// JSON-RPC error code returned when a request is cancelled by the client.
+pub const REQUEST_CANCELLED: ErrorCode = ErrorCode(-32800);
+
+/// The ID of the session a request belongs to, added to the request extensions by
+/// the service before it dispatches the request to a plugin.
+#[derive(Clone, Copy, Debug)]
+pub struct SessionId(pub Uuid);
+
+/// A `ref/tool` reference to the tool whose argument is completed, which rmcp's
+/// `Reference` does not know.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ToolReference {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+}
+
+/// The params of a `completion/complete` request for a tool argument.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompleteToolArgumentParam {
+    #[serde(rename = "ref")]
+    pub r#ref: ToolReference,
+    pub argument: ArgumentInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<CompletionContext>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct PluginRequestContext {
     pub id: NumberOrString,
     #[serde(rename = "_meta")]
     pub meta: Meta,
+    // Subject, issuer and serial of the client certificate when serving
+    // streamable-http over mTLS.
+    pub metadata: JsonObject,
+    pub session_id: String,
+    // Common name of the client certificate when serving streamable-http over mTLS.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
 }
 
 impl<'a> From<&'a RequestContext<RoleServer>> for PluginRequestContext {
     fn from(context: &'a RequestContext<RoleServer>) -> Self {
+        let identity = context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.extensions.get::<ClientIdentity>())
+            .cloned()
+            .unwrap_or_default();
         PluginRequestContext {
             id: context.id.clone(),
             meta: context.meta.clone(),
+            metadata: identity.metadata,
+            session_id: context
+                .extensions
+                .get::<SessionId>()
+                .map(|session_id| session_id.0.to_string())
+                .unwrap_or_default(),
+            user_id: identity.common_name,
         }
     }
 }
@@ -46,6 +94,16 @@ impl<'a> From<&'a NotificationContext<RoleServer>> for PluginNotificationContext
     }
 }
 
+/// The MCP features a plugin implements, used to build the capabilities the
+/// server advertises during `initialize`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PluginCapabilities {
+    pub completions: bool,
+    pub prompts: bool,
+    pub resources: bool,
+    pub tools: bool,
+}
+
 #[async_trait]
 #[allow(unused_variables)]
 pub trait Plugin: Send + Sync + Debug {
@@ -55,6 +113,13 @@ pub trait Plugin: Send + Sync + Debug {
         context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError>;
 
+    fn capabilities(&self) -> PluginCapabilities {
+        PluginCapabilities {
+            tools: true,
+            ..Default::default()
+        }
+    }
+
     async fn complete(
         &self,
         request: CompleteRequestParam,
@@ -63,6 +128,14 @@ pub trait Plugin: Send + Sync + Debug {
         Ok(CompleteResult::default())
     }
 
+    async fn complete_tool_argument(
+        &self,
+        request: CompleteToolArgumentParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        Ok(CompleteResult::default())
+    }
+
     async fn get_prompt(
         &self,
         request: GetPromptRequestParam,
@@ -163,19 +236,10 @@ where
     });
 
     tokio::select! {
-        // Finished normally
-        res = &mut join => {
-            match res {
-                Ok(Ok(result)) => Ok(result),
-                Ok(Err(e)) => Err(e),
-                Err(e) => Err(McpError::internal_error(
-                    format!("Failed to spawn blocking task for plugin {plugin_name}: {e}"),
-                    None,
-                )),
-            }
-        }
+        // A request cancelled before the plugin finished is reported as cancelled
+        biased;
 
-        //Cancellation requested
+        // Cancellation requested
         _ = ct.cancelled() => {
             if let Err(e) = cancel_handle.cancel() {
                 tracing::error!("Failed to cancel plugin {plugin_name}: {e}");
@@ -185,12 +249,11 @@ where
                 ));
             }
             match tokio::time::timeout(std::time::Duration::from_millis(250), join).await {
-                Ok(Ok(Ok(_))) => Err(McpError::internal_error(
-                    format!("Plugin {plugin_name} was cancelled"),
-                    None,
-                )),
-                Ok(Ok(Err(e))) => Err(McpError::internal_error(
-                    format!("Failed to execute plugin {plugin_name}: {e}"),
+                // The plugin either finished or was aborted by the cancel handle, either way
+                // the client asked for the request to be dropped.
+                Ok(Ok(_)) => Err(McpError::new(
+                    REQUEST_CANCELLED,
+                    format!("Request to plugin {plugin_name} was cancelled"),
                     None,
                 )),
                 Ok(Err(e)) => Err(McpError::internal_error(
@@ -203,6 +266,18 @@ where
                 )),
             }
         }
+
+        // Finished normally
+        res = &mut join => {
+            match res {
+                Ok(Ok(result)) => Ok(result),
+                Ok(Err(e)) => Err(e),
+                Err(e) => Err(McpError::internal_error(
+                    format!("Failed to spawn blocking task for plugin {plugin_name}: {e}"),
+                    None,
+                )),
+            }
+        }
     }
 }
 
@@ -321,6 +396,16 @@ impl Plugin for PluginV2 {
         .await
     }
 
+    fn capabilities(&self) -> PluginCapabilities {
+        PluginCapabilities {
+            completions: function_exists_plugin(self, "complete"),
+            prompts: function_exists_plugin(self, "list_prompts"),
+            resources: function_exists_plugin(self, "list_resources")
+                || function_exists_plugin(self, "list_resource_templates"),
+            tools: function_exists_plugin(self, "list_tools"),
+        }
+    }
+
     async fn complete(
         &self,
         request: CompleteRequestParam,
@@ -361,6 +446,26 @@ impl Plugin for PluginV2 {
         .await
     }
 
+    async fn complete_tool_argument(
+        &self,
+        mut request: CompleteToolArgumentParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        // Plugins expect the reference types without the "ref/" prefix
+        request.r#ref.r#type = "tool".to_string();
+        call_plugin::<CompleteResult>(
+            self,
+            "complete",
+            serde_json::to_string(&json!({
+                "request": request,
+                "context": PluginRequestContext::from(&context),
+            }))
+            .expect("Failed to serialize request"),
+            context.ct,
+        )
+        .await
+    }
+
     async fn get_prompt(
         &self,
         request: GetPromptRequestParam,
@@ -381,7 +486,7 @@ impl Plugin for PluginV2 {
 
     async fn list_prompts(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         context: RequestContext<RoleServer>,
     ) -> Result<ListPromptsResult, McpError> {
         if !function_exists_plugin(self, "list_prompts") {
@@ -391,9 +496,10 @@ impl Plugin for PluginV2 {
             self,
             "list_prompts",
             serde_json::to_string(&json!({
+                "request": request,
                 "context": PluginRequestContext::from(&context),
             }))
-            .expect("Failed to serialize context"),
+            .expect("Failed to serialize request"),
             context.ct,
         )
         .await
@@ -401,7 +507,7 @@ impl Plugin for PluginV2 {
 
     async fn list_resources(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, McpError> {
         if !function_exists_plugin(self, "list_resources") {
@@ -411,9 +517,10 @@ impl Plugin for PluginV2 {
             self,
             "list_resources",
             serde_json::to_string(&json!({
+                "request": request,
                 "context": PluginRequestContext::from(&context),
             }))
-            .expect("Failed to serialize context"),
+            .expect("Failed to serialize request"),
             context.ct,
         )
         .await
@@ -421,7 +528,7 @@ impl Plugin for PluginV2 {
 
     async fn list_resource_templates(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         context: RequestContext<RoleServer>,
     ) -> Result<ListResourceTemplatesResult, McpError> {
         if !function_exists_plugin(self, "list_resource_templates") {
@@ -431,9 +538,10 @@ impl Plugin for PluginV2 {
             self,
             "list_resource_templates",
             serde_json::to_string(&json!({
+                "request": request,
                 "context": PluginRequestContext::from(&context),
             }))
-            .expect("Failed to serialize context"),
+            .expect("Failed to serialize request"),
             context.ct,
         )
         .await
@@ -441,7 +549,7 @@ impl Plugin for PluginV2 {
 
     async fn list_tools(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
         if !function_exists_plugin(self, "list_tools") {
@@ -451,9 +559,10 @@ impl Plugin for PluginV2 {
             self,
             "list_tools",
             serde_json::to_string(&json!({
+                "request": request,
                 "context": PluginRequestContext::from(&context),
             }))
-            .expect("Failed to serialize context"),
+            .expect("Failed to serialize request"),
             context.ct,
         )
         .await
@@ -0,0 +1,89 @@
+use std::{sync::LazyLock, time::Duration};
+use tokio_util::task::{TaskTracker, task_tracker::TaskTrackerToken};
+
+// Tool calls of every session, so shutdown can wait for them before exiting.
+static TOOL_CALLS: LazyLock<TaskTracker> = LazyLock::new(TaskTracker::new);
+
+/// Registers a tool call for as long as the returned token is held. Returns `None`
+/// once shutdown has started, as the call might not finish before the server exits.
+pub fn track_tool_call() -> Option<TaskTrackerToken> {
+    (!TOOL_CALLS.is_closed()).then(|| TOOL_CALLS.token())
+}
+
+/// Waits for `SIGTERM` or `SIGINT` and returns the name of the signal received.
+pub async fn signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut sigterm = signal(SignalKind::terminate()).expect("Failed to listen for SIGTERM");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => "SIGINT",
+            _ = sigterm.recv() => "SIGTERM",
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for Ctrl+C");
+        "Ctrl+C"
+    }
+}
+
+/// Waits up to `timeout` for the in-flight tool calls to finish and exits the
+/// process, with status 0 if they all finished and 1 otherwise.
+pub async fn drain_and_exit(signal: &str, timeout: Duration) -> ! {
+    tracing::info!(
+        "Received {signal}, shutting down hyper-mcp server with {} tool calls in flight...",
+        TOOL_CALLS.len()
+    );
+    let drained = drain(&TOOL_CALLS, timeout).await;
+    if drained {
+        tracing::info!("All tool calls finished, exiting");
+    } else {
+        tracing::warn!(
+            "{} tool calls still running after {timeout:?}, exiting",
+            TOOL_CALLS.len()
+        );
+    }
+    // Give the log a moment to flush
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    std::process::exit(if drained { 0 } else { 1 });
+}
+
+async fn drain(tracker: &TaskTracker, timeout: Duration) -> bool {
+    tracker.close();
+    tokio::time::timeout(timeout, tracker.wait()).await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_without_calls() {
+        let tracker = TaskTracker::new();
+        assert!(drain(&tracker, Duration::from_millis(10)).await);
+        assert!(tracker.is_closed());
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_calls() {
+        let tracker = TaskTracker::new();
+        let token = tracker.token();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            drop(token);
+        });
+        assert!(drain(&tracker, Duration::from_secs(5)).await);
+    }
+
+    #[tokio::test]
+    async fn test_drain_timeout() {
+        let tracker = TaskTracker::new();
+        let _token = tracker.token();
+        assert!(!drain(&tracker, Duration::from_millis(50)).await);
+        assert_eq!(tracker.len(), 1);
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result};
+use axum_server::{
+    accept::Accept,
+    tls_rustls::{RustlsAcceptor, RustlsConfig},
+};
+use rmcp::model::JsonObject;
+use rustls::{
+    RootCertStore, ServerConfig,
+    crypto::{CryptoProvider, aws_lc_rs},
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::WebPkiClientVerifier,
+};
+use serde_json::Value;
+use std::{future::Future, io, path::Path, pin::Pin, sync::Arc};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower_http::add_extension::AddExtension;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+/// Identity of the certificate the client presented during the TLS handshake. Added to
+/// the extensions of every HTTP request served over TLS, it is empty when the client sent
+/// no certificate.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClientIdentity {
+    /// Subject common name, `None` also for certificates without one.
+    pub common_name: Option<String>,
+    /// The `subject`, `issuer` and `serial` of the certificate.
+    pub metadata: JsonObject,
+}
+
+/// Builds the rustls config of the streamable-http transport. With a CA certificate,
+/// clients must present a certificate signed by it, otherwise no client certificate
+/// is requested.
+pub fn tls_config(cert: &Path, key: &Path, ca_cert: Option<&Path>) -> Result<RustlsConfig> {
+    // Pinned because rustls cannot pick a default when both providers are compiled in.
+    let provider = Arc::new(aws_lc_rs::default_provider());
+
+    let certs = CertificateDer::pem_file_iter(cert)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("Failed to read TLS certificate {}", cert.display()))?;
+    let key = PrivateKeyDer::from_pem_file(key)
+        .with_context(|| format!("Failed to read TLS key {}", key.display()))?;
+
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+    let builder = match ca_cert {
+        Some(ca_cert) => builder.with_client_cert_verifier(client_verifier(ca_cert, provider)?),
+        None => builder.with_no_client_auth(),
+    };
+    let mut config = builder.with_single_cert(certs, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+fn client_verifier(
+    ca_cert: &Path,
+    provider: Arc<CryptoProvider>,
+) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>> {
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_file_iter(ca_cert)
+        .with_context(|| format!("Failed to read TLS CA certificate {}", ca_cert.display()))?
+    {
+        roots.add(cert?)?;
+    }
+    Ok(WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build()?)
+}
+
+fn client_identity(cert: &CertificateDer) -> ClientIdentity {
+    let Ok((_, cert)) = X509Certificate::from_der(cert.as_ref()) else {
+        return ClientIdentity::default();
+    };
+    let common_name = cert
+        .subject()
+        .iter_common_name()
+        .next()
+        .and_then(|common_name| common_name.as_str().ok())
+        .map(String::from);
+    let metadata = JsonObject::from_iter([
+        (
+            "subject".to_string(),
+            Value::from(cert.subject().to_string()),
+        ),
+        ("issuer".to_string(), Value::from(cert.issuer().to_string())),
+        (
+            "serial".to_string(),
+            Value::from(cert.raw_serial_as_string()),
+        ),
+    ]);
+    ClientIdentity {
+        common_name,
+        metadata,
+    }
+}
+
+type AcceptFuture<I, S> = Pin<
+    Box<dyn Future<Output = io::Result<(TlsStream<I>, AddExtension<S, ClientIdentity>)>> + Send>,
+>;
+
+/// Accepts TLS connections like [`RustlsAcceptor`] and adds the [`ClientIdentity`] of
+/// each connection to the requests made on it.
+#[derive(Clone)]
+pub struct ClientIdentityAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl ClientIdentityAcceptor {
+    pub fn new(config: RustlsConfig) -> Self {
+        ClientIdentityAcceptor {
+            inner: RustlsAcceptor::new(config),
+        }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientIdentityAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, ClientIdentity>;
+    type Future = AcceptFuture<I, S>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            let identity = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .map(client_identity)
+                .unwrap_or_default();
+            Ok((stream, AddExtension::new(service, identity)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{CertificateParams, DnType, KeyPair};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn self_signed(common_name: Option<&str>) -> (rcgen::Certificate, KeyPair) {
+        let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params.distinguished_name = rcgen::DistinguishedName::new();
+        if let Some(common_name) = common_name {
+            params
+                .distinguished_name
+                .push(DnType::CommonName, common_name);
+        }
+        let key = KeyPair::generate().unwrap();
+        (params.self_signed(&key).unwrap(), key)
+    }
+
+    #[test]
+    fn test_client_identity() {
+        let (cert, _) = self_signed(Some("alice"));
+        let identity = client_identity(cert.der());
+        assert_eq!(identity.common_name, Some("alice".to_string()));
+        assert_eq!(identity.metadata["subject"], "CN=alice");
+        // Self-signed, so issued by itself
+        assert_eq!(identity.metadata["issuer"], "CN=alice");
+        assert!(identity.metadata["serial"].is_string());
+
+        let (cert, _) = self_signed(None);
+        assert_eq!(client_identity(cert.der()).common_name, None);
+
+        assert_eq!(
+            client_identity(&CertificateDer::from(vec![1, 2, 3])),
+            ClientIdentity::default()
+        );
+    }
+
+    #[test]
+    fn test_tls_config() {
+        let dir = TempDir::new().unwrap();
+        let (cert, key) = self_signed(Some("hyper-mcp"));
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        fs::write(&cert_path, cert.pem()).unwrap();
+        fs::write(&key_path, key.serialize_pem()).unwrap();
+
+        assert!(tls_config(&cert_path, &key_path, None).is_ok());
+        assert!(tls_config(&cert_path, &key_path, Some(&cert_path)).is_ok());
+        assert!(tls_config(&cert_path, &dir.path().join("missing.pem"), None).is_err());
+        assert!(tls_config(&cert_path, &key_path, Some(&key_path)).is_err());
+    }
+}
@@ -1,33 +1,245 @@
-use crate::{config::AuthConfig, https_auth::Authenticator};
+use crate::{
+    config::AuthConfig,
+    https_auth::Authenticator,
+    wasm::{cache_dir, digests_match, is_cache_expired, sha256_digest},
+};
 use anyhow::{Result, anyhow};
-use reqwest::Client;
-use std::collections::HashMap;
+use reqwest::{
+    Client, StatusCode,
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, path::Path, time::Duration};
 use tokio::sync::OnceCell;
 use url::Url;
 
 static REQWEST_CLIENT: OnceCell<Client> = OnceCell::const_new();
 
-pub async fn load_wasm(url: &Url, auths: &Option<HashMap<Url, AuthConfig>>) -> Result<Vec<u8>> {
-    match url.scheme() {
-        "http" => Ok(REQWEST_CLIENT
-            .get_or_init(|| async { reqwest::Client::new() })
+/// Validators recorded next to a cached plugin, so that a plugin without a digest or a
+/// TTL can be revalidated with a conditional request instead of being reused forever.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CachedValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+pub async fn load_wasm(
+    url: &Url,
+    auths: &Option<HashMap<Url, AuthConfig>>,
+    digest: Option<&str>,
+    cache_ttl: Option<Duration>,
+) -> Result<Vec<u8>> {
+    let url_hash = hex::encode(Sha256::digest(url.as_str()));
+    let cache_path = cache_dir()?.join(format!("http-{}.wasm", &url_hash[..16]));
+    load_wasm_cached(url, auths, digest, cache_ttl, &cache_path).await
+}
+
+async fn load_wasm_cached(
+    url: &Url,
+    auths: &Option<HashMap<Url, AuthConfig>>,
+    digest: Option<&str>,
+    cache_ttl: Option<Duration>,
+    cache_path: &Path,
+) -> Result<Vec<u8>> {
+    let validators_path = cache_path.with_extension("validators.json");
+    let mut cached = None;
+
+    if cache_path.exists() && !is_cache_expired(cache_path, cache_ttl) {
+        let content = tokio::fs::read(cache_path).await?;
+        match digest {
+            Some(digest) if !digests_match(&sha256_digest(&content), digest) => {
+                tracing::warn!(
+                    "Cached plugin {} does not match digest {digest}, downloading again",
+                    cache_path.display()
+                );
+            }
+            // Neither pinned nor expiring, so ask the server whether it changed
+            None if cache_ttl.is_none() => cached = Some(content),
+            _ => {
+                tracing::info!("Plugin {url} already cached at: {}", cache_path.display());
+                return Ok(content);
+            }
+        }
+    }
+
+    let client = REQWEST_CLIENT
+        .get_or_init(|| async { reqwest::Client::new() })
+        .await;
+    let mut request = match url.scheme() {
+        "http" => client.get(url.as_str()),
+        "https" => client.get(url.as_str()).add_auth(auths, url),
+        _ => return Err(anyhow!("Unsupported URL scheme: {}", url.scheme())),
+    };
+    if cached.is_some() {
+        let validators: CachedValidators = tokio::fs::read(&validators_path)
             .await
-            .get(url.as_str())
-            .send()
-            .await?
-            .bytes()
-            .await?
-            .to_vec()),
-        "https" => Ok(REQWEST_CLIENT
-            .get_or_init(|| async { reqwest::Client::new() })
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        if let Some(etag) = validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            if let Some(content) = cached {
+                tracing::warn!(
+                    "Failed to check {url} for a newer plugin, using the cached one: {e}"
+                );
+                return Ok(content);
+            }
+            return Err(e.into());
+        }
+    };
+    if response.status() == StatusCode::NOT_MODIFIED
+        && let Some(content) = cached
+    {
+        tracing::info!(
+            "Plugin {url} not modified since cached at: {}",
+            cache_path.display()
+        );
+        return Ok(content);
+    }
+
+    let response = response.error_for_status()?;
+    let validators = CachedValidators {
+        etag: header_value(&response, ETAG),
+        last_modified: header_value(&response, LAST_MODIFIED),
+    };
+    let content = response.bytes().await?.to_vec();
+
+    let actual_digest = sha256_digest(&content);
+    if let Some(digest) = digest
+        && !digests_match(&actual_digest, digest)
+    {
+        return Err(anyhow!(
+            "Plugin downloaded from {url} has digest {actual_digest}, expected {digest}"
+        ));
+    }
+
+    if let Err(e) = tokio::fs::write(cache_path, &content).await {
+        tracing::warn!("Failed to cache plugin at {}: {e}", cache_path.display());
+    } else if let Err(e) =
+        tokio::fs::write(&validators_path, serde_json::to_vec(&validators)?).await
+    {
+        tracing::warn!(
+            "Failed to record validators at {}: {e}",
+            validators_path.display()
+        );
+    }
+    Ok(content)
+}
+
+fn header_value(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        http::{HeaderMap, StatusCode as AxumStatusCode},
+        routing::get,
+    };
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use tempfile::TempDir;
+
+    /// Serves `/plugin.wasm` with an ETag and counts the full downloads.
+    async fn serve_plugin(body: &'static [u8], etag: &'static str) -> (Url, Arc<AtomicUsize>) {
+        let downloads = Arc::new(AtomicUsize::new(0));
+        let counter = downloads.clone();
+        let app = Router::new().route(
+            "/plugin.wasm",
+            get(move |headers: HeaderMap| {
+                let counter = counter.clone();
+                async move {
+                    if headers.get("if-none-match").is_some_and(|v| v == etag) {
+                        return (AxumStatusCode::NOT_MODIFIED, [("etag", etag)], Vec::new());
+                    }
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    (AxumStatusCode::OK, [("etag", etag)], body.to_vec())
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let url = Url::parse(&format!("http://{addr}/plugin.wasm")).unwrap();
+        (url, downloads)
+    }
+
+    #[tokio::test]
+    async fn test_load_wasm_revalidates_unpinned_cache() {
+        let dir = TempDir::new().unwrap();
+        let cache_path = dir.path().join("http-test.wasm");
+        let (url, downloads) = serve_plugin(b"new", "\"v2\"").await;
+
+        // A copy cached before the plugin was updated is replaced
+        std::fs::write(&cache_path, b"old").unwrap();
+        std::fs::write(
+            cache_path.with_extension("validators.json"),
+            r#"{"etag":"\"v1\"","last_modified":null}"#,
+        )
+        .unwrap();
+        let content = load_wasm_cached(&url, &None, None, None, &cache_path)
+            .await
+            .unwrap();
+        assert_eq!(content, b"new");
+        assert_eq!(downloads.load(Ordering::SeqCst), 1);
+
+        // An unchanged plugin is served from the cache
+        let content = load_wasm_cached(&url, &None, None, None, &cache_path)
+            .await
+            .unwrap();
+        assert_eq!(content, b"new");
+        assert_eq!(downloads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_wasm_reuses_pinned_cache() {
+        let dir = TempDir::new().unwrap();
+        let cache_path = dir.path().join("http-test.wasm");
+        let (url, downloads) = serve_plugin(b"new", "\"v2\"").await;
+
+        std::fs::write(&cache_path, b"old").unwrap();
+        let digest = sha256_digest(b"old");
+        let content = load_wasm_cached(&url, &None, Some(&digest), None, &cache_path)
+            .await
+            .unwrap();
+        assert_eq!(content, b"old");
+        assert_eq!(downloads.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_load_wasm_falls_back_to_cache_when_offline() {
+        let dir = TempDir::new().unwrap();
+        let cache_path = dir.path().join("http-test.wasm");
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!(
+            "http://{}/plugin.wasm",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        drop(listener);
+
+        std::fs::write(&cache_path, b"old").unwrap();
+        let content = load_wasm_cached(&url, &None, None, None, &cache_path)
             .await
-            .get(url.as_str())
-            .add_auth(auths, url)
-            .send()
-            .await?
-            .bytes()
-            .await?
-            .to_vec()),
-        _ => Err(anyhow!("Unsupported URL scheme: {}", url.scheme())),
+            .unwrap();
+        assert_eq!(content, b"old");
     }
 }
@@ -1,3 +1,101 @@
+use anyhow::{Result, anyhow};
+use sha2::{Digest, Sha256};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
 pub mod http;
 pub mod oci;
 pub mod s3;
+
+/// Returns the `sha256:<hex>` digest of `data`.
+pub fn sha256_digest(data: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(data)))
+}
+
+/// Compares two SHA-256 digests, with or without the `sha256:` prefix.
+pub fn digests_match(a: &str, b: &str) -> bool {
+    a.trim_start_matches("sha256:")
+        .eq_ignore_ascii_case(b.trim_start_matches("sha256:"))
+}
+
+/// Directory in which downloaded plugins are cached.
+pub fn cache_dir() -> Result<PathBuf> {
+    let mut path =
+        dirs::cache_dir().ok_or_else(|| anyhow!("Unable to determine the cache directory"))?;
+    path.push("hyper-mcp");
+    std::fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+/// Writes the Wasmtime cache configuration that stores compiled plugins under
+/// `cache_dir`, usually [`cache_dir()`], and returns its path. Wasmtime keys each
+/// compiled artifact by the hash of the wasm module and the compiler version, so a
+/// changed plugin or an Extism upgrade never reuses a stale artifact.
+pub fn compile_cache_config(cache_dir: &Path) -> Result<PathBuf> {
+    let config_path = cache_dir.join("compile-cache.toml");
+    let directory = toml::Value::String(cache_dir.join("compiled").display().to_string());
+    let config = format!("[cache]\ndirectory = {directory}\n");
+    if std::fs::read_to_string(&config_path).ok().as_deref() != Some(config.as_str()) {
+        std::fs::write(&config_path, config)?;
+    }
+    Ok(config_path)
+}
+
+/// Whether the cached file at `path` is older than `ttl`. Without a TTL cached files
+/// never expire.
+pub fn is_cache_expired(path: &Path, ttl: Option<Duration>) -> bool {
+    let Some(ttl) = ttl else {
+        return false;
+    };
+    match std::fs::metadata(path).and_then(|m| m.modified()) {
+        Ok(modified) => modified.elapsed().map(|age| age > ttl).unwrap_or(false),
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::{NamedTempFile, TempDir};
+
+    #[test]
+    fn test_digests_match() {
+        let digest = sha256_digest(b"hello");
+        assert!(digests_match(&digest, &digest));
+        assert!(digests_match(
+            &digest,
+            "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824"
+        ));
+        assert!(!digests_match(&digest, &sha256_digest(b"world")));
+    }
+
+    #[test]
+    fn test_compile_cache_config() {
+        let cache_dir = TempDir::new().unwrap();
+        let path = compile_cache_config(cache_dir.path()).unwrap();
+        let config: toml::Table = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let cache = config["cache"].as_table().unwrap();
+        assert_eq!(
+            cache["directory"].as_str().map(PathBuf::from),
+            Some(cache_dir.path().join("compiled"))
+        );
+    }
+
+    #[test]
+    fn test_is_cache_expired() {
+        let file = NamedTempFile::new().unwrap();
+        assert!(!is_cache_expired(file.path(), None));
+        assert!(!is_cache_expired(
+            file.path(),
+            Some(Duration::from_secs(3600))
+        ));
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(is_cache_expired(file.path(), Some(Duration::ZERO)));
+        assert!(is_cache_expired(
+            &file.path().with_extension("missing"),
+            Some(Duration::from_secs(3600))
+        ));
+    }
+}
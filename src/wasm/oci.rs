@@ -1,4 +1,7 @@
-use crate::config::{OciConfig, PluginName};
+use crate::{
+    config::{OciConfig, PluginName, RegistryConfig},
+    wasm::{cache_dir, digests_match, is_cache_expired, sha256_digest},
+};
 use anyhow::{Result, anyhow};
 use docker_credential::{CredentialRetrievalError, DockerCredential};
 use flate2::read::GzDecoder;
@@ -6,6 +9,7 @@ use oci_client::{
     Client, Reference, client::ClientConfig, manifest, manifest::OciDescriptor,
     secrets::RegistryAuth,
 };
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use sigstore::{
     cosign::{
@@ -20,14 +24,36 @@ use sigstore::{
     registry::{Auth, OciReference},
     trust::{ManualTrustRoot, TrustRoot, sigstore::SigstoreTrustRoot},
 };
-use std::{fs, io::Read, path::Path, str::FromStr};
+use std::{collections::HashMap, fs, io::Read, path::Path, str::FromStr, time::Duration};
 use tar::Archive;
 use tokio::sync::OnceCell;
 use url::Url;
 
 static OCI_CLIENT: OnceCell<Client> = OnceCell::const_new();
 
-fn build_auth(reference: &Reference) -> RegistryAuth {
+/// Credentials configured under `registries` for the registry of `reference`, if any.
+fn registry_credentials<'a>(
+    reference: &Reference,
+    registries: &'a Option<HashMap<String, RegistryConfig>>,
+) -> Option<&'a RegistryConfig> {
+    let registries = registries.as_ref()?;
+    [reference.registry(), reference.resolve_registry()]
+        .into_iter()
+        .find_map(|registry| registries.get(registry.trim_end_matches('/')))
+}
+
+fn build_auth(
+    reference: &Reference,
+    registries: &Option<HashMap<String, RegistryConfig>>,
+) -> RegistryAuth {
+    if let Some(credentials) = registry_credentials(reference, registries) {
+        tracing::info!(
+            "Using configured credentials for registry {}",
+            reference.registry()
+        );
+        return RegistryAuth::Basic(credentials.username.clone(), credentials.password.clone());
+    }
+
     let server = reference
         .resolve_registry()
         .strip_suffix('/')
@@ -57,31 +83,141 @@ fn build_auth(reference: &Reference) -> RegistryAuth {
     }
 }
 
-pub async fn load_wasm(url: &Url, config: &OciConfig, plugin_name: &PluginName) -> Result<Vec<u8>> {
+/// Digests recorded next to a cached plugin so that it can be verified before it is loaded.
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedDigests {
+    // Digest of the image manifest the plugin was pulled from
+    manifest_digest: Option<String>,
+    // Digest of the extracted wasm file
+    wasm_digest: String,
+}
+
+/// Checks a cached plugin against the digests recorded when it was pulled, and against
+/// `pinned_digest` if the plugin config pins one.
+fn verify_cached_plugin(
+    config: &OciConfig,
+    wasm_path: &Path,
+    digest_path: &Path,
+    pinned_digest: Option<&str>,
+) -> Result<()> {
+    if !config.verify_digest && pinned_digest.is_none() {
+        return Ok(());
+    }
+    let digests: CachedDigests = serde_json::from_slice(
+        &fs::read(digest_path).map_err(|e| anyhow!("no digest recorded for cached plugin: {e}"))?,
+    )?;
+    if let Some(pinned_digest) = pinned_digest
+        && !digests
+            .manifest_digest
+            .as_deref()
+            .is_some_and(|manifest_digest| digests_match(manifest_digest, pinned_digest))
+    {
+        return Err(anyhow!(
+            "manifest digest {} does not match pinned digest {pinned_digest}",
+            digests.manifest_digest.as_deref().unwrap_or("<unknown>")
+        ));
+    }
+    if config.verify_digest {
+        let wasm_digest = sha256_digest(&fs::read(wasm_path)?);
+        if !digests_match(&wasm_digest, &digests.wasm_digest) {
+            return Err(anyhow!(
+                "digest {wasm_digest} does not match recorded digest {}",
+                digests.wasm_digest
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The reference to pull `image_reference` by. A pinned digest replaces the tag, so the
+/// registry serves exactly the pinned image rather than whatever the tag points to now.
+fn pull_reference(image_reference: &str, pinned_digest: Option<&str>) -> Result<String> {
+    let Some(pinned_digest) = pinned_digest else {
+        return Ok(image_reference.to_string());
+    };
+    let reference = Reference::try_from(image_reference)?;
+    let digest = format!(
+        "sha256:{}",
+        pinned_digest
+            .trim_start_matches("sha256:")
+            .to_ascii_lowercase()
+    );
+    Ok(reference.clone_with_digest(digest).whole())
+}
+
+fn remove_cached_plugin(wasm_path: &Path, digest_path: &Path) {
+    for path in [wasm_path, digest_path] {
+        if let Err(e) = fs::remove_file(path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove cached file {}: {e}", path.display());
+        }
+    }
+}
+
+pub async fn load_wasm(
+    url: &Url,
+    config: &OciConfig,
+    registries: &Option<HashMap<String, RegistryConfig>>,
+    plugin_name: &PluginName,
+    pinned_digest: Option<&str>,
+    cache_ttl: Option<Duration>,
+) -> Result<Vec<u8>> {
     let image_reference = url.as_str().strip_prefix("oci://").unwrap();
     let target_file_path = "/plugin.wasm";
     let mut hasher = Sha256::new();
     hasher.update(image_reference);
     let hash = hasher.finalize();
     let short_hash = &hex::encode(hash)[..7];
-    let cache_dir = dirs::cache_dir()
-        .map(|mut path| {
-            path.push("hyper-mcp");
-            path
-        })
-        .unwrap();
-    std::fs::create_dir_all(&cache_dir)?;
+    let cache_dir = cache_dir()?;
 
     let local_output_path = cache_dir.join(format!("{plugin_name}-{short_hash}.wasm"));
+    let digest_path = cache_dir.join(format!("{plugin_name}-{short_hash}.digest.json"));
     let local_output_path = local_output_path.to_str().unwrap();
 
-    if let Err(e) =
-        pull_and_extract_oci_image(config, image_reference, target_file_path, local_output_path)
-            .await
+    if Path::new(local_output_path).exists()
+        && is_cache_expired(Path::new(local_output_path), cache_ttl)
+    {
+        tracing::info!("Cached plugin `{plugin_name}` expired, pulling again");
+        remove_cached_plugin(Path::new(local_output_path), &digest_path);
+    }
+
+    // A cached copy that fails verification is pulled again, e.g. after a pinned digest changed
+    if Path::new(local_output_path).exists()
+        && let Err(e) = verify_cached_plugin(
+            config,
+            Path::new(local_output_path),
+            &digest_path,
+            pinned_digest,
+        )
+    {
+        tracing::warn!("Discarding cached plugin `{plugin_name}`: {e}");
+        remove_cached_plugin(Path::new(local_output_path), &digest_path);
+    }
+
+    if let Err(e) = pull_and_extract_oci_image(
+        config,
+        registries,
+        &pull_reference(image_reference, pinned_digest)?,
+        target_file_path,
+        local_output_path,
+        &digest_path,
+    )
+    .await
     {
         tracing::error!("Error pulling oci plugin: {e}");
         return Err(anyhow::anyhow!("Failed to pull OCI plugin: {e}"));
     }
+    if let Err(e) = verify_cached_plugin(
+        config,
+        Path::new(local_output_path),
+        &digest_path,
+        pinned_digest,
+    ) {
+        tracing::error!("Digest verification failed for plugin `{plugin_name}`: {e}");
+        remove_cached_plugin(Path::new(local_output_path), &digest_path);
+        return Err(anyhow!("Refusing to load OCI plugin `{plugin_name}`: {e}"));
+    }
     tracing::info!("cache plugin `{plugin_name}` to : {local_output_path}");
     tokio::fs::read(local_output_path)
         .await
@@ -155,12 +291,15 @@ async fn setup_trust_repository(config: &OciConfig) -> Result<Box<dyn TrustRoot>
     Ok(Box::new(data))
 }
 
-async fn verify_image_signature(config: &OciConfig, image_reference: &str) -> Result<bool> {
+async fn verify_image_signature(
+    config: &OciConfig,
+    image_reference: &str,
+    auth: &Auth,
+) -> Result<bool> {
     tracing::info!("Verifying signature for {image_reference}");
 
     // Set up the trust repository based on CLI arguments
     let repo = setup_trust_repository(config).await?;
-    let auth = &Auth::Anonymous;
 
     // Create a client builder
     let client_builder = ClientBuilder::default();
@@ -258,9 +397,11 @@ async fn verify_image_signature(config: &OciConfig, image_reference: &str) -> Re
 
 async fn pull_and_extract_oci_image(
     config: &OciConfig,
+    registries: &Option<HashMap<String, RegistryConfig>>,
     image_reference: &str,
     target_file_path: &str,
     local_output_path: &str,
+    digest_path: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if Path::new(local_output_path).exists() {
         tracing::info!(
@@ -272,12 +413,18 @@ async fn pull_and_extract_oci_image(
     tracing::info!("Pulling {image_reference} ...");
 
     let reference = Reference::try_from(image_reference)?;
-    let auth = build_auth(&reference);
+    let auth = build_auth(&reference, registries);
 
     // Verify the image signature if it's an OCI image and verification is enabled
     if !config.insecure_skip_signature {
         tracing::info!("Signature verification enabled for {image_reference}");
-        match verify_image_signature(config, image_reference).await {
+        let signature_auth = match &auth {
+            RegistryAuth::Basic(username, password) => {
+                Auth::Basic(username.clone(), password.clone())
+            }
+            _ => Auth::Anonymous,
+        };
+        match verify_image_signature(config, image_reference, &signature_auth).await {
             Ok(verified) => {
                 if !verified {
                     return Err(format!(
@@ -311,7 +458,18 @@ async fn pull_and_extract_oci_image(
         )
         .await?;
 
-    for layer in manifest.layers.iter() {
+    let layer_digests = manifest
+        .manifest
+        .as_ref()
+        .map(|m| {
+            m.layers
+                .iter()
+                .map(|layer| layer.digest.clone())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for (index, layer) in manifest.layers.iter().enumerate() {
         let mut buf = Vec::new();
         let desc = OciDescriptor {
             digest: layer.sha256_digest().clone(),
@@ -320,6 +478,21 @@ async fn pull_and_extract_oci_image(
         };
         client.pull_blob(&reference, &desc, &mut buf).await.unwrap();
 
+        if config.verify_digest {
+            let Some(expected_digest) = layer_digests.get(index) else {
+                return Err(
+                    format!("No manifest digest for layer {index} of {image_reference}").into(),
+                );
+            };
+            let actual_digest = sha256_digest(&buf);
+            if &actual_digest != expected_digest {
+                return Err(format!(
+                    "Layer digest {actual_digest} does not match manifest digest {expected_digest}"
+                )
+                .into());
+            }
+        }
+
         let gz_extract = GzDecoder::new(&buf[..]);
         let mut archive_extract = Archive::new(gz_extract);
 
@@ -335,7 +508,14 @@ async fn pull_and_extract_oci_image(
                             }
                             let mut content = Vec::new();
                             entry.read_to_end(&mut content)?;
-                            fs::write(local_output_path, content)?;
+                            fs::write(local_output_path, &content)?;
+                            fs::write(
+                                digest_path,
+                                serde_json::to_vec(&CachedDigests {
+                                    manifest_digest: manifest.digest.clone(),
+                                    wasm_digest: sha256_digest(&content),
+                                })?,
+                            )?;
                             tracing::info!("Successfully extracted to: {local_output_path}");
                             return Ok(());
                         }
@@ -348,3 +528,168 @@ async fn pull_and_extract_oci_image(
 
     Err("Target file not found in any layer".into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_cached_plugin(dir: &TempDir, content: &[u8], manifest_digest: Option<&str>) {
+        fs::write(dir.path().join("plugin.wasm"), content).unwrap();
+        fs::write(
+            dir.path().join("plugin.digest.json"),
+            serde_json::to_vec(&CachedDigests {
+                manifest_digest: manifest_digest.map(str::to_string),
+                wasm_digest: sha256_digest(content),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn verify(dir: &TempDir, config: &OciConfig, pinned_digest: Option<&str>) -> Result<()> {
+        verify_cached_plugin(
+            config,
+            &dir.path().join("plugin.wasm"),
+            &dir.path().join("plugin.digest.json"),
+            pinned_digest,
+        )
+    }
+
+    #[test]
+    fn test_sha256_digest() {
+        assert_eq!(
+            sha256_digest(b"hello"),
+            "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
+    #[test]
+    fn test_build_auth_uses_configured_registry() {
+        let registries = Some(HashMap::from([
+            (
+                "ghcr.io".to_string(),
+                RegistryConfig {
+                    username: "myuser".to_string(),
+                    password: "ghp_xxx".to_string(),
+                },
+            ),
+            (
+                "registry.mycompany.com".to_string(),
+                RegistryConfig {
+                    username: "robot$project".to_string(),
+                    password: "secret".to_string(),
+                },
+            ),
+        ]));
+
+        let reference = Reference::try_from("ghcr.io/tuananh/time-plugin:latest").unwrap();
+        assert!(matches!(
+            build_auth(&reference, &registries),
+            RegistryAuth::Basic(username, password) if username == "myuser" && password == "ghp_xxx"
+        ));
+
+        let reference = Reference::try_from("registry.mycompany.com/plugins/private:1.0").unwrap();
+        assert!(matches!(
+            build_auth(&reference, &registries),
+            RegistryAuth::Basic(username, password) if username == "robot$project" && password == "secret"
+        ));
+    }
+
+    #[test]
+    fn test_registry_credentials_no_match() {
+        let registries = Some(HashMap::from([(
+            "ghcr.io".to_string(),
+            RegistryConfig {
+                username: "myuser".to_string(),
+                password: "ghp_xxx".to_string(),
+            },
+        )]));
+
+        let reference = Reference::try_from("quay.io/org/plugin:latest").unwrap();
+        assert!(registry_credentials(&reference, &registries).is_none());
+        assert!(registry_credentials(&reference, &None).is_none());
+    }
+
+    #[test]
+    fn test_verify_cached_plugin_valid() {
+        let dir = TempDir::new().unwrap();
+        write_cached_plugin(&dir, b"wasm", Some("sha256:abc"));
+
+        assert!(verify(&dir, &OciConfig::default(), None).is_ok());
+        assert!(verify(&dir, &OciConfig::default(), Some("sha256:abc")).is_ok());
+    }
+
+    #[test]
+    fn test_verify_cached_plugin_corrupted() {
+        let dir = TempDir::new().unwrap();
+        write_cached_plugin(&dir, b"wasm", None);
+        fs::write(dir.path().join("plugin.wasm"), b"corrupted").unwrap();
+
+        let err = verify(&dir, &OciConfig::default(), None).unwrap_err();
+        assert!(err.to_string().contains("does not match recorded digest"));
+
+        let config = OciConfig {
+            verify_digest: false,
+            ..Default::default()
+        };
+        assert!(verify(&dir, &config, None).is_ok());
+    }
+
+    #[test]
+    fn test_verify_cached_plugin_pinned_digest_case() {
+        let dir = TempDir::new().unwrap();
+        write_cached_plugin(&dir, b"wasm", Some("sha256:abc"));
+
+        assert!(verify(&dir, &OciConfig::default(), Some("sha256:ABC")).is_ok());
+        assert!(verify(&dir, &OciConfig::default(), Some("abc")).is_ok());
+    }
+
+    #[test]
+    fn test_pull_reference() {
+        assert_eq!(
+            pull_reference("ghcr.io/tuananh/time-plugin:latest", None).unwrap(),
+            "ghcr.io/tuananh/time-plugin:latest"
+        );
+        assert_eq!(
+            pull_reference("ghcr.io/tuananh/time-plugin:latest", Some("sha256:ABC123")).unwrap(),
+            "ghcr.io/tuananh/time-plugin@sha256:abc123"
+        );
+        assert_eq!(
+            pull_reference("ghcr.io/tuananh/time-plugin:latest", Some("abc123")).unwrap(),
+            "ghcr.io/tuananh/time-plugin@sha256:abc123"
+        );
+    }
+
+    #[test]
+    fn test_verify_cached_plugin_pinned_digest_mismatch() {
+        let dir = TempDir::new().unwrap();
+        write_cached_plugin(&dir, b"wasm", Some("sha256:abc"));
+
+        let err = verify(&dir, &OciConfig::default(), Some("sha256:def")).unwrap_err();
+        assert!(err.to_string().contains("does not match pinned digest"));
+    }
+
+    #[test]
+    fn test_verify_cached_plugin_missing_digest() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("plugin.wasm"), b"wasm").unwrap();
+
+        assert!(verify(&dir, &OciConfig::default(), None).is_err());
+    }
+
+    #[test]
+    fn test_remove_cached_plugin() {
+        let dir = TempDir::new().unwrap();
+        write_cached_plugin(&dir, b"wasm", None);
+
+        let wasm_path = dir.path().join("plugin.wasm");
+        let digest_path = dir.path().join("plugin.digest.json");
+        remove_cached_plugin(&wasm_path, &digest_path);
+        assert!(!wasm_path.exists());
+        assert!(!digest_path.exists());
+
+        // Removing files that are already gone is not an error
+        remove_cached_plugin(&wasm_path, &digest_path);
+    }
+}
@@ -661,8 +661,11 @@ type PluginNotificationContext struct {
 
 // PluginRequestContext represents the context for a plugin request
 type PluginRequestContext struct {
-	Meta Meta            `json:"_meta"`
-	ID   PluginRequestId `json:"id"`
+	Meta      Meta            `json:"_meta"`
+	ID        PluginRequestId `json:"id"`
+	Metadata  map[string]any  `json:"metadata,omitempty"`
+	SessionID string          `json:"session_id"`
+	UserID    *string         `json:"user_id,omitempty"`
 }
 
 type PluginRequestId struct {
@@ -847,6 +850,7 @@ type ReadResourceResult struct {
 type Reference struct {
 	Prompt           *PromptReference
 	ResourceTemplate *ResourceTemplateReference
+	Tool             *ToolReference
 }
 
 func (r Reference) MarshalJSON() ([]byte, error) {
@@ -855,6 +859,8 @@ func (r Reference) MarshalJSON() ([]byte, error) {
 		return json.Marshal(r.Prompt)
 	case r.ResourceTemplate != nil:
 		return json.Marshal(r.ResourceTemplate)
+	case r.Tool != nil:
+		return json.Marshal(r.Tool)
 	default:
 		return nil, fmt.Errorf("empty Reference")
 	}
@@ -881,6 +887,12 @@ func (r *Reference) UnmarshalJSON(data []byte) error {
 			return err
 		}
 		r.ResourceTemplate = &rt
+	case "tool":
+		var t ToolReference
+		if err := json.Unmarshal(data, &t); err != nil {
+			return err
+		}
+		r.Tool = &t
 	default:
 		return fmt.Errorf("unknown reference type %q", head.Type)
 	}
@@ -1286,6 +1298,41 @@ type Tool struct {
 	Title        *string      `json:"title,omitempty"`
 }
 
+// ToolReference represents a reference to a tool
+type ToolReference struct {
+	Name string `json:"name"`
+}
+
+func (t ToolReference) MarshalJSON() ([]byte, error) {
+	type alias ToolReference
+	return json.Marshal(&struct {
+		Type string `json:"type"`
+		alias
+	}{
+		Type:  "tool",
+		alias: (alias)(t),
+	})
+}
+
+func (t *ToolReference) UnmarshalJSON(data []byte) error {
+	type alias ToolReference
+	aux := struct {
+		Type string `json:"type"`
+		alias
+	}{}
+
+	if err := json.Unmarshal(data, &aux); err != nil {
+		return err
+	}
+
+	if aux.Type != "tool" && aux.Type != "" { // allow empty if missing
+		return fmt.Errorf("invalid type %q, expected \"tool\"", aux.Type)
+	}
+
+	*t = ToolReference(aux.alias)
+	return nil
+}
+
 // ToolSchema represents the schema for tool input or output
 type ToolSchema struct {
 	Properties map[string]any `json:"properties,omitempty"`
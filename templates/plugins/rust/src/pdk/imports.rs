@@ -74,6 +74,15 @@ pub(crate) fn notify_resource_list_changed() -> Result<(), Error> {
     Ok(())
 }
 
+/// notify_resource_changed Notify every session subscribed to a resource that it has changed.
+///
+/// Unlike notify_resource_updated, which only reaches the session that owns this plugin instance, the server fans this notification out to all sessions that subscribed to the resource. Use it for resources backed by live data shared across sessions.
+/// It takes the URI of the resource, as returned by list_resources
+pub(crate) fn notify_resource_changed(uri: &str) -> Result<(), Error> {
+    unsafe { raw_imports::notify_resource_changed(uri.to_string())? }
+    Ok(())
+}
+
 /// notify_resource_updated Notify the client that a specific resource has been updated.
 ///
 /// Plugins should call this when they modify the contents of a resource. The client can use this to invalidate caches and refresh resource displays.
@@ -115,6 +124,8 @@ mod raw_imports {
 
         pub(crate) fn notify_resource_list_changed();
 
+        pub(crate) fn notify_resource_changed(uri: String);
+
         pub(crate) fn notify_resource_updated(input: Json<ResourceUpdatedNotificationParam>);
 
         pub(crate) fn notify_tool_list_changed();
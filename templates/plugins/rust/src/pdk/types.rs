@@ -187,7 +187,7 @@ pub struct CompleteRequestParam {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<CompleteRequestParamContext>,
 
-    /// Reference to either a PromptReference or ResourceTemplateReference
+    /// Reference to either a PromptReference, ResourceTemplateReference or ToolReference
     #[serde(rename = "ref")]
     pub r#ref: Reference,
 }
@@ -520,11 +520,22 @@ pub enum ImageType {
 pub struct ListPromptsRequest {
     #[serde(rename = "context")]
     pub context: PluginRequestContext,
+
+    #[serde(rename = "request")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub request: Option<PaginatedRequestParam>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
 #[encoding(Json)]
 pub struct ListPromptsResult {
+    /// Cursor for the next page of prompts, if there are more
+    #[serde(rename = "nextCursor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+
     /// Array of available prompts
     #[serde(rename = "prompts")]
     pub prompts: Vec<Prompt>,
@@ -535,11 +546,22 @@ pub struct ListPromptsResult {
 pub struct ListResourcesRequest {
     #[serde(rename = "context")]
     pub context: PluginRequestContext,
+
+    #[serde(rename = "request")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub request: Option<PaginatedRequestParam>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
 #[encoding(Json)]
 pub struct ListResourcesResult {
+    /// Cursor for the next page of resources, if there are more
+    #[serde(rename = "nextCursor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+
     /// Array of available resources
     #[serde(rename = "resources")]
     pub resources: Vec<Resource>,
@@ -550,11 +572,22 @@ pub struct ListResourcesResult {
 pub struct ListResourceTemplatesRequest {
     #[serde(rename = "context")]
     pub context: PluginRequestContext,
+
+    #[serde(rename = "request")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub request: Option<PaginatedRequestParam>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
 #[encoding(Json)]
 pub struct ListResourceTemplatesResult {
+    /// Cursor for the next page of resource templates, if there are more
+    #[serde(rename = "nextCursor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+
     /// Array of resource templates
     #[serde(rename = "resourceTemplates")]
     pub resource_templates: Vec<ResourceTemplate>,
@@ -573,11 +606,22 @@ pub struct ListRootsResult {
 pub struct ListToolsRequest {
     #[serde(rename = "context")]
     pub context: PluginRequestContext,
+
+    #[serde(rename = "request")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub request: Option<PaginatedRequestParam>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
 #[encoding(Json)]
 pub struct ListToolsResult {
+    /// Cursor for the next page of tools, if there are more
+    #[serde(rename = "nextCursor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+
     /// Array of available tools
     #[serde(rename = "tools")]
     pub tools: Vec<Tool>,
@@ -701,6 +745,16 @@ pub enum ObjectType {
     Object,
 }
 
+#[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
+#[encoding(Json)]
+pub struct PaginatedRequestParam {
+    /// Opaque cursor returned as `next_cursor` by the previous page
+    #[serde(rename = "cursor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
 #[encoding(Json)]
 pub struct PluginNotificationContext {
@@ -716,6 +770,22 @@ pub struct PluginRequestContext {
     #[serde(rename = "_meta")]
     pub meta: Meta,
 
+    /// Subject, issuer and serial of the client certificate when the server requires mTLS
+    #[serde(rename = "metadata")]
+    #[serde(default)]
+    pub metadata: Map<String, Value>,
+
+    /// Identifier of the client session the request belongs to
+    #[serde(rename = "session_id")]
+    #[serde(default)]
+    pub session_id: String,
+
+    /// Identity of the user, e.g. the subject common name of the client certificate when the server requires mTLS
+    #[serde(rename = "user_id")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub user_id: Option<String>,
+
     /// Unique identifier for this request
     #[serde(rename = "id")]
     pub id: PluginRequestId,
@@ -896,6 +966,7 @@ pub struct ReadResourceResult {
 pub enum Reference {
     Prompt(PromptReference),
     ResourceTemplate(ResourceTemplateReference),
+    Tool(ToolReference),
     Empty(Empty),
 }
 
@@ -1264,11 +1335,11 @@ pub enum TextType {
 #[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
 #[encoding(Json)]
 pub struct Tool {
-    /// Optional tool annotations
+    /// Optional hints describing the tool's behavior
     #[serde(rename = "annotations")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    pub annotations: Option<Annotations>,
+    pub annotations: Option<ToolAnnotations>,
 
     /// Description of what the tool does
     #[serde(rename = "description")]
@@ -1295,6 +1366,59 @@ pub struct Tool {
     pub title: Option<String>,
 }
 
+#[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
+#[encoding(Json)]
+pub struct ToolAnnotations {
+    /// If true, the tool may perform destructive updates to its environment
+    #[serde(rename = "destructiveHint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub destructive_hint: Option<bool>,
+
+    /// If true, calling the tool repeatedly with the same arguments has no additional effect
+    #[serde(rename = "idempotentHint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub idempotent_hint: Option<bool>,
+
+    /// If true, the tool may interact with an open world of external entities
+    #[serde(rename = "openWorldHint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub open_world_hint: Option<bool>,
+
+    /// If true, the tool does not modify its environment
+    #[serde(rename = "readOnlyHint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub read_only_hint: Option<bool>,
+
+    /// Human-readable title for the tool
+    #[serde(rename = "title")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
+#[encoding(Json)]
+pub struct ToolReference {
+    /// Name of the tool
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "type")]
+    pub r#type: ToolReferenceType,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
+#[encoding(Json)]
+pub enum ToolReferenceType {
+    #[default]
+    #[serde(rename = "tool")]
+    Tool,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize, FromBytes, ToBytes)]
 #[encoding(Json)]
 pub struct ToolSchema {
@@ -1313,3 +1437,61 @@ pub struct ToolSchema {
     #[serde(rename = "type")]
     pub r#type: ObjectType,
 }
+
+/// Returns the string argument `name`, if present.
+pub fn get_string_arg<'a>(args: &'a Map<String, Value>, name: &str) -> Option<&'a str> {
+    args.get(name).and_then(Value::as_str)
+}
+
+/// Returns the integer argument `name`, if present.
+pub fn get_int_arg(args: &Map<String, Value>, name: &str) -> Option<i64> {
+    args.get(name).and_then(Value::as_i64)
+}
+
+/// Returns the boolean argument `name`, if present.
+pub fn get_bool_arg(args: &Map<String, Value>, name: &str) -> Option<bool> {
+    args.get(name).and_then(Value::as_bool)
+}
+
+/// Returns the array argument `name`, if present.
+pub fn get_array_arg<'a>(args: &'a Map<String, Value>, name: &str) -> Option<&'a Vec<Value>> {
+    args.get(name).and_then(Value::as_array)
+}
+
+/// Returns the string argument `name`, or an error if it is missing or not a string.
+pub fn get_required_string_arg<'a>(
+    args: &'a Map<String, Value>,
+    name: &str,
+) -> anyhow::Result<&'a str> {
+    get_string_arg(args, name)
+        .ok_or_else(|| anyhow::anyhow!("'{}' argument is required and must be a string", name))
+}
+
+/// Returns an error `CallToolResult` from the enclosing function if any of the named
+/// arguments is missing from `args`.
+///
+/// ```ignore
+/// let args = input.request.arguments.unwrap_or_default();
+/// require_args!(args, "owner", "repo");
+/// ```
+macro_rules! require_args {
+    ($args:expr, $($name:literal),+ $(,)?) => {
+        let missing: Vec<&str> = [$($name),+]
+            .into_iter()
+            .filter(|name| !$args.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            return Ok($crate::pdk::types::CallToolResult {
+                content: vec![$crate::pdk::types::ContentBlock::Text(
+                    $crate::pdk::types::TextContent {
+                        text: format!("Error: missing required argument(s): {}", missing.join(", ")),
+                        ..Default::default()
+                    },
+                )],
+                is_error: Some(true),
+                ..Default::default()
+            });
+        }
+    };
+}
+pub(crate) use require_args;